@@ -0,0 +1,163 @@
+//! CPU affinity sets
+//!
+//! This module holds the set of CPUs a process is allowed to run on
+//! (similar to C htop's Affinity.c). Sets are shown and recorded in the
+//! kernel's list format (`Cpus_allowed_list` in /proc/[pid]/status),
+//! e.g. "0-3,6,8-9".
+
+use std::fmt;
+use std::str::FromStr;
+
+/// Set of CPU ids, kept sorted and without duplicates
+#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Affinity {
+    cpus: Vec<u32>,
+}
+
+impl Affinity {
+    /// Create an empty affinity set
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a CPU to the set
+    pub fn add(&mut self, cpu: u32) {
+        if let Err(pos) = self.cpus.binary_search(&cpu) {
+            self.cpus.insert(pos, cpu);
+        }
+    }
+
+    /// Remove a CPU from the set
+    pub fn remove(&mut self, cpu: u32) {
+        if let Ok(pos) = self.cpus.binary_search(&cpu) {
+            self.cpus.remove(pos);
+        }
+    }
+
+    /// Check whether a CPU is in the set
+    pub fn contains(&self, cpu: u32) -> bool {
+        self.cpus.binary_search(&cpu).is_ok()
+    }
+
+    /// CPU ids in ascending order
+    pub fn cpus(&self) -> &[u32] {
+        &self.cpus
+    }
+
+    /// Number of CPUs in the set
+    pub fn len(&self) -> usize {
+        self.cpus.len()
+    }
+
+    /// Check if the set is empty
+    pub fn is_empty(&self) -> bool {
+        self.cpus.is_empty()
+    }
+}
+
+impl FromIterator<u32> for Affinity {
+    fn from_iter<I: IntoIterator<Item = u32>>(iter: I) -> Self {
+        let mut cpus: Vec<u32> = iter.into_iter().collect();
+        cpus.sort_unstable();
+        cpus.dedup();
+        Affinity { cpus }
+    }
+}
+
+impl fmt::Display for Affinity {
+    /// Format as a kernel CPU list, collapsing consecutive ids into ranges
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut iter = self.cpus.iter().copied().peekable();
+        let mut first = true;
+        while let Some(start) = iter.next() {
+            let mut end = start;
+            while iter.peek() == Some(&(end + 1)) {
+                end += 1;
+                iter.next();
+            }
+            if !first {
+                f.write_str(",")?;
+            }
+            first = false;
+            if end == start {
+                write!(f, "{}", start)?;
+            } else {
+                write!(f, "{}-{}", start, end)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Error returned when a CPU list cannot be parsed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseAffinityError;
+
+impl fmt::Display for ParseAffinityError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("invalid CPU list")
+    }
+}
+
+impl std::error::Error for ParseAffinityError {}
+
+impl FromStr for Affinity {
+    type Err = ParseAffinityError;
+
+    /// Parse a kernel CPU list such as "0-3,6" (an empty string is an empty set)
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut affinity = Affinity::new();
+        for part in s.trim().split(',').filter(|p| !p.is_empty()) {
+            let (start, end) = match part.split_once('-') {
+                Some((start, end)) => (start, end),
+                None => (part, part),
+            };
+            let start: u32 = start.trim().parse().map_err(|_| ParseAffinityError)?;
+            let end: u32 = end.trim().parse().map_err(|_| ParseAffinityError)?;
+            if end < start {
+                return Err(ParseAffinityError);
+            }
+            for cpu in start..=end {
+                affinity.add(cpu);
+            }
+        }
+        Ok(affinity)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_collapses_ranges() {
+        let affinity: Affinity = [0, 1, 2, 3, 6, 8, 9].into_iter().collect();
+        assert_eq!(affinity.to_string(), "0-3,6,8-9");
+        assert_eq!(Affinity::new().to_string(), "");
+        let single: Affinity = [5].into_iter().collect();
+        assert_eq!(single.to_string(), "5");
+    }
+
+    #[test]
+    fn test_parse_cpu_list() {
+        let affinity: Affinity = "0-3,6,8-9\n".parse().unwrap();
+        assert_eq!(affinity.cpus(), &[0, 1, 2, 3, 6, 8, 9]);
+        assert!(affinity.contains(6));
+        assert!(!affinity.contains(7));
+        assert!("".parse::<Affinity>().unwrap().is_empty());
+        assert!("3-1".parse::<Affinity>().is_err());
+        assert!("a,b".parse::<Affinity>().is_err());
+    }
+
+    #[test]
+    fn test_add_remove_keep_order() {
+        let mut affinity = Affinity::new();
+        affinity.add(4);
+        affinity.add(1);
+        affinity.add(4);
+        assert_eq!(affinity.cpus(), &[1, 4]);
+        affinity.remove(1);
+        assert_eq!(affinity.cpus(), &[4]);
+        assert_eq!(affinity.len(), 1);
+    }
+}
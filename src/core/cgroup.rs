@@ -0,0 +1,430 @@
+//! Cgroup tree aggregated from the process list
+//!
+//! Groups processes by their cgroup path (as read from /proc/PID/cgroup)
+//! into a tree like systemd-cgtop. Every node sums the usage of all
+//! processes in its subtree, so a slice shows the load of all services
+//! below it. The kernel's own accounting of a cgroup (memory.current,
+//! memory.max, cpu.stat) is attached by the platform layer.
+
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+
+use super::Process;
+
+/// Path of the root cgroup
+pub const CGROUP_ROOT: &str = "/";
+
+/// Resource usage summed over the processes of a cgroup subtree
+#[derive(Debug, Clone, PartialEq)]
+pub struct CgroupUsage {
+    pub percent_cpu: f32,
+    /// Resident memory in KB
+    pub m_resident: i64,
+    /// IO rates in bytes/sec (NaN if no process had readable IO stats)
+    pub io_read_bps: f64,
+    pub io_write_bps: f64,
+    /// Number of tasks (threads of all processes)
+    pub tasks: i64,
+}
+
+impl Default for CgroupUsage {
+    fn default() -> Self {
+        CgroupUsage {
+            percent_cpu: 0.0,
+            m_resident: 0,
+            io_read_bps: f64::NAN,
+            io_write_bps: f64::NAN,
+            tasks: 0,
+        }
+    }
+}
+
+/// Add a rate to a sum, skipping unavailable (NaN) rates
+fn add_rate(sum: &mut f64, rate: f64) {
+    if rate.is_finite() && rate >= 0.0 {
+        *sum = if sum.is_nan() { rate } else { *sum + rate };
+    }
+}
+
+impl CgroupUsage {
+    /// Add the usage of a process
+    pub fn add_process(&mut self, process: &Process) {
+        self.percent_cpu += process.percent_cpu;
+        self.m_resident += process.m_resident;
+        add_rate(&mut self.io_read_bps, process.io_rate_read_bps);
+        add_rate(&mut self.io_write_bps, process.io_rate_write_bps);
+        self.tasks += process.nlwp.max(1);
+    }
+
+    /// Combined read and write rate (0 if unavailable), used for sorting
+    pub fn io_total_bps(&self) -> f64 {
+        let read = if self.io_read_bps.is_nan() {
+            0.0
+        } else {
+            self.io_read_bps
+        };
+        let write = if self.io_write_bps.is_nan() {
+            0.0
+        } else {
+            self.io_write_bps
+        };
+        read + write
+    }
+}
+
+/// Accounting of a cgroup as reported by the kernel
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CgroupStats {
+    /// memory.current in bytes
+    pub memory_current: Option<u64>,
+    /// memory.max in bytes (u64::MAX if unlimited)
+    pub memory_max: Option<u64>,
+    /// Total CPU time from cpu.stat in microseconds
+    pub cpu_usage_usec: Option<u64>,
+    /// Number of throttled periods from cpu.stat
+    pub nr_throttled: Option<u64>,
+}
+
+/// A cgroup in the tree
+#[derive(Debug, Clone, PartialEq)]
+pub struct CgroupNode {
+    /// Path below the cgroup root, "/" for the root itself
+    pub path: String,
+    pub depth: usize,
+    /// Indices of the child cgroups in `CgroupTree::nodes`
+    pub children: Vec<usize>,
+    /// Usage summed over all processes in this cgroup and its children
+    pub usage: CgroupUsage,
+    pub stats: CgroupStats,
+}
+
+impl CgroupNode {
+    fn new(path: &str, depth: usize) -> Self {
+        CgroupNode {
+            path: path.to_string(),
+            depth,
+            children: Vec::new(),
+            usage: CgroupUsage::default(),
+            stats: CgroupStats::default(),
+        }
+    }
+
+    /// Last path component ("/" for the root)
+    pub fn name(&self) -> &str {
+        match self.path.rsplit_once('/') {
+            Some((_, name)) if !name.is_empty() => name,
+            _ => CGROUP_ROOT,
+        }
+    }
+
+    /// Memory used by the cgroup in bytes: the kernel's memory.current,
+    /// or the resident memory of its processes if that is unavailable
+    pub fn memory_bytes(&self) -> u64 {
+        self.stats
+            .memory_current
+            .unwrap_or(self.usage.m_resident.max(0) as u64 * 1024)
+    }
+}
+
+/// Order of sibling cgroups (the keys of systemd-cgtop)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CgroupSortKey {
+    Path,
+    #[default]
+    Cpu,
+    Memory,
+    Tasks,
+    Io,
+}
+
+impl CgroupSortKey {
+    /// All sort keys in cycling order
+    pub const ALL: [CgroupSortKey; 5] = [
+        CgroupSortKey::Path,
+        CgroupSortKey::Cpu,
+        CgroupSortKey::Memory,
+        CgroupSortKey::Tasks,
+        CgroupSortKey::Io,
+    ];
+
+    /// The sort key after this one
+    pub fn next(self) -> Self {
+        let index = Self::ALL.iter().position(|&k| k == self).unwrap_or(0);
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+
+    fn compare(self, a: &CgroupNode, b: &CgroupNode) -> Ordering {
+        // Largest first, ties ordered by name
+        let by_value = match self {
+            CgroupSortKey::Path => Ordering::Equal,
+            CgroupSortKey::Cpu => b.usage.percent_cpu.total_cmp(&a.usage.percent_cpu),
+            CgroupSortKey::Memory => b.memory_bytes().cmp(&a.memory_bytes()),
+            CgroupSortKey::Tasks => b.usage.tasks.cmp(&a.usage.tasks),
+            CgroupSortKey::Io => b.usage.io_total_bps().total_cmp(&a.usage.io_total_bps()),
+        };
+        by_value.then_with(|| a.name().cmp(b.name()))
+    }
+}
+
+/// Tree of all cgroups that contain at least one process
+#[derive(Debug, Clone, PartialEq)]
+pub struct CgroupTree {
+    /// All cgroups, the root first
+    pub nodes: Vec<CgroupNode>,
+    index: HashMap<String, usize>,
+}
+
+impl Default for CgroupTree {
+    fn default() -> Self {
+        let mut index = HashMap::new();
+        index.insert(CGROUP_ROOT.to_string(), 0);
+        CgroupTree {
+            nodes: vec![CgroupNode::new(CGROUP_ROOT, 0)],
+            index,
+        }
+    }
+}
+
+impl CgroupTree {
+    /// Build the tree from the process list. Userland threads are skipped
+    /// since their process already accounts for them, and processes whose
+    /// cgroup has not been read yet are left out.
+    pub fn build(processes: &[Process]) -> Self {
+        let mut tree = CgroupTree::default();
+        for process in processes {
+            if process.is_userland_thread {
+                continue;
+            }
+            let Some(ref cgroup) = process.cgroup else {
+                continue;
+            };
+            let mut node = Some(tree.insert(cgroup));
+            while let Some(index) = node {
+                tree.nodes[index].usage.add_process(process);
+                node = tree.parent(index);
+            }
+        }
+        tree
+    }
+
+    /// Index of the node with the given path, creating it and any missing
+    /// ancestors
+    fn insert(&mut self, path: &str) -> usize {
+        let path = match path.trim_end_matches('/') {
+            "" => CGROUP_ROOT,
+            trimmed => trimmed,
+        };
+        if let Some(&index) = self.index.get(path) {
+            return index;
+        }
+
+        let parent_path = match path.rsplit_once('/') {
+            Some((parent, _)) if !parent.is_empty() => parent,
+            _ => CGROUP_ROOT,
+        };
+        let parent = self.insert(parent_path);
+        let index = self.nodes.len();
+        self.nodes
+            .push(CgroupNode::new(path, self.nodes[parent].depth + 1));
+        self.nodes[parent].children.push(index);
+        self.index.insert(path.to_string(), index);
+        index
+    }
+
+    fn parent(&self, index: usize) -> Option<usize> {
+        let path = &self.nodes[index].path;
+        if path == CGROUP_ROOT {
+            return None;
+        }
+        let parent_path = match path.rsplit_once('/') {
+            Some((parent, _)) if !parent.is_empty() => parent,
+            _ => CGROUP_ROOT,
+        };
+        self.index.get(parent_path).copied()
+    }
+
+    /// Index of the node with the given path
+    pub fn find(&self, path: &str) -> Option<usize> {
+        self.index.get(path).copied()
+    }
+
+    /// Sort the children of every node
+    pub fn sort(&mut self, key: CgroupSortKey) {
+        for i in 0..self.nodes.len() {
+            let mut children = std::mem::take(&mut self.nodes[i].children);
+            children.sort_by(|&a, &b| key.compare(&self.nodes[a], &self.nodes[b]));
+            self.nodes[i].children = children;
+        }
+    }
+
+    /// Node indices in display order (depth first), leaving out the
+    /// descendants of collapsed paths
+    pub fn flatten(&self, collapsed: &HashSet<String>) -> Vec<usize> {
+        let mut rows = Vec::with_capacity(self.nodes.len());
+        let mut stack = vec![0];
+        while let Some(index) = stack.pop() {
+            rows.push(index);
+            let node = &self.nodes[index];
+            if !collapsed.contains(&node.path) {
+                stack.extend(node.children.iter().rev());
+            }
+        }
+        rows
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn process(pid: i32, cgroup: &str, percent_cpu: f32, m_resident: i64) -> Process {
+        Process {
+            pid,
+            cgroup: Some(cgroup.to_string()),
+            percent_cpu,
+            m_resident,
+            nlwp: 1,
+            ..Default::default()
+        }
+    }
+
+    fn paths(tree: &CgroupTree, rows: &[usize]) -> Vec<String> {
+        rows.iter().map(|&i| tree.nodes[i].path.clone()).collect()
+    }
+
+    #[test]
+    fn test_build_sums_subtrees() {
+        let processes = vec![
+            process(1, "/init.scope", 0.5, 10_000),
+            process(100, "/system.slice/nginx.service", 10.0, 20_000),
+            process(101, "/system.slice/nginx.service", 5.0, 30_000),
+            process(200, "/system.slice/sshd.service", 1.0, 5_000),
+            process(
+                300,
+                "/user.slice/user-1000.slice/session-2.scope",
+                20.0,
+                100_000,
+            ),
+        ];
+        let tree = CgroupTree::build(&processes);
+
+        let root = &tree.nodes[0];
+        assert_eq!(root.path, "/");
+        assert_eq!(root.usage.percent_cpu, 36.5);
+        assert_eq!(root.usage.m_resident, 165_000);
+        assert_eq!(root.usage.tasks, 5);
+
+        let system = &tree.nodes[tree.find("/system.slice").unwrap()];
+        assert_eq!(system.name(), "system.slice");
+        assert_eq!(system.depth, 1);
+        assert_eq!(system.usage.percent_cpu, 16.0);
+        assert_eq!(system.children.len(), 2);
+
+        let nginx = &tree.nodes[tree.find("/system.slice/nginx.service").unwrap()];
+        assert_eq!(nginx.depth, 2);
+        assert_eq!(nginx.usage.m_resident, 50_000);
+        assert_eq!(nginx.usage.tasks, 2);
+
+        // Intermediate slices are created for nested cgroups
+        let user = tree.find("/user.slice/user-1000.slice").unwrap();
+        assert_eq!(tree.nodes[user].usage.percent_cpu, 20.0);
+    }
+
+    #[test]
+    fn test_build_skips_threads_and_unknown_cgroups() {
+        let mut thread = process(101, "/system.slice/nginx.service", 5.0, 20_000);
+        thread.is_userland_thread = true;
+        let mut parent = process(100, "/system.slice/nginx.service", 5.0, 20_000);
+        parent.nlwp = 4;
+        let unknown = Process {
+            pid: 300,
+            percent_cpu: 50.0,
+            ..Default::default()
+        };
+        let tree = CgroupTree::build(&[parent, thread, unknown]);
+
+        let nginx = &tree.nodes[tree.find("/system.slice/nginx.service").unwrap()];
+        assert_eq!(nginx.usage.percent_cpu, 5.0);
+        assert_eq!(nginx.usage.tasks, 4);
+        assert_eq!(tree.nodes[0].usage.percent_cpu, 5.0);
+    }
+
+    #[test]
+    fn test_io_rates() {
+        let mut a = process(1, "/a.service", 0.0, 0);
+        a.io_rate_read_bps = 100.0;
+        a.io_rate_write_bps = f64::NAN;
+        let mut b = process(2, "/a.service", 0.0, 0);
+        b.io_rate_read_bps = 50.0;
+        b.io_rate_write_bps = 10.0;
+        let c = process(3, "/b.service", 0.0, 0);
+        let tree = CgroupTree::build(&[a, b, c]);
+
+        let a = &tree.nodes[tree.find("/a.service").unwrap()].usage;
+        assert_eq!(a.io_read_bps, 150.0);
+        assert_eq!(a.io_write_bps, 10.0);
+        assert_eq!(a.io_total_bps(), 160.0);
+
+        // No process with readable IO stats
+        let b = &tree.nodes[tree.find("/b.service").unwrap()].usage;
+        assert!(b.io_read_bps.is_nan());
+        assert_eq!(b.io_total_bps(), 0.0);
+    }
+
+    #[test]
+    fn test_sort_and_flatten() {
+        let processes = vec![
+            process(1, "/a.slice/x.service", 1.0, 300),
+            process(2, "/a.slice/y.service", 3.0, 100),
+            process(3, "/b.slice", 2.0, 200),
+        ];
+        let mut tree = CgroupTree::build(&processes);
+        let none = HashSet::new();
+
+        tree.sort(CgroupSortKey::Cpu);
+        assert_eq!(
+            paths(&tree, &tree.flatten(&none)),
+            vec![
+                "/",
+                "/a.slice",
+                "/a.slice/y.service",
+                "/a.slice/x.service",
+                "/b.slice"
+            ]
+        );
+
+        tree.sort(CgroupSortKey::Memory);
+        assert_eq!(
+            paths(&tree, &tree.flatten(&none)),
+            vec![
+                "/",
+                "/a.slice",
+                "/a.slice/x.service",
+                "/a.slice/y.service",
+                "/b.slice"
+            ]
+        );
+
+        let collapsed: HashSet<String> = ["/a.slice".to_string()].into();
+        assert_eq!(
+            paths(&tree, &tree.flatten(&collapsed)),
+            vec!["/", "/a.slice", "/b.slice"]
+        );
+    }
+
+    #[test]
+    fn test_memory_bytes_prefers_kernel_accounting() {
+        let mut node = CgroupNode::new("/a.service", 1);
+        node.usage.m_resident = 2;
+        assert_eq!(node.memory_bytes(), 2048);
+        node.stats.memory_current = Some(10_000);
+        assert_eq!(node.memory_bytes(), 10_000);
+    }
+
+    #[test]
+    fn test_sort_key_next() {
+        assert_eq!(CgroupSortKey::default(), CgroupSortKey::Cpu);
+        assert_eq!(CgroupSortKey::Cpu.next(), CgroupSortKey::Memory);
+        assert_eq!(CgroupSortKey::Io.next(), CgroupSortKey::Path);
+    }
+}
@@ -0,0 +1,195 @@
+//! Process environment variables
+//!
+//! Decodes the NUL-separated `NAME=value` list of `/proc/PID/environ` and
+//! compares the environments of two processes. The environment is an
+//! arbitrary byte string, so bytes that are not valid UTF-8 and control
+//! characters are escaped instead of being dropped.
+
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+/// Decode one variable, escaping invalid UTF-8 as `\xNN` and control
+/// characters (newlines in multi-line values) as Rust escapes
+pub fn decode_variable(bytes: &[u8]) -> String {
+    let mut decoded = String::with_capacity(bytes.len());
+    for chunk in bytes.utf8_chunks() {
+        for c in chunk.valid().chars() {
+            if c.is_control() {
+                decoded.extend(c.escape_default());
+            } else {
+                decoded.push(c);
+            }
+        }
+        for byte in chunk.invalid() {
+            let _ = write!(decoded, "\\x{:02x}", byte);
+        }
+    }
+    decoded
+}
+
+/// Split the contents of an environ file into the raw variables, sorted
+/// like `parse_environ()`
+pub fn split_environ(bytes: &[u8]) -> Vec<Vec<u8>> {
+    let mut vars: Vec<Vec<u8>> = bytes
+        .split(|&b| b == 0)
+        .filter(|var| !var.is_empty())
+        .map(<[u8]>::to_vec)
+        .collect();
+    vars.sort_by_cached_key(|var| decode_variable(var));
+    vars
+}
+
+/// Parse the contents of an environ file into sorted `NAME=value` strings
+pub fn parse_environ(bytes: &[u8]) -> Vec<String> {
+    split_environ(bytes)
+        .iter()
+        .map(|var| decode_variable(var))
+        .collect()
+}
+
+/// Split a `NAME=value` string (a variable without '=' has an empty value)
+pub fn split_variable(var: &str) -> (&str, &str) {
+    var.split_once('=').unwrap_or((var, ""))
+}
+
+/// Difference of one variable between two environments
+#[derive(Debug, Clone, PartialEq)]
+pub enum EnvChange {
+    /// Only set in the first environment
+    Removed { name: String, value: String },
+    /// Only set in the second environment
+    Added { name: String, value: String },
+    /// Set in both with different values
+    Changed {
+        name: String,
+        old: String,
+        new: String,
+    },
+}
+
+impl EnvChange {
+    pub fn name(&self) -> &str {
+        match self {
+            EnvChange::Removed { name, .. }
+            | EnvChange::Added { name, .. }
+            | EnvChange::Changed { name, .. } => name,
+        }
+    }
+}
+
+/// Compare two environments. Returns the changes sorted by name and the
+/// number of variables set to the same value in both.
+pub fn diff_environ(first: &[String], second: &[String]) -> (Vec<EnvChange>, usize) {
+    let first: BTreeMap<&str, &str> = first.iter().map(|v| split_variable(v)).collect();
+    let second: BTreeMap<&str, &str> = second.iter().map(|v| split_variable(v)).collect();
+
+    let mut changes = Vec::new();
+    let mut identical = 0;
+    for (&name, &old) in &first {
+        match second.get(name) {
+            Some(&new) if new == old => identical += 1,
+            Some(&new) => changes.push(EnvChange::Changed {
+                name: name.to_string(),
+                old: old.to_string(),
+                new: new.to_string(),
+            }),
+            None => changes.push(EnvChange::Removed {
+                name: name.to_string(),
+                value: old.to_string(),
+            }),
+        }
+    }
+    for (&name, &value) in &second {
+        if !first.contains_key(name) {
+            changes.push(EnvChange::Added {
+                name: name.to_string(),
+                value: value.to_string(),
+            });
+        }
+    }
+    changes.sort_by(|a, b| a.name().cmp(b.name()));
+    (changes, identical)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_environ() {
+        let vars = parse_environ(b"TERM=xterm\0HOME=/root\0\0EMPTY=\0");
+        assert_eq!(vars, vec!["EMPTY=", "HOME=/root", "TERM=xterm"]);
+        assert!(parse_environ(b"").is_empty());
+    }
+
+    #[test]
+    fn test_split_environ() {
+        let bytes = b"TERM=xterm\0MULTI=a\nb\0\0NAME=caf\xe9\0";
+        let vars = split_environ(bytes);
+        assert_eq!(
+            vars,
+            vec![
+                b"MULTI=a\nb".to_vec(),
+                b"NAME=caf\xe9".to_vec(),
+                b"TERM=xterm".to_vec()
+            ]
+        );
+        // In the same order as the decoded variables
+        let decoded: Vec<String> = vars.iter().map(|var| decode_variable(var)).collect();
+        assert_eq!(decoded, parse_environ(bytes));
+    }
+
+    #[test]
+    fn test_decode_variable() {
+        assert_eq!(
+            decode_variable("LANG=de_DE.UTF-8 ä".as_bytes()),
+            "LANG=de_DE.UTF-8 ä"
+        );
+        assert_eq!(decode_variable(b"NAME=caf\xe9"), "NAME=caf\\xe9");
+        assert_eq!(decode_variable(b"MULTI=a\nb\tc"), "MULTI=a\\nb\\tc");
+    }
+
+    #[test]
+    fn test_split_variable() {
+        assert_eq!(split_variable("A=b=c"), ("A", "b=c"));
+        assert_eq!(split_variable("A="), ("A", ""));
+        assert_eq!(split_variable("A"), ("A", ""));
+    }
+
+    #[test]
+    fn test_diff_environ() {
+        let first: Vec<String> = ["A=1", "B=2", "C=3", "PATH=/bin"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        let second: Vec<String> = ["A=1", "B=20", "D=4", "PATH=/bin"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+
+        let (changes, identical) = diff_environ(&first, &second);
+        assert_eq!(identical, 2);
+        assert_eq!(
+            changes,
+            vec![
+                EnvChange::Changed {
+                    name: "B".to_string(),
+                    old: "2".to_string(),
+                    new: "20".to_string(),
+                },
+                EnvChange::Removed {
+                    name: "C".to_string(),
+                    value: "3".to_string(),
+                },
+                EnvChange::Added {
+                    name: "D".to_string(),
+                    value: "4".to_string(),
+                },
+            ]
+        );
+
+        let (changes, identical) = diff_environ(&first, &first);
+        assert!(changes.is_empty());
+        assert_eq!(identical, 4);
+    }
+}
@@ -149,6 +149,15 @@ impl FieldWidths {
         }
     }
 
+    /// Get the width of a field's values: the dynamic width for auto-sized
+    /// columns, otherwise the static title width minus its trailing separator
+    pub fn get_display_width(&self, field: ProcessField) -> usize {
+        match self.get_width(field) {
+            0 => field.title().len().saturating_sub(1),
+            width => width,
+        }
+    }
+
     /// Get the title for a field with proper width padding
     /// Returns a String because PID columns need dynamic padding
     pub fn get_title(&self, field: ProcessField) -> String {
@@ -245,4 +254,13 @@ mod tests {
         fw.update_percent_cpu_width(10.0);
         assert_eq!(fw.percent_cpu_width, 6);
     }
+
+    #[test]
+    fn test_display_width() {
+        let mut fw = FieldWidths::new();
+        fw.set_pid_width(1000000);
+        assert_eq!(fw.get_display_width(ProcessField::Pid), 7);
+        // Static columns use their title width without the separator
+        assert_eq!(fw.get_display_width(ProcessField::User), "USER      ".len());
+    }
 }
@@ -0,0 +1,732 @@
+//! Process filter expressions (F4 / --filter)
+//!
+//! A filter without any query syntax keeps the classic htop behavior: a
+//! case-insensitive substring match on the command line. Otherwise it is
+//! parsed as a small query language:
+//!
+//! - `word` or `"two words"`: substring of the command (case-insensitive)
+//! - `/regex/`: regular expression on the command (case-insensitive)
+//! - `key:value`: field match (substring for text fields, exact user,
+//!   any of the given state letters, equality for numbers)
+//! - `key~regex`: regular expression on a text field
+//! - `key=value`, `key!=value`: exact match
+//! - `key>N`, `key>=N`, `key<N`, `key<=N`: numeric comparison
+//! - `and`, `or`, `not` and parentheses; adjacent terms are and-ed
+//!
+//! Sizes (`mem`, `virt`, `io`) are in bytes and accept K/M/G/T suffixes
+//! (powers of 1024), e.g. `mem>1G`. `time` is CPU time in seconds.
+//! Text keys: user, cmd, comm, exe, cwd, tty, cgroup, container, secattr, state.
+//! Numeric keys: pid, ppid, pgrp, sid, tgid, uid, nice, prio, cpu, mem%, mem,
+//! virt, threads, time, io.
+
+use regex::{Regex, RegexBuilder};
+use thiserror::Error;
+
+use super::process::Process;
+#[cfg(target_os = "linux")]
+use super::process::ScanFlags;
+
+/// Error from parsing a filter expression
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum FilterError {
+    #[error("unexpected end of filter")]
+    UnexpectedEnd,
+    #[error("unexpected '{0}'")]
+    Unexpected(String),
+    #[error("unterminated {0}")]
+    Unterminated(&'static str),
+    #[error("invalid number '{0}'")]
+    InvalidNumber(String),
+    #[error("operator '{op}' cannot be used with '{key}'")]
+    InvalidOperator { key: String, op: &'static str },
+    #[error("invalid regex: {0}")]
+    InvalidRegex(String),
+}
+
+/// Text attributes of a process that can be matched
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TextKey {
+    User,
+    Command,
+    Comm,
+    Exe,
+    Cwd,
+    Tty,
+    CGroup,
+    Container,
+    SecAttr,
+    State,
+}
+
+/// Numeric attributes of a process that can be compared
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum NumKey {
+    Pid,
+    Ppid,
+    Pgrp,
+    Session,
+    Tgid,
+    Uid,
+    Nice,
+    Priority,
+    PercentCpu,
+    PercentMem,
+    /// Resident memory in bytes
+    Mem,
+    /// Virtual memory in bytes
+    Virt,
+    Threads,
+    /// CPU time in seconds
+    Time,
+    /// Total IO rate in bytes per second
+    IORate,
+}
+
+impl NumKey {
+    /// Whether values for this key accept size suffixes
+    fn is_size(self) -> bool {
+        matches!(self, NumKey::Mem | NumKey::Virt | NumKey::IORate)
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Key {
+    Text(TextKey),
+    Num(NumKey),
+}
+
+impl Key {
+    fn from_name(name: &str) -> Option<Key> {
+        let key = match name.to_ascii_lowercase().as_str() {
+            "user" => Key::Text(TextKey::User),
+            "cmd" | "command" => Key::Text(TextKey::Command),
+            "comm" | "name" => Key::Text(TextKey::Comm),
+            "exe" => Key::Text(TextKey::Exe),
+            "cwd" => Key::Text(TextKey::Cwd),
+            "tty" => Key::Text(TextKey::Tty),
+            "cgroup" => Key::Text(TextKey::CGroup),
+            "container" => Key::Text(TextKey::Container),
+            "secattr" => Key::Text(TextKey::SecAttr),
+            "state" | "s" => Key::Text(TextKey::State),
+            "pid" => Key::Num(NumKey::Pid),
+            "ppid" => Key::Num(NumKey::Ppid),
+            "pgrp" => Key::Num(NumKey::Pgrp),
+            "sid" | "session" => Key::Num(NumKey::Session),
+            "tgid" => Key::Num(NumKey::Tgid),
+            "uid" => Key::Num(NumKey::Uid),
+            "nice" | "ni" => Key::Num(NumKey::Nice),
+            "prio" | "priority" | "pri" => Key::Num(NumKey::Priority),
+            "cpu" | "cpu%" => Key::Num(NumKey::PercentCpu),
+            "mem%" => Key::Num(NumKey::PercentMem),
+            "mem" | "res" | "rss" => Key::Num(NumKey::Mem),
+            "virt" => Key::Num(NumKey::Virt),
+            "threads" | "nlwp" => Key::Num(NumKey::Threads),
+            "time" => Key::Num(NumKey::Time),
+            "io" => Key::Num(NumKey::IORate),
+            _ => return None,
+        };
+        Some(key)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CmpOp {
+    Eq,
+    Ne,
+    Gt,
+    Ge,
+    Lt,
+    Le,
+}
+
+impl CmpOp {
+    fn apply(self, lhs: f64, rhs: f64) -> bool {
+        match self {
+            CmpOp::Eq => lhs == rhs,
+            CmpOp::Ne => lhs != rhs,
+            CmpOp::Gt => lhs > rhs,
+            CmpOp::Ge => lhs >= rhs,
+            CmpOp::Lt => lhs < rhs,
+            CmpOp::Le => lhs <= rhs,
+        }
+    }
+}
+
+/// How a text field is matched
+#[derive(Debug, Clone)]
+enum TextMatch {
+    /// Case-insensitive substring (value is lowercased)
+    Contains(String),
+    /// Exact match
+    Equals(String),
+    Regex(Regex),
+}
+
+#[derive(Debug, Clone)]
+enum Expr {
+    Text(TextKey, TextMatch),
+    /// Negated exact text match (`key!=value`)
+    TextNe(TextKey, String),
+    Num(NumKey, CmpOp, f64),
+    Not(Box<Expr>),
+    And(Box<Expr>, Box<Expr>),
+    Or(Box<Expr>, Box<Expr>),
+}
+
+/// A compiled process filter
+#[derive(Debug, Clone)]
+pub struct ProcessFilter {
+    text: String,
+    expr: Expr,
+    /// Why the text is matched as a substring instead of as a query
+    error: Option<FilterError>,
+}
+
+impl ProcessFilter {
+    /// Compile a filter, falling back to a plain substring match if the
+    /// text is not a valid query (so partially typed queries still filter)
+    pub fn new(text: &str) -> Self {
+        Self::parse(text).unwrap_or_else(|error| ProcessFilter {
+            error: Some(error),
+            ..Self::literal(text)
+        })
+    }
+
+    /// Compile a filter, reporting query syntax errors
+    pub fn parse(text: &str) -> Result<Self, FilterError> {
+        let tokens = tokenize(text)?;
+        if tokens
+            .iter()
+            .all(|t| matches!(t, Token::Word(w) if !is_query_word(w)))
+        {
+            return Ok(Self::literal(text));
+        }
+        let mut parser = Parser { tokens, pos: 0 };
+        let expr = parser.parse_or()?;
+        if let Some(token) = parser.tokens.get(parser.pos) {
+            return Err(FilterError::Unexpected(token.to_string()));
+        }
+        Ok(ProcessFilter {
+            text: text.to_string(),
+            expr,
+            error: None,
+        })
+    }
+
+    /// Classic htop filter: case-insensitive substring of the command
+    fn literal(text: &str) -> Self {
+        ProcessFilter {
+            text: text.to_string(),
+            expr: Expr::Text(TextKey::Command, TextMatch::Contains(text.to_lowercase())),
+            error: None,
+        }
+    }
+
+    /// The filter text as typed
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// The parse error if the text is not a valid query and `new` fell
+    /// back to a substring match
+    pub fn error(&self) -> Option<&FilterError> {
+        self.error.as_ref()
+    }
+
+    /// Check whether a process matches the filter
+    pub fn matches(&self, process: &Process) -> bool {
+        eval(&self.expr, process)
+    }
+
+    /// Scan flags needed for the fields this filter looks at
+    #[cfg(target_os = "linux")]
+    pub fn scan_flags(&self) -> ScanFlags {
+        fn collect(expr: &Expr) -> ScanFlags {
+            match expr {
+                Expr::Text(key, _) | Expr::TextNe(key, _) => match key {
+                    TextKey::CGroup | TextKey::Container => ScanFlags::CGROUP,
+                    TextKey::SecAttr => ScanFlags::SEC_ATTR,
+                    TextKey::Cwd => ScanFlags::CWD,
+                    _ => ScanFlags::empty(),
+                },
+                Expr::Num(NumKey::IORate, _, _) => ScanFlags::IO,
+                Expr::Num(..) => ScanFlags::empty(),
+                Expr::Not(inner) => collect(inner),
+                Expr::And(a, b) | Expr::Or(a, b) => collect(a) | collect(b),
+            }
+        }
+        collect(&self.expr)
+    }
+}
+
+// ==================== Evaluation ====================
+
+fn text_value(process: &Process, key: TextKey) -> Option<String> {
+    match key {
+        TextKey::User => process.user.clone(),
+        TextKey::Command => Some(process.get_command().to_string()),
+        TextKey::Comm => process.comm.clone(),
+        TextKey::Exe => process.exe.clone(),
+        TextKey::Cwd => process.cwd.clone(),
+        TextKey::Tty => process.tty_name.clone(),
+        TextKey::CGroup => process.cgroup.clone(),
+        TextKey::Container => process.container_short.clone(),
+        TextKey::SecAttr => process.sec_attr.clone(),
+        TextKey::State => Some(process.state.to_char().to_string()),
+    }
+}
+
+fn num_value(process: &Process, key: NumKey) -> f64 {
+    match key {
+        NumKey::Pid => process.pid as f64,
+        NumKey::Ppid => process.ppid as f64,
+        NumKey::Pgrp => process.pgrp as f64,
+        NumKey::Session => process.session as f64,
+        NumKey::Tgid => process.tgid as f64,
+        NumKey::Uid => process.uid as f64,
+        NumKey::Nice => process.nice as f64,
+        NumKey::Priority => process.priority as f64,
+        NumKey::PercentCpu => process.percent_cpu as f64,
+        NumKey::PercentMem => process.percent_mem as f64,
+        NumKey::Mem => process.m_resident as f64 * 1024.0,
+        NumKey::Virt => process.m_virt as f64 * 1024.0,
+        NumKey::Threads => process.nlwp as f64,
+        NumKey::Time => process.time as f64 / 100.0,
+        NumKey::IORate => {
+            let rate = |r: f64| if r.is_nan() { 0.0 } else { r };
+            rate(process.io_rate_read_bps) + rate(process.io_rate_write_bps)
+        }
+    }
+}
+
+fn eval(expr: &Expr, process: &Process) -> bool {
+    match expr {
+        Expr::Text(key, m) => {
+            let Some(value) = text_value(process, *key) else {
+                return false;
+            };
+            match (key, m) {
+                // state:DZ matches any of the given state letters
+                (TextKey::State, TextMatch::Contains(letters)) => value
+                    .chars()
+                    .any(|c| letters.chars().any(|l| l.eq_ignore_ascii_case(&c))),
+                // user:name is an exact match
+                (TextKey::User, TextMatch::Contains(name)) => value.eq_ignore_ascii_case(name),
+                (_, TextMatch::Contains(needle)) => value.to_lowercase().contains(needle),
+                (_, TextMatch::Equals(expected)) => value == *expected,
+                (_, TextMatch::Regex(re)) => re.is_match(&value),
+            }
+        }
+        Expr::TextNe(key, expected) => {
+            text_value(process, *key).is_none_or(|value| value != *expected)
+        }
+        Expr::Num(key, op, rhs) => op.apply(num_value(process, *key), *rhs),
+        Expr::Not(inner) => !eval(inner, process),
+        Expr::And(a, b) => eval(a, process) && eval(b, process),
+        Expr::Or(a, b) => eval(a, process) || eval(b, process),
+    }
+}
+
+// ==================== Parsing ====================
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    LParen,
+    RParen,
+    /// Unquoted word (may be a keyword or a `key<op>value` term)
+    Word(String),
+    /// Quoted string (always a command substring)
+    Quoted(String),
+    /// `/regex/` on the command
+    Regex(String),
+}
+
+impl std::fmt::Display for Token {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Token::LParen => f.write_str("("),
+            Token::RParen => f.write_str(")"),
+            Token::Word(w) => f.write_str(w),
+            Token::Quoted(q) => write!(f, "\"{}\"", q),
+            Token::Regex(r) => write!(f, "/{}/", r),
+        }
+    }
+}
+
+/// Read up to the closing delimiter, honoring backslash escapes of it
+fn read_delimited(
+    chars: &mut std::iter::Peekable<std::str::Chars>,
+    delim: char,
+    what: &'static str,
+) -> Result<String, FilterError> {
+    let mut out = String::new();
+    loop {
+        match chars.next() {
+            None => return Err(FilterError::Unterminated(what)),
+            Some(c) if c == delim => return Ok(out),
+            Some('\\') if chars.peek() == Some(&delim) => {
+                out.push(delim);
+                chars.next();
+            }
+            Some(c) => out.push(c),
+        }
+    }
+}
+
+/// Whether the input at `chars` (starting with '/') is a regex closed by
+/// a '/' that ends the token
+fn regex_ahead(chars: &std::iter::Peekable<std::str::Chars>) -> bool {
+    let mut ahead = chars.clone();
+    ahead.next();
+    read_delimited(&mut ahead, '/', "regex").is_ok()
+        && ahead.peek().is_none_or(|&c| c.is_whitespace() || c == ')')
+}
+
+fn tokenize(text: &str) -> Result<Vec<Token>, FilterError> {
+    let mut tokens = Vec::new();
+    let mut chars = text.chars().peekable();
+    while let Some(&c) = chars.peek() {
+        match c {
+            c if c.is_whitespace() => {
+                chars.next();
+            }
+            '(' => {
+                chars.next();
+                tokens.push(Token::LParen);
+            }
+            ')' => {
+                chars.next();
+                tokens.push(Token::RParen);
+            }
+            '"' => {
+                chars.next();
+                tokens.push(Token::Quoted(read_delimited(&mut chars, '"', "quote")?));
+            }
+            // Only a fully delimited /.../ is a regex, so paths like "/tmp"
+            // or "/opt/app" stay plain words
+            '/' if regex_ahead(&chars) => {
+                chars.next();
+                tokens.push(Token::Regex(read_delimited(&mut chars, '/', "regex")?));
+            }
+            _ => {
+                let mut word = String::new();
+                while let Some(&c) = chars.peek() {
+                    if c.is_whitespace() || c == '(' || c == ')' {
+                        break;
+                    }
+                    chars.next();
+                    if c == '"' {
+                        // Quoted value, e.g. cmd:"foo bar"
+                        word.push_str(&read_delimited(&mut chars, '"', "quote")?);
+                    } else {
+                        word.push(c);
+                    }
+                }
+                tokens.push(Token::Word(word));
+            }
+        }
+    }
+    Ok(tokens)
+}
+
+fn keyword(word: &str) -> Option<&'static str> {
+    match word.to_ascii_lowercase().as_str() {
+        "and" => Some("and"),
+        "or" => Some("or"),
+        "not" => Some("not"),
+        _ => None,
+    }
+}
+
+/// A `key<op>value` term split into its parts
+struct Term<'a> {
+    name: &'a str,
+    key: Key,
+    op: &'static str,
+    value: &'a str,
+}
+
+/// Split `key<op>value` where key is a known field name
+fn split_term(word: &str) -> Option<Term<'_>> {
+    const OPS: [&str; 8] = ["!=", ">=", "<=", ":", "~", "=", ">", "<"];
+    let pos = word.find(|c: char| ":~=!<>".contains(c))?;
+    let name = &word[..pos];
+    let key = Key::from_name(name)?;
+    let rest = &word[pos..];
+    let op = OPS.iter().find(|op| rest.starts_with(*op))?;
+    Some(Term {
+        name,
+        key,
+        op,
+        value: &rest[op.len()..],
+    })
+}
+
+/// Whether a word uses query syntax (keyword or field term)
+fn is_query_word(word: &str) -> bool {
+    keyword(word).is_some() || split_term(word).is_some()
+}
+
+fn build_regex(pattern: &str) -> Result<Regex, FilterError> {
+    RegexBuilder::new(pattern)
+        .case_insensitive(true)
+        .build()
+        .map_err(|e| FilterError::InvalidRegex(e.to_string()))
+}
+
+/// Parse a number with an optional K/M/G/T size suffix (powers of 1024)
+fn parse_number(value: &str, allow_size: bool) -> Result<f64, FilterError> {
+    let invalid = || FilterError::InvalidNumber(value.to_string());
+    let trimmed = value.trim_end_matches(['%', 'B', 'b']);
+    let (digits, multiplier) = match trimmed.chars().last().map(|c| c.to_ascii_uppercase()) {
+        Some('K') if allow_size => (&trimmed[..trimmed.len() - 1], 1024f64),
+        Some('M') if allow_size => (&trimmed[..trimmed.len() - 1], 1024f64.powi(2)),
+        Some('G') if allow_size => (&trimmed[..trimmed.len() - 1], 1024f64.powi(3)),
+        Some('T') if allow_size => (&trimmed[..trimmed.len() - 1], 1024f64.powi(4)),
+        _ => (trimmed, 1.0),
+    };
+    let number: f64 = digits.parse().map_err(|_| invalid())?;
+    if !number.is_finite() {
+        return Err(invalid());
+    }
+    Ok(number * multiplier)
+}
+
+fn build_term(term: Term) -> Result<Expr, FilterError> {
+    let Term {
+        name,
+        key,
+        op,
+        value,
+    } = term;
+    let invalid_op = || FilterError::InvalidOperator {
+        key: name.to_string(),
+        op,
+    };
+    match key {
+        Key::Text(key) => match op {
+            ":" => Ok(Expr::Text(key, TextMatch::Contains(value.to_lowercase()))),
+            "=" => Ok(Expr::Text(key, TextMatch::Equals(value.to_string()))),
+            "!=" => Ok(Expr::TextNe(key, value.to_string())),
+            "~" => Ok(Expr::Text(key, TextMatch::Regex(build_regex(value)?))),
+            _ => Err(invalid_op()),
+        },
+        Key::Num(key) => {
+            let op = match op {
+                ":" | "=" => CmpOp::Eq,
+                "!=" => CmpOp::Ne,
+                ">" => CmpOp::Gt,
+                ">=" => CmpOp::Ge,
+                "<" => CmpOp::Lt,
+                "<=" => CmpOp::Le,
+                _ => return Err(invalid_op()),
+            };
+            Ok(Expr::Num(key, op, parse_number(value, key.is_size())?))
+        }
+    }
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek_keyword(&self) -> Option<&'static str> {
+        match self.tokens.get(self.pos) {
+            Some(Token::Word(w)) => keyword(w),
+            _ => None,
+        }
+    }
+
+    /// or := and ("or" and)*
+    fn parse_or(&mut self) -> Result<Expr, FilterError> {
+        let mut lhs = self.parse_and()?;
+        while self.peek_keyword() == Some("or") {
+            self.pos += 1;
+            let rhs = self.parse_and()?;
+            lhs = Expr::Or(Box::new(lhs), Box::new(rhs));
+        }
+        Ok(lhs)
+    }
+
+    /// and := unary (["and"] unary)*
+    fn parse_and(&mut self) -> Result<Expr, FilterError> {
+        let mut lhs = self.parse_unary()?;
+        loop {
+            match self.tokens.get(self.pos) {
+                None | Some(Token::RParen) => break,
+                _ if self.peek_keyword() == Some("or") => break,
+                _ => {}
+            }
+            if self.peek_keyword() == Some("and") {
+                self.pos += 1;
+            }
+            let rhs = self.parse_unary()?;
+            lhs = Expr::And(Box::new(lhs), Box::new(rhs));
+        }
+        Ok(lhs)
+    }
+
+    /// unary := "not" unary | "(" or ")" | term
+    fn parse_unary(&mut self) -> Result<Expr, FilterError> {
+        let token = self
+            .tokens
+            .get(self.pos)
+            .cloned()
+            .ok_or(FilterError::UnexpectedEnd)?;
+        self.pos += 1;
+        match token {
+            Token::LParen => {
+                let expr = self.parse_or()?;
+                match self.tokens.get(self.pos) {
+                    Some(Token::RParen) => {
+                        self.pos += 1;
+                        Ok(expr)
+                    }
+                    Some(other) => Err(FilterError::Unexpected(other.to_string())),
+                    None => Err(FilterError::Unterminated("parenthesis")),
+                }
+            }
+            Token::RParen => Err(FilterError::Unexpected(")".to_string())),
+            Token::Quoted(text) => Ok(Expr::Text(
+                TextKey::Command,
+                TextMatch::Contains(text.to_lowercase()),
+            )),
+            Token::Regex(pattern) => Ok(Expr::Text(
+                TextKey::Command,
+                TextMatch::Regex(build_regex(&pattern)?),
+            )),
+            Token::Word(word) => match keyword(&word) {
+                Some("not") => Ok(Expr::Not(Box::new(self.parse_unary()?))),
+                Some(_) => Err(FilterError::Unexpected(word)),
+                None => match split_term(&word) {
+                    Some(term) => build_term(term),
+                    None => Ok(Expr::Text(
+                        TextKey::Command,
+                        TextMatch::Contains(word.to_lowercase()),
+                    )),
+                },
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::ProcessState;
+
+    fn process(pid: i32, user: &str, cmd: &str) -> Process {
+        let mut p = Process::new(pid);
+        p.user = Some(user.to_string());
+        p.cmdline = Some(cmd.to_string());
+        p
+    }
+
+    fn matches(filter: &str, p: &Process) -> bool {
+        ProcessFilter::parse(filter).unwrap().matches(p)
+    }
+
+    #[test]
+    fn test_plain_text_is_substring() {
+        let p = process(1, "root", "/usr/bin/Python3 manage.py runserver");
+        assert!(matches("python", &p));
+        assert!(matches("python3 manage", &p));
+        assert!(!matches("manage python3", &p));
+        // Unknown keys are not query syntax
+        assert!(!matches("http://localhost", &p));
+    }
+
+    #[test]
+    fn test_paths_are_not_regexes() {
+        let p = process(1, "root", "/opt/app/bin/server --tmp /tmp");
+        assert!(matches("/tmp", &p));
+        assert!(matches("/opt/app", &p));
+        assert!(!matches("/opt/other", &p));
+        assert!(matches("/opt/app/bin/", &p));
+        // A fully delimited regex is still one
+        assert!(matches("/SERV(ER)?/", &p));
+        assert!(matches("(/app/ and user:root)", &p));
+    }
+
+    #[test]
+    fn test_field_terms() {
+        let mut p = process(42, "postgres", "postgres: checkpointer");
+        p.state = ProcessState::UninterruptibleWait;
+        p.percent_cpu = 75.0;
+        p.m_resident = 2 * 1024 * 1024; // 2 GiB in KB
+        p.cgroup = Some("/system.slice/docker-abc.scope".to_string());
+
+        assert!(matches("user:postgres", &p));
+        assert!(!matches("user:post", &p));
+        assert!(matches("state:D", &p));
+        assert!(matches("state:ZD", &p));
+        assert!(!matches("state:R", &p));
+        assert!(matches("cpu>50", &p));
+        assert!(!matches("cpu<=50", &p));
+        assert!(matches("mem>1G", &p));
+        assert!(!matches("mem>3G", &p));
+        assert!(matches("cgroup~docker-[a-z]+", &p));
+        assert!(matches("pid=42", &p));
+        assert!(matches("pid!=1", &p));
+    }
+
+    #[test]
+    fn test_boolean_operators() {
+        let a = process(1, "postgres", "postgres: writer");
+        let b = process(2, "www", "nginx: worker");
+
+        let filter = ProcessFilter::parse("user:postgres or nginx").unwrap();
+        assert!(filter.matches(&a) && filter.matches(&b));
+
+        let filter = ProcessFilter::parse("not user:postgres").unwrap();
+        assert!(!filter.matches(&a) && filter.matches(&b));
+
+        let filter = ProcessFilter::parse("(user:www or pid=1) and /WORK(ER)?/").unwrap();
+        assert!(!filter.matches(&a) && filter.matches(&b));
+
+        // Adjacent terms are and-ed
+        let filter = ProcessFilter::parse("user:www pid=1").unwrap();
+        assert!(!filter.matches(&a) && !filter.matches(&b));
+    }
+
+    #[test]
+    fn test_parse_errors_and_fallback() {
+        assert!(ProcessFilter::parse("cpu>").is_err());
+        assert!(ProcessFilter::parse("cpu~5").is_err());
+        assert!(ProcessFilter::parse("(user:root").is_err());
+        assert!(ProcessFilter::parse("/[/").is_err());
+        assert!(ProcessFilter::parse("user:root or").is_err());
+
+        // Invalid queries fall back to a substring match while typing
+        let p = process(1, "root", "echo cpu>");
+        let filter = ProcessFilter::new("cpu>");
+        assert!(filter.matches(&p));
+        assert_eq!(
+            filter.error(),
+            Some(&FilterError::InvalidNumber(String::new()))
+        );
+        assert_eq!(ProcessFilter::new("user:root").error(), None);
+        assert_eq!(ProcessFilter::new("bash").error(), None);
+    }
+
+    #[test]
+    fn test_parse_number_suffixes() {
+        assert_eq!(parse_number("512", true), Ok(512.0));
+        assert_eq!(parse_number("1K", true), Ok(1024.0));
+        assert_eq!(parse_number("1.5M", true), Ok(1.5 * 1024.0 * 1024.0));
+        assert_eq!(parse_number("2GB", true), Ok(2.0 * 1024f64.powi(3)));
+        assert_eq!(parse_number("50%", false), Ok(50.0));
+        assert!(parse_number("1G", false).is_err());
+        assert!(parse_number("abc", true).is_err());
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_scan_flags() {
+        let filter = ProcessFilter::parse("cgroup~docker or io>1M").unwrap();
+        assert_eq!(filter.scan_flags(), ScanFlags::CGROUP | ScanFlags::IO);
+        assert!(ProcessFilter::new("bash").scan_flags().is_empty());
+    }
+}
@@ -20,6 +20,182 @@ pub type OptionalMemory = i64;
 /// Constant for "not available" optional memory value
 pub const MEMORY_NOT_AVAILABLE: OptionalMemory = -1;
 
+/// Busy time of one GPU engine class, summed over all DRM clients
+/// (like C htop's GPUEngineData)
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct GpuEngineData {
+    /// Engine name from the `drm-engine-<name>` fdinfo key (e.g. "gfx", "render")
+    pub name: String,
+    /// Cumulative busy time in nanoseconds
+    pub time_ns: u64,
+    /// Busy percentage over the last sampling interval
+    pub percent: f64,
+}
+
+/// ZFS ARC statistics from the arcstats kstat, sizes in KB
+/// (like C htop's ZfsArcStats)
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ZfsArcStats {
+    /// Whether ZFS is loaded and the kstat could be read
+    pub enabled: bool,
+    /// Whether ARC compression is in use (compressed_size is reported)
+    pub is_compressed: bool,
+    /// Minimum ARC size the cache will not shrink below (c_min)
+    pub min: u64,
+    /// Maximum ARC size (c_max)
+    pub max: u64,
+    /// Current ARC size
+    pub size: u64,
+    pub mfu: u64,
+    pub mru: u64,
+    pub anon: u64,
+    pub header: u64,
+    /// dbuf, dnode and bonus buffers (other_size on older ZFS versions)
+    pub other: u64,
+    pub compressed: u64,
+    pub uncompressed: u64,
+}
+
+impl ZfsArcStats {
+    /// Part of the ARC the kernel can reclaim under memory pressure
+    /// (the ARC does not shrink below c_min)
+    pub fn shrinkable(&self) -> u64 {
+        if self.enabled {
+            self.size.saturating_sub(self.min)
+        } else {
+            0
+        }
+    }
+
+    /// Compression ratio of the ARC (uncompressed / compressed)
+    pub fn compression_ratio(&self) -> f64 {
+        if self.compressed > 0 {
+            self.uncompressed as f64 / self.compressed as f64
+        } else {
+            1.0
+        }
+    }
+}
+
+/// Zram statistics summed over all zram devices, sizes in KB
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ZramStats {
+    /// Sum of the device sizes (disksize), 0 if there are no zram devices
+    pub total: u64,
+    /// Uncompressed size of the data stored on the devices
+    pub original: u64,
+    /// Memory used to hold the compressed data
+    pub compressed: u64,
+    /// Swap space on zram devices (from /proc/swaps)
+    pub swap_total: u64,
+    pub swap_used: u64,
+}
+
+impl ZramStats {
+    /// Compression ratio of the stored data (original / compressed)
+    pub fn compression_ratio(&self) -> f64 {
+        if self.compressed > 0 {
+            self.original as f64 / self.compressed as f64
+        } else {
+            1.0
+        }
+    }
+}
+
+/// One huge page pool, i.e. all huge pages of a given size
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct HugePageData {
+    /// Page size in KB
+    pub size_kb: u64,
+    /// Number of pages in the pool
+    pub total: u64,
+    /// Number of pages not in use
+    pub free: u64,
+}
+
+impl HugePageData {
+    /// Size of the pool in KB
+    pub fn total_kb(&self) -> u64 {
+        self.total * self.size_kb
+    }
+
+    /// Size of the pages in use in KB
+    pub fn used_kb(&self) -> u64 {
+        self.total.saturating_sub(self.free) * self.size_kb
+    }
+}
+
+/// IO statistics of a single block device
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DiskIoData {
+    /// Device name as in /proc/diskstats (e.g. "sda", "nvme0n1")
+    pub name: String,
+    pub read_bytes: u64,    // Total bytes read (cumulative)
+    pub write_bytes: u64,   // Total bytes written (cumulative)
+    pub ms_time_spend: u64, // Total ms spent on IO (cumulative)
+    pub read_rate: f64,     // Bytes per second
+    pub write_rate: f64,    // Bytes per second
+    pub utilization: f64,   // Percentage of time the device was busy
+}
+
+impl DiskIoData {
+    /// Compute the rates from the previous sample of the same device
+    pub fn update_rates(&mut self, prev: &DiskIoData, elapsed_ms: u64) {
+        if elapsed_ms == 0 {
+            return;
+        }
+        let elapsed = elapsed_ms as f64;
+        self.read_rate = self.read_bytes.saturating_sub(prev.read_bytes) as f64 * 1000.0 / elapsed;
+        self.write_rate =
+            self.write_bytes.saturating_sub(prev.write_bytes) as f64 * 1000.0 / elapsed;
+        self.utilization =
+            self.ms_time_spend.saturating_sub(prev.ms_time_spend) as f64 * 100.0 / elapsed;
+    }
+}
+
+/// IO statistics of a single network interface
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct NetIoData {
+    /// Interface name (e.g. "eth0")
+    pub name: String,
+    /// Loopback, veth, bridge or bond slave; left out of the totals since
+    /// its traffic is also counted on another interface
+    pub is_virtual: bool,
+    pub bytes_received: u64,      // Total bytes received (cumulative)
+    pub bytes_transmitted: u64,   // Total bytes transmitted (cumulative)
+    pub packets_received: u64,    // Total packets received (cumulative)
+    pub packets_transmitted: u64, // Total packets transmitted (cumulative)
+    pub receive_rate: f64,        // Bytes per second
+    pub transmit_rate: f64,       // Bytes per second
+    pub receive_packets: u64,     // Packets per second (rate)
+    pub transmit_packets: u64,    // Packets per second (rate)
+}
+
+impl NetIoData {
+    /// Compute the rates from the previous sample of the same interface
+    pub fn update_rates(&mut self, prev: &NetIoData, elapsed_ms: u64) {
+        if elapsed_ms == 0 {
+            return;
+        }
+        let per_second =
+            |cur: u64, prev: u64| cur.saturating_sub(prev) as f64 * 1000.0 / elapsed_ms as f64;
+        self.receive_rate = per_second(self.bytes_received, prev.bytes_received);
+        self.transmit_rate = per_second(self.bytes_transmitted, prev.bytes_transmitted);
+        self.receive_packets = per_second(self.packets_received, prev.packets_received) as u64;
+        self.transmit_packets =
+            per_second(self.packets_transmitted, prev.packets_transmitted) as u64;
+    }
+}
+
+/// CPU temperatures in Celsius read from the hardware sensors
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CpuTemperatures {
+    /// Hottest CPU package
+    pub package: Option<f32>,
+    /// Temperature of each logical CPU, indexed by CPU number
+    pub cpus: Vec<Option<f32>>,
+}
+
 /// CPU time data for a single CPU core
 #[derive(Debug, Clone, Default)]
 pub struct CpuData {
@@ -172,13 +348,22 @@ pub struct Machine {
     pub cached_mem: Memory,
     pub shared_mem: OptionalMemory, // -1 if not available (e.g., macOS)
     pub available_mem: OptionalMemory, // -1 if not available
-    pub compressed_mem: OptionalMemory, // -1 if not available (only macOS has this)
+    pub compressed_mem: OptionalMemory, // -1 if not available (macOS, zram on Linux)
 
     // Swap statistics (in KB)
     pub total_swap: Memory,
     pub used_swap: Memory,
     pub cached_swap: Memory,
 
+    // ZFS ARC statistics (disabled if ZFS is not loaded)
+    pub zfs: ZfsArcStats,
+
+    // Zram statistics (all zero if there are no zram devices)
+    pub zram: ZramStats,
+
+    // Huge page pools, sorted by page size
+    pub huge_pages: Vec<HugePageData>,
+
     // CPU information
     pub active_cpus: u32,
     pub existing_cpus: u32,
@@ -194,6 +379,7 @@ pub struct Machine {
     pub disk_io_write_rate: f64,    // Bytes per second
     pub disk_io_utilization: f64,   // Percentage utilization (across all disks)
     pub disk_io_last_update: u64,   // Last update time in ms
+    pub disks: Vec<DiskIoData>,     // Per-device statistics, in /proc/diskstats order
 
     // Network IO statistics
     pub net_io_bytes_received: u64, // Total bytes received (cumulative)
@@ -205,6 +391,17 @@ pub struct Machine {
     pub net_io_receive_packets: u64, // Packets per second (rate)
     pub net_io_transmit_packets: u64, // Packets per second (rate)
     pub net_io_last_update: u64,    // Last update time in ms
+    pub net_interfaces: Vec<NetIoData>, // Per-interface statistics, sorted by name
+    // Traffic of every network namespace at the last scan, for the per-process rates
+    #[cfg(target_os = "linux")]
+    pub net_namespace_counters: HashMap<u64, crate::platform::linux_bg_scanner::NetCounters>,
+
+    // GPU usage (from DRM fdinfo on Linux, empty if no DRM clients were seen)
+    pub gpu_engines: Vec<GpuEngineData>,
+    pub gpu_last_update: u64, // Time of the last GPU sample in ms
+    // Per-process GPU usage at the last sample, for the engine busy deltas
+    #[cfg(target_os = "linux")]
+    pub gpu_prev_usage: HashMap<i32, crate::platform::linux_gpu::DrmUsage>,
 
     // Users
     pub users_table: UsersTable,
@@ -244,6 +441,7 @@ pub struct Machine {
     // Settings copied from Settings for platform access
     pub update_process_names: bool,
     pub show_cpu_frequency: bool,
+    pub show_cpu_temperature: bool,
     pub hide_userland_threads: bool,
 
     /// Whether to check for deleted libraries (highlight_deleted_exe setting)
@@ -294,6 +492,9 @@ impl Machine {
             total_swap: 0,
             used_swap: 0,
             cached_swap: 0,
+            zfs: ZfsArcStats::default(),
+            zram: ZramStats::default(),
+            huge_pages: Vec::new(),
             active_cpus: 1,
             existing_cpus: 1,
             cpus: Vec::new(),
@@ -306,6 +507,7 @@ impl Machine {
             disk_io_write_rate: 0.0,
             disk_io_utilization: 0.0,
             disk_io_last_update: 0,
+            disks: Vec::new(),
             net_io_bytes_received: 0,
             net_io_bytes_transmitted: 0,
             net_io_packets_received: 0,
@@ -315,6 +517,13 @@ impl Machine {
             net_io_receive_packets: 0,
             net_io_transmit_packets: 0,
             net_io_last_update: 0,
+            net_interfaces: Vec::new(),
+            #[cfg(target_os = "linux")]
+            net_namespace_counters: HashMap::new(),
+            gpu_engines: Vec::new(),
+            gpu_last_update: 0,
+            #[cfg(target_os = "linux")]
+            gpu_prev_usage: HashMap::new(),
             users_table: UsersTable::new(),
             htop_user_id,
             max_user_id: 0,
@@ -338,6 +547,7 @@ impl Machine {
             fd_max: None,
             update_process_names: false,
             show_cpu_frequency: false,
+            show_cpu_temperature: false,
             hide_userland_threads: false,
             check_deleted_libs: true, // Default to true, matches highlight_deleted_exe default
             #[cfg(target_os = "linux")]
@@ -437,6 +647,20 @@ impl Machine {
         }
     }
 
+    /// IO statistics of a block device by name
+    pub fn disk(&self, name: &str) -> Option<&DiskIoData> {
+        self.disks.iter().find(|disk| disk.name == name)
+    }
+
+    /// Store sensor readings in the CPU data, the average CPU gets the
+    /// package temperature (None clears all temperatures)
+    pub fn set_cpu_temperatures(&mut self, temperatures: Option<&CpuTemperatures>) {
+        self.avg_cpu.temperature = temperatures.and_then(|t| t.package);
+        for (i, cpu) in self.cpus.iter_mut().enumerate() {
+            cpu.temperature = temperatures.and_then(|t| t.cpus.get(i).copied().flatten());
+        }
+    }
+
     /// Check if a CPU is online
     pub fn is_cpu_online(&self, id: usize) -> bool {
         self.cpus.get(id).map(|c| c.online).unwrap_or(false)
@@ -5,15 +5,33 @@
 //! - Machine: represents system state (CPU, memory, processes)
 //! - Settings: user configuration
 //! - FieldWidths: dynamic column width management
+//! - Snapshot: JSON serialization of scan results
+//! - Recording: record/replay log of scans
+//! - Affinity: CPU affinity sets
+//! - Environ: process environment parsing and comparison
+//! - Filter: process filter expressions
+//! - Cgroup: per-cgroup aggregation of the process list
 
 #![allow(dead_code)]
 
+mod affinity;
+mod cgroup;
+mod environ;
 mod field_widths;
+mod filter;
 mod machine;
 mod process;
+mod recording;
 mod settings;
+mod snapshot;
 
+pub use affinity::*;
+pub use cgroup::*;
+pub use environ::*;
 pub use field_widths::*;
+pub use filter::*;
 pub use machine::*;
 pub use process::*;
+pub use recording::*;
 pub use settings::*;
+pub use snapshot::*;
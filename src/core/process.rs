@@ -7,6 +7,9 @@
 
 use std::cmp::Ordering;
 
+use super::affinity::Affinity;
+use super::cgroup::CgroupUsage;
+
 /// Process state enum - core states shared by all platforms
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
 pub enum ProcessState {
@@ -115,6 +118,10 @@ bitflags::bitflags! {
         const CONTAINER    = 0x0020_0000;
         /// Read /proc/[pid]/statm for shared/text/data memory
         const STATM        = 0x0040_0000;
+        /// Get CPU affinity via sched_getaffinity
+        const AFFINITY     = 0x0080_0000;
+        /// Read /proc/[pid]/fd sockets and the network namespace counters
+        const NET          = 0x0100_0000;
     }
 }
 
@@ -250,6 +257,15 @@ pub enum ProcessField {
     GpuPercent = 133,
     #[cfg(target_os = "linux")]
     IsContainer = 134,
+    // htop-rs extension (not in C htop's RowField.h)
+    #[cfg(target_os = "linux")]
+    Affinity = 135,
+    #[cfg(target_os = "linux")]
+    NetRxRate = 136,
+    #[cfg(target_os = "linux")]
+    NetTxRate = 137,
+    #[cfg(target_os = "linux")]
+    Sockets = 138,
 
     // === macOS-specific fields - from darwin/ProcessField.h ===
     #[cfg(target_os = "macos")]
@@ -335,6 +351,10 @@ impl ProcessField {
                 ProcessField::GpuTime,
                 ProcessField::GpuPercent,
                 ProcessField::IsContainer,
+                ProcessField::Affinity,
+                ProcessField::NetRxRate,
+                ProcessField::NetTxRate,
+                ProcessField::Sockets,
             ]);
         }
 
@@ -460,6 +480,14 @@ impl ProcessField {
             ProcessField::GpuPercent => "GPU_PERCENT",
             #[cfg(target_os = "linux")]
             ProcessField::IsContainer => "ISCONTAINER",
+            #[cfg(target_os = "linux")]
+            ProcessField::Affinity => "AFFINITY",
+            #[cfg(target_os = "linux")]
+            ProcessField::NetRxRate => "NET_RX_RATE",
+            #[cfg(target_os = "linux")]
+            ProcessField::NetTxRate => "NET_TX_RATE",
+            #[cfg(target_os = "linux")]
+            ProcessField::Sockets => "SOCKETS",
 
             // macOS-specific fields
             #[cfg(target_os = "macos")]
@@ -604,6 +632,14 @@ impl ProcessField {
             ProcessField::GpuPercent => " GPU% ",
             #[cfg(target_os = "linux")]
             ProcessField::IsContainer => "CONT ",
+            #[cfg(target_os = "linux")]
+            ProcessField::Affinity => "AFFINITY   ",
+            #[cfg(target_os = "linux")]
+            ProcessField::NetRxRate => "   NET RECV ",
+            #[cfg(target_os = "linux")]
+            ProcessField::NetTxRate => "   NET SEND ",
+            #[cfg(target_os = "linux")]
+            ProcessField::Sockets => " SOCK ",
 
             // macOS-specific fields
             #[cfg(target_os = "macos")]
@@ -724,6 +760,14 @@ impl ProcessField {
             ProcessField::GpuPercent => "Percentage of the GPU time the process used in the last sampling",
             #[cfg(target_os = "linux")]
             ProcessField::IsContainer => "Whether the process is running inside a child container",
+            #[cfg(target_os = "linux")]
+            ProcessField::Affinity => "CPUs the process is allowed to run on",
+            #[cfg(target_os = "linux")]
+            ProcessField::NetRxRate => "Estimated network receive rate in bytes per second (namespace traffic shared by active sockets)",
+            #[cfg(target_os = "linux")]
+            ProcessField::NetTxRate => "Estimated network transmit rate in bytes per second (namespace traffic shared by active sockets)",
+            #[cfg(target_os = "linux")]
+            ProcessField::Sockets => "Number of open TCP, UDP and Unix sockets",
 
             // macOS-specific fields
             #[cfg(target_os = "macos")]
@@ -788,6 +832,14 @@ impl ProcessField {
             // Container detection uses cgroup info
             ProcessField::IsContainer => ScanFlags::CONTAINER,
 
+            // CPU affinity requires the sched_getaffinity syscall
+            ProcessField::Affinity => ScanFlags::AFFINITY,
+
+            // Network fields require reading /proc/[pid]/fd and the socket tables
+            ProcessField::NetRxRate | ProcessField::NetTxRate | ProcessField::Sockets => {
+                ScanFlags::NET
+            }
+
             // GPU fields require reading GPU-specific data
             ProcessField::GpuTime | ProcessField::GpuPercent => ScanFlags::GPU,
 
@@ -913,6 +965,14 @@ impl ProcessField {
             "GPU%" | "GPU_PERCENT" => Some(ProcessField::GpuPercent),
             #[cfg(target_os = "linux")]
             "CONT" | "ISCONTAINER" => Some(ProcessField::IsContainer),
+            #[cfg(target_os = "linux")]
+            "AFFINITY" => Some(ProcessField::Affinity),
+            #[cfg(target_os = "linux")]
+            "NET_RX_RATE" | "NET_RECV" => Some(ProcessField::NetRxRate),
+            #[cfg(target_os = "linux")]
+            "NET_TX_RATE" | "NET_SEND" => Some(ProcessField::NetTxRate),
+            #[cfg(target_os = "linux")]
+            "SOCKETS" | "SOCK" => Some(ProcessField::Sockets),
 
             // macOS-specific fields
             #[cfg(target_os = "macos")]
@@ -968,7 +1028,10 @@ impl ProcessField {
             | ProcessField::Ctxt
             | ProcessField::MPriv
             | ProcessField::GpuTime
-            | ProcessField::GpuPercent => true,
+            | ProcessField::GpuPercent
+            | ProcessField::NetRxRate
+            | ProcessField::NetTxRate
+            | ProcessField::Sockets => true,
 
             _ => false,
         }
@@ -1099,6 +1162,14 @@ impl ProcessField {
             133 => Some(ProcessField::GpuPercent),
             #[cfg(target_os = "linux")]
             134 => Some(ProcessField::IsContainer),
+            #[cfg(target_os = "linux")]
+            135 => Some(ProcessField::Affinity),
+            #[cfg(target_os = "linux")]
+            136 => Some(ProcessField::NetRxRate),
+            #[cfg(target_os = "linux")]
+            137 => Some(ProcessField::NetTxRate),
+            #[cfg(target_os = "linux")]
+            138 => Some(ProcessField::Sockets),
 
             // macOS-specific fields
             #[cfg(target_os = "macos")]
@@ -1221,6 +1292,14 @@ impl ProcessField {
             ProcessField::GpuPercent => 133,
             #[cfg(target_os = "linux")]
             ProcessField::IsContainer => 134,
+            #[cfg(target_os = "linux")]
+            ProcessField::Affinity => 135,
+            #[cfg(target_os = "linux")]
+            ProcessField::NetRxRate => 136,
+            #[cfg(target_os = "linux")]
+            ProcessField::NetTxRate => 137,
+            #[cfg(target_os = "linux")]
+            ProcessField::Sockets => 138,
 
             // macOS-specific fields
             #[cfg(target_os = "macos")]
@@ -1339,6 +1418,10 @@ pub struct Process {
     pub show_children: bool,
     pub is_visible: bool,
     pub is_root: bool, // True if this is a root process in the tree
+    /// Topmost process of a container when the tree is grouped by container
+    pub is_container_root: bool,
+    /// Usage of the whole container, set on its first root
+    pub container_usage: Option<CgroupUsage>,
 
     // I/O statistics (from /proc/[pid]/io on Linux)
     pub io_rchar: u64,                 // Characters read (includes cached)
@@ -1389,6 +1472,19 @@ pub struct Process {
     // Security
     pub sec_attr: Option<String>,
 
+    // CPU affinity (Linux-specific, from sched_getaffinity)
+    pub cpu_affinity: Option<Affinity>,
+
+    // GPU usage (Linux-specific, from DRM fdinfo)
+    pub gpu_time: u64,              // Total GPU busy time in nanoseconds
+    pub gpu_percent: f32,           // GPU busy % over the last sample (NaN = N/A)
+    pub gpu_last_scan_time_ms: u64, // Last sample time for percent calculation
+
+    // Network usage (Linux-specific, from sockets and namespace counters)
+    pub net_rx_rate_bps: f64, // Estimated receive rate in bytes/sec (NaN = N/A)
+    pub net_tx_rate_bps: f64, // Estimated transmit rate in bytes/sec (NaN = N/A)
+    pub socket_count: i64,    // TCP, UDP and Unix sockets (-1 = N/A)
+
     // For display
     pub merged_command: MergedCommand,
 
@@ -1461,6 +1557,8 @@ impl Process {
             show_children: true,
             is_visible: true,
             is_root: false,
+            is_container_root: false,
+            container_usage: None,
             io_rchar: 0,
             io_wchar: 0,
             io_syscr: 0,
@@ -1489,6 +1587,13 @@ impl Process {
             autogroup_nice: 0,
             translated: false,
             sec_attr: None,
+            cpu_affinity: None,
+            gpu_time: 0,
+            gpu_percent: f32::NAN,
+            gpu_last_scan_time_ms: 0,
+            net_rx_rate_bps: f64::NAN,
+            net_tx_rate_bps: f64::NAN,
+            socket_count: -1,
             merged_command: MergedCommand::default(),
             updated: false,
             was_shown: false,
@@ -1537,6 +1642,22 @@ impl Process {
         self.is_kernel_thread || self.is_userland_thread
     }
 
+    /// Check if the security attribute marks the process as not confined
+    /// by a mandatory access control policy: the AppArmor label
+    /// "unconfined" (or a profile in unconfined mode) or an SELinux
+    /// context with an unconfined_* type
+    pub fn is_unconfined(&self) -> bool {
+        let Some(attr) = self.sec_attr.as_deref() else {
+            return false;
+        };
+        // SELinux context: user:role:type[:level]
+        let mut context = attr.split(':');
+        match (context.next(), context.next(), context.next()) {
+            (Some(_), Some(_), Some(selinux_type)) => selinux_type.starts_with("unconfined_"),
+            _ => attr == "unconfined" || attr.ends_with(" (unconfined)"),
+        }
+    }
+
     /// Get the group or parent ID for sorting purposes
     /// This matches C htop's Row_getGroupOrParent():
     /// - For threads (tgid != pid): returns tgid (thread group leader's PID)
@@ -1684,12 +1805,34 @@ impl Process {
             ProcessField::Command | ProcessField::ProcComm => {
                 self.get_command().cmp(other.get_command())
             }
+            #[cfg(target_os = "linux")]
+            ProcessField::Affinity => self.cpu_affinity.cmp(&other.cpu_affinity),
+            #[cfg(target_os = "linux")]
+            ProcessField::GpuTime => self.gpu_time.cmp(&other.gpu_time),
+            #[cfg(target_os = "linux")]
+            ProcessField::GpuPercent => self
+                .gpu_percent
+                .partial_cmp(&other.gpu_percent)
+                .unwrap_or(Ordering::Equal),
+            #[cfg(target_os = "linux")]
+            ProcessField::NetRxRate => self
+                .net_rx_rate_bps
+                .partial_cmp(&other.net_rx_rate_bps)
+                .unwrap_or(Ordering::Equal),
+            #[cfg(target_os = "linux")]
+            ProcessField::NetTxRate => self
+                .net_tx_rate_bps
+                .partial_cmp(&other.net_tx_rate_bps)
+                .unwrap_or(Ordering::Equal),
+            #[cfg(target_os = "linux")]
+            ProcessField::Sockets => self.socket_count.cmp(&other.socket_count),
             _ => self.pid.cmp(&other.pid),
         }
     }
 
-    /// Format a field value as a string for display
-    pub fn format_field(&self, field: ProcessField, width: usize) -> String {
+    /// Format a field value as a string for display. `active_cpus` is the
+    /// number of online CPUs NCPU% is normalized by.
+    pub fn format_field(&self, field: ProcessField, width: usize, active_cpus: u32) -> String {
         match field {
             ProcessField::Pid => format!("{:>width$}", self.pid, width = width),
             ProcessField::Ppid => format!("{:>width$}", self.ppid, width = width),
@@ -1711,6 +1854,14 @@ impl Process {
                     format!("{:>4.0}", self.percent_cpu)
                 }
             }
+            ProcessField::PercentNormCpu => {
+                let norm_cpu = self.percent_cpu / active_cpus.max(1) as f32;
+                if norm_cpu.is_finite() && norm_cpu >= 0.0 {
+                    format!("{:>width$.1}", norm_cpu, width = width)
+                } else {
+                    format!("{:>width$}", "N/A", width = width)
+                }
+            }
             ProcessField::PercentMem => format!("{:>4.1}", self.percent_mem),
             ProcessField::MSize => Self::format_memory(self.m_virt),
             ProcessField::MResident => Self::format_memory(self.m_resident),
@@ -1721,10 +1872,293 @@ impl Process {
             ProcessField::Nlwp => format!("{:>4}", self.nlwp),
             ProcessField::Processor => format!("{:>3}", self.processor),
             ProcessField::Tty => self.tty_name.as_deref().unwrap_or("?").to_string(),
+            ProcessField::Pgrp => format!("{:>width$}", self.pgrp, width = width),
+            ProcessField::Session => format!("{:>width$}", self.session, width = width),
+            ProcessField::Tpgid => format!("{:>width$}", self.tpgid, width = width),
+            ProcessField::Tgid => {
+                // Same derivation as the TGID column in MainPanel
+                let tgid = if self.is_userland_thread {
+                    self.ppid
+                } else {
+                    self.pid
+                };
+                format!("{:>width$}", tgid, width = width)
+            }
+            ProcessField::StUid => format!("{:>width$}", self.uid, width = width),
+            ProcessField::Minflt => format!("{:>11}", self.minflt),
+            ProcessField::Majflt => format!("{:>11}", self.majflt),
+            ProcessField::Starttime => {
+                // Same layout as the START column: HH:MM today, MmmDD this year, else YYYY
+                use chrono::{Local, TimeZone};
+                match Local.timestamp_opt(self.starttime_ctime, 0).single() {
+                    Some(dt) if self.starttime_ctime > 0 => {
+                        let age_seconds = Local::now().timestamp() - self.starttime_ctime;
+                        if age_seconds < 86400 {
+                            dt.format("%H:%M").to_string()
+                        } else if age_seconds < 364 * 86400 {
+                            dt.format("%b%d").to_string()
+                        } else {
+                            dt.format("%Y").to_string()
+                        }
+                    }
+                    _ => "N/A".to_string(),
+                }
+            }
+            ProcessField::Elapsed => {
+                let now = std::time::SystemTime::now()
+                    .duration_since(std::time::UNIX_EPOCH)
+                    .map(|d| d.as_secs() as i64)
+                    .unwrap_or(0);
+                let elapsed_secs = now.saturating_sub(self.starttime_ctime).max(0) as u64;
+                Self::format_time(elapsed_secs * 100)
+            }
+            ProcessField::SchedulerPolicy => {
+                let policy = match self.scheduling_policy {
+                    0 => "OTHER",
+                    1 => "FIFO",
+                    2 => "RR",
+                    3 => "BATCH",
+                    5 => "IDLE",
+                    6 => "EDF",
+                    -1 => "N/A",
+                    _ => "???",
+                };
+                policy.to_string()
+            }
+            ProcessField::ProcComm => self.comm.as_deref().unwrap_or("?").to_string(),
+            ProcessField::ProcExe => match self.exe.as_deref() {
+                Some(exe) => exe.rsplit('/').next().unwrap_or(exe).to_string(),
+                None if self.is_kernel_thread => "kernel".to_string(),
+                None => "N/A".to_string(),
+            },
+            ProcessField::Cwd => self.cwd.as_deref().unwrap_or("N/A").to_string(),
+
+            #[cfg(target_os = "linux")]
+            ProcessField::Cminflt => format!("{:>11}", self.cminflt),
+            #[cfg(target_os = "linux")]
+            ProcessField::Cmajflt => format!("{:>11}", self.cmajflt),
+            #[cfg(target_os = "linux")]
+            ProcessField::Utime => Self::format_time(self.utime),
+            #[cfg(target_os = "linux")]
+            ProcessField::Stime => Self::format_time(self.stime),
+            #[cfg(target_os = "linux")]
+            ProcessField::Cutime => Self::format_time(self.cutime),
+            #[cfg(target_os = "linux")]
+            ProcessField::Cstime => Self::format_time(self.cstime),
+            #[cfg(target_os = "linux")]
+            ProcessField::MText => Self::format_memory(self.m_text),
+            #[cfg(target_os = "linux")]
+            ProcessField::MData => Self::format_memory(self.m_data),
+            #[cfg(target_os = "linux")]
+            ProcessField::MLib => {
+                if self.m_lib == 0 {
+                    format!("{:>6}", "N/A")
+                } else {
+                    Self::format_memory(self.m_lib)
+                }
+            }
+            #[cfg(target_os = "linux")]
+            ProcessField::MPriv => {
+                Self::format_memory(self.m_resident.saturating_sub(self.m_share))
+            }
+            #[cfg(target_os = "linux")]
+            ProcessField::MPss => Self::format_optional_memory(self.m_pss),
+            #[cfg(target_os = "linux")]
+            ProcessField::MSwap => Self::format_optional_memory(self.m_swap),
+            #[cfg(target_os = "linux")]
+            ProcessField::MPsswp => Self::format_optional_memory(self.m_psswp),
+            #[cfg(target_os = "linux")]
+            ProcessField::Rchar => Self::format_io_bytes(self.io_rchar),
+            #[cfg(target_os = "linux")]
+            ProcessField::Wchar => Self::format_io_bytes(self.io_wchar),
+            #[cfg(target_os = "linux")]
+            ProcessField::Rbytes => Self::format_io_bytes(self.io_read_bytes),
+            #[cfg(target_os = "linux")]
+            ProcessField::Wbytes => Self::format_io_bytes(self.io_write_bytes),
+            #[cfg(target_os = "linux")]
+            ProcessField::Cnclwb => Self::format_io_bytes(self.io_cancelled_write_bytes),
+            #[cfg(target_os = "linux")]
+            ProcessField::Syscr => Self::format_io_count(self.io_syscr),
+            #[cfg(target_os = "linux")]
+            ProcessField::Syscw => Self::format_io_count(self.io_syscw),
+            #[cfg(target_os = "linux")]
+            ProcessField::IOReadRate => Self::format_rate(self.io_rate_read_bps),
+            #[cfg(target_os = "linux")]
+            ProcessField::IOWriteRate => Self::format_rate(self.io_rate_write_bps),
+            #[cfg(target_os = "linux")]
+            ProcessField::IORate => {
+                let read = if self.io_rate_read_bps.is_nan() {
+                    0.0
+                } else {
+                    self.io_rate_read_bps
+                };
+                let write = if self.io_rate_write_bps.is_nan() {
+                    0.0
+                } else {
+                    self.io_rate_write_bps
+                };
+                Self::format_rate(read + write)
+            }
+            #[cfg(target_os = "linux")]
+            ProcessField::IOPriority => self.format_io_priority(),
+            #[cfg(target_os = "linux")]
+            ProcessField::CGroup => self.cgroup.as_deref().unwrap_or("?").to_string(),
+            #[cfg(target_os = "linux")]
+            ProcessField::CCGroup => self
+                .cgroup_short
+                .as_deref()
+                .or(self.cgroup.as_deref())
+                .unwrap_or("?")
+                .to_string(),
+            #[cfg(target_os = "linux")]
+            ProcessField::Container => self.container_short.as_deref().unwrap_or("/").to_string(),
+            #[cfg(target_os = "linux")]
+            ProcessField::IsContainer => {
+                let is_container = self
+                    .container_short
+                    .as_ref()
+                    .map(|s| !s.is_empty() && s != "/")
+                    .unwrap_or(false);
+                let value = if is_container { "YES" } else { "NO" };
+                value.to_string()
+            }
+            #[cfg(target_os = "linux")]
+            ProcessField::Oom => format!("{:>5}", self.oom_score),
+            #[cfg(target_os = "linux")]
+            ProcessField::PercentCpuDelay => Self::format_delay(self.cpu_delay_percent),
+            #[cfg(target_os = "linux")]
+            ProcessField::PercentIODelay => Self::format_delay(self.blkio_delay_percent),
+            #[cfg(target_os = "linux")]
+            ProcessField::PercentSwapDelay => Self::format_delay(self.swapin_delay_percent),
+            #[cfg(target_os = "linux")]
+            ProcessField::Ctxt => format!("{:>6}", self.ctxt_switches),
+            #[cfg(target_os = "linux")]
+            ProcessField::GpuTime => Self::format_time(self.gpu_time / 10_000_000),
+            #[cfg(target_os = "linux")]
+            ProcessField::GpuPercent => Self::format_delay(self.gpu_percent),
+            #[cfg(target_os = "linux")]
+            ProcessField::NetRxRate => Self::format_rate(self.net_rx_rate_bps),
+            #[cfg(target_os = "linux")]
+            ProcessField::NetTxRate => Self::format_rate(self.net_tx_rate_bps),
+            #[cfg(target_os = "linux")]
+            ProcessField::Sockets => {
+                if self.socket_count < 0 {
+                    format!("{:>5}", "N/A")
+                } else {
+                    format!("{:>5}", self.socket_count)
+                }
+            }
+            #[cfg(target_os = "linux")]
+            ProcessField::SecAttr => self.sec_attr.as_deref().unwrap_or("?").to_string(),
+            #[cfg(target_os = "linux")]
+            ProcessField::Affinity => self
+                .cpu_affinity
+                .as_ref()
+                .map_or_else(|| "N/A".to_string(), |a| a.to_string()),
+            #[cfg(target_os = "linux")]
+            ProcessField::AutogroupId => {
+                if self.autogroup_id == -1 {
+                    format!("{:>4}", "N/A")
+                } else {
+                    format!("{:>4}", self.autogroup_id)
+                }
+            }
+            #[cfg(target_os = "linux")]
+            ProcessField::AutogroupNice => {
+                if self.autogroup_id == -1 {
+                    "N/A".to_string()
+                } else {
+                    format!("{:>3}", self.autogroup_nice)
+                }
+            }
+
+            #[cfg(target_os = "macos")]
+            ProcessField::Translated => {
+                let value = if self.translated { "T" } else { "N" };
+                value.to_string()
+            }
+
+            // Every field has an arm on Linux, other platforms lack some
+            #[cfg(not(target_os = "linux"))]
             _ => "?".to_string(),
         }
     }
 
+    /// Format a memory value that may be unavailable (negative = N/A)
+    #[cfg(target_os = "linux")]
+    fn format_optional_memory(kb: i64) -> String {
+        if kb < 0 {
+            format!("{:>6}", "N/A")
+        } else {
+            Self::format_memory(kb)
+        }
+    }
+
+    /// Format an IO byte counter (u64::MAX = N/A)
+    #[cfg(target_os = "linux")]
+    fn format_io_bytes(bytes: u64) -> String {
+        if bytes == u64::MAX {
+            format!("{:>6}", "N/A")
+        } else {
+            Self::format_memory((bytes / 1024) as i64)
+        }
+    }
+
+    /// Format an IO syscall counter (u64::MAX = N/A)
+    #[cfg(target_os = "linux")]
+    fn format_io_count(count: u64) -> String {
+        if count == u64::MAX {
+            format!("{:>11}", "N/A")
+        } else {
+            format!("{:>11}", count)
+        }
+    }
+
+    /// Format a byte rate with a unit suffix (matches Row_printRate units)
+    pub fn format_rate(rate: f64) -> String {
+        if !rate.is_finite() || rate < 0.0 {
+            return format!("{:>11}", "N/A");
+        }
+        const UNITS: [&str; 5] = ["B/s", "K/s", "M/s", "G/s", "T/s"];
+        let mut value = rate;
+        let mut unit = 0;
+        while value >= 1024.0 && unit < UNITS.len() - 1 {
+            value /= 1024.0;
+            unit += 1;
+        }
+        format!("{:>7.2} {}", value, UNITS[unit])
+    }
+
+    /// Format a delay accounting percentage (NaN = N/A)
+    #[cfg(target_os = "linux")]
+    fn format_delay(percent: f32) -> String {
+        if percent.is_nan() {
+            format!("{:>5}", "N/A")
+        } else {
+            format!("{:>5.1}", percent)
+        }
+    }
+
+    /// Format IO priority as Bn/Rn/id (matches the IO_PRIORITY column)
+    #[cfg(target_os = "linux")]
+    fn format_io_priority(&self) -> String {
+        use crate::platform::linux::{
+            ioprio_class, ioprio_data, IOPRIO_CLASS_BE, IOPRIO_CLASS_IDLE, IOPRIO_CLASS_NONE,
+            IOPRIO_CLASS_RT,
+        };
+
+        if self.io_priority < 0 {
+            return "??".to_string();
+        }
+        match ioprio_class(self.io_priority) {
+            IOPRIO_CLASS_NONE => format!("B{}", (self.nice + 20) / 5),
+            IOPRIO_CLASS_BE => format!("B{}", ioprio_data(self.io_priority)),
+            IOPRIO_CLASS_RT => format!("R{}", ioprio_data(self.io_priority)),
+            IOPRIO_CLASS_IDLE => "id".to_string(),
+            _ => "??".to_string(),
+        }
+    }
+
     /// Format memory value with human-readable units
     pub fn format_memory(kb: i64) -> String {
         if kb < 1000 {
@@ -2424,6 +2858,8 @@ pub struct ProcessList {
     /// Note: Does NOT store indices - use linear search for position lookup
     pub by_pid: std::collections::HashSet<i32>,
     pub tree_display_order: Vec<i32>, // PIDs in tree display order
+    /// Root the tree at each container instead of the runtime's shim process
+    pub group_by_container: bool,
 }
 
 impl ProcessList {
@@ -2432,6 +2868,7 @@ impl ProcessList {
             processes: Vec::new(),
             by_pid: std::collections::HashSet::new(),
             tree_display_order: Vec::new(),
+            group_by_container: false,
         }
     }
 
@@ -2584,15 +3021,34 @@ impl ProcessList {
         // First, mark root processes and set all as visible
         let pids: std::collections::HashSet<i32> = self.processes.iter().map(|p| p.pid).collect();
 
+        // Container of every process, to find the topmost process of each container
+        let group_by_container = self.group_by_container;
+        let containers: std::collections::HashMap<i32, Option<String>> = if group_by_container {
+            self.processes
+                .iter()
+                .map(|p| (p.pid, p.container_short.clone()))
+                .collect()
+        } else {
+            std::collections::HashMap::new()
+        };
+
         for process in &mut self.processes {
             // A process is a root if:
             // 1. ppid == 0 (truly root), OR
             // 2. ppid == pid (self-parented), OR
             // 3. ppid doesn't exist AND ppid != 1 (orphan, but not if parent is init/launchd)
+            // 4. it is the topmost process of a container (when grouping by container)
             // Note: On macOS, we may not have permission to see PID 1 (launchd),
             // but we know it exists, so treat ppid=1 processes as children of an implicit root
             let is_orphan = !pids.contains(&process.ppid) && process.ppid != 1;
-            process.is_root = process.ppid == 0 || process.ppid == process.pid || is_orphan;
+            process.is_container_root = group_by_container
+                && process.container_short.is_some()
+                && containers.get(&process.ppid) != Some(&process.container_short);
+            process.container_usage = None;
+            process.is_root = process.ppid == 0
+                || process.ppid == process.pid
+                || is_orphan
+                || process.is_container_root;
             process.indent = 0;
             process.tree_depth = 0;
             process.is_visible = true; // Reset visibility
@@ -2604,17 +3060,47 @@ impl ProcessList {
             let b_parent = if b.is_root { 0 } else { b.ppid };
             match a_parent.cmp(&b_parent) {
                 std::cmp::Ordering::Equal => {
-                    let cmp = a.compare_by_field(b, sort_key);
-                    if ascending {
-                        cmp
-                    } else {
-                        cmp.reverse()
-                    }
+                    // Containers follow the host tree, ordered by name
+                    let by_container = a
+                        .is_container_root
+                        .then_some(&a.container_short)
+                        .cmp(&b.is_container_root.then_some(&b.container_short));
+                    by_container.then_with(|| {
+                        let cmp = a.compare_by_field(b, sort_key);
+                        if ascending {
+                            cmp
+                        } else {
+                            cmp.reverse()
+                        }
+                    })
                 }
                 other => other,
             }
         });
 
+        // Sum up the usage of every container for its first root
+        if group_by_container {
+            let mut usage: std::collections::HashMap<String, CgroupUsage> =
+                std::collections::HashMap::new();
+            for process in &self.processes {
+                if let Some(ref container) = process.container_short {
+                    if !process.is_userland_thread {
+                        usage
+                            .entry(container.clone())
+                            .or_default()
+                            .add_process(process);
+                    }
+                }
+            }
+            for process in &mut self.processes {
+                if process.is_container_root {
+                    if let Some(container) = process.container_short.as_ref() {
+                        process.container_usage = usage.remove(container);
+                    }
+                }
+            }
+        }
+
         // No need to rebuild by_pid - it only tracks existence, not position
 
         // Build display list in tree order
@@ -2719,7 +3205,7 @@ impl ProcessList {
             .processes
             .iter()
             .enumerate()
-            .filter(|(_, p)| p.ppid == parent_id && p.pid != parent_id)
+            .filter(|(_, p)| p.ppid == parent_id && p.pid != parent_id && !p.is_container_root)
             .map(|(i, p)| (i, p.pid))
             .collect();
 
@@ -3018,6 +3504,10 @@ mod tests {
         assert_eq!(ProcessField::CGroup.name(), "CGROUP");
         assert_eq!(ProcessField::Oom.name(), "OOM");
         assert_eq!(ProcessField::IOPriority.name(), "IO_PRIORITY");
+        assert_eq!(ProcessField::Affinity.name(), "AFFINITY");
+        assert_eq!(ProcessField::NetRxRate.name(), "NET_RX_RATE");
+        assert_eq!(ProcessField::NetTxRate.name(), "NET_TX_RATE");
+        assert_eq!(ProcessField::Sockets.name(), "SOCKETS");
     }
 
     #[test]
@@ -3194,6 +3684,22 @@ mod tests {
             ProcessField::from_name("IO_RATE"),
             Some(ProcessField::IORate)
         );
+        assert_eq!(
+            ProcessField::from_name("AFFINITY"),
+            Some(ProcessField::Affinity)
+        );
+        assert_eq!(
+            ProcessField::from_name("NET_RECV"),
+            Some(ProcessField::NetRxRate)
+        );
+        assert_eq!(
+            ProcessField::from_name("NET_TX_RATE"),
+            Some(ProcessField::NetTxRate)
+        );
+        assert_eq!(
+            ProcessField::from_name("SOCKETS"),
+            Some(ProcessField::Sockets)
+        );
     }
 
     #[test]
@@ -3364,8 +3870,86 @@ mod tests {
         assert!(set.contains(&ProcessField::Command));
     }
 
+    // ==================== Format Field Tests ====================
+
+    #[test]
+    fn test_format_field_pid_columns_use_width() {
+        let mut p = Process::new(42);
+        p.ppid = 1;
+        p.pgrp = 42;
+        p.session = 7;
+        assert_eq!(p.format_field(ProcessField::Pid, 5, 1), "   42");
+        assert_eq!(p.format_field(ProcessField::Ppid, 5, 1), "    1");
+        assert_eq!(p.format_field(ProcessField::Pgrp, 6, 1), "    42");
+        assert_eq!(p.format_field(ProcessField::Session, 3, 1), "  7");
+    }
+
+    #[test]
+    fn test_format_field_percent_norm_cpu() {
+        let mut p = Process::new(1);
+        p.percent_cpu = 150.0;
+        assert_eq!(p.format_field(ProcessField::PercentNormCpu, 5, 4), " 37.5");
+        assert_eq!(p.format_field(ProcessField::PercentNormCpu, 5, 1), "150.0");
+        // No CPU count yet: not normalized
+        assert_eq!(p.format_field(ProcessField::PercentNormCpu, 5, 0), "150.0");
+        p.percent_cpu = f32::NAN;
+        assert_eq!(p.format_field(ProcessField::PercentNormCpu, 5, 4), "  N/A");
+    }
+
+    #[test]
+    fn test_format_field_text_columns() {
+        let mut p = Process::new(1);
+        p.comm = Some("bash".to_string());
+        p.exe = Some("/usr/bin/bash".to_string());
+        p.scheduling_policy = 1;
+        assert_eq!(p.format_field(ProcessField::ProcComm, 0, 1), "bash");
+        assert_eq!(p.format_field(ProcessField::ProcExe, 0, 1), "bash");
+        assert_eq!(p.format_field(ProcessField::Cwd, 0, 1), "N/A");
+        assert_eq!(p.format_field(ProcessField::SchedulerPolicy, 0, 1), "FIFO");
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_format_field_linux_unavailable_values() {
+        let p = Process::new(1);
+        // Fresh processes have not been scanned by the background scanner yet
+        assert_eq!(p.format_field(ProcessField::MPss, 0, 1).trim(), "N/A");
+        assert_eq!(p.format_field(ProcessField::IOPriority, 0, 1), "??");
+        assert_eq!(p.format_field(ProcessField::IOReadRate, 0, 1).trim(), "N/A");
+        assert_eq!(p.format_field(ProcessField::Container, 0, 1), "/");
+        assert_eq!(p.format_field(ProcessField::GpuPercent, 0, 1).trim(), "N/A");
+        assert_eq!(p.format_field(ProcessField::GpuTime, 0, 1), "0:00.00");
+        assert_eq!(p.format_field(ProcessField::NetRxRate, 0, 1).trim(), "N/A");
+        assert_eq!(p.format_field(ProcessField::Sockets, 0, 1).trim(), "N/A");
+    }
+
+    #[test]
+    fn test_format_rate_units() {
+        assert_eq!(Process::format_rate(512.0), " 512.00 B/s");
+        assert_eq!(Process::format_rate(2048.0), "   2.00 K/s");
+        assert_eq!(Process::format_rate(f64::NAN).trim(), "N/A");
+    }
+
     // ==================== Tree Building Tests ====================
 
+    #[test]
+    fn test_process_is_unconfined() {
+        let with_attr = |attr: Option<&str>| Process {
+            sec_attr: attr.map(str::to_string),
+            ..Process::new(1)
+        };
+        assert!(!with_attr(None).is_unconfined());
+        assert!(with_attr(Some("unconfined")).is_unconfined());
+        assert!(with_attr(Some("unprivileged_userns (unconfined)")).is_unconfined());
+        assert!(!with_attr(Some("/usr/sbin/cupsd (enforce)")).is_unconfined());
+        assert!(with_attr(Some(
+            "unconfined_u:unconfined_r:unconfined_t:s0-s0:c0.c1023"
+        ))
+        .is_unconfined());
+        assert!(with_attr(Some("system_u:system_r:unconfined_service_t:s0")).is_unconfined());
+        assert!(!with_attr(Some("system_u:system_r:sshd_t:s0-s0:c0.c1023")).is_unconfined());
+    }
+
     #[test]
     fn test_tree_building() {
         let mut pl = ProcessList::new();
@@ -3617,4 +4201,63 @@ mod tests {
             "Slack helper should have non-zero indent"
         );
     }
+
+    #[test]
+    fn test_tree_group_by_container() {
+        // init -> containerd-shim -> nginx (web) -> worker (web)
+        //      -> containerd-shim -> postgres (db)
+        //      -> sshd
+        let add = |pl: &mut ProcessList, pid: i32, ppid: i32, container: Option<&str>, cpu: f32| {
+            let process = Process {
+                ppid,
+                container_short: container.map(str::to_string),
+                percent_cpu: cpu,
+                m_resident: 1000,
+                nlwp: 1,
+                ..Process::new(pid)
+            };
+            pl.add(process, 0);
+        };
+        let mut pl = ProcessList::new();
+        add(&mut pl, 1, 0, None, 0.0);
+        add(&mut pl, 10, 1, None, 0.0);
+        add(&mut pl, 11, 10, Some("docker:web"), 10.0);
+        add(&mut pl, 12, 11, Some("docker:web"), 5.0);
+        add(&mut pl, 20, 1, None, 0.0);
+        add(&mut pl, 21, 20, Some("docker:db"), 2.0);
+        add(&mut pl, 30, 1, None, 0.0);
+
+        pl.build_tree(ProcessField::Pid, true);
+        assert_eq!(pl.tree_display_order, vec![1, 10, 11, 12, 20, 21, 30]);
+        assert!(pl.get(11).unwrap().container_usage.is_none());
+
+        pl.group_by_container = true;
+        pl.build_tree(ProcessField::Pid, true);
+        // Host tree first, then one tree per container ordered by name
+        assert_eq!(pl.tree_display_order, vec![1, 10, 20, 30, 21, 11, 12]);
+
+        let nginx = pl.get(11).unwrap();
+        assert!(nginx.is_root && nginx.is_container_root);
+        assert_eq!(nginx.tree_depth, 0);
+        let usage = nginx.container_usage.as_ref().unwrap();
+        assert_eq!(usage.percent_cpu, 15.0);
+        assert_eq!(usage.m_resident, 2000);
+        assert_eq!(usage.tasks, 2);
+
+        let worker = pl.get(12).unwrap();
+        assert!(!worker.is_container_root);
+        assert_eq!(worker.tree_depth, 1);
+        assert!(worker.container_usage.is_none());
+
+        assert_eq!(
+            pl.get(21)
+                .unwrap()
+                .container_usage
+                .as_ref()
+                .unwrap()
+                .percent_cpu,
+            2.0
+        );
+        assert!(pl.get(10).unwrap().container_usage.is_none());
+    }
 }
@@ -0,0 +1,1012 @@
+//! Record and replay of scan sessions
+//!
+//! `--record=FILE` appends every scan (system stats plus the full process
+//! list) to a compact binary log; `--replay=FILE` loads it back so the UI
+//! can step through the recorded frames with the usual views.
+//!
+//! File layout:
+//! - header: the magic bytes `HTOPREC` followed by a format version byte
+//! - frames: a varint payload length followed by the payload
+//!
+//! Integers are stored as LEB128 varints (zigzag for signed values) and
+//! floats as little-endian bytes. Strings are interned in a table shared by
+//! the whole file: each frame first lists the strings it introduces, then
+//! refers to strings by index, so command lines, users and cgroups are only
+//! written once per session.
+
+use std::collections::HashMap;
+use std::fs::{File, OpenOptions};
+use std::io::{self, BufWriter, Read, Write};
+use std::path::Path;
+use std::time::{Duration, UNIX_EPOCH};
+
+use super::affinity::Affinity;
+use super::machine::{
+    CpuData, DiskIoData, GpuEngineData, HugePageData, Machine, NetIoData, ZfsArcStats,
+};
+use super::process::{Process, ProcessState, Tristate};
+
+/// Magic bytes at the start of a recording
+const MAGIC: &[u8; 7] = b"HTOPREC";
+
+/// Recording format version
+const VERSION: u8 = 8;
+
+/// Process states in encoding order
+const STATES: [ProcessState; 14] = [
+    ProcessState::Unknown,
+    ProcessState::Runnable,
+    ProcessState::Running,
+    ProcessState::Queued,
+    ProcessState::Waiting,
+    ProcessState::UninterruptibleWait,
+    ProcessState::Blocked,
+    ProcessState::Paging,
+    ProcessState::Stopped,
+    ProcessState::Traced,
+    ProcessState::Zombie,
+    ProcessState::Defunct,
+    ProcessState::Idle,
+    ProcessState::Sleeping,
+];
+
+fn invalid_data(msg: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg.to_string())
+}
+
+/// Field visitor shared by the encoder and the decoder, so the record layout
+/// is defined once: the encoder reads each value, the decoder overwrites it.
+trait Codec {
+    fn u64(&mut self, v: &mut u64) -> io::Result<()>;
+    fn f32(&mut self, v: &mut f32) -> io::Result<()>;
+    fn f64(&mut self, v: &mut f64) -> io::Result<()>;
+    fn string(&mut self, v: &mut Option<String>) -> io::Result<()>;
+
+    fn i64(&mut self, v: &mut i64) -> io::Result<()> {
+        // Zigzag encoding keeps small negative values (e.g. -1 = N/A) short
+        let mut raw = ((*v << 1) ^ (*v >> 63)) as u64;
+        self.u64(&mut raw)?;
+        *v = ((raw >> 1) as i64) ^ -((raw & 1) as i64);
+        Ok(())
+    }
+
+    fn u32(&mut self, v: &mut u32) -> io::Result<()> {
+        let mut raw = *v as u64;
+        self.u64(&mut raw)?;
+        *v = u32::try_from(raw).map_err(|_| invalid_data("u32 out of range"))?;
+        Ok(())
+    }
+
+    fn i32(&mut self, v: &mut i32) -> io::Result<()> {
+        let mut raw = *v as i64;
+        self.i64(&mut raw)?;
+        *v = i32::try_from(raw).map_err(|_| invalid_data("i32 out of range"))?;
+        Ok(())
+    }
+
+    fn usize(&mut self, v: &mut usize) -> io::Result<()> {
+        let mut raw = *v as u64;
+        self.u64(&mut raw)?;
+        *v = raw as usize;
+        Ok(())
+    }
+
+    fn bool(&mut self, v: &mut bool) -> io::Result<()> {
+        let mut raw = *v as u64;
+        self.u64(&mut raw)?;
+        *v = raw != 0;
+        Ok(())
+    }
+
+    /// Optional counter, stored as value + 1 (0 = None)
+    fn opt_u64(&mut self, v: &mut Option<u64>) -> io::Result<()> {
+        let mut raw = v.map_or(0, |n| n.saturating_add(1));
+        self.u64(&mut raw)?;
+        *v = raw.checked_sub(1);
+        Ok(())
+    }
+
+    fn opt_f32(&mut self, v: &mut Option<f32>) -> io::Result<()> {
+        let mut present = v.is_some();
+        self.bool(&mut present)?;
+        if present {
+            let mut value = v.unwrap_or(0.0);
+            self.f32(&mut value)?;
+            *v = Some(value);
+        } else {
+            *v = None;
+        }
+        Ok(())
+    }
+
+    fn text(&mut self, v: &mut String) -> io::Result<()> {
+        let mut value = Some(std::mem::take(v));
+        self.string(&mut value)?;
+        *v = value.unwrap_or_default();
+        Ok(())
+    }
+
+    fn state(&mut self, v: &mut ProcessState) -> io::Result<()> {
+        let mut raw = STATES.iter().position(|s| s == v).unwrap_or(0) as u64;
+        self.u64(&mut raw)?;
+        *v = *STATES
+            .get(raw as usize)
+            .ok_or_else(|| invalid_data("invalid process state"))?;
+        Ok(())
+    }
+
+    /// CPU affinity, stored as a kernel CPU list string
+    fn affinity(&mut self, v: &mut Option<Affinity>) -> io::Result<()> {
+        let mut list = v.as_ref().map(|a| a.to_string());
+        self.string(&mut list)?;
+        *v = match list {
+            Some(list) => Some(
+                list.parse()
+                    .map_err(|_| invalid_data("invalid CPU affinity"))?,
+            ),
+            None => None,
+        };
+        Ok(())
+    }
+
+    fn tristate(&mut self, v: &mut Tristate) -> io::Result<()> {
+        let mut raw = match v {
+            Tristate::Initial => 0,
+            Tristate::Off => 1,
+            Tristate::On => 2,
+        };
+        self.u64(&mut raw)?;
+        *v = match raw {
+            0 => Tristate::Initial,
+            1 => Tristate::Off,
+            2 => Tristate::On,
+            _ => return Err(invalid_data("invalid tristate")),
+        };
+        Ok(())
+    }
+}
+
+/// Per-CPU record
+fn cpu_fields<C: Codec>(c: &mut C, cpu: &mut CpuData) -> io::Result<()> {
+    c.u64(&mut cpu.user_time)?;
+    c.u64(&mut cpu.nice_time)?;
+    c.u64(&mut cpu.system_time)?;
+    c.u64(&mut cpu.idle_time)?;
+    c.u64(&mut cpu.iowait_time)?;
+    c.u64(&mut cpu.irq_time)?;
+    c.u64(&mut cpu.softirq_time)?;
+    c.u64(&mut cpu.steal_time)?;
+    c.u64(&mut cpu.guest_time)?;
+    c.u64(&mut cpu.guest_nice_time)?;
+    c.f64(&mut cpu.user_percent)?;
+    c.f64(&mut cpu.nice_percent)?;
+    c.f64(&mut cpu.system_percent)?;
+    c.f64(&mut cpu.irq_percent)?;
+    c.f64(&mut cpu.softirq_percent)?;
+    c.f64(&mut cpu.steal_percent)?;
+    c.f64(&mut cpu.guest_percent)?;
+    c.f64(&mut cpu.iowait_percent)?;
+    c.f64(&mut cpu.total_percent)?;
+    c.bool(&mut cpu.online)?;
+    c.f64(&mut cpu.frequency)?;
+    c.opt_f32(&mut cpu.temperature)
+}
+
+/// System-wide record (everything the header meters read)
+fn machine_fields<C: Codec>(c: &mut C, m: &mut Machine) -> io::Result<()> {
+    c.u64(&mut m.realtime_ms)?;
+    c.u64(&mut m.monotonic_ms)?;
+
+    c.u64(&mut m.total_mem)?;
+    c.u64(&mut m.used_mem)?;
+    c.u64(&mut m.buffers_mem)?;
+    c.u64(&mut m.cached_mem)?;
+    c.i64(&mut m.shared_mem)?;
+    c.i64(&mut m.available_mem)?;
+    c.i64(&mut m.compressed_mem)?;
+    c.u64(&mut m.total_swap)?;
+    c.u64(&mut m.used_swap)?;
+    c.u64(&mut m.cached_swap)?;
+    zfs_fields(c, &mut m.zfs)?;
+    c.u64(&mut m.zram.total)?;
+    c.u64(&mut m.zram.original)?;
+    c.u64(&mut m.zram.compressed)?;
+    c.u64(&mut m.zram.swap_total)?;
+    c.u64(&mut m.zram.swap_used)?;
+    let mut num_huge_pages = m.huge_pages.len();
+    c.usize(&mut num_huge_pages)?;
+    m.huge_pages.resize(num_huge_pages, HugePageData::default());
+    for pool in &mut m.huge_pages {
+        c.u64(&mut pool.size_kb)?;
+        c.u64(&mut pool.total)?;
+        c.u64(&mut pool.free)?;
+    }
+
+    c.u32(&mut m.active_cpus)?;
+    c.u32(&mut m.existing_cpus)?;
+    let mut num_cpus = m.cpus.len();
+    c.usize(&mut num_cpus)?;
+    m.cpus.resize(num_cpus, CpuData::default());
+    for cpu in &mut m.cpus {
+        cpu_fields(c, cpu)?;
+    }
+    cpu_fields(c, &mut m.avg_cpu)?;
+
+    c.u64(&mut m.disk_io_read_bytes)?;
+    c.u64(&mut m.disk_io_write_bytes)?;
+    c.u64(&mut m.disk_io_ms_time_spend)?;
+    c.u64(&mut m.disk_io_num_disks)?;
+    c.f64(&mut m.disk_io_read_rate)?;
+    c.f64(&mut m.disk_io_write_rate)?;
+    c.f64(&mut m.disk_io_utilization)?;
+    c.u64(&mut m.disk_io_last_update)?;
+    let mut num_disks = m.disks.len();
+    c.usize(&mut num_disks)?;
+    m.disks.resize(num_disks, DiskIoData::default());
+    for disk in &mut m.disks {
+        c.text(&mut disk.name)?;
+        c.u64(&mut disk.read_bytes)?;
+        c.u64(&mut disk.write_bytes)?;
+        c.u64(&mut disk.ms_time_spend)?;
+        c.f64(&mut disk.read_rate)?;
+        c.f64(&mut disk.write_rate)?;
+        c.f64(&mut disk.utilization)?;
+    }
+
+    c.u64(&mut m.net_io_bytes_received)?;
+    c.u64(&mut m.net_io_bytes_transmitted)?;
+    c.u64(&mut m.net_io_packets_received)?;
+    c.u64(&mut m.net_io_packets_transmitted)?;
+    c.f64(&mut m.net_io_receive_rate)?;
+    c.f64(&mut m.net_io_transmit_rate)?;
+    c.u64(&mut m.net_io_receive_packets)?;
+    c.u64(&mut m.net_io_transmit_packets)?;
+    c.u64(&mut m.net_io_last_update)?;
+    let mut num_interfaces = m.net_interfaces.len();
+    c.usize(&mut num_interfaces)?;
+    m.net_interfaces
+        .resize(num_interfaces, NetIoData::default());
+    for net in &mut m.net_interfaces {
+        c.text(&mut net.name)?;
+        c.bool(&mut net.is_virtual)?;
+        c.u64(&mut net.bytes_received)?;
+        c.u64(&mut net.bytes_transmitted)?;
+        c.u64(&mut net.packets_received)?;
+        c.u64(&mut net.packets_transmitted)?;
+        c.f64(&mut net.receive_rate)?;
+        c.f64(&mut net.transmit_rate)?;
+        c.u64(&mut net.receive_packets)?;
+        c.u64(&mut net.transmit_packets)?;
+    }
+
+    let mut num_engines = m.gpu_engines.len();
+    c.usize(&mut num_engines)?;
+    m.gpu_engines.resize(num_engines, GpuEngineData::default());
+    for engine in &mut m.gpu_engines {
+        c.text(&mut engine.name)?;
+        c.u64(&mut engine.time_ns)?;
+        c.f64(&mut engine.percent)?;
+    }
+    c.u64(&mut m.gpu_last_update)?;
+
+    c.u32(&mut m.running_tasks)?;
+    c.u32(&mut m.total_tasks)?;
+    c.u32(&mut m.userland_threads)?;
+    c.u32(&mut m.kernel_threads)?;
+    c.i32(&mut m.max_pid)?;
+
+    c.text(&mut m.hostname)?;
+    c.text(&mut m.kernel_version)?;
+    let mut uptime_ms = m.uptime.as_millis() as u64;
+    c.u64(&mut uptime_ms)?;
+    m.uptime = Duration::from_millis(uptime_ms);
+    for load in &mut m.load_average {
+        c.f64(load)?;
+    }
+    c.i64(&mut m.boot_time)?;
+    c.opt_u64(&mut m.fd_used)?;
+    c.opt_u64(&mut m.fd_max)
+}
+
+/// ZFS ARC record
+fn zfs_fields<C: Codec>(c: &mut C, z: &mut ZfsArcStats) -> io::Result<()> {
+    c.bool(&mut z.enabled)?;
+    c.bool(&mut z.is_compressed)?;
+    c.u64(&mut z.min)?;
+    c.u64(&mut z.max)?;
+    c.u64(&mut z.size)?;
+    c.u64(&mut z.mfu)?;
+    c.u64(&mut z.mru)?;
+    c.u64(&mut z.anon)?;
+    c.u64(&mut z.header)?;
+    c.u64(&mut z.other)?;
+    c.u64(&mut z.compressed)?;
+    c.u64(&mut z.uncompressed)
+}
+
+/// Per-process record (scanned data only; UI state such as tags and
+/// collapsed tree nodes is not recorded)
+fn process_fields<C: Codec>(c: &mut C, p: &mut Process) -> io::Result<()> {
+    c.i32(&mut p.pid)?;
+    c.i32(&mut p.ppid)?;
+    c.i32(&mut p.tgid)?;
+    c.i32(&mut p.pgrp)?;
+    c.i32(&mut p.session)?;
+    c.i32(&mut p.tpgid)?;
+    c.u64(&mut p.tty_nr)?;
+    c.string(&mut p.tty_name)?;
+    c.u32(&mut p.uid)?;
+    c.string(&mut p.user)?;
+
+    c.bool(&mut p.is_kernel_thread)?;
+    c.bool(&mut p.is_userland_thread)?;
+    c.tristate(&mut p.is_running_in_container)?;
+    c.tristate(&mut p.elevated_priv)?;
+
+    c.string(&mut p.cmdline)?;
+    c.usize(&mut p.cmdline_basename_start)?;
+    c.usize(&mut p.cmdline_basename_end)?;
+    c.string(&mut p.comm)?;
+    c.string(&mut p.exe)?;
+    c.usize(&mut p.exe_basename_offset)?;
+    c.bool(&mut p.exe_deleted)?;
+    c.bool(&mut p.uses_deleted_lib)?;
+    c.string(&mut p.cwd)?;
+
+    c.i32(&mut p.processor)?;
+    c.f32(&mut p.percent_cpu)?;
+    c.f32(&mut p.percent_mem)?;
+    c.i64(&mut p.priority)?;
+    c.i64(&mut p.nice)?;
+    c.i32(&mut p.scheduling_policy)?;
+
+    c.u64(&mut p.time)?;
+    c.i64(&mut p.starttime_ctime)?;
+    c.text(&mut p.starttime_show)?;
+    c.u64(&mut p.utime)?;
+    c.u64(&mut p.stime)?;
+    c.u64(&mut p.cutime)?;
+    c.u64(&mut p.cstime)?;
+    c.u64(&mut p.cminflt)?;
+    c.u64(&mut p.cmajflt)?;
+
+    c.i64(&mut p.m_virt)?;
+    c.i64(&mut p.m_resident)?;
+    c.i64(&mut p.m_share)?;
+    c.i64(&mut p.m_text)?;
+    c.i64(&mut p.m_lib)?;
+    c.i64(&mut p.m_data)?;
+    c.i64(&mut p.m_dirty)?;
+    c.u64(&mut p.minflt)?;
+    c.u64(&mut p.majflt)?;
+
+    c.state(&mut p.state)?;
+    c.i64(&mut p.nlwp)?;
+
+    c.u64(&mut p.io_rchar)?;
+    c.u64(&mut p.io_wchar)?;
+    c.u64(&mut p.io_syscr)?;
+    c.u64(&mut p.io_syscw)?;
+    c.u64(&mut p.io_read_bytes)?;
+    c.u64(&mut p.io_write_bytes)?;
+    c.u64(&mut p.io_cancelled_write_bytes)?;
+    c.f64(&mut p.io_rate_read_bps)?;
+    c.f64(&mut p.io_rate_write_bps)?;
+
+    c.f32(&mut p.cpu_delay_percent)?;
+    c.f32(&mut p.blkio_delay_percent)?;
+    c.f32(&mut p.swapin_delay_percent)?;
+    c.u64(&mut p.ctxt_switches)?;
+
+    c.string(&mut p.cgroup)?;
+    c.string(&mut p.cgroup_short)?;
+    c.string(&mut p.container_short)?;
+    c.i32(&mut p.oom_score)?;
+    c.i32(&mut p.io_priority)?;
+    c.i64(&mut p.m_pss)?;
+    c.i64(&mut p.m_swap)?;
+    c.i64(&mut p.m_psswp)?;
+    c.i64(&mut p.autogroup_id)?;
+    c.i32(&mut p.autogroup_nice)?;
+    c.bool(&mut p.translated)?;
+    c.string(&mut p.sec_attr)?;
+    c.affinity(&mut p.cpu_affinity)?;
+    c.u64(&mut p.gpu_time)?;
+    c.f32(&mut p.gpu_percent)?;
+    c.f64(&mut p.net_rx_rate_bps)?;
+    c.f64(&mut p.net_tx_rate_bps)?;
+    c.i64(&mut p.socket_count)
+}
+
+// ==================== Encoding ====================
+
+/// Append a LEB128 varint
+fn put_varint(buf: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        buf.push((value as u8) | 0x80);
+        value >>= 7;
+    }
+    buf.push(value as u8);
+}
+
+/// Encoder for one frame; new strings are collected so they can be written
+/// ahead of the records that reference them
+struct Encoder<'a> {
+    buf: Vec<u8>,
+    strings: &'a mut HashMap<String, u64>,
+    new_strings: Vec<String>,
+}
+
+impl Codec for Encoder<'_> {
+    fn u64(&mut self, v: &mut u64) -> io::Result<()> {
+        put_varint(&mut self.buf, *v);
+        Ok(())
+    }
+
+    fn f32(&mut self, v: &mut f32) -> io::Result<()> {
+        self.buf.extend_from_slice(&v.to_le_bytes());
+        Ok(())
+    }
+
+    fn f64(&mut self, v: &mut f64) -> io::Result<()> {
+        self.buf.extend_from_slice(&v.to_le_bytes());
+        Ok(())
+    }
+
+    fn string(&mut self, v: &mut Option<String>) -> io::Result<()> {
+        // 0 = None, otherwise string table index + 1
+        let reference = match v {
+            None => 0,
+            Some(s) => match self.strings.get(s.as_str()) {
+                Some(&id) => id + 1,
+                None => {
+                    let id = self.strings.len() as u64;
+                    self.strings.insert(s.clone(), id);
+                    self.new_strings.push(s.clone());
+                    id + 1
+                }
+            },
+        };
+        put_varint(&mut self.buf, reference);
+        Ok(())
+    }
+}
+
+/// Encode one scan into a frame payload
+fn encode_frame(machine: &mut Machine, strings: &mut HashMap<String, u64>) -> Vec<u8> {
+    let mut enc = Encoder {
+        buf: Vec::with_capacity(64 * 1024),
+        strings,
+        new_strings: Vec::new(),
+    };
+
+    // Encoding never fails: the encoder only reads values
+    let _ = machine_fields(&mut enc, machine);
+    put_varint(&mut enc.buf, machine.processes.processes.len() as u64);
+    for process in &mut machine.processes.processes {
+        let _ = process_fields(&mut enc, process);
+    }
+
+    let mut payload = Vec::with_capacity(enc.buf.len() + 1024);
+    put_varint(&mut payload, enc.new_strings.len() as u64);
+    for s in &enc.new_strings {
+        put_varint(&mut payload, s.len() as u64);
+        payload.extend_from_slice(s.as_bytes());
+    }
+    payload.extend_from_slice(&enc.buf);
+    payload
+}
+
+/// Appends scans to a recording file
+pub struct Recorder {
+    writer: BufWriter<File>,
+    strings: HashMap<String, u64>,
+}
+
+impl Recorder {
+    /// Open a recording for appending, creating it if needed.
+    /// Appending to an existing recording continues its string table.
+    pub fn open(path: &Path) -> io::Result<Self> {
+        let mut strings = HashMap::new();
+        let existing = std::fs::read(path).unwrap_or_default();
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        if !existing.is_empty() {
+            let index = index_frames(&existing)?;
+            // Drop a partially written last frame so new frames stay readable
+            file.set_len(index.valid_len as u64)?;
+            for (id, s) in index.strings.into_iter().enumerate() {
+                strings.insert(s, id as u64);
+            }
+        }
+
+        let mut writer = BufWriter::new(file);
+        if existing.is_empty() {
+            writer.write_all(MAGIC)?;
+            writer.write_all(&[VERSION])?;
+        }
+
+        Ok(Recorder { writer, strings })
+    }
+
+    /// Append the current scan
+    pub fn record(&mut self, machine: &mut Machine) -> io::Result<()> {
+        let payload = encode_frame(machine, &mut self.strings);
+        let mut len = Vec::with_capacity(10);
+        put_varint(&mut len, payload.len() as u64);
+        self.writer.write_all(&len)?;
+        self.writer.write_all(&payload)?;
+        self.writer.flush()
+    }
+}
+
+// ==================== Decoding ====================
+
+/// Cursor over a byte slice
+struct Reader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Reader { data, pos: 0 }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.pos >= self.data.len()
+    }
+
+    fn bytes(&mut self, len: usize) -> io::Result<&'a [u8]> {
+        let end = self
+            .pos
+            .checked_add(len)
+            .filter(|&end| end <= self.data.len())
+            .ok_or_else(|| invalid_data("truncated recording"))?;
+        let bytes = &self.data[self.pos..end];
+        self.pos = end;
+        Ok(bytes)
+    }
+
+    fn varint(&mut self) -> io::Result<u64> {
+        let mut value = 0u64;
+        for shift in (0..64).step_by(7) {
+            let byte = self.bytes(1)?[0];
+            value |= ((byte & 0x7f) as u64) << shift;
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+        Err(invalid_data("varint too long"))
+    }
+}
+
+/// Decoder for one frame payload
+struct Decoder<'a> {
+    reader: Reader<'a>,
+    strings: &'a [String],
+}
+
+impl Codec for Decoder<'_> {
+    fn u64(&mut self, v: &mut u64) -> io::Result<()> {
+        *v = self.reader.varint()?;
+        Ok(())
+    }
+
+    fn f32(&mut self, v: &mut f32) -> io::Result<()> {
+        let bytes = self.reader.bytes(4)?;
+        *v = f32::from_le_bytes(bytes.try_into().unwrap_or_default());
+        Ok(())
+    }
+
+    fn f64(&mut self, v: &mut f64) -> io::Result<()> {
+        let bytes = self.reader.bytes(8)?;
+        *v = f64::from_le_bytes(bytes.try_into().unwrap_or_default());
+        Ok(())
+    }
+
+    fn string(&mut self, v: &mut Option<String>) -> io::Result<()> {
+        *v = match self.reader.varint()? {
+            0 => None,
+            reference => Some(
+                self.strings
+                    .get(reference as usize - 1)
+                    .ok_or_else(|| invalid_data("invalid string reference"))?
+                    .clone(),
+            ),
+        };
+        Ok(())
+    }
+}
+
+/// Frame offsets and the complete string table of a recording
+struct FrameIndex {
+    /// (start, end) of each frame's records, after its string definitions
+    frames: Vec<(usize, usize)>,
+    strings: Vec<String>,
+    /// Length of the file up to the end of the last complete frame
+    valid_len: usize,
+}
+
+/// Read one frame: define its strings and return the (start, end) of its
+/// records relative to the reader's data
+fn read_frame(reader: &mut Reader, strings: &mut Vec<String>) -> io::Result<(usize, usize)> {
+    let len = reader.varint()? as usize;
+    let start = reader.pos;
+    let mut frame = Reader::new(reader.bytes(len)?);
+    let count = frame.varint()?;
+    for _ in 0..count {
+        let len = frame.varint()? as usize;
+        let bytes = frame.bytes(len)?;
+        strings.push(String::from_utf8_lossy(bytes).into_owned());
+    }
+    Ok((start + frame.pos, start + len))
+}
+
+/// Walk a recording once, collecting frame boundaries and interned strings
+fn index_frames(data: &[u8]) -> io::Result<FrameIndex> {
+    let header_len = MAGIC.len() + 1;
+    if !data.starts_with(MAGIC) || data.len() < header_len {
+        return Err(invalid_data("not an htop recording"));
+    }
+    if data[MAGIC.len()] != VERSION {
+        return Err(invalid_data("unsupported recording version"));
+    }
+
+    let mut index = FrameIndex {
+        frames: Vec::new(),
+        strings: Vec::new(),
+        valid_len: header_len,
+    };
+    let mut reader = Reader::new(&data[header_len..]);
+    while !reader.is_empty() {
+        let num_strings = index.strings.len();
+        match read_frame(&mut reader, &mut index.strings) {
+            Ok((start, end)) => {
+                index.frames.push((header_len + start, header_len + end));
+                index.valid_len = header_len + reader.pos;
+            }
+            Err(_) => {
+                // The last frame was cut short (e.g. htop was killed while
+                // writing it): keep everything before it
+                index.strings.truncate(num_strings);
+                break;
+            }
+        }
+    }
+    Ok(index)
+}
+
+/// A recording loaded for replay
+pub struct Replay {
+    data: Vec<u8>,
+    index: FrameIndex,
+    position: usize,
+}
+
+impl Replay {
+    /// Load a recording
+    pub fn open(path: &Path) -> io::Result<Self> {
+        let mut data = Vec::new();
+        File::open(path)?.read_to_end(&mut data)?;
+        let index = index_frames(&data)?;
+        if index.frames.is_empty() {
+            return Err(invalid_data("recording contains no frames"));
+        }
+        Ok(Replay {
+            data,
+            index,
+            position: 0,
+        })
+    }
+
+    /// Number of recorded frames
+    pub fn len(&self) -> usize {
+        self.index.frames.len()
+    }
+
+    /// Whether the recording has no frames (never true for an opened replay)
+    pub fn is_empty(&self) -> bool {
+        self.index.frames.is_empty()
+    }
+
+    /// Index of the current frame
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Whether the current frame is the last one
+    pub fn at_end(&self) -> bool {
+        self.position + 1 >= self.len()
+    }
+
+    /// Move by `delta` frames, clamped to the recording.
+    /// Returns true if the position changed.
+    pub fn step(&mut self, delta: isize) -> bool {
+        let target = (self.position as isize + delta).clamp(0, self.len() as isize - 1) as usize;
+        let changed = target != self.position;
+        self.position = target;
+        changed
+    }
+
+    /// Load the current frame into the machine, in place of a platform scan
+    pub fn load(&self, machine: &mut Machine) -> io::Result<()> {
+        let (start, end) = self.index.frames[self.position];
+        let mut dec = Decoder {
+            reader: Reader::new(&self.data[start..end]),
+            strings: &self.index.strings,
+        };
+
+        machine.prev_monotonic_ms = machine.monotonic_ms;
+        machine_fields(&mut dec, machine)?;
+        machine.realtime = UNIX_EPOCH + Duration::from_millis(machine.realtime_ms);
+
+        let count = dec.reader.varint()?;
+        let mut max_uid = 0;
+        let mut max_percent_cpu: f32 = 0.0;
+        for _ in 0..count {
+            let mut process = Process::new(0);
+            process_fields(&mut dec, &mut process)?;
+            process.updated = true;
+            max_uid = max_uid.max(process.uid);
+            max_percent_cpu = max_percent_cpu.max(process.percent_cpu);
+
+            // Keep UI state of processes that are already listed
+            let reappeared = match machine.processes.get(process.pid) {
+                Some(existing) => {
+                    process.tagged = existing.tagged;
+                    process.show_children = existing.show_children;
+                    existing.tomb_stamp_ms > 0
+                }
+                None => false,
+            };
+            let pid = process.pid;
+            machine.processes.add(process, machine.monotonic_ms);
+            if reappeared {
+                // Stepping backwards can bring an exited process back
+                if let Some(p) = machine.processes.get_mut(pid) {
+                    p.tomb_stamp_ms = 0;
+                }
+            }
+        }
+
+        machine.field_widths.reset_auto_widths();
+        machine.field_widths.set_pid_width(machine.max_pid);
+        machine.field_widths.set_uid_width(max_uid);
+        machine
+            .field_widths
+            .update_percent_cpu_width(max_percent_cpu);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::ZramStats;
+
+    fn temp_path(name: &str) -> std::path::PathBuf {
+        std::env::temp_dir().join(format!("htop-rs-{}-{}.rec", name, std::process::id()))
+    }
+
+    fn sample_machine() -> Machine {
+        let mut machine = Machine {
+            realtime_ms: 1_700_000_000_000,
+            total_mem: 16 * 1024 * 1024,
+            used_mem: 4 * 1024 * 1024,
+            available_mem: -1,
+            hostname: "db01".to_string(),
+            load_average: [1.5, 0.75, 0.25],
+            fd_used: Some(1024),
+            fd_max: None,
+            cpus: vec![CpuData::default(); 2],
+            ..Default::default()
+        };
+        machine.cpus[1].total_percent = 87.5;
+        machine.cpus[1].temperature = Some(61.0);
+        machine.gpu_engines = vec![GpuEngineData {
+            name: "gfx".to_string(),
+            time_ns: 5_000_000_000,
+            percent: 42.5,
+        }];
+        machine.zfs = ZfsArcStats {
+            enabled: true,
+            max: 8 * 1024 * 1024,
+            size: 4 * 1024 * 1024,
+            mfu: 1024 * 1024,
+            ..Default::default()
+        };
+        machine.zram = ZramStats {
+            total: 4 * 1024 * 1024,
+            original: 1024 * 1024,
+            compressed: 256 * 1024,
+            swap_total: 4 * 1024 * 1024,
+            swap_used: 1024 * 1024,
+        };
+        machine.disks = vec![DiskIoData {
+            name: "nvme0n1".to_string(),
+            read_bytes: 1 << 30,
+            read_rate: 4096.0,
+            utilization: 12.5,
+            ..Default::default()
+        }];
+        machine.net_interfaces = vec![NetIoData {
+            name: "eth0".to_string(),
+            bytes_received: 1 << 20,
+            receive_rate: 2048.0,
+            transmit_packets: 12,
+            ..Default::default()
+        }];
+        machine.huge_pages = vec![HugePageData {
+            size_kb: 2048,
+            total: 512,
+            free: 128,
+        }];
+
+        let mut p = Process::new(4242);
+        p.ppid = 1;
+        p.user = Some("postgres".to_string());
+        p.cmdline = Some("postgres: checkpointer".to_string());
+        p.state = ProcessState::Sleeping;
+        p.percent_cpu = 12.5;
+        p.m_resident = 81920;
+        p.m_pss = -1;
+        p.cpu_affinity = Some("0-1".parse().unwrap());
+        p.gpu_time = 5_000_000_000;
+        p.gpu_percent = 42.5;
+        p.net_rx_rate_bps = 2048.0;
+        p.socket_count = 12;
+        machine.processes.add(p, 0);
+        machine
+    }
+
+    #[test]
+    fn test_varint_roundtrip() {
+        for value in [0u64, 1, 127, 128, 300, u32::MAX as u64, u64::MAX] {
+            let mut buf = Vec::new();
+            put_varint(&mut buf, value);
+            assert_eq!(Reader::new(&buf).varint().unwrap(), value);
+        }
+    }
+
+    #[test]
+    fn test_record_and_replay_roundtrip() {
+        let path = temp_path("roundtrip");
+        let _ = std::fs::remove_file(&path);
+
+        let mut machine = sample_machine();
+        let mut recorder = Recorder::open(&path).unwrap();
+        recorder.record(&mut machine).unwrap();
+        machine.realtime_ms += 1500;
+        machine.processes.processes[0].percent_cpu = 99.0;
+        recorder.record(&mut machine).unwrap();
+        drop(recorder);
+
+        let mut replay = Replay::open(&path).unwrap();
+        assert_eq!(replay.len(), 2);
+
+        let mut target = Machine::default();
+        replay.load(&mut target).unwrap();
+        assert_eq!(target.hostname, "db01");
+        assert_eq!(target.total_mem, 16 * 1024 * 1024);
+        assert_eq!(target.available_mem, -1);
+        assert_eq!(target.load_average, [1.5, 0.75, 0.25]);
+        assert_eq!(target.fd_used, Some(1024));
+        assert_eq!(target.fd_max, None);
+        assert_eq!(target.cpus.len(), 2);
+        assert_eq!(target.cpus[1].total_percent, 87.5);
+        assert_eq!(target.cpus[1].temperature, Some(61.0));
+        assert_eq!(target.gpu_engines[0].name, "gfx");
+        assert_eq!(target.gpu_engines[0].percent, 42.5);
+        assert_eq!(target.zfs, machine.zfs);
+        assert_eq!(target.zram, machine.zram);
+        assert_eq!(target.net_interfaces, machine.net_interfaces);
+        assert_eq!(target.disk("nvme0n1"), machine.disk("nvme0n1"));
+        assert_eq!(target.huge_pages, machine.huge_pages);
+
+        let p = target.processes.get(4242).unwrap();
+        assert_eq!(p.user.as_deref(), Some("postgres"));
+        assert_eq!(p.cmdline.as_deref(), Some("postgres: checkpointer"));
+        assert_eq!(p.state, ProcessState::Sleeping);
+        assert_eq!(p.percent_cpu, 12.5);
+        assert_eq!(p.m_pss, -1);
+        assert_eq!(p.cpu_affinity.as_ref().unwrap().cpus(), &[0, 1]);
+        assert_eq!(p.gpu_time, 5_000_000_000);
+        assert_eq!(p.gpu_percent, 42.5);
+        assert_eq!(p.net_rx_rate_bps, 2048.0);
+        assert!(p.net_tx_rate_bps.is_nan());
+        assert_eq!(p.socket_count, 12);
+
+        assert!(replay.step(1));
+        assert!(replay.at_end());
+        assert!(!replay.step(1));
+        replay.load(&mut target).unwrap();
+        assert_eq!(target.realtime_ms, 1_700_000_001_500);
+        assert_eq!(target.processes.get(4242).unwrap().percent_cpu, 99.0);
+
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_strings_written_once() {
+        let path = temp_path("strings");
+        let _ = std::fs::remove_file(&path);
+
+        let mut machine = sample_machine();
+        let mut recorder = Recorder::open(&path).unwrap();
+        recorder.record(&mut machine).unwrap();
+        let first_len = std::fs::metadata(&path).unwrap().len();
+        recorder.record(&mut machine).unwrap();
+        let second_len = std::fs::metadata(&path).unwrap().len() - first_len;
+        drop(recorder);
+
+        // Appending continues the string table from the existing file
+        let mut recorder = Recorder::open(&path).unwrap();
+        recorder.record(&mut machine).unwrap();
+        drop(recorder);
+        let third_len = std::fs::metadata(&path).unwrap().len() - first_len - second_len;
+
+        assert!(second_len < first_len - "postgres: checkpointer".len() as u64);
+        assert_eq!(third_len, second_len);
+        assert_eq!(Replay::open(&path).unwrap().len(), 3);
+
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_truncated_last_frame_is_ignored() {
+        let path = temp_path("truncated");
+        let _ = std::fs::remove_file(&path);
+
+        let mut machine = sample_machine();
+        let mut recorder = Recorder::open(&path).unwrap();
+        recorder.record(&mut machine).unwrap();
+        recorder.record(&mut machine).unwrap();
+        drop(recorder);
+
+        // Cut the second frame short
+        let len = std::fs::metadata(&path).unwrap().len();
+        let file = OpenOptions::new().write(true).open(&path).unwrap();
+        file.set_len(len - 3).unwrap();
+        drop(file);
+        assert_eq!(Replay::open(&path).unwrap().len(), 1);
+
+        // Recording again replaces the partial frame
+        let mut recorder = Recorder::open(&path).unwrap();
+        recorder.record(&mut machine).unwrap();
+        drop(recorder);
+        assert_eq!(Replay::open(&path).unwrap().len(), 2);
+
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_replay_rejects_garbage() {
+        let path = temp_path("garbage");
+        std::fs::write(&path, b"not a recording").unwrap();
+        assert!(Replay::open(&path).is_err());
+        assert!(Recorder::open(&path).is_err());
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_replay_keeps_tags() {
+        let path = temp_path("tags");
+        let _ = std::fs::remove_file(&path);
+
+        let mut machine = sample_machine();
+        let mut recorder = Recorder::open(&path).unwrap();
+        recorder.record(&mut machine).unwrap();
+        recorder.record(&mut machine).unwrap();
+        drop(recorder);
+
+        let mut replay = Replay::open(&path).unwrap();
+        let mut target = Machine::default();
+        replay.load(&mut target).unwrap();
+        target.processes.toggle_tag(4242);
+        replay.step(1);
+        replay.load(&mut target).unwrap();
+        assert!(target.processes.get(4242).unwrap().tagged);
+
+        let _ = std::fs::remove_file(&path);
+    }
+}
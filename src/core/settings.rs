@@ -232,9 +232,22 @@ impl MeterMode {
 pub struct MeterConfig {
     pub name: String,
     pub param: u32,
+    /// Device the meter is restricted to, e.g. "sda" for "DiskIO(sda)" or an
+    /// interface glob such as "enp*" for "NetworkIO(enp*)"
+    pub device: Option<String>,
     pub mode: MeterMode,
 }
 
+impl MeterConfig {
+    /// Name as written to the config file, e.g. "CPU(1)" or "DiskIO(sda)"
+    pub fn config_name(&self) -> String {
+        match &self.device {
+            Some(device) => format!("{}({})", self.name, device),
+            None => format_meter_name(&self.name, self.param),
+        }
+    }
+}
+
 /// Parse meter name with optional parameter, e.g., "CPU(1)" -> ("CPU", 1)
 fn parse_meter_name(name: &str) -> (String, u32) {
     if let Some(open_paren) = name.find('(') {
@@ -248,6 +261,18 @@ fn parse_meter_name(name: &str) -> (String, u32) {
     (name.to_string(), 0)
 }
 
+/// Parse a device parameter, e.g., "DiskIO(sda)" -> Some("sda")
+/// (numeric parameters are CPU numbers, see parse_meter_name)
+fn parse_meter_device(name: &str) -> Option<String> {
+    let open_paren = name.find('(')?;
+    let close_paren = name.find(')')?;
+    let device = name.get(open_paren + 1..close_paren)?;
+    if device.is_empty() || device.parse::<u32>().is_ok() {
+        return None;
+    }
+    Some(device.to_string())
+}
+
 /// Format meter name with optional parameter for config file
 fn format_meter_name(name: &str, param: u32) -> String {
     if param > 0 {
@@ -269,6 +294,14 @@ pub struct ScreenSettings {
     pub tree_view: bool,
     pub tree_view_always_by_pid: bool,
     pub all_branches_collapsed: bool,
+    /// Process filter expression applied when the screen is shown
+    pub filter: Option<String>,
+    /// Only show processes of this user
+    pub user: Option<String>,
+    /// Per-screen override of the global hide_kernel_threads setting
+    pub hide_kernel_threads: Option<bool>,
+    /// Per-screen override of the global hide_userland_threads setting
+    pub hide_userland_threads: Option<bool>,
 }
 
 impl ScreenSettings {
@@ -313,6 +346,10 @@ impl ScreenSettings {
             tree_view: false,
             tree_view_always_by_pid: false,
             all_branches_collapsed: false,
+            filter: None,
+            user: None,
+            hide_kernel_threads: None,
+            hide_userland_threads: None,
         }
     }
 
@@ -339,6 +376,10 @@ impl ScreenSettings {
             tree_view: false,
             tree_view_always_by_pid: false,
             all_branches_collapsed: false,
+            filter: None,
+            user: None,
+            hide_kernel_threads: None,
+            hide_userland_threads: None,
         }
     }
 
@@ -408,8 +449,12 @@ pub struct Settings {
     pub hide_kernel_threads: bool,
     pub hide_userland_threads: bool,
     pub hide_running_in_container: bool,
+    /// Root the tree view at the topmost process of every container
+    pub tree_group_by_container: bool,
     pub highlight_base_name: bool,
     pub highlight_deleted_exe: bool,
+    /// Highlight unconfined labels in the security attribute column
+    pub highlight_unconfined: bool,
     pub shadow_dist_path_prefix: bool,
     pub highlight_megabytes: bool,
     pub highlight_threads: bool,
@@ -420,6 +465,8 @@ pub struct Settings {
     pub show_merged_command: bool,
     pub update_process_names: bool,
     pub show_cached_memory: bool,
+    /// Show each interface's rates after the totals in the NetworkIO text mode
+    pub show_network_interfaces: bool,
 
     // Tree view
     pub tree_view: bool,
@@ -449,16 +496,19 @@ impl Settings {
             MeterConfig {
                 name: "LeftCPUs2".to_string(),
                 param: 0,
+                device: None,
                 mode: MeterMode::Bar,
             },
             MeterConfig {
                 name: "Memory".to_string(),
                 param: 0,
+                device: None,
                 mode: MeterMode::Bar,
             },
             MeterConfig {
                 name: "Swap".to_string(),
                 param: 0,
+                device: None,
                 mode: MeterMode::Bar,
             },
         ];
@@ -467,21 +517,25 @@ impl Settings {
             MeterConfig {
                 name: "RightCPUs2".to_string(),
                 param: 0,
+                device: None,
                 mode: MeterMode::Bar,
             },
             MeterConfig {
                 name: "Tasks".to_string(),
                 param: 0,
+                device: None,
                 mode: MeterMode::Text,
             },
             MeterConfig {
                 name: "LoadAverage".to_string(),
                 param: 0,
+                device: None,
                 mode: MeterMode::Text,
             },
             MeterConfig {
                 name: "Uptime".to_string(),
                 param: 0,
+                device: None,
                 mode: MeterMode::Text,
             },
         ];
@@ -520,8 +574,10 @@ impl Settings {
             hide_kernel_threads: true,
             hide_userland_threads: false,
             hide_running_in_container: false,
+            tree_group_by_container: false,
             highlight_base_name: false,
             highlight_deleted_exe: true,
+            highlight_unconfined: false,
             shadow_dist_path_prefix: false,
             highlight_megabytes: true,
             highlight_threads: true,
@@ -532,6 +588,7 @@ impl Settings {
             show_merged_command: false,
             update_process_names: false,
             show_cached_memory: true,
+            show_network_interfaces: false,
             tree_view: false,
             tree_view_always_by_pid: false,
             all_branches_collapsed: false,
@@ -734,6 +791,10 @@ impl Settings {
             tree_view: false,
             tree_view_always_by_pid: false,
             all_branches_collapsed: false,
+            filter: None,
+            user: None,
+            hide_kernel_threads: None,
+            hide_userland_threads: None,
         }
     }
 
@@ -784,6 +845,18 @@ impl Settings {
             "all_branches_collapsed" => {
                 screen.all_branches_collapsed = value == "1";
             }
+            "filter" => {
+                screen.filter = Some(value).filter(|v| !v.is_empty()).map(str::to_string);
+            }
+            "user" => {
+                screen.user = Some(value).filter(|v| !v.is_empty()).map(str::to_string);
+            }
+            "hide_kernel_threads" => {
+                screen.hide_kernel_threads = Some(value == "1");
+            }
+            "hide_userland_threads" => {
+                screen.hide_userland_threads = Some(value == "1");
+            }
             _ => {}
         }
     }
@@ -824,6 +897,7 @@ impl Settings {
                     MeterConfig {
                         name: meter_name,
                         param,
+                        device: parse_meter_device(name),
                         mode: MeterMode::from_i32(mode_id),
                     }
                 })
@@ -853,6 +927,7 @@ impl Settings {
                 MeterConfig {
                     name: meter_name,
                     param,
+                    device: parse_meter_device(name),
                     mode: MeterMode::from_i32(mode_id),
                 }
             })
@@ -868,6 +943,7 @@ impl Settings {
                 MeterConfig {
                     name: meter_name,
                     param,
+                    device: parse_meter_device(name),
                     mode: MeterMode::from_i32(mode_id),
                 }
             })
@@ -952,6 +1028,9 @@ impl Settings {
             "show_cached_memory" => {
                 self.show_cached_memory = value == "1";
             }
+            "show_network_interfaces" => {
+                self.show_network_interfaces = value == "1";
+            }
             "update_process_names" => {
                 self.update_process_names = value == "1";
             }
@@ -966,6 +1045,9 @@ impl Settings {
             "highlight_deleted_exe" => {
                 self.highlight_deleted_exe = value == "1";
             }
+            "highlight_unconfined" => {
+                self.highlight_unconfined = value == "1";
+            }
             "shadow_dist_path_prefix" => {
                 self.shadow_dist_path_prefix = value == "1";
             }
@@ -1055,6 +1137,9 @@ impl Settings {
             "hide_running_in_container" => {
                 self.hide_running_in_container = value == "1";
             }
+            "tree_group_by_container" => {
+                self.tree_group_by_container = value == "1";
+            }
             "shadow_distribution_path_prefix" => {
                 // Alternative name from C htop
                 self.shadow_dist_path_prefix = value == "1";
@@ -1124,6 +1209,11 @@ impl Settings {
             "hide_running_in_container={}",
             if self.hide_running_in_container { 1 } else { 0 }
         )?;
+        writeln!(
+            file,
+            "tree_group_by_container={}",
+            if self.tree_group_by_container { 1 } else { 0 }
+        )?;
         writeln!(
             file,
             "shadow_other_users={}",
@@ -1149,6 +1239,11 @@ impl Settings {
             "highlight_deleted_exe={}",
             if self.highlight_deleted_exe { 1 } else { 0 }
         )?;
+        writeln!(
+            file,
+            "highlight_unconfined={}",
+            if self.highlight_unconfined { 1 } else { 0 }
+        )?;
         writeln!(
             file,
             "shadow_distribution_path_prefix={}",
@@ -1230,6 +1325,11 @@ impl Settings {
             "update_process_names={}",
             if self.update_process_names { 1 } else { 0 }
         )?;
+        writeln!(
+            file,
+            "show_network_interfaces={}",
+            if self.show_network_interfaces { 1 } else { 0 }
+        )?;
         writeln!(
             file,
             "account_guest_in_cpu_meter={}",
@@ -1257,10 +1357,7 @@ impl Settings {
         // Meter columns
         for (idx, column) in self.header_columns.iter().enumerate() {
             // Write meter names
-            let meter_names: Vec<String> = column
-                .iter()
-                .map(|m| format_meter_name(&m.name, m.param))
-                .collect();
+            let meter_names: Vec<String> = column.iter().map(MeterConfig::config_name).collect();
             writeln!(file, "column_meters_{}={}", idx, meter_names.join(" "))?;
 
             // Write meter modes
@@ -1291,6 +1388,18 @@ impl Settings {
                 ".all_branches_collapsed={}",
                 if screen.all_branches_collapsed { 1 } else { 0 }
             )?;
+            if let Some(ref filter) = screen.filter {
+                writeln!(file, ".filter={}", filter)?;
+            }
+            if let Some(ref user) = screen.user {
+                writeln!(file, ".user={}", user)?;
+            }
+            if let Some(hide) = screen.hide_kernel_threads {
+                writeln!(file, ".hide_kernel_threads={}", if hide { 1 } else { 0 })?;
+            }
+            if let Some(hide) = screen.hide_userland_threads {
+                writeln!(file, ".hide_userland_threads={}", if hide { 1 } else { 0 })?;
+            }
         }
 
         // Ensure file is fully written
@@ -1312,6 +1421,56 @@ impl Settings {
     pub fn current_screen_mut(&mut self) -> &mut ScreenSettings {
         &mut self.screens[self.active_screen]
     }
+
+    /// Whether kernel threads are hidden on the current screen
+    pub fn kernel_threads_hidden(&self) -> bool {
+        self.screens
+            .get(self.active_screen)
+            .and_then(|screen| screen.hide_kernel_threads)
+            .unwrap_or(self.hide_kernel_threads)
+    }
+
+    /// Whether userland threads are hidden on the current screen
+    pub fn userland_threads_hidden(&self) -> bool {
+        self.screens
+            .get(self.active_screen)
+            .and_then(|screen| screen.hide_userland_threads)
+            .unwrap_or(self.hide_userland_threads)
+    }
+
+    /// Whether H/K change the thread visibility of the current screen only:
+    /// when screen tabs are shown and there is more than one screen
+    fn thread_toggles_per_screen(&self) -> bool {
+        self.screen_tabs && self.screens.len() > 1
+    }
+
+    /// Toggle kernel thread visibility. Changes the screen's override if it
+    /// has one, or creates it when toggling per screen.
+    pub fn toggle_kernel_threads(&mut self) {
+        let hidden = !self.kernel_threads_hidden();
+        let per_screen = self.thread_toggles_per_screen();
+        match self.screens.get_mut(self.active_screen) {
+            Some(screen) if per_screen || screen.hide_kernel_threads.is_some() => {
+                screen.hide_kernel_threads = Some(hidden)
+            }
+            _ => self.hide_kernel_threads = hidden,
+        }
+        self.changed = true;
+    }
+
+    /// Toggle userland thread visibility. Changes the screen's override if
+    /// it has one, or creates it when toggling per screen.
+    pub fn toggle_userland_threads(&mut self) {
+        let hidden = !self.userland_threads_hidden();
+        let per_screen = self.thread_toggles_per_screen();
+        match self.screens.get_mut(self.active_screen) {
+            Some(screen) if per_screen || screen.hide_userland_threads.is_some() => {
+                screen.hide_userland_threads = Some(hidden)
+            }
+            _ => self.hide_userland_threads = hidden,
+        }
+        self.changed = true;
+    }
 }
 
 // Stub for dirs crate functionality
@@ -1479,6 +1638,7 @@ mod tests {
         let config = MeterConfig {
             name: "CPU".to_string(),
             param: 0,
+            device: None,
             mode: MeterMode::Bar,
         };
         assert_eq!(config.name, "CPU");
@@ -1491,6 +1651,7 @@ mod tests {
         let config = MeterConfig {
             name: "CPU".to_string(),
             param: 2,
+            device: None,
             mode: MeterMode::Graph,
         };
         assert_eq!(config.name, "CPU");
@@ -1503,6 +1664,7 @@ mod tests {
         let config = MeterConfig {
             name: "Memory".to_string(),
             param: 0,
+            device: None,
             mode: MeterMode::Text,
         };
         let cloned = config.clone();
@@ -1620,6 +1782,36 @@ mod tests {
         }
     }
 
+    // ==================== Device parameter Tests ====================
+
+    #[test]
+    fn test_parse_meter_device() {
+        assert_eq!(parse_meter_device("DiskIO(sda)"), Some("sda".to_string()));
+        assert_eq!(
+            parse_meter_device("DiskIOTime(nvme0n1)"),
+            Some("nvme0n1".to_string())
+        );
+        // CPU numbers and missing parameters are not devices
+        assert_eq!(parse_meter_device("CPU(1)"), None);
+        assert_eq!(parse_meter_device("DiskIO()"), None);
+        assert_eq!(parse_meter_device("DiskIO"), None);
+        assert_eq!(parse_meter_device("DiskIO(sda"), None);
+    }
+
+    #[test]
+    fn test_meter_config_name_roundtrip() {
+        for name in ["DiskIO(nvme0n1)", "CPU(2)", "Memory"] {
+            let (meter_name, param) = parse_meter_name(name);
+            let config = MeterConfig {
+                name: meter_name,
+                param,
+                device: parse_meter_device(name),
+                mode: MeterMode::Text,
+            };
+            assert_eq!(config.config_name(), name);
+        }
+    }
+
     // ==================== ScreenSettings Tests ====================
 
     #[test]
@@ -1683,4 +1875,81 @@ mod tests {
         assert_eq!(cloned.sort_key, screen.sort_key);
         assert_eq!(cloned.tree_view, screen.tree_view);
     }
+
+    #[test]
+    fn test_load_screen_filters() {
+        let path = std::env::temp_dir().join(format!("htop-rs-screens-{}.rc", std::process::id()));
+        fs::write(
+            &path,
+            "screen:DB=PID USER Command\n\
+             .filter=cmd:postgres and cpu>=1\n\
+             .user=postgres\n\
+             .hide_kernel_threads=0\n\
+             screen:Main=PID Command\n",
+        )
+        .unwrap();
+        let mut settings = Settings {
+            filename: Some(path.clone()),
+            ..Default::default()
+        };
+        let result = settings.load();
+        fs::remove_file(&path).unwrap();
+        result.unwrap();
+
+        assert_eq!(settings.screens.len(), 2);
+        let db = &settings.screens[0];
+        assert_eq!(db.filter.as_deref(), Some("cmd:postgres and cpu>=1"));
+        assert_eq!(db.user.as_deref(), Some("postgres"));
+        assert_eq!(db.hide_kernel_threads, Some(false));
+        assert_eq!(db.hide_userland_threads, None);
+        let main = &settings.screens[1];
+        assert!(main.filter.is_none() && main.user.is_none());
+    }
+
+    #[test]
+    fn test_thread_visibility_screen_override() {
+        let mut settings = Settings {
+            hide_kernel_threads: true,
+            screen_tabs: false,
+            screens: vec![ScreenSettings::main_screen(), ScreenSettings::main_screen()],
+            ..Default::default()
+        };
+        settings.screens[1].hide_kernel_threads = Some(false);
+
+        assert!(settings.kernel_threads_hidden());
+        settings.active_screen = 1;
+        assert!(!settings.kernel_threads_hidden());
+
+        // Toggling on a screen with an override leaves the global setting alone
+        settings.toggle_kernel_threads();
+        assert_eq!(settings.screens[1].hide_kernel_threads, Some(true));
+        assert!(settings.hide_kernel_threads);
+
+        settings.active_screen = 0;
+        settings.toggle_kernel_threads();
+        assert!(!settings.hide_kernel_threads);
+        assert!(!settings.userland_threads_hidden());
+    }
+
+    #[test]
+    fn test_thread_toggles_create_screen_override_with_tabs() {
+        let mut settings = Settings {
+            hide_userland_threads: false,
+            screen_tabs: true,
+            screens: vec![ScreenSettings::main_screen(), ScreenSettings::main_screen()],
+            ..Default::default()
+        };
+        settings.active_screen = 1;
+        settings.toggle_userland_threads();
+        assert_eq!(settings.screens[1].hide_userland_threads, Some(true));
+        assert_eq!(settings.screens[0].hide_userland_threads, None);
+        assert!(!settings.hide_userland_threads);
+
+        // A single screen keeps using the global setting
+        settings.screens.truncate(1);
+        settings.active_screen = 0;
+        settings.toggle_userland_threads();
+        assert_eq!(settings.screens[0].hide_userland_threads, None);
+        assert!(settings.hide_userland_threads);
+    }
 }
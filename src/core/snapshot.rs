@@ -0,0 +1,769 @@
+//! Machine-readable snapshots of Machine and ProcessList
+//!
+//! This module serializes scan results to JSON for `--output=json|ndjson`,
+//! and the visible process table to CSV for the TUI export action.
+//! Values are the raw numbers htop-rs computes, in the same units as the
+//! underlying structs:
+//! - memory values in KiB (`*_kb`), IO byte counters in bytes
+//! - rates in bytes (or packets) per second
+//! - CPU times in hundredths of a second
+//! - unavailable values (e.g. PSS before the background scanner ran) as `null`
+//!
+//! There is no serde dependency; the small `JsonValue`/`JsonObject` helpers
+//! below are enough for the flat records we emit.
+
+use std::fmt::Write;
+
+use super::field_widths::FieldWidths;
+use super::machine::{CpuData, Machine, OptionalMemory};
+use super::process::{Process, ProcessField};
+
+/// A JSON value
+#[derive(Debug, Clone, PartialEq)]
+pub enum JsonValue {
+    Null,
+    Bool(bool),
+    Int(i64),
+    UInt(u64),
+    Float(f64),
+    Str(String),
+    /// Pre-serialized JSON (nested objects and arrays)
+    Raw(String),
+}
+
+impl JsonValue {
+    /// Append the serialized value to `out`
+    pub fn write_to(&self, out: &mut String) {
+        match self {
+            JsonValue::Null => out.push_str("null"),
+            JsonValue::Bool(b) => out.push_str(if *b { "true" } else { "false" }),
+            JsonValue::Int(n) => {
+                let _ = write!(out, "{}", n);
+            }
+            JsonValue::UInt(n) => {
+                let _ = write!(out, "{}", n);
+            }
+            JsonValue::Float(f) => {
+                // JSON has no NaN/Infinity
+                if f.is_finite() {
+                    let _ = write!(out, "{}", f);
+                } else {
+                    out.push_str("null");
+                }
+            }
+            JsonValue::Str(s) => write_escaped(out, s),
+            JsonValue::Raw(raw) => out.push_str(raw),
+        }
+    }
+
+    /// Optional memory value (-1 = not available)
+    fn optional_memory(kb: OptionalMemory) -> Self {
+        if kb < 0 {
+            JsonValue::Null
+        } else {
+            JsonValue::Int(kb)
+        }
+    }
+
+    /// Counter that uses u64::MAX as "not available"
+    fn counter(value: u64) -> Self {
+        if value == u64::MAX {
+            JsonValue::Null
+        } else {
+            JsonValue::UInt(value)
+        }
+    }
+
+    fn optional_str(value: Option<&str>) -> Self {
+        value.map_or(JsonValue::Null, |s| JsonValue::Str(s.to_string()))
+    }
+}
+
+impl From<bool> for JsonValue {
+    fn from(b: bool) -> Self {
+        JsonValue::Bool(b)
+    }
+}
+
+impl From<i32> for JsonValue {
+    fn from(n: i32) -> Self {
+        JsonValue::Int(n as i64)
+    }
+}
+
+impl From<i64> for JsonValue {
+    fn from(n: i64) -> Self {
+        JsonValue::Int(n)
+    }
+}
+
+impl From<u32> for JsonValue {
+    fn from(n: u32) -> Self {
+        JsonValue::UInt(n as u64)
+    }
+}
+
+impl From<u64> for JsonValue {
+    fn from(n: u64) -> Self {
+        JsonValue::UInt(n)
+    }
+}
+
+impl From<f32> for JsonValue {
+    fn from(f: f32) -> Self {
+        JsonValue::Float(f as f64)
+    }
+}
+
+impl From<f64> for JsonValue {
+    fn from(f: f64) -> Self {
+        JsonValue::Float(f)
+    }
+}
+
+impl From<&str> for JsonValue {
+    fn from(s: &str) -> Self {
+        JsonValue::Str(s.to_string())
+    }
+}
+
+impl<T: Into<JsonValue>> From<Option<T>> for JsonValue {
+    fn from(value: Option<T>) -> Self {
+        value.map_or(JsonValue::Null, Into::into)
+    }
+}
+
+/// Builder for a JSON object with keys in insertion order
+#[derive(Debug, Default)]
+pub struct JsonObject {
+    buf: String,
+}
+
+impl JsonObject {
+    pub fn new() -> Self {
+        JsonObject {
+            buf: String::from("{"),
+        }
+    }
+
+    /// Append a key/value pair
+    pub fn field(&mut self, key: &str, value: impl Into<JsonValue>) -> &mut Self {
+        if self.buf.len() > 1 {
+            self.buf.push(',');
+        }
+        write_escaped(&mut self.buf, key);
+        self.buf.push(':');
+        value.into().write_to(&mut self.buf);
+        self
+    }
+
+    /// Close the object and return the serialized string
+    pub fn finish(&mut self) -> String {
+        let mut out = std::mem::take(&mut self.buf);
+        if out.is_empty() {
+            out.push('{');
+        }
+        out.push('}');
+        out
+    }
+}
+
+/// Serialize a list of values as a JSON array
+pub fn json_array(values: impl IntoIterator<Item = JsonValue>) -> String {
+    let mut out = String::from("[");
+    for (i, value) in values.into_iter().enumerate() {
+        if i > 0 {
+            out.push(',');
+        }
+        value.write_to(&mut out);
+    }
+    out.push(']');
+    out
+}
+
+/// Write a JSON string literal with escaping
+fn write_escaped(out: &mut String, s: &str) {
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => {
+                let _ = write!(out, "\\u{:04x}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+/// JSON key for a process field (lowercase of the htoprc field name)
+pub fn field_key(field: ProcessField) -> String {
+    field.name().to_lowercase()
+}
+
+/// Raw value of a process field for machine-readable output
+pub fn process_field_value(process: &Process, field: ProcessField, machine: &Machine) -> JsonValue {
+    match field {
+        ProcessField::Pid => process.pid.into(),
+        ProcessField::Command => process.get_command().into(),
+        ProcessField::State => process.state.to_char().to_string().as_str().into(),
+        ProcessField::Ppid => process.ppid.into(),
+        ProcessField::Pgrp => process.pgrp.into(),
+        ProcessField::Session => process.session.into(),
+        ProcessField::Tty => JsonValue::optional_str(process.tty_name.as_deref()),
+        ProcessField::Tpgid => process.tpgid.into(),
+        ProcessField::Minflt => process.minflt.into(),
+        ProcessField::Majflt => process.majflt.into(),
+        ProcessField::Priority => process.priority.into(),
+        ProcessField::Nice => process.nice.into(),
+        ProcessField::Starttime => process.starttime_ctime.into(),
+        ProcessField::Processor => process.processor.into(),
+        ProcessField::MSize => process.m_virt.into(),
+        ProcessField::MResident => process.m_resident.into(),
+        ProcessField::StUid => process.uid.into(),
+        ProcessField::PercentCpu => process.percent_cpu.into(),
+        ProcessField::PercentMem => process.percent_mem.into(),
+        ProcessField::User => JsonValue::optional_str(process.user.as_deref()),
+        ProcessField::Time => process.time.into(),
+        ProcessField::Nlwp => process.nlwp.into(),
+        ProcessField::Tgid => process.tgid.into(),
+        ProcessField::PercentNormCpu => {
+            let active_cpus = machine.active_cpus.max(1) as f32;
+            (process.percent_cpu / active_cpus).into()
+        }
+        ProcessField::Elapsed => {
+            let now_secs = (machine.realtime_ms / 1000) as i64;
+            if process.starttime_ctime > 0 {
+                (now_secs.saturating_sub(process.starttime_ctime).max(0) * 100).into()
+            } else {
+                JsonValue::Null
+            }
+        }
+        ProcessField::SchedulerPolicy => {
+            if process.scheduling_policy < 0 {
+                JsonValue::Null
+            } else {
+                process.scheduling_policy.into()
+            }
+        }
+        ProcessField::ProcComm => JsonValue::optional_str(process.comm.as_deref()),
+        ProcessField::ProcExe => JsonValue::optional_str(process.exe.as_deref()),
+        ProcessField::Cwd => JsonValue::optional_str(process.cwd.as_deref()),
+
+        #[cfg(target_os = "linux")]
+        ProcessField::Cminflt => process.cminflt.into(),
+        #[cfg(target_os = "linux")]
+        ProcessField::Cmajflt => process.cmajflt.into(),
+        #[cfg(target_os = "linux")]
+        ProcessField::Utime => process.utime.into(),
+        #[cfg(target_os = "linux")]
+        ProcessField::Stime => process.stime.into(),
+        #[cfg(target_os = "linux")]
+        ProcessField::Cutime => process.cutime.into(),
+        #[cfg(target_os = "linux")]
+        ProcessField::Cstime => process.cstime.into(),
+        #[cfg(target_os = "linux")]
+        ProcessField::MShare => process.m_share.into(),
+        #[cfg(target_os = "linux")]
+        ProcessField::MText => process.m_text.into(),
+        #[cfg(target_os = "linux")]
+        ProcessField::MData => process.m_data.into(),
+        #[cfg(target_os = "linux")]
+        ProcessField::MLib => {
+            if process.m_lib == 0 {
+                JsonValue::Null
+            } else {
+                process.m_lib.into()
+            }
+        }
+        #[cfg(target_os = "linux")]
+        ProcessField::Rchar => JsonValue::counter(process.io_rchar),
+        #[cfg(target_os = "linux")]
+        ProcessField::Wchar => JsonValue::counter(process.io_wchar),
+        #[cfg(target_os = "linux")]
+        ProcessField::Syscr => JsonValue::counter(process.io_syscr),
+        #[cfg(target_os = "linux")]
+        ProcessField::Syscw => JsonValue::counter(process.io_syscw),
+        #[cfg(target_os = "linux")]
+        ProcessField::Rbytes => JsonValue::counter(process.io_read_bytes),
+        #[cfg(target_os = "linux")]
+        ProcessField::Wbytes => JsonValue::counter(process.io_write_bytes),
+        #[cfg(target_os = "linux")]
+        ProcessField::Cnclwb => JsonValue::counter(process.io_cancelled_write_bytes),
+        #[cfg(target_os = "linux")]
+        ProcessField::IOReadRate => process.io_rate_read_bps.into(),
+        #[cfg(target_os = "linux")]
+        ProcessField::IOWriteRate => process.io_rate_write_bps.into(),
+        #[cfg(target_os = "linux")]
+        ProcessField::IORate => {
+            let read = process.io_rate_read_bps;
+            let write = process.io_rate_write_bps;
+            if read.is_nan() && write.is_nan() {
+                JsonValue::Null
+            } else {
+                let read = if read.is_nan() { 0.0 } else { read };
+                let write = if write.is_nan() { 0.0 } else { write };
+                (read + write).into()
+            }
+        }
+        #[cfg(target_os = "linux")]
+        ProcessField::CGroup => JsonValue::optional_str(process.cgroup.as_deref()),
+        #[cfg(target_os = "linux")]
+        ProcessField::Oom => process.oom_score.into(),
+        #[cfg(target_os = "linux")]
+        ProcessField::IOPriority => {
+            if process.io_priority < 0 {
+                JsonValue::Null
+            } else {
+                process
+                    .format_field(ProcessField::IOPriority, 0, machine.active_cpus)
+                    .as_str()
+                    .into()
+            }
+        }
+        #[cfg(target_os = "linux")]
+        ProcessField::PercentCpuDelay => process.cpu_delay_percent.into(),
+        #[cfg(target_os = "linux")]
+        ProcessField::PercentIODelay => process.blkio_delay_percent.into(),
+        #[cfg(target_os = "linux")]
+        ProcessField::PercentSwapDelay => process.swapin_delay_percent.into(),
+        #[cfg(target_os = "linux")]
+        ProcessField::MPss => JsonValue::optional_memory(process.m_pss),
+        #[cfg(target_os = "linux")]
+        ProcessField::MSwap => JsonValue::optional_memory(process.m_swap),
+        #[cfg(target_os = "linux")]
+        ProcessField::MPsswp => JsonValue::optional_memory(process.m_psswp),
+        #[cfg(target_os = "linux")]
+        ProcessField::Ctxt => process.ctxt_switches.into(),
+        #[cfg(target_os = "linux")]
+        ProcessField::SecAttr => JsonValue::optional_str(process.sec_attr.as_deref()),
+        #[cfg(target_os = "linux")]
+        ProcessField::AutogroupId => {
+            if process.autogroup_id < 0 {
+                JsonValue::Null
+            } else {
+                process.autogroup_id.into()
+            }
+        }
+        #[cfg(target_os = "linux")]
+        ProcessField::AutogroupNice => {
+            if process.autogroup_id < 0 {
+                JsonValue::Null
+            } else {
+                process.autogroup_nice.into()
+            }
+        }
+        #[cfg(target_os = "linux")]
+        ProcessField::CCGroup => JsonValue::optional_str(
+            process
+                .cgroup_short
+                .as_deref()
+                .or(process.cgroup.as_deref()),
+        ),
+        #[cfg(target_os = "linux")]
+        ProcessField::Container => JsonValue::optional_str(process.container_short.as_deref()),
+        #[cfg(target_os = "linux")]
+        ProcessField::MPriv => process.m_resident.saturating_sub(process.m_share).into(),
+        #[cfg(target_os = "linux")]
+        ProcessField::GpuTime => process.gpu_time.into(),
+        #[cfg(target_os = "linux")]
+        ProcessField::GpuPercent => process.gpu_percent.into(),
+        #[cfg(target_os = "linux")]
+        ProcessField::IsContainer => process
+            .container_short
+            .as_ref()
+            .map(|s| !s.is_empty() && s != "/")
+            .unwrap_or(false)
+            .into(),
+        #[cfg(target_os = "linux")]
+        ProcessField::Affinity => process.cpu_affinity.as_ref().map_or(JsonValue::Null, |a| {
+            JsonValue::Raw(json_array(a.cpus().iter().map(|&cpu| cpu.into())))
+        }),
+        #[cfg(target_os = "linux")]
+        ProcessField::NetRxRate => process.net_rx_rate_bps.into(),
+        #[cfg(target_os = "linux")]
+        ProcessField::NetTxRate => process.net_tx_rate_bps.into(),
+        #[cfg(target_os = "linux")]
+        ProcessField::Sockets => {
+            if process.socket_count < 0 {
+                JsonValue::Null
+            } else {
+                process.socket_count.into()
+            }
+        }
+
+        #[cfg(target_os = "macos")]
+        ProcessField::Translated => process.translated.into(),
+    }
+}
+
+/// Serialize a process with every ProcessField available on this platform
+pub fn process_json(process: &Process, machine: &Machine) -> String {
+    let mut obj = JsonObject::new();
+    append_process_fields(&mut obj, process, machine);
+    obj.finish()
+}
+
+/// Append all ProcessField values plus tree/thread metadata to an object
+pub fn append_process_fields(obj: &mut JsonObject, process: &Process, machine: &Machine) {
+    for field in ProcessField::all() {
+        obj.field(
+            &field_key(field),
+            process_field_value(process, field, machine),
+        );
+    }
+    obj.field("is_kernel_thread", process.is_kernel_thread)
+        .field("is_userland_thread", process.is_userland_thread)
+        .field("tree_depth", process.tree_depth);
+}
+
+/// Serialize per-CPU data (raw counters and computed percentages)
+fn cpu_json(id: Option<usize>, cpu: &CpuData) -> String {
+    let mut obj = JsonObject::new();
+    if let Some(id) = id {
+        obj.field("id", id as u64);
+    }
+    obj.field("online", cpu.online)
+        .field("user_time", cpu.user_time)
+        .field("nice_time", cpu.nice_time)
+        .field("system_time", cpu.system_time)
+        .field("idle_time", cpu.idle_time)
+        .field("iowait_time", cpu.iowait_time)
+        .field("irq_time", cpu.irq_time)
+        .field("softirq_time", cpu.softirq_time)
+        .field("steal_time", cpu.steal_time)
+        .field("guest_time", cpu.guest_time)
+        .field("guest_nice_time", cpu.guest_nice_time)
+        .field("user_percent", cpu.user_percent)
+        .field("nice_percent", cpu.nice_percent)
+        .field("system_percent", cpu.system_percent)
+        .field("irq_percent", cpu.irq_percent)
+        .field("softirq_percent", cpu.softirq_percent)
+        .field("steal_percent", cpu.steal_percent)
+        .field("guest_percent", cpu.guest_percent)
+        .field("iowait_percent", cpu.iowait_percent)
+        .field("total_percent", cpu.total_percent)
+        .field("frequency_mhz", cpu.frequency)
+        .field("temperature_c", cpu.temperature);
+    obj.finish()
+}
+
+/// Append system-wide Machine state to an object
+pub fn append_machine_fields(obj: &mut JsonObject, machine: &Machine) {
+    let mut memory = JsonObject::new();
+    memory
+        .field("total_kb", machine.total_mem)
+        .field("used_kb", machine.used_mem)
+        .field("buffers_kb", machine.buffers_mem)
+        .field("cached_kb", machine.cached_mem)
+        .field("shared_kb", JsonValue::optional_memory(machine.shared_mem))
+        .field(
+            "available_kb",
+            JsonValue::optional_memory(machine.available_mem),
+        )
+        .field(
+            "compressed_kb",
+            JsonValue::optional_memory(machine.compressed_mem),
+        );
+
+    let mut swap = JsonObject::new();
+    swap.field("total_kb", machine.total_swap)
+        .field("used_kb", machine.used_swap)
+        .field("cached_kb", machine.cached_swap);
+
+    let mut tasks = JsonObject::new();
+    tasks
+        .field("total", machine.total_tasks)
+        .field("running", machine.running_tasks)
+        .field("userland_threads", machine.userland_threads)
+        .field("kernel_threads", machine.kernel_threads);
+
+    let mut disk_io = JsonObject::new();
+    disk_io
+        .field("read_bytes", machine.disk_io_read_bytes)
+        .field("write_bytes", machine.disk_io_write_bytes)
+        .field("ms_time_spent", machine.disk_io_ms_time_spend)
+        .field("num_disks", machine.disk_io_num_disks)
+        .field("read_rate", machine.disk_io_read_rate)
+        .field("write_rate", machine.disk_io_write_rate)
+        .field("utilization_percent", machine.disk_io_utilization);
+
+    let mut network_io = JsonObject::new();
+    network_io
+        .field("bytes_received", machine.net_io_bytes_received)
+        .field("bytes_transmitted", machine.net_io_bytes_transmitted)
+        .field("packets_received", machine.net_io_packets_received)
+        .field("packets_transmitted", machine.net_io_packets_transmitted)
+        .field("receive_rate", machine.net_io_receive_rate)
+        .field("transmit_rate", machine.net_io_transmit_rate)
+        .field("receive_packets_rate", machine.net_io_receive_packets)
+        .field("transmit_packets_rate", machine.net_io_transmit_packets);
+
+    let mut fds = JsonObject::new();
+    fds.field("used", machine.fd_used)
+        .field("max", machine.fd_max);
+
+    let cpus = json_array(
+        machine
+            .cpus
+            .iter()
+            .enumerate()
+            .map(|(i, cpu)| JsonValue::Raw(cpu_json(Some(i), cpu))),
+    );
+    let load_average = json_array(machine.load_average.iter().map(|l| JsonValue::Float(*l)));
+
+    obj.field("timestamp_ms", machine.realtime_ms)
+        .field("hostname", machine.hostname.as_str())
+        .field("kernel_version", machine.kernel_version.as_str())
+        .field("uptime_secs", machine.uptime.as_secs())
+        .field("boot_time", machine.boot_time)
+        .field("load_average", JsonValue::Raw(load_average))
+        .field("tasks", JsonValue::Raw(tasks.finish()))
+        .field("memory", JsonValue::Raw(memory.finish()))
+        .field("swap", JsonValue::Raw(swap.finish()))
+        .field("active_cpus", machine.active_cpus)
+        .field("existing_cpus", machine.existing_cpus)
+        .field("cpu", JsonValue::Raw(cpu_json(None, &machine.avg_cpu)))
+        .field("cpus", JsonValue::Raw(cpus))
+        .field("disk_io", JsonValue::Raw(disk_io.finish()))
+        .field("network_io", JsonValue::Raw(network_io.finish()))
+        .field("file_descriptors", JsonValue::Raw(fds.finish()));
+}
+
+/// Serialize system-wide Machine state
+pub fn machine_json(machine: &Machine) -> String {
+    let mut obj = JsonObject::new();
+    append_machine_fields(&mut obj, machine);
+    obj.finish()
+}
+
+/// Serialize one full scan: machine state plus the given processes
+pub fn snapshot_json<'a>(
+    scan: u64,
+    machine: &Machine,
+    processes: impl IntoIterator<Item = &'a Process>,
+) -> String {
+    let processes = json_array(
+        processes
+            .into_iter()
+            .map(|p| JsonValue::Raw(process_json(p, machine))),
+    );
+    let mut obj = JsonObject::new();
+    obj.field("scan", scan)
+        .field("machine", JsonValue::Raw(machine_json(machine)))
+        .field("processes", JsonValue::Raw(processes));
+    obj.finish()
+}
+
+/// Serialize one scan as NDJSON: a machine record followed by one record per process
+pub fn snapshot_ndjson<'a>(
+    scan: u64,
+    machine: &Machine,
+    processes: impl IntoIterator<Item = &'a Process>,
+) -> String {
+    let mut out = String::new();
+
+    let mut obj = JsonObject::new();
+    obj.field("type", "machine").field("scan", scan);
+    append_machine_fields(&mut obj, machine);
+    out.push_str(&obj.finish());
+    out.push('\n');
+
+    for process in processes {
+        let mut obj = JsonObject::new();
+        obj.field("type", "process").field("scan", scan);
+        append_process_fields(&mut obj, process, machine);
+        out.push_str(&obj.finish());
+        out.push('\n');
+    }
+
+    out
+}
+
+/// Quote a CSV cell if it contains a separator, quote or line break (RFC 4180)
+pub fn csv_escape(value: &str) -> std::borrow::Cow<'_, str> {
+    if value.contains([',', '"', '\n', '\r']) {
+        std::borrow::Cow::Owned(format!("\"{}\"", value.replace('"', "\"\"")))
+    } else {
+        std::borrow::Cow::Borrowed(value)
+    }
+}
+
+/// Serialize process rows as CSV, one column per field
+///
+/// Cells are the values as shown in the process table (formatted with the
+/// current FieldWidths, surrounding padding removed). In tree view the
+/// command is prefixed with its tree indentation.
+pub fn processes_csv<'a>(
+    processes: impl IntoIterator<Item = &'a Process>,
+    fields: &[ProcessField],
+    field_widths: &FieldWidths,
+    active_cpus: u32,
+    tree_view: bool,
+) -> String {
+    let mut out = String::new();
+
+    let header: Vec<String> = fields
+        .iter()
+        .map(|field| csv_escape(field_widths.get_title(*field).trim()).into_owned())
+        .collect();
+    out.push_str(&header.join(","));
+    out.push_str("\r\n");
+
+    for process in processes {
+        for (i, field) in fields.iter().enumerate() {
+            if i > 0 {
+                out.push(',');
+            }
+            let width = field_widths.get_display_width(*field);
+            let value = process.format_field(*field, width, active_cpus);
+            if *field == ProcessField::Command && tree_view && process.tree_depth > 0 {
+                let depth = process.tree_depth as usize;
+                let cell = format!("{}`- {}", "   ".repeat(depth - 1), value.trim());
+                out.push_str(&csv_escape(&cell));
+            } else {
+                out.push_str(&csv_escape(value.trim()));
+            }
+        }
+        out.push_str("\r\n");
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_json_escaping() {
+        let mut out = String::new();
+        JsonValue::Str("a\"b\\c\nd\u{1}".to_string()).write_to(&mut out);
+        assert_eq!(out, "\"a\\\"b\\\\c\\nd\\u0001\"");
+    }
+
+    #[test]
+    fn test_json_non_finite_floats_are_null() {
+        let mut out = String::new();
+        JsonValue::Float(f64::NAN).write_to(&mut out);
+        assert_eq!(out, "null");
+    }
+
+    #[test]
+    fn test_json_object_builder() {
+        let mut obj = JsonObject::new();
+        obj.field("a", 1i32)
+            .field("b", "x")
+            .field("c", None::<u64>)
+            .field("d", true);
+        assert_eq!(obj.finish(), r#"{"a":1,"b":"x","c":null,"d":true}"#);
+        assert_eq!(JsonObject::new().finish(), "{}");
+    }
+
+    #[test]
+    fn test_json_array() {
+        let arr = json_array(vec![JsonValue::Int(1), JsonValue::Null]);
+        assert_eq!(arr, "[1,null]");
+        assert_eq!(json_array(Vec::new()), "[]");
+    }
+
+    #[test]
+    fn test_field_key() {
+        assert_eq!(field_key(ProcessField::PercentCpu), "percent_cpu");
+        assert_eq!(field_key(ProcessField::Command), "command");
+    }
+
+    #[test]
+    fn test_process_json_contains_all_fields() {
+        let machine = Machine::default();
+        let mut p = Process::new(42);
+        p.user = Some("postgres".to_string());
+        let json = process_json(&p, &machine);
+        for field in ProcessField::all() {
+            assert!(
+                json.contains(&format!("\"{}\":", field_key(field))),
+                "missing {:?}",
+                field
+            );
+        }
+        assert!(json.contains("\"pid\":42"));
+        assert!(json.contains("\"user\":\"postgres\""));
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_process_json_unavailable_values_are_null() {
+        let machine = Machine::default();
+        let p = Process::new(1);
+        let json = process_json(&p, &machine);
+        assert!(json.contains("\"m_pss\":null"));
+        assert!(json.contains("\"io_read_rate\":null"));
+        assert!(json.contains("\"cgroup\":null"));
+    }
+
+    #[test]
+    fn test_snapshot_ndjson_one_line_per_record() {
+        let mut machine = Machine::default();
+        machine.processes.add(Process::new(1), 0);
+        machine.processes.add(Process::new(2), 0);
+        let out = snapshot_ndjson(3, &machine, machine.processes.iter());
+        let lines: Vec<&str> = out.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert!(lines[0].starts_with(r#"{"type":"machine","scan":3,"#));
+        assert!(lines[1].starts_with(r#"{"type":"process","scan":3,"#));
+    }
+
+    #[test]
+    fn test_csv_escape() {
+        assert_eq!(csv_escape("plain"), "plain");
+        assert_eq!(csv_escape("a,b"), "\"a,b\"");
+        assert_eq!(csv_escape("say \"hi\""), "\"say \"\"hi\"\"\"");
+        assert_eq!(csv_escape("two\nlines"), "\"two\nlines\"");
+    }
+
+    #[test]
+    fn test_processes_csv_rows() {
+        let widths = FieldWidths::new();
+        let mut p = Process::new(7);
+        p.user = Some("root".to_string());
+        p.cmdline = Some("sh -c echo a,b".to_string());
+        let fields = [ProcessField::Pid, ProcessField::User, ProcessField::Command];
+        let csv = processes_csv([&p], &fields, &widths, 1, false);
+        let lines: Vec<&str> = csv.split("\r\n").collect();
+        assert_eq!(lines[0], "PID,USER,Command");
+        assert_eq!(lines[1], "7,root,\"sh -c echo a,b\"");
+        assert_eq!(lines[2], "");
+    }
+
+    #[test]
+    fn test_processes_csv_normalized_cpu() {
+        let widths = FieldWidths::new();
+        let mut p = Process::new(3);
+        p.percent_cpu = 80.0;
+        let fields = [ProcessField::PercentCpu, ProcessField::PercentNormCpu];
+        let csv = processes_csv([&p], &fields, &widths, 8, false);
+        assert_eq!(csv, "CPU%,NCPU%\r\n80,10.0\r\n");
+    }
+
+    #[test]
+    fn test_processes_csv_tree_prefix() {
+        let widths = FieldWidths::new();
+        let mut p = Process::new(9);
+        p.cmdline = Some("child".to_string());
+        p.tree_depth = 2;
+        let csv = processes_csv([&p], &[ProcessField::Command], &widths, 1, true);
+        assert_eq!(csv, "Command\r\n   `- child\r\n");
+    }
+
+    #[test]
+    fn test_snapshot_json_structure() {
+        let machine = Machine::default();
+        let out = snapshot_json(0, &machine, std::iter::empty());
+        assert!(out.starts_with(r#"{"scan":0,"machine":{"#));
+        assert!(out.ends_with(r#""processes":[]}"#));
+    }
+}
@@ -8,11 +8,13 @@ mod meters;
 mod platform;
 mod ui;
 
-use anyhow::Result;
-use clap::{ArgAction, Parser};
+use anyhow::{Context, Result};
+use clap::{ArgAction, Parser, ValueEnum};
+use std::io::Write;
 use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
 
-use crate::core::{Machine, Settings};
+use crate::core::{Machine, ProcessField, Settings};
 use crate::ui::{Crt, Header, MainPanel, ScreenManager};
 
 /// Static flag for clean shutdown
@@ -48,17 +50,22 @@ fn print_version() {
 fn print_help() {
     print_version_full();
     println!();
+    println!("-b --batch                      Print process snapshots to stdout instead of running interactively");
     println!("-C --no-color                   Use a monochrome color scheme");
     println!("-d --delay=DELAY                Set the delay between updates, in tenths of seconds");
     println!("-F --filter=FILTER              Show only the commands matching the given filter");
+    println!("                                (/regex/, user:NAME, cpu>50, mem>1G, and/or/not)");
     println!("   --no-function-bar             Hide the function bar");
     println!("-h --help                       Print this help screen");
     println!("-H --highlight-changes[=DELAY]  Highlight new and old processes");
     println!("-M --no-mouse                   Disable the mouse");
     println!("   --no-meters                  Hide meters");
     println!("-n --max-iterations=NUMBER      Exit htop after NUMBER iterations/frame updates");
+    println!("   --output=FORMAT              Print snapshots as json or ndjson (implies --batch)");
     println!("-p --pid=PID[,PID,PID...]       Show only the given PIDs");
     println!("   --readonly                   Disable all system and process changing features");
+    println!("   --record=FILE                Append every scan to a recording FILE");
+    println!("   --replay=FILE                Browse a recording made with --record (implies --readonly)");
     println!("-s --sort-key=COLUMN            Sort by COLUMN in list view (try --sort-key=help for a list)");
     println!("-t --tree                       Show the tree view (can be combined with -s)");
     println!("-u --user[=USERNAME]            Show only processes for a given user (or $USER)");
@@ -69,12 +76,25 @@ fn print_help() {
     println!("See 'man htop' for more information.");
 }
 
+/// Machine-readable snapshot format for `--output`
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+enum OutputFormat {
+    /// A single JSON array with one object per scan
+    Json,
+    /// One JSON object per line: a machine record, then one record per process
+    Ndjson,
+}
+
 /// htop - an interactive process viewer
 #[derive(Parser, Debug)]
 #[command(name = "htop")]
 #[command(disable_help_flag = true)]
 #[command(disable_version_flag = true)]
 struct Args {
+    /// Print process snapshots to stdout instead of running interactively
+    #[arg(short = 'b', long = "batch")]
+    batch: bool,
+
     /// Use a monochrome color scheme
     #[arg(short = 'C', long = "no-color")]
     no_color: bool,
@@ -99,6 +119,10 @@ struct Args {
     #[arg(short = 'n', long = "max-iterations", value_name = "NUMBER")]
     max_iterations: Option<i64>,
 
+    /// Print snapshots as json or ndjson (implies --batch)
+    #[arg(long = "output", value_name = "FORMAT", value_enum)]
+    output: Option<OutputFormat>,
+
     /// Show only the given PIDs
     #[arg(short = 'p', long = "pid", value_name = "PID", value_delimiter = ',')]
     pids: Option<Vec<u32>>,
@@ -107,6 +131,14 @@ struct Args {
     #[arg(long = "readonly")]
     readonly: bool,
 
+    /// Append every scan to a recording file
+    #[arg(long = "record", value_name = "FILE", conflicts_with = "replay")]
+    record: Option<std::path::PathBuf>,
+
+    /// Browse a recording made with --record
+    #[arg(long = "replay", value_name = "FILE", conflicts_with_all = ["batch", "output"])]
+    replay: Option<std::path::PathBuf>,
+
     /// Sort by COLUMN in list view
     #[arg(short = 's', long = "sort-key", value_name = "COLUMN")]
     sort_key: Option<String>,
@@ -237,17 +269,73 @@ fn main() -> Result<()> {
     if args.no_function_bar {
         settings.hide_function_bar = 2;
     }
-    if args.readonly {
+    // A replayed session shows processes that may no longer exist (or whose
+    // PIDs were reused), so never act on them
+    if args.readonly || args.replay.is_some() {
         settings.readonly = true;
     }
 
     settings.allow_unicode = !args.no_unicode;
+
+    // Without -u/-F, start with the filters saved for the startup screen
+    if args.user.is_none() {
+        if let Some(ref user) = settings.current_screen().user {
+            machine.filter_user_id = Some(
+                platform::get_uid_for_username(user)
+                    .or_else(|| user.parse().ok())
+                    .unwrap_or(u32::MAX),
+            );
+        }
+    }
+    // A filter that is not a valid query matches the command as text, like
+    // in the filter bar
+    let filter = args
+        .filter
+        .or_else(|| settings.current_screen().filter.clone())
+        .map(|text| core::ProcessFilter::new(&text));
+    if let Some(e) = filter.as_ref().and_then(|f| f.error()) {
+        if args.batch || args.output.is_some() {
+            eprintln!(
+                "Warning: Filter is not a valid query ({}), matching it as text",
+                e
+            );
+        }
+    }
     machine.iterations_remaining = args.max_iterations.unwrap_or(-1);
 
     if let Some(ref pids) = args.pids {
         machine.set_pid_filter(pids.clone());
     }
 
+    // Open the recording before touching the terminal so errors are readable
+    let recorder = match args.record {
+        Some(ref path) => Some(
+            core::Recorder::open(path)
+                .with_context(|| format!("cannot record to {}", path.display()))?,
+        ),
+        None => None,
+    };
+    let replay = match args.replay {
+        Some(ref path) => Some(
+            core::Replay::open(path)
+                .with_context(|| format!("cannot replay {}", path.display()))?,
+        ),
+        None => None,
+    };
+
+    // Batch mode never touches the terminal (like `top -b`)
+    if args.batch || args.output.is_some() {
+        let result = run_batch(
+            &mut machine,
+            &settings,
+            filter.as_ref(),
+            args.output,
+            recorder,
+        );
+        platform::done();
+        return result;
+    }
+
     // Initialize CRT (terminal)
     let mut crt = Crt::new(&settings)?;
 
@@ -257,24 +345,35 @@ fn main() -> Result<()> {
 
     // Create main panel
     let mut main_panel = MainPanel::new();
-    if let Some(filter) = args.filter {
-        main_panel.set_filter(&filter);
+    if let Some(filter) = filter {
+        main_panel.set_filter(filter.text());
     }
 
     // Create screen manager
     let mut screen_manager = ScreenManager::new(header, &mut machine, &settings);
     screen_manager.add_panel(main_panel);
+    if let Some(recorder) = recorder {
+        screen_manager.set_recorder(recorder);
+    }
+    if let Some(replay) = replay {
+        screen_manager.set_replay(replay);
+    }
 
     // Main loop (platform::scan is called inside run())
     screen_manager.run(&mut crt, &mut machine, &RUNNING)?;
 
     // Get the updated settings back from screen manager
+    let record_error = screen_manager.take_record_error();
     let settings = screen_manager.take_settings();
 
     // Cleanup
     crt.done();
     platform::done();
 
+    if let Some(e) = record_error {
+        eprintln!("Warning: Recording stopped: {}", e);
+    }
+
     // Save settings if changed
     if settings.changed {
         if let Err(e) = settings.write() {
@@ -285,6 +384,259 @@ fn main() -> Result<()> {
     Ok(())
 }
 
+/// Run in batch mode: scan and print the current screen's columns (or a JSON
+/// snapshot, with `--output`) to stdout for each frame, until the iteration
+/// limit is reached or we are interrupted.
+fn run_batch(
+    machine: &mut Machine,
+    settings: &Settings,
+    filter: Option<&core::ProcessFilter>,
+    output: Option<OutputFormat>,
+    mut recorder: Option<core::Recorder>,
+) -> Result<()> {
+    let screen = settings.current_screen();
+
+    // Copy settings to machine for platform access (same as ScreenManager::run)
+    machine.update_process_names = settings.update_process_names;
+    machine.show_cpu_frequency = settings.show_cpu_frequency;
+    machine.hide_userland_threads = settings.userland_threads_hidden();
+    machine.processes.group_by_container = settings.tree_group_by_container;
+    machine.check_deleted_libs = settings.highlight_deleted_exe;
+    #[cfg(target_os = "linux")]
+    {
+        // Snapshots carry every column, so collect everything the scanner can provide
+        machine.scan_flags = if output.is_some() {
+            core::ScanFlags::from_fields(&ProcessField::all())
+        } else {
+            core::ScanFlags::from_fields(&screen.fields)
+        } | filter.map_or(core::ScanFlags::empty(), |f| f.scan_flags());
+        if settings.tree_view && settings.tree_group_by_container {
+            machine.scan_flags |= core::ScanFlags::CGROUP;
+        }
+    }
+
+    let sort_key = settings.sort_key.unwrap_or(screen.sort_key);
+    machine.sort_key = sort_key;
+    machine.sort_descending = if sort_key == screen.sort_key {
+        screen.direction < 0
+    } else {
+        sort_key.default_sort_desc()
+    };
+
+    let delay = Duration::from_millis(settings.delay as u64 * 100);
+    let stdout = std::io::stdout();
+    let mut out = std::io::BufWriter::new(stdout.lock());
+    let mut first_frame = true;
+    let mut scan_count: u64 = 0;
+
+    // Snapshots should not contain first-scan placeholders (0% CPU, no
+    // PSS/cgroup data from the background scanner), so prime with one
+    // unprinted scan and wait a full delay before the first frame.
+    if output.is_some() && machine.iterations_remaining != 0 {
+        platform::scan(machine);
+    }
+
+    while RUNNING.load(Ordering::SeqCst) && machine.iterations_remaining != 0 {
+        if !first_frame || output.is_some() {
+            let next_frame = Instant::now() + delay;
+            while Instant::now() < next_frame && RUNNING.load(Ordering::SeqCst) {
+                std::thread::sleep(Duration::from_millis(50));
+            }
+            if !RUNNING.load(Ordering::SeqCst) {
+                break;
+            }
+        }
+
+        machine.needs_sort = true;
+        platform::scan(machine);
+        if let Some(ref mut recorder) = recorder {
+            recorder.record(machine).context("recording failed")?;
+        }
+        machine.update_processes(
+            None,
+            "|",
+            settings.highlight_changes,
+            settings.highlight_delay_secs,
+        );
+
+        if settings.tree_view {
+            let (tree_key, ascending) = if screen.tree_view_always_by_pid {
+                (ProcessField::Pid, true)
+            } else {
+                (screen.tree_sort_key, screen.tree_direction > 0)
+            };
+            machine.processes.expand_all();
+            machine.processes.build_tree(tree_key, ascending);
+        }
+
+        let result = match output {
+            Some(format) => {
+                write_snapshot_frame(&mut out, machine, settings, filter, format, scan_count)
+            }
+            None => write_batch_frame(&mut out, machine, settings, filter, first_frame),
+        };
+        match result {
+            Ok(()) => {}
+            // Reader went away (e.g. piped into `head`) - stop quietly
+            Err(e) if e.kind() == std::io::ErrorKind::BrokenPipe => break,
+            Err(e) => return Err(e.into()),
+        }
+
+        first_frame = false;
+        scan_count += 1;
+        if machine.iterations_remaining > 0 {
+            machine.iterations_remaining -= 1;
+        }
+    }
+
+    // Close the JSON array (also after Ctrl+C) so the output stays valid
+    if output == Some(OutputFormat::Json) {
+        let result = if first_frame {
+            writeln!(out, "[]")
+        } else {
+            writeln!(out, "\n]")
+        };
+        match result.and_then(|_| out.flush()) {
+            Err(e) if e.kind() != std::io::ErrorKind::BrokenPipe => return Err(e.into()),
+            _ => {}
+        }
+    }
+
+    Ok(())
+}
+
+/// Processes shown in batch output, in display order
+fn batch_processes<'a>(
+    machine: &'a Machine,
+    settings: &Settings,
+    filter: Option<&core::ProcessFilter>,
+) -> Vec<&'a core::Process> {
+    let visible = |p: &&core::Process| {
+        machine.should_show_process(p)
+            && !(settings.kernel_threads_hidden() && p.is_kernel_thread)
+            && !(settings.userland_threads_hidden() && p.is_userland_thread)
+            && filter.is_none_or(|f| f.matches(p))
+    };
+    if settings.tree_view {
+        machine.processes.iter_tree().filter(visible).collect()
+    } else {
+        machine.processes.iter().filter(visible).collect()
+    }
+}
+
+/// Write one scan as JSON (an element of the top-level array) or NDJSON
+fn write_snapshot_frame<W: Write>(
+    out: &mut W,
+    machine: &Machine,
+    settings: &Settings,
+    filter: Option<&core::ProcessFilter>,
+    format: OutputFormat,
+    scan: u64,
+) -> std::io::Result<()> {
+    let processes = batch_processes(machine, settings, filter);
+    match format {
+        OutputFormat::Json => {
+            let separator = if scan == 0 { "[\n" } else { ",\n" };
+            out.write_all(separator.as_bytes())?;
+            out.write_all(core::snapshot_json(scan, machine, processes).as_bytes())?;
+        }
+        OutputFormat::Ndjson => {
+            out.write_all(core::snapshot_ndjson(scan, machine, processes).as_bytes())?;
+        }
+    }
+    out.flush()
+}
+
+/// Write one batch frame: a header line followed by one line per visible process
+fn write_batch_frame<W: Write>(
+    out: &mut W,
+    machine: &Machine,
+    settings: &Settings,
+    filter: Option<&core::ProcessFilter>,
+    first_frame: bool,
+) -> std::io::Result<()> {
+    let fields = &settings.current_screen().fields;
+    let widths: Vec<usize> = fields
+        .iter()
+        .map(|field| machine.field_widths.get_display_width(*field))
+        .collect();
+
+    if !first_frame {
+        writeln!(out)?;
+    }
+
+    let mut line = String::new();
+    for (i, field) in fields.iter().enumerate() {
+        if i > 0 {
+            line.push(' ');
+        }
+        let title = machine.field_widths.get_title(*field);
+        if *field == ProcessField::Command {
+            line.push_str(title.trim());
+        } else if is_text_field(*field) {
+            line.push_str(&format!("{:<w$}", title.trim(), w = widths[i]));
+        } else {
+            line.push_str(&format!("{:>w$}", title.trim(), w = widths[i]));
+        }
+    }
+    writeln!(out, "{}", line.trim_end())?;
+
+    for process in batch_processes(machine, settings, filter) {
+        line.clear();
+        for (i, field) in fields.iter().enumerate() {
+            if i > 0 {
+                line.push(' ');
+            }
+            let width = widths[i];
+            let value = process.format_field(*field, width, machine.active_cpus);
+            if *field == ProcessField::Command {
+                if settings.tree_view && process.tree_depth > 0 {
+                    let depth = process.tree_depth as usize;
+                    line.push_str(&"   ".repeat(depth - 1));
+                    line.push_str("`- ");
+                }
+                line.push_str(&value);
+            } else if is_text_field(*field) {
+                let truncated: String = value.chars().take(width).collect();
+                line.push_str(&format!("{:<w$}", truncated, w = width));
+            } else {
+                line.push_str(&format!("{:>w$}", value.trim(), w = width));
+            }
+        }
+        writeln!(out, "{}", line.trim_end())?;
+    }
+
+    out.flush()
+}
+
+/// Whether a column holds free text (left-aligned) rather than a number
+fn is_text_field(field: ProcessField) -> bool {
+    #[cfg(target_os = "linux")]
+    if matches!(
+        field,
+        ProcessField::CGroup
+            | ProcessField::CCGroup
+            | ProcessField::Container
+            | ProcessField::IsContainer
+            | ProcessField::SecAttr
+            | ProcessField::IOPriority
+            | ProcessField::Affinity
+    ) {
+        return true;
+    }
+    matches!(
+        field,
+        ProcessField::User
+            | ProcessField::Tty
+            | ProcessField::State
+            | ProcessField::Starttime
+            | ProcessField::SchedulerPolicy
+            | ProcessField::ProcComm
+            | ProcessField::ProcExe
+            | ProcessField::Cwd
+    )
+}
+
 fn print_sort_keys() {
     println!("Available sort keys:");
     for field in core::ProcessField::all() {
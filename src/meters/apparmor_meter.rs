@@ -0,0 +1,247 @@
+//! AppArmor Meter
+//!
+//! Displays AppArmor state (Linux-specific):
+//! "enabled; 42 enforce, 3 complain"
+//!
+//! Whether AppArmor is enabled comes from the module parameter, the
+//! profile counts from the securityfs profile list, which only root can
+//! read. Both are read by the background scanner and arrive via
+//! `merge_expensive_data()`.
+
+use super::meter_bg_scanner::{MeterDataId, MeterExpensiveData};
+use super::{draw_text_segments, Meter, MeterMode, TextSegment};
+use crate::core::{Machine, Settings};
+use crate::ui::{ColorElement, Crt};
+
+/// Module parameter that is "Y" when AppArmor is enabled
+pub const APPARMOR_ENABLED_PATH: &str = "/sys/module/apparmor/parameters/enabled";
+
+/// List of loaded profiles, one "name (mode)" per line
+pub const APPARMOR_PROFILES_PATH: &str = "/sys/kernel/security/apparmor/profiles";
+
+/// Number of loaded profiles per mode
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AppArmorProfiles {
+    pub enforce: u32,
+    pub complain: u32,
+    pub kill: u32,
+    pub unconfined: u32,
+}
+
+/// State of an enabled AppArmor
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AppArmorState {
+    /// Profile counts (None if the profile list is not readable)
+    pub profiles: Option<AppArmorProfiles>,
+}
+
+/// Count the profiles per mode in the contents of the profile list
+pub fn parse_apparmor_profiles(content: &str) -> AppArmorProfiles {
+    let mut profiles = AppArmorProfiles::default();
+    for line in content.lines() {
+        let Some((_, mode)) = line.rsplit_once(" (") else {
+            continue;
+        };
+        match mode.trim_end().trim_end_matches(')') {
+            "enforce" => profiles.enforce += 1,
+            "complain" => profiles.complain += 1,
+            "kill" => profiles.kill += 1,
+            "unconfined" => profiles.unconfined += 1,
+            _ => {}
+        }
+    }
+    profiles
+}
+
+/// AppArmor Meter - displays AppArmor state and profile overview
+#[derive(Debug, Default)]
+pub struct AppArmorMeter {
+    mode: MeterMode,
+    /// Latest state: None before the first scan, Some(None) if AppArmor
+    /// is disabled
+    state: Option<Option<AppArmorState>>,
+}
+
+impl AppArmorMeter {
+    pub fn new() -> Self {
+        Self {
+            mode: MeterMode::Text,
+            state: None,
+        }
+    }
+
+    /// Text shown after the caption
+    fn text(&self) -> String {
+        let state = match &self.state {
+            None => return "N/A".to_string(),
+            Some(None) => return "disabled".to_string(),
+            Some(Some(state)) => state,
+        };
+        let Some(ref profiles) = state.profiles else {
+            return "enabled; profiles: N/A".to_string();
+        };
+
+        let mut text = format!(
+            "enabled; {} enforce, {} complain",
+            profiles.enforce, profiles.complain
+        );
+        if profiles.kill > 0 {
+            text.push_str(&format!(", {} kill", profiles.kill));
+        }
+        if profiles.unconfined > 0 {
+            text.push_str(&format!(", {} unconfined", profiles.unconfined));
+        }
+        text
+    }
+}
+
+impl Meter for AppArmorMeter {
+    fn name(&self) -> &'static str {
+        "AppArmor"
+    }
+
+    fn caption(&self) -> &str {
+        "AppArmor: "
+    }
+
+    fn supported_modes(&self) -> u32 {
+        1 << MeterMode::Text as u32
+    }
+
+    fn default_mode(&self) -> MeterMode {
+        MeterMode::Text
+    }
+
+    fn update(&mut self, _machine: &Machine) {
+        // State comes from the background scanner via merge_expensive_data()
+    }
+
+    fn expensive_data_id(&self) -> Option<MeterDataId> {
+        Some(MeterDataId::AppArmor)
+    }
+
+    fn merge_expensive_data(&mut self, data: &MeterExpensiveData) {
+        if let MeterExpensiveData::AppArmor(state) = data {
+            self.state = Some(state.clone());
+        }
+    }
+
+    fn draw(
+        &self,
+        crt: &mut Crt,
+        _machine: &Machine,
+        _settings: &Settings,
+        x: i32,
+        y: i32,
+        _width: i32,
+    ) {
+        // Profiles in complain mode only log violations
+        let value_attr = match &self.state {
+            Some(Some(AppArmorState {
+                profiles: Some(profiles),
+            })) if profiles.complain > 0 => crt.color(ColorElement::MeterValueWarn),
+            Some(Some(_)) => crt.color(ColorElement::MeterValueOk),
+            _ => crt.color(ColorElement::MeterValueError),
+        };
+        let text = self.text();
+        let segments = [
+            TextSegment {
+                text: self.caption(),
+                attr: crt.color(ColorElement::MeterText),
+            },
+            TextSegment {
+                text: &text,
+                attr: value_attr,
+            },
+        ];
+        draw_text_segments(crt, x, y, &segments);
+    }
+
+    fn mode(&self) -> MeterMode {
+        self.mode
+    }
+
+    fn set_mode(&mut self, mode: MeterMode) {
+        self.mode = mode;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_apparmor_meter_new() {
+        let meter = AppArmorMeter::new();
+        assert_eq!(meter.mode, MeterMode::Text);
+        assert_eq!(meter.state, None);
+    }
+
+    #[test]
+    fn test_apparmor_meter_name() {
+        let meter = AppArmorMeter::new();
+        assert_eq!(meter.name(), "AppArmor");
+        assert_eq!(meter.caption(), "AppArmor: ");
+    }
+
+    #[test]
+    fn test_apparmor_meter_supported_modes() {
+        let meter = AppArmorMeter::new();
+        assert_eq!(meter.supported_modes(), 1 << MeterMode::Text as u32);
+        assert_eq!(meter.default_mode(), MeterMode::Text);
+    }
+
+    #[test]
+    fn test_apparmor_meter_expensive_data_id() {
+        let meter = AppArmorMeter::new();
+        assert_eq!(meter.expensive_data_id(), Some(MeterDataId::AppArmor));
+    }
+
+    #[test]
+    fn test_apparmor_meter_text() {
+        let mut meter = AppArmorMeter::new();
+        assert_eq!(meter.text(), "N/A");
+
+        meter.merge_expensive_data(&MeterExpensiveData::AppArmor(None));
+        assert_eq!(meter.text(), "disabled");
+
+        meter.merge_expensive_data(&MeterExpensiveData::AppArmor(Some(AppArmorState {
+            profiles: None,
+        })));
+        assert_eq!(meter.text(), "enabled; profiles: N/A");
+
+        meter.merge_expensive_data(&MeterExpensiveData::AppArmor(Some(AppArmorState {
+            profiles: Some(AppArmorProfiles {
+                enforce: 42,
+                complain: 3,
+                kill: 0,
+                unconfined: 1,
+            }),
+        })));
+        assert_eq!(
+            meter.text(),
+            "enabled; 42 enforce, 3 complain, 1 unconfined"
+        );
+    }
+
+    #[test]
+    fn test_parse_apparmor_profiles() {
+        let content = "\
+/usr/sbin/cupsd (enforce)
+/usr/sbin/cupsd//third_party (enforce)
+/usr/bin/man (enforce)
+firefox (complain)
+unprivileged_userns (unconfined)
+";
+        assert_eq!(
+            parse_apparmor_profiles(content),
+            AppArmorProfiles {
+                enforce: 3,
+                complain: 1,
+                kill: 0,
+                unconfined: 1,
+            }
+        );
+        assert_eq!(parse_apparmor_profiles(""), AppArmorProfiles::default());
+    }
+}
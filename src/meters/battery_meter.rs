@@ -80,17 +80,15 @@ impl Meter for BatteryMeter {
     }
 
     fn merge_expensive_data(&mut self, data: &MeterExpensiveData) {
-        // Use match to be future-proof when more variants are added
-        match data {
-            MeterExpensiveData::Battery {
-                percent,
-                ac_presence,
-                available,
-            } => {
-                self.percent = *percent;
-                self.ac_presence = *ac_presence;
-                self.available = *available;
-            }
+        if let MeterExpensiveData::Battery {
+            percent,
+            ac_presence,
+            available,
+        } = data
+        {
+            self.percent = *percent;
+            self.ac_presence = *ac_presence;
+            self.available = *available;
         }
     }
 
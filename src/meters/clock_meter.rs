@@ -5,7 +5,7 @@
 //! This meter demonstrates direct use of the ncurses-rs pure Rust API
 //! instead of the compatibility shim.
 
-use chrono::Local;
+use chrono::{DateTime, Local};
 
 use super::{draw_led, Meter, MeterMode};
 use crate::core::{Machine, Settings};
@@ -46,9 +46,10 @@ impl Meter for ClockMeter {
         MeterMode::Text
     }
 
-    fn update(&mut self, _machine: &Machine) {
-        // Get current local time
-        let now = Local::now();
+    fn update(&mut self, machine: &Machine) {
+        // Time of the last scan (like C htop's host->realtime), so replayed
+        // recordings show when each frame was captured
+        let now: DateTime<Local> = machine.realtime.into();
         self.time_str = now.format("%H:%M:%S").to_string();
     }
 
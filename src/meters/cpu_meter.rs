@@ -4,6 +4,7 @@
 
 use std::cell::RefCell;
 
+use super::meter_bg_scanner::MeterDataId;
 use super::{
     draw_cpu_stacked_graph, draw_graph, CpuStackedGraphData, GraphData, Meter, MeterMode,
     CPU_STACKED_GRAPH_SEGMENTS, DEFAULT_GRAPH_HEIGHT,
@@ -44,6 +45,10 @@ pub struct CpuMeter {
     guest: f64,
     iowait: f64,
     frequency: f64,
+    /// Temperature in Celsius (package temperature for the average)
+    temperature: Option<f32>,
+    /// Whether CPU temperatures are requested from the background scanner
+    show_temperature: bool,
     /// Number of CPUs (cached for height calculation)
     cpu_count: usize,
     /// Graph data for historical display (RefCell for interior mutability)
@@ -71,6 +76,8 @@ impl CpuMeter {
             guest: 0.0,
             iowait: 0.0,
             frequency: 0.0,
+            temperature: None,
+            show_temperature: false,
             cpu_count: 1,
             graph_data: RefCell::new(vec![GraphData::new()]),
             stacked_graph_data: RefCell::new(vec![CpuStackedGraphData::new()]),
@@ -130,6 +137,15 @@ impl CpuMeter {
         }
     }
 
+    /// Format a CPU temperature for display, or "N/A" if there is no sensor
+    pub(crate) fn format_temperature(celsius: Option<f32>, fahrenheit: bool) -> String {
+        match celsius {
+            Some(c) if fahrenheit => format!("{:5.1}\u{b0}F", c * 9.0 / 5.0 + 32.0),
+            Some(c) => format!("{:5.1}\u{b0}C", c),
+            None => "N/A".to_string(),
+        }
+    }
+
     /// Format CPU display text for Text/LED modes (matches C htop CPUMeter_display)
     #[allow(clippy::too_many_arguments)]
     pub(crate) fn format_cpu_display_text(
@@ -144,6 +160,7 @@ impl CpuMeter {
         frequency: f64,
         detailed_cpu_time: bool,
         show_cpu_frequency: bool,
+        temperature: Option<&str>,
     ) -> String {
         let mut text = String::new();
 
@@ -178,6 +195,10 @@ impl CpuMeter {
             text.push_str(&format!("freq: {} ", Self::format_frequency(frequency)));
         }
 
+        if let Some(temperature) = temperature {
+            text.push_str(&format!("temp:{} ", temperature));
+        }
+
         text
     }
 
@@ -194,6 +215,7 @@ impl CpuMeter {
         show_cpu_usage: bool,
         show_cpu_frequency: bool,
         frequency: f64,
+        temperature: Option<&str>,
     ) {
         // Draw caption (exactly 3 chars)
         let caption_attr = crt.color(ColorElement::MeterText);
@@ -218,7 +240,8 @@ impl CpuMeter {
         let inner_width = (bar_width - 2) as usize;
 
         // Build the text to display inside the bar (right-aligned)
-        // Matches C htop: cpuUsageBuffer + " " + cpuFrequencyBuffer (all inside the bar)
+        // Matches C htop: cpuUsageBuffer + " " + cpuFrequencyBuffer + " " +
+        // cpuTemperatureBuffer (all inside the bar)
         let mut text = String::new();
         if show_cpu_usage {
             text.push_str(&format!("{:.1}%", total_percent));
@@ -229,8 +252,15 @@ impl CpuMeter {
             }
             text.push_str(&Self::format_frequency(frequency));
         }
+        if let Some(temperature) = temperature {
+            if !text.is_empty() {
+                text.push(' ');
+            }
+            text.push_str(temperature.trim_start());
+        }
 
-        let text_len = text.len();
+        // Count characters, not bytes: the degree sign is two bytes long
+        let text_len = text.chars().count();
         let padding = inner_width.saturating_sub(text_len);
 
         // Calculate how many chars each segment takes and pre-compute color attrs
@@ -272,7 +302,8 @@ impl CpuMeter {
                 for _ in 0..*chars {
                     if !text.is_empty() && pos >= padding && pos - padding < text_len {
                         let ch = text.chars().nth(pos - padding).unwrap_or(bar_ch);
-                        let _ = win.addch(ch as u32);
+                        // addstr, as addch can't take the multi-byte degree sign
+                        let _ = win.addstr(ch.encode_utf8(&mut [0; 4]));
                     } else {
                         let _ = win.addch(bar_ch as u32);
                     }
@@ -285,7 +316,7 @@ impl CpuMeter {
             while pos < inner_width {
                 if !text.is_empty() && pos >= padding && pos - padding < text_len {
                     let ch = text.chars().nth(pos - padding).unwrap_or(' ');
-                    let _ = win.addch(ch as u32);
+                    let _ = win.addstr(ch.encode_utf8(&mut [0; 4]));
                 } else {
                     let _ = win.addch(' ' as u32);
                 }
@@ -311,6 +342,7 @@ impl CpuMeter {
         account_guest: bool,
         detailed_cpu_time: bool,
         show_cpu_frequency: bool,
+        temperature: Option<&str>,
     ) {
         // Build values array based on detailed_cpu_time setting
         // When detailed: show all 8 segments
@@ -370,6 +402,7 @@ impl CpuMeter {
             show_cpu_usage,
             show_cpu_frequency,
             cpu.frequency,
+            temperature,
         );
     }
 
@@ -393,6 +426,7 @@ impl CpuMeter {
         width: i32,
         detailed_cpu_time: bool,
         show_cpu_frequency: bool,
+        temperature: Option<&str>,
     ) {
         use crate::ui::ColorElement;
 
@@ -471,6 +505,11 @@ impl CpuMeter {
             ));
         }
 
+        if let Some(temperature) = temperature {
+            segments.push(("temp:".to_string(), caption_attr));
+            segments.push((format!("{} ", temperature), value_attr));
+        }
+
         crt.with_window(|win| {
             let mut cur_x = x;
 
@@ -503,6 +542,7 @@ impl CpuMeter {
         count_from_one: bool,
         detailed_cpu_time: bool,
         show_cpu_frequency: bool,
+        temperature: Option<&str>,
     ) {
         // Apply count_cpus_from_one setting
         let display_id = if count_from_one { cpu_idx + 1 } else { cpu_idx };
@@ -525,6 +565,7 @@ impl CpuMeter {
             width,
             detailed_cpu_time,
             show_cpu_frequency,
+            temperature,
         );
     }
 }
@@ -566,7 +607,13 @@ impl Meter for CpuMeter {
             self.guest = cpu.guest_percent;
             self.iowait = cpu.iowait_percent;
             self.frequency = cpu.frequency;
+            self.temperature = cpu.temperature;
         }
+        self.show_temperature = machine.show_cpu_temperature;
+    }
+
+    fn expensive_data_id(&self) -> Option<MeterDataId> {
+        self.show_temperature.then_some(MeterDataId::CpuTemperature)
     }
 
     fn height(&self) -> i32 {
@@ -596,6 +643,12 @@ impl Meter for CpuMeter {
         y: i32,
         width: i32,
     ) {
+        let temperature = |celsius: Option<f32>| {
+            settings
+                .show_cpu_temperature
+                .then(|| Self::format_temperature(celsius, settings.degree_fahrenheit))
+        };
+
         match self.mode {
             MeterMode::Bar => {
                 match self.selection {
@@ -633,6 +686,7 @@ impl Meter for CpuMeter {
                                     settings.account_guest_in_cpu_meter,
                                     settings.detailed_cpu_time,
                                     settings.show_cpu_frequency,
+                                    temperature(cpu.temperature).as_deref(),
                                 );
                             }
                         }
@@ -704,6 +758,7 @@ impl Meter for CpuMeter {
                             settings.show_cpu_usage,
                             settings.show_cpu_frequency,
                             self.frequency,
+                            temperature(self.temperature).as_deref(),
                         );
                     }
                 }
@@ -744,6 +799,7 @@ impl Meter for CpuMeter {
                                     settings.count_cpus_from_one,
                                     settings.detailed_cpu_time,
                                     settings.show_cpu_frequency,
+                                    temperature(cpu.temperature).as_deref(),
                                 );
                             }
                         }
@@ -780,6 +836,7 @@ impl Meter for CpuMeter {
                             width,
                             settings.detailed_cpu_time,
                             settings.show_cpu_frequency,
+                            temperature(self.temperature).as_deref(),
                         );
                     }
                 }
@@ -964,6 +1021,7 @@ impl Meter for CpuMeter {
                                     cpu.frequency,
                                     settings.detailed_cpu_time,
                                     settings.show_cpu_frequency,
+                                    temperature(cpu.temperature).as_deref(),
                                 );
                                 super::draw_led(crt, col_x, row_y, col_width, &caption, &text);
                             }
@@ -997,6 +1055,7 @@ impl Meter for CpuMeter {
                             self.frequency,
                             settings.detailed_cpu_time,
                             settings.show_cpu_frequency,
+                            temperature(self.temperature).as_deref(),
                         );
                         super::draw_led(crt, x, y, width, &caption, &text);
                     }
@@ -1183,7 +1242,7 @@ impl Meter for CpuMeter {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::core::{CpuData, Machine};
+    use crate::core::{CpuData, CpuTemperatures, Machine};
 
     // =========================================================================
     // Helper functions for creating test data
@@ -1416,6 +1475,7 @@ mod tests {
             2400.0, // frequency
             true,   // detailed_cpu_time
             false,  // show_cpu_frequency
+            None,   // temperature
         );
 
         assert!(text.contains(": 50.0%"));
@@ -1427,6 +1487,7 @@ mod tests {
         assert!(text.contains("gu:  0.1%"));
         assert!(text.contains("wa:  3.0%"));
         assert!(!text.contains("freq:"));
+        assert!(!text.contains("temp:"));
     }
 
     #[test]
@@ -1434,11 +1495,45 @@ mod tests {
         let text = CpuMeter::format_cpu_display_text(
             50.0, 10.0, 5.0, 1.0, 0.5, 0.2, 0.1, 3.0, 2400.0, true, // detailed_cpu_time
             true, // show_cpu_frequency
+            None,
         );
 
         assert!(text.contains("freq: 2400MHz"));
     }
 
+    #[test]
+    fn test_format_cpu_display_text_with_temperature() {
+        let text = CpuMeter::format_cpu_display_text(
+            50.0,
+            10.0,
+            5.0,
+            1.0,
+            0.5,
+            0.2,
+            0.1,
+            3.0,
+            2400.0,
+            true,
+            true,
+            Some(" 45.0\u{b0}C"),
+        );
+
+        assert!(text.ends_with("freq: 2400MHz temp: 45.0\u{b0}C "));
+    }
+
+    #[test]
+    fn test_format_temperature() {
+        assert_eq!(
+            CpuMeter::format_temperature(Some(45.0), false),
+            " 45.0\u{b0}C"
+        );
+        assert_eq!(
+            CpuMeter::format_temperature(Some(45.0), true),
+            "113.0\u{b0}F"
+        );
+        assert_eq!(CpuMeter::format_temperature(None, false), "N/A");
+    }
+
     #[test]
     fn test_format_cpu_display_text_non_detailed() {
         let text = CpuMeter::format_cpu_display_text(
@@ -1452,6 +1547,7 @@ mod tests {
             3.0,  // iowait
             2400.0, false, // detailed_cpu_time
             false, // show_cpu_frequency
+            None,
         );
 
         // Non-detailed should combine values
@@ -1470,7 +1566,7 @@ mod tests {
             50.0, 10.0, 5.0, 1.0, 0.5, -1.0, // steal (negative = unsupported)
             -1.0, // guest (negative = unsupported)
             3.0, 0.0, true, // detailed_cpu_time
-            false,
+            false, None,
         );
 
         assert!(!text.contains("st:"));
@@ -1500,6 +1596,29 @@ mod tests {
         assert_eq!(meter.cpu_count, 4);
     }
 
+    #[test]
+    fn test_cpu_meter_update_temperature() {
+        let mut meter = CpuMeter::new(Some(1));
+        let mut machine = create_test_machine_with_cpus(2);
+
+        meter.update(&machine);
+        assert_eq!(meter.temperature, None);
+        assert_eq!(meter.expensive_data_id(), None);
+
+        machine.show_cpu_temperature = true;
+        machine.set_cpu_temperatures(Some(&CpuTemperatures {
+            package: Some(60.0),
+            cpus: vec![Some(50.0), Some(55.0)],
+        }));
+        meter.update(&machine);
+        assert_eq!(meter.temperature, Some(55.0));
+        assert_eq!(meter.expensive_data_id(), Some(MeterDataId::CpuTemperature));
+
+        let mut average = CpuMeter::average();
+        average.update(&machine);
+        assert_eq!(average.temperature, Some(60.0));
+    }
+
     #[test]
     fn test_cpu_meter_update_specific_cpu() {
         let mut meter = CpuMeter::new(Some(2));
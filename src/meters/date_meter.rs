@@ -2,7 +2,7 @@
 //!
 //! Displays the current date in YYYY-MM-DD format.
 
-use chrono::Local;
+use chrono::{DateTime, Local};
 
 use super::{draw_led, Meter, MeterMode};
 use crate::core::{Machine, Settings};
@@ -43,9 +43,10 @@ impl Meter for DateMeter {
         MeterMode::Text
     }
 
-    fn update(&mut self, _machine: &Machine) {
-        // Get current local date
-        let now = Local::now();
+    fn update(&mut self, machine: &Machine) {
+        // Time of the last scan (like C htop's host->realtime), so replayed
+        // recordings show when each frame was captured
+        let now: DateTime<Local> = machine.realtime.into();
         self.date_str = now.format("%Y-%m-%d").to_string();
     }
 
@@ -2,7 +2,7 @@
 //!
 //! Displays the current date and time in YYYY-MM-DD HH:MM:SS format.
 
-use chrono::Local;
+use chrono::{DateTime, Local};
 
 use super::{draw_led, Meter, MeterMode};
 use crate::core::{Machine, Settings};
@@ -43,9 +43,10 @@ impl Meter for DateTimeMeter {
         MeterMode::Text
     }
 
-    fn update(&mut self, _machine: &Machine) {
-        // Get current local date and time
-        let now = Local::now();
+    fn update(&mut self, machine: &Machine) {
+        // Time of the last scan (like C htop's host->realtime), so replayed
+        // recordings show when each frame was captured
+        let now: DateTime<Local> = machine.realtime.into();
         self.datetime_str = now.format("%Y-%m-%d %H:%M:%S").to_string();
     }
 
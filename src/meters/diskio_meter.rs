@@ -23,9 +23,13 @@ enum RateStatus {
 /// Displays disk read/write rates and utilization.
 /// In bar mode, shows two half-width bars: one for rate, one for utilization.
 /// In text mode, shows "read: XiB/s write: YiB/s; Z% busy (N disks)".
+/// Configured as "DiskIO(sda)" it shows a single device instead of all disks.
 #[derive(Debug)]
 pub struct DiskIOMeter {
     mode: MeterMode,
+    /// Device to show, None for the sum of all disks
+    device: Option<String>,
+    caption: String,
     status: RateStatus,
     /// Cached read rate in bytes per second
     read_rate: f64,
@@ -51,6 +55,8 @@ impl DiskIOMeter {
     pub fn new() -> Self {
         DiskIOMeter {
             mode: MeterMode::Text, // Default to text mode like C htop
+            device: None,
+            caption: "Disk IO".to_string(),
             status: RateStatus::Init,
             read_rate: 0.0,
             write_rate: 0.0,
@@ -61,6 +67,15 @@ impl DiskIOMeter {
         }
     }
 
+    /// Create a meter for a single block device (e.g. "nvme0n1")
+    pub fn for_device(device: &str) -> Self {
+        DiskIOMeter {
+            device: Some(device.to_string()),
+            caption: format!("{} IO", device),
+            ..Self::new()
+        }
+    }
+
     /// Format a rate value using human-readable units (like C htop's Meter_humanUnit)
     pub(crate) fn human_unit(bytes_per_sec: f64) -> String {
         const UNIT_PREFIXES: [char; 5] = ['K', 'M', 'G', 'T', 'P'];
@@ -89,14 +104,16 @@ impl Meter for DiskIOMeter {
     }
 
     fn caption(&self) -> &str {
-        "Disk IO"
+        &self.caption
     }
 
     fn update(&mut self, machine: &Machine) {
         // Check if we have valid rate data
         // disk_io_last_update > 0 means we've scanned at least once
         // disk_io_num_disks > 0 means we found disks and have valid data
-        if machine.disk_io_last_update == 0 || machine.disk_io_num_disks == 0 {
+        if machine.disk_io_last_update == 0
+            || (self.device.is_none() && machine.disk_io_num_disks == 0)
+        {
             self.status = RateStatus::Init;
             return;
         }
@@ -110,18 +127,31 @@ impl Meter for DiskIOMeter {
             return;
         }
 
-        // We have valid data (rates may be 0 if disk is idle, that's fine)
-        self.status = RateStatus::Data;
-        self.read_rate = machine.disk_io_read_rate;
-        self.write_rate = machine.disk_io_write_rate;
-        self.utilization = machine.disk_io_utilization;
-        self.num_disks = machine.disk_io_num_disks;
+        if let Some(device) = &self.device {
+            // The device may have been removed (or its name mistyped)
+            let Some(disk) = machine.disk(device) else {
+                self.status = RateStatus::NoData;
+                return;
+            };
+            self.status = RateStatus::Data;
+            self.read_rate = disk.read_rate;
+            self.write_rate = disk.write_rate;
+            self.utilization = disk.utilization;
+            self.num_disks = 1;
+        } else {
+            // We have valid data (rates may be 0 if disk is idle, that's fine)
+            self.status = RateStatus::Data;
+            self.read_rate = machine.disk_io_read_rate;
+            self.write_rate = machine.disk_io_write_rate;
+            self.utilization = machine.disk_io_utilization;
+            self.num_disks = machine.disk_io_num_disks;
+        }
 
         // Normalize utilization for bar display (0-1)
         // Utilization can exceed 100% if multiple disks are busy, so we normalize
         // to the number of disks
-        self.utilization_norm = if machine.disk_io_num_disks > 0 {
-            (self.utilization / 100.0 / machine.disk_io_num_disks as f64).min(1.0)
+        self.utilization_norm = if self.num_disks > 0 {
+            (self.utilization / 100.0 / self.num_disks as f64).min(1.0)
         } else {
             0.0
         };
@@ -303,7 +333,7 @@ impl Meter for DiskIOMeter {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::core::Machine;
+    use crate::core::{DiskIoData, Machine};
 
     // ==================== Constructor Tests ====================
 
@@ -442,6 +472,39 @@ mod tests {
         assert_eq!(meter.status, RateStatus::Stale);
     }
 
+    #[test]
+    fn test_diskio_meter_update_device() {
+        let mut meter = DiskIOMeter::for_device("nvme0n1");
+        let machine = Machine {
+            disk_io_last_update: 1000,
+            realtime_ms: 1500,
+            disk_io_read_rate: 4096.0,
+            disk_io_num_disks: 2,
+            disks: vec![DiskIoData {
+                name: "nvme0n1".to_string(),
+                read_rate: 1024.0,
+                write_rate: 2048.0,
+                utilization: 75.0,
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+
+        meter.update(&machine);
+
+        assert_eq!(meter.caption(), "nvme0n1 IO");
+        assert_eq!(meter.status, RateStatus::Data);
+        assert_eq!(meter.read_rate, 1024.0);
+        assert_eq!(meter.write_rate, 2048.0);
+        assert_eq!(meter.num_disks, 1);
+        assert_eq!(meter.utilization_norm, 0.75);
+
+        // Unknown devices have no data
+        let mut missing = DiskIOMeter::for_device("sdz");
+        missing.update(&machine);
+        assert_eq!(missing.status, RateStatus::NoData);
+    }
+
     // ==================== Meter Trait Tests ====================
 
     #[test]
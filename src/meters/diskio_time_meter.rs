@@ -1,23 +1,55 @@
 //! DiskIO Time Meter
 //!
-//! Displays disk percent time busy.
+//! Displays disk percent time busy, for all disks or (configured as
+//! "DiskIOTime(sda)") for a single device.
 
-use super::{Meter, MeterMode};
+use std::cell::RefCell;
+
+use super::{
+    draw_bar_with_text, draw_graph, draw_led, draw_text_segments, BarSegment, GraphData, Meter,
+    MeterMode, TextSegment,
+};
 use crate::core::{Machine, Settings};
 use crate::ui::{ColorElement, Crt};
 
 /// DiskIO Time Meter - displays disk percent time busy
+///
+/// Bar mode: "Dsk[|||||                 12.5%]"
+/// Text mode: "Dsk: 12.5% busy"
 #[derive(Debug, Default)]
 pub struct DiskIOTimeMeter {
     mode: MeterMode,
+    /// Device to show, None for the average of all disks
+    device: Option<String>,
+    /// Caption for a single device, e.g. "sda: "
+    device_caption: Option<String>,
+    /// Percent time busy, None until two scans have been made
+    busy: Option<f64>,
+    /// Graph data for historical display (RefCell for interior mutability)
+    graph_data: RefCell<GraphData>,
 }
 
 impl DiskIOTimeMeter {
     pub fn new() -> Self {
         Self {
             mode: MeterMode::Text,
+            ..Default::default()
+        }
+    }
+
+    /// Create a meter for a single block device (e.g. "sda")
+    pub fn for_device(device: &str) -> Self {
+        Self {
+            device: Some(device.to_string()),
+            device_caption: Some(format!("{}: ", device)),
+            ..Self::new()
         }
     }
+
+    /// Caption without the trailing ": ", used as bar and graph label
+    fn label(&self) -> &str {
+        self.caption().trim_end_matches(": ")
+    }
 }
 
 impl Meter for DiskIOTimeMeter {
@@ -26,35 +58,93 @@ impl Meter for DiskIOTimeMeter {
     }
 
     fn caption(&self) -> &str {
-        "Dsk: "
+        self.device_caption.as_deref().unwrap_or("Dsk: ")
+    }
+
+    fn supported_modes(&self) -> u32 {
+        (1 << MeterMode::Bar as u32)
+            | (1 << MeterMode::Text as u32)
+            | (1 << MeterMode::Graph as u32)
+            | (1 << MeterMode::Led as u32)
     }
 
-    fn update(&mut self, _machine: &Machine) {
-        // Not yet implemented
+    fn update(&mut self, machine: &Machine) {
+        // disk_io_last_update > 0 means we've scanned at least once
+        if machine.disk_io_last_update == 0 {
+            self.busy = None;
+            return;
+        }
+
+        self.busy = match &self.device {
+            Some(device) => machine.disk(device).map(|disk| disk.utilization.min(100.0)),
+            // Busy time adds up over the disks, so average it
+            None if machine.disk_io_num_disks > 0 => {
+                Some((machine.disk_io_utilization / machine.disk_io_num_disks as f64).min(100.0))
+            }
+            None => None,
+        };
     }
 
     fn draw(
         &self,
         crt: &mut Crt,
         _machine: &Machine,
-        _settings: &Settings,
+        settings: &Settings,
         x: i32,
         y: i32,
-        _width: i32,
+        width: i32,
     ) {
-        let caption_attr = crt.color(ColorElement::MeterText);
-        let text_attr = crt.color(ColorElement::MeterValueError);
-        let reset_attr = crt.color(ColorElement::ResetColor);
-        let caption = self.caption();
-
-        crt.with_window(|win| {
-            let _ = win.mv(y, x);
-            let _ = win.attrset(caption_attr);
-            let _ = win.addstr(caption);
-            let _ = win.attrset(text_attr);
-            let _ = win.addstr("Not implemented");
-            let _ = win.attrset(reset_attr);
-        });
+        let text = match self.busy {
+            Some(busy) => format!("{:.1}%", busy),
+            None => "N/A".to_string(),
+        };
+
+        match self.mode {
+            MeterMode::Graph => {
+                if let Some(busy) = self.busy {
+                    let mut graph_data = self.graph_data.borrow_mut();
+                    graph_data.record(busy / 100.0, settings.delay * 100);
+                }
+
+                let graph_data = self.graph_data.borrow();
+                draw_graph(crt, x, y, width, self.height(), &graph_data, self.label());
+            }
+            MeterMode::Text => {
+                // Highlight busy disks like the DiskIO meter
+                let value_attr = match self.busy {
+                    Some(busy) if busy > 40.0 => crt.color(ColorElement::MeterValueNotice),
+                    Some(_) => crt.color(ColorElement::MeterValue),
+                    None => crt.color(ColorElement::MeterValueError),
+                };
+                let mut segments = vec![
+                    TextSegment {
+                        text: self.caption(),
+                        attr: crt.color(ColorElement::MeterText),
+                    },
+                    TextSegment {
+                        text: &text,
+                        attr: value_attr,
+                    },
+                ];
+                if self.busy.is_some() {
+                    segments.push(TextSegment {
+                        text: " busy",
+                        attr: crt.color(ColorElement::MeterText),
+                    });
+                }
+                draw_text_segments(crt, x, y, &segments);
+            }
+            MeterMode::Led => {
+                draw_led(crt, x, y, width, self.caption(), &text);
+            }
+            _ => {
+                let segments = [BarSegment {
+                    value: self.busy.unwrap_or(0.0),
+                    attr: crt.color(ColorElement::MeterValueNotice),
+                }];
+                draw_bar_with_text(crt, x, y, width, self.label(), &segments, 100.0, &text);
+            }
+        }
     }
 
     fn mode(&self) -> MeterMode {
@@ -69,6 +159,7 @@ impl Meter for DiskIOTimeMeter {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::core::DiskIoData;
 
     #[test]
     fn test_diskio_time_meter_new() {
@@ -108,7 +199,45 @@ mod tests {
     fn test_diskio_time_meter_update_does_nothing() {
         let mut meter = DiskIOTimeMeter::new();
         let machine = Machine::default();
-        // Update is not yet implemented but should not panic
+        // Nothing has been scanned yet
+        meter.update(&machine);
+        assert_eq!(meter.busy, None);
+    }
+
+    #[test]
+    fn test_diskio_time_meter_update_average() {
+        let mut meter = DiskIOTimeMeter::new();
+        let machine = Machine {
+            disk_io_last_update: 1000,
+            disk_io_utilization: 150.0,
+            disk_io_num_disks: 2,
+            ..Default::default()
+        };
         meter.update(&machine);
+        assert_eq!(meter.busy, Some(75.0));
+    }
+
+    #[test]
+    fn test_diskio_time_meter_update_device() {
+        let machine = Machine {
+            disk_io_last_update: 1000,
+            disk_io_num_disks: 2,
+            disks: vec![DiskIoData {
+                name: "sda".to_string(),
+                utilization: 30.0,
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+
+        let mut meter = DiskIOTimeMeter::for_device("sda");
+        meter.update(&machine);
+        assert_eq!(meter.caption(), "sda: ");
+        assert_eq!(meter.label(), "sda");
+        assert_eq!(meter.busy, Some(30.0));
+
+        let mut missing = DiskIOTimeMeter::for_device("sdb");
+        missing.update(&machine);
+        assert_eq!(missing.busy, None);
     }
 }
@@ -1,23 +1,93 @@
 //! GPU Meter
 //!
-//! Displays GPU usage statistics.
-//! Available on Linux and macOS.
+//! Displays GPU engine utilization. On Linux the data comes from the
+//! DRM fdinfo of all processes (see `platform::linux_gpu`), so only
+//! engines of GPUs with a drm-usage-stats capable driver are shown.
 
-use super::{Meter, MeterMode};
+use std::cell::RefCell;
+
+use super::{
+    draw_bar_with_text, draw_graph, draw_stacked_graph, draw_text_segments, BarSegment, GraphData,
+    Meter, MeterMode, StackedGraphData, TextSegment, STACKED_GRAPH_SEGMENTS,
+};
+#[cfg(target_os = "linux")]
+use crate::core::ScanFlags;
 use crate::core::{Machine, Settings};
 use crate::ui::{ColorElement, Crt};
 
-/// GPU Meter - displays GPU usage
-#[derive(Debug, Default)]
+/// Number of engines drawn with their own color, the rest are summed up
+const GPU_ENGINE_COLORS: usize = STACKED_GRAPH_SEGMENTS - 1;
+
+/// GPU Meter - displays per-engine GPU utilization
+///
+/// Bar mode: "GPU[|||||||||        23.5%]"
+/// Text mode: "GPU:23.5% gfx:20.0% dec:3.5%"
+#[derive(Debug)]
 pub struct GpuMeter {
     mode: MeterMode,
+    /// Busy percentage per engine, sorted by engine name
+    engines: Vec<(String, f64)>,
+    /// Graph data for historical display (RefCell for interior mutability)
+    graph_data: RefCell<GraphData>,
+    /// Stacked graph data with one segment per engine
+    stacked_graph_data: RefCell<StackedGraphData>,
+}
+
+impl Default for GpuMeter {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl GpuMeter {
     pub fn new() -> Self {
-        Self {
-            mode: MeterMode::Text,
+        GpuMeter {
+            mode: MeterMode::Bar,
+            engines: Vec::new(),
+            graph_data: RefCell::new(GraphData::new()),
+            stacked_graph_data: RefCell::new(StackedGraphData::new()),
+        }
+    }
+
+    /// Sum of all engine utilizations, capped at 100%
+    fn total(&self) -> f64 {
+        self.engines
+            .iter()
+            .map(|(_, percent)| percent)
+            .sum::<f64>()
+            .min(100.0)
+    }
+
+    /// Per-engine percentages folded into the colored segments:
+    /// the first engines get their own segment, the rest go to the last one
+    fn segments(&self) -> [f64; STACKED_GRAPH_SEGMENTS] {
+        let mut segments = [0.0; STACKED_GRAPH_SEGMENTS];
+        for (i, (_, percent)) in self.engines.iter().enumerate() {
+            segments[i.min(GPU_ENGINE_COLORS)] += percent;
+        }
+        segments
+    }
+
+    fn segment_colors(crt: &Crt) -> [u32; STACKED_GRAPH_SEGMENTS] {
+        [
+            crt.color(ColorElement::GpuEngine1),
+            crt.color(ColorElement::GpuEngine2),
+            crt.color(ColorElement::GpuEngine3),
+            crt.color(ColorElement::GpuEngine4),
+            crt.color(ColorElement::GpuResidue),
+        ]
+    }
+
+    /// Text shown for Text and LED modes
+    fn text(&self) -> String {
+        if self.engines.is_empty() {
+            return "N/A".to_string();
         }
+        let mut text = format!("{:.1}%", self.total());
+        for (name, percent) in &self.engines {
+            text.push_str(&format!(" {}:{:.1}%", name, percent));
+        }
+        text
     }
 }
 
@@ -31,39 +101,147 @@ impl Meter for GpuMeter {
     }
 
     fn supported_modes(&self) -> u32 {
-        1 << MeterMode::Text as u32
+        (1 << MeterMode::Bar as u32)
+            | (1 << MeterMode::Text as u32)
+            | (1 << MeterMode::Graph as u32)
+            | (1 << MeterMode::Led as u32)
+            | (1 << MeterMode::StackedGraph as u32)
     }
 
     fn default_mode(&self) -> MeterMode {
-        MeterMode::Text
+        MeterMode::Bar
+    }
+
+    #[cfg(target_os = "linux")]
+    fn scan_flags(&self) -> ScanFlags {
+        ScanFlags::GPU
     }
 
-    fn update(&mut self, _machine: &Machine) {
-        // Not yet implemented
+    fn update(&mut self, machine: &Machine) {
+        self.engines = machine
+            .gpu_engines
+            .iter()
+            .map(|engine| (engine.name.clone(), engine.percent))
+            .collect();
     }
 
     fn draw(
         &self,
         crt: &mut Crt,
         _machine: &Machine,
-        _settings: &Settings,
+        settings: &Settings,
         x: i32,
         y: i32,
-        _width: i32,
+        width: i32,
     ) {
-        let caption_attr = crt.color(ColorElement::MeterText);
-        let text_attr = crt.color(ColorElement::MeterValueError);
-        let reset_attr = crt.color(ColorElement::ResetColor);
-        let caption = self.caption();
+        match self.mode {
+            MeterMode::Bar => {
+                let colors = Self::segment_colors(crt);
+                let segments: Vec<BarSegment> = self
+                    .segments()
+                    .iter()
+                    .zip(colors)
+                    .map(|(&value, attr)| BarSegment { value, attr })
+                    .collect();
+                let text = if self.engines.is_empty() {
+                    "N/A".to_string()
+                } else {
+                    format!("{:.1}%", self.total())
+                };
+                draw_bar_with_text(crt, x, y, width, "GPU", &segments, 100.0, &text);
+            }
+            MeterMode::Text => {
+                let text_attr = crt.color(ColorElement::MeterText);
+                let value_attr = crt.color(ColorElement::MeterValue);
+                let colors = Self::segment_colors(crt);
+
+                if self.engines.is_empty() {
+                    let error_attr = crt.color(ColorElement::MeterValueError);
+                    let segments = [
+                        TextSegment {
+                            text: "GPU:",
+                            attr: text_attr,
+                        },
+                        TextSegment {
+                            text: "N/A",
+                            attr: error_attr,
+                        },
+                    ];
+                    draw_text_segments(crt, x, y, &segments);
+                    return;
+                }
+
+                // Pre-compute strings so segments can borrow them
+                let total_str = format!("{:.1}%", self.total());
+                let engine_strs: Vec<(String, String)> = self
+                    .engines
+                    .iter()
+                    .map(|(name, percent)| (format!(" {}:", name), format!("{:.1}%", percent)))
+                    .collect();
+
+                let mut segments = vec![
+                    TextSegment {
+                        text: "GPU:",
+                        attr: text_attr,
+                    },
+                    TextSegment {
+                        text: &total_str,
+                        attr: value_attr,
+                    },
+                ];
+                for (i, (label, value)) in engine_strs.iter().enumerate() {
+                    segments.push(TextSegment {
+                        text: label,
+                        attr: text_attr,
+                    });
+                    segments.push(TextSegment {
+                        text: value,
+                        attr: colors[i.min(GPU_ENGINE_COLORS)],
+                    });
+                }
+
+                draw_text_segments(crt, x, y, &segments);
+            }
+            MeterMode::Led => {
+                let text = format!(":{}", self.text());
+                super::draw_led(crt, x, y, width, "GPU", &text);
+            }
+            MeterMode::Graph => {
+                {
+                    let mut graph_data = self.graph_data.borrow_mut();
+                    graph_data.record(self.total() / 100.0, settings.delay * 100);
+                }
+
+                let graph_data = self.graph_data.borrow();
+                draw_graph(crt, x, y, width, self.height(), &graph_data, "GPU");
+            }
+            MeterMode::StackedGraph => {
+                // Segments stack up to the total, so scale them down if the
+                // engines add up to more than 100%
+                let segments = self.segments();
+                let sum: f64 = segments.iter().sum();
+                let scale = if sum > 100.0 { 1.0 / sum } else { 0.01 };
+                let normalized = segments.map(|value| value * scale);
+
+                {
+                    let mut stacked_graph_data = self.stacked_graph_data.borrow_mut();
+                    stacked_graph_data.record(normalized, settings.delay * 100);
+                }
 
-        crt.with_window(|win| {
-            let _ = win.mv(y, x);
-            let _ = win.attrset(caption_attr);
-            let _ = win.addstr(caption);
-            let _ = win.attrset(text_attr);
-            let _ = win.addstr("Not implemented");
-            let _ = win.attrset(reset_attr);
-        });
+                let colors = Self::segment_colors(crt);
+                let stacked_graph_data = self.stacked_graph_data.borrow();
+                draw_stacked_graph(
+                    crt,
+                    x,
+                    y,
+                    width,
+                    self.height(),
+                    &stacked_graph_data,
+                    "GPU",
+                    &colors,
+                );
+            }
+        }
     }
 
     fn mode(&self) -> MeterMode {
@@ -78,17 +256,32 @@ impl Meter for GpuMeter {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::core::GpuEngineData;
+
+    fn machine_with_engines(engines: &[(&str, f64)]) -> Machine {
+        Machine {
+            gpu_engines: engines
+                .iter()
+                .map(|&(name, percent)| GpuEngineData {
+                    name: name.to_string(),
+                    percent,
+                    ..Default::default()
+                })
+                .collect(),
+            ..Default::default()
+        }
+    }
 
     #[test]
     fn test_gpu_meter_new() {
         let meter = GpuMeter::new();
-        assert_eq!(meter.mode, MeterMode::Text);
+        assert_eq!(meter.mode, MeterMode::Bar);
+        assert!(meter.engines.is_empty());
     }
 
     #[test]
     fn test_gpu_meter_default() {
         let meter = GpuMeter::default();
-        // MeterMode::default() is Bar
         assert_eq!(meter.mode, MeterMode::Bar);
     }
 
@@ -107,35 +300,72 @@ mod tests {
     #[test]
     fn test_gpu_meter_default_mode() {
         let meter = GpuMeter::new();
-        assert_eq!(meter.default_mode(), MeterMode::Text);
+        assert_eq!(meter.default_mode(), MeterMode::Bar);
     }
 
     #[test]
     fn test_gpu_meter_supported_modes() {
         let meter = GpuMeter::new();
         let modes = meter.supported_modes();
-        // Should support only Text mode
+        assert!(modes & (1 << MeterMode::Bar as u32) != 0);
         assert!(modes & (1 << MeterMode::Text as u32) != 0);
-        // Should not support Led, Bar or Graph modes
-        assert!(modes & (1 << MeterMode::Led as u32) == 0);
-        assert!(modes & (1 << MeterMode::Bar as u32) == 0);
-        assert!(modes & (1 << MeterMode::Graph as u32) == 0);
+        assert!(modes & (1 << MeterMode::Graph as u32) != 0);
+        assert!(modes & (1 << MeterMode::Led as u32) != 0);
+        assert!(modes & (1 << MeterMode::StackedGraph as u32) != 0);
     }
 
     #[test]
     fn test_gpu_meter_mode() {
         let mut meter = GpuMeter::new();
+        assert_eq!(meter.mode(), MeterMode::Bar);
+
+        meter.set_mode(MeterMode::Text);
         assert_eq!(meter.mode(), MeterMode::Text);
+    }
 
-        meter.set_mode(MeterMode::Bar);
-        assert_eq!(meter.mode(), MeterMode::Bar);
+    #[test]
+    fn test_gpu_meter_update_no_gpu() {
+        let mut meter = GpuMeter::new();
+        meter.update(&Machine::default());
+        assert!(meter.engines.is_empty());
+        assert_eq!(meter.text(), "N/A");
     }
 
     #[test]
-    fn test_gpu_meter_update_does_nothing() {
+    fn test_gpu_meter_update_engines() {
         let mut meter = GpuMeter::new();
-        let machine = Machine::default();
-        // Update is not yet implemented but should not panic
-        meter.update(&machine);
+        meter.update(&machine_with_engines(&[("dec", 3.5), ("gfx", 20.0)]));
+        assert_eq!(meter.engines.len(), 2);
+        assert!((meter.total() - 23.5).abs() < 1e-9);
+        assert_eq!(meter.text(), "23.5% dec:3.5% gfx:20.0%");
+    }
+
+    #[test]
+    fn test_gpu_meter_residue_segment() {
+        let mut meter = GpuMeter::new();
+        meter.update(&machine_with_engines(&[
+            ("a", 10.0),
+            ("b", 10.0),
+            ("c", 10.0),
+            ("d", 10.0),
+            ("e", 5.0),
+            ("f", 5.0),
+        ]));
+        // Engines beyond the fourth are summed into the residue segment
+        assert_eq!(meter.segments(), [10.0, 10.0, 10.0, 10.0, 10.0]);
+    }
+
+    #[test]
+    fn test_gpu_meter_total_capped() {
+        let mut meter = GpuMeter::new();
+        meter.update(&machine_with_engines(&[("gfx", 90.0), ("compute", 60.0)]));
+        assert_eq!(meter.total(), 100.0);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_gpu_meter_scan_flags() {
+        let meter = GpuMeter::new();
+        assert_eq!(meter.scan_flags(), ScanFlags::GPU);
     }
 }
@@ -1,23 +1,67 @@
 //! HugePages Meter
 //!
-//! Displays HugePages usage (Linux-specific).
+//! Displays HugePages usage (Linux-specific). The huge page pools are read
+//! into `Machine::huge_pages` by the platform scan.
 
-use super::{Meter, MeterMode};
-use crate::core::{Machine, Settings};
+use std::cell::RefCell;
+
+use super::{
+    draw_bar_with_text, draw_graph, draw_text_segments, human_unit, BarSegment, GraphData, Meter,
+    MeterMode, TextSegment,
+};
+use crate::core::{HugePageData, Machine, Settings};
 use crate::ui::{ColorElement, Crt};
 
+/// Colors of the page sizes, pools beyond the fourth share the last one
+const HUGEPAGE_COLORS: [ColorElement; 4] = [
+    ColorElement::HugePage1,
+    ColorElement::HugePage2,
+    ColorElement::HugePage3,
+    ColorElement::HugePage4,
+];
+
+/// Short page size label, e.g. "2M" or "1G"
+fn page_size_label(size_kb: u64) -> String {
+    const UNIT_PREFIXES: [char; 4] = ['K', 'M', 'G', 'T'];
+    let mut size = size_kb;
+    let mut i = 0;
+    while size >= 1024 && size.is_multiple_of(1024) && i < UNIT_PREFIXES.len() - 1 {
+        size /= 1024;
+        i += 1;
+    }
+    format!("{}{}", size, UNIT_PREFIXES[i])
+}
+
 /// HugePages Meter - displays HugePages usage
+///
+/// Bar mode: "HP[||||||            768M/1.00G]" with one segment per page size
+/// Text mode: "HP: 3.00G used:768M 2M:768M 1G:0K"
 #[derive(Debug, Default)]
 pub struct HugePagesMeter {
     mode: MeterMode,
+    pools: Vec<HugePageData>,
+    /// Graph data for historical display (RefCell for interior mutability)
+    graph_data: RefCell<GraphData>,
 }
 
 impl HugePagesMeter {
     pub fn new() -> Self {
         Self {
             mode: MeterMode::Bar,
+            pools: Vec::new(),
+            graph_data: RefCell::new(GraphData::new()),
         }
     }
+
+    /// Size of all pools in KB
+    fn total(&self) -> u64 {
+        self.pools.iter().map(HugePageData::total_kb).sum()
+    }
+
+    /// Size of the pages in use in KB
+    fn used(&self) -> u64 {
+        self.pools.iter().map(HugePageData::used_kb).sum()
+    }
 }
 
 impl Meter for HugePagesMeter {
@@ -29,32 +73,117 @@ impl Meter for HugePagesMeter {
         "HP: "
     }
 
-    fn update(&mut self, _machine: &Machine) {
-        // Not yet implemented
+    fn supported_modes(&self) -> u32 {
+        (1 << MeterMode::Bar as u32)
+            | (1 << MeterMode::Text as u32)
+            | (1 << MeterMode::Graph as u32)
+    }
+
+    fn update(&mut self, machine: &Machine) {
+        self.pools = machine.huge_pages.clone();
     }
 
     fn draw(
         &self,
         crt: &mut Crt,
         _machine: &Machine,
-        _settings: &Settings,
+        settings: &Settings,
         x: i32,
         y: i32,
-        _width: i32,
+        width: i32,
     ) {
-        let caption_attr = crt.color(ColorElement::MeterText);
-        let text_attr = crt.color(ColorElement::MeterValueError);
-        let reset_attr = crt.color(ColorElement::ResetColor);
-        let caption = self.caption();
+        let total = self.total();
+        if total == 0 {
+            let segments = [
+                TextSegment {
+                    text: self.caption(),
+                    attr: crt.color(ColorElement::MeterText),
+                },
+                TextSegment {
+                    text: "N/A",
+                    attr: crt.color(ColorElement::MeterValueError),
+                },
+            ];
+            draw_text_segments(crt, x, y, &segments);
+            return;
+        }
 
-        crt.with_window(|win| {
-            let _ = win.mv(y, x);
-            let _ = win.attrset(caption_attr);
-            let _ = win.addstr(caption);
-            let _ = win.attrset(text_attr);
-            let _ = win.addstr("Not implemented");
-            let _ = win.attrset(reset_attr);
-        });
+        match self.mode {
+            MeterMode::Graph => {
+                {
+                    let mut graph_data = self.graph_data.borrow_mut();
+                    graph_data.record(self.used() as f64 / total as f64, settings.delay * 100);
+                }
+
+                let graph_data = self.graph_data.borrow();
+                draw_graph(crt, x, y, width, self.height(), &graph_data, "HP");
+            }
+            MeterMode::Text => {
+                let text_attr = crt.color(ColorElement::MeterText);
+                let total_str = human_unit(total as f64);
+                let used_str = human_unit(self.used() as f64);
+                let pool_strs: Vec<(String, String)> = self
+                    .pools
+                    .iter()
+                    .map(|pool| {
+                        (
+                            format!(" {}:", page_size_label(pool.size_kb)),
+                            human_unit(pool.used_kb() as f64),
+                        )
+                    })
+                    .collect();
+
+                let mut segments = vec![
+                    TextSegment {
+                        text: self.caption(),
+                        attr: text_attr,
+                    },
+                    TextSegment {
+                        text: &total_str,
+                        attr: crt.color(ColorElement::MeterValue),
+                    },
+                    TextSegment {
+                        text: " used:",
+                        attr: text_attr,
+                    },
+                    TextSegment {
+                        text: &used_str,
+                        attr: crt.color(ColorElement::MeterValue),
+                    },
+                ];
+                for (i, (label, value)) in pool_strs.iter().enumerate() {
+                    segments.push(TextSegment {
+                        text: label,
+                        attr: text_attr,
+                    });
+                    segments.push(TextSegment {
+                        text: value,
+                        attr: crt.color(HUGEPAGE_COLORS[i.min(HUGEPAGE_COLORS.len() - 1)]),
+                    });
+                }
+                draw_text_segments(crt, x, y, &segments);
+            }
+            _ => {
+                let mut values = [0.0; HUGEPAGE_COLORS.len()];
+                for (i, pool) in self.pools.iter().enumerate() {
+                    values[i.min(HUGEPAGE_COLORS.len() - 1)] += pool.used_kb() as f64;
+                }
+                let segments: Vec<BarSegment> = values
+                    .iter()
+                    .zip(HUGEPAGE_COLORS)
+                    .map(|(&value, element)| BarSegment {
+                        value,
+                        attr: crt.color(element),
+                    })
+                    .collect();
+                let text = format!(
+                    "{}/{}",
+                    human_unit(self.used() as f64),
+                    human_unit(total as f64)
+                );
+                draw_bar_with_text(crt, x, y, width, "HP", &segments, total as f64, &text);
+            }
+        }
     }
 
     fn mode(&self) -> MeterMode {
@@ -108,7 +237,39 @@ mod tests {
     fn test_hugepages_meter_update_does_nothing() {
         let mut meter = HugePagesMeter::new();
         let machine = Machine::default();
-        // Update is not yet implemented but should not panic
+        // Without huge page pools there is nothing to show
         meter.update(&machine);
+        assert_eq!(meter.total(), 0);
+    }
+
+    #[test]
+    fn test_hugepages_meter_update_from_machine() {
+        let mut meter = HugePagesMeter::new();
+        let machine = Machine {
+            huge_pages: vec![
+                HugePageData {
+                    size_kb: 2048,
+                    total: 512,
+                    free: 128,
+                },
+                HugePageData {
+                    size_kb: 1024 * 1024,
+                    total: 2,
+                    free: 1,
+                },
+            ],
+            ..Default::default()
+        };
+        meter.update(&machine);
+        assert_eq!(meter.total(), 3 * 1024 * 1024);
+        assert_eq!(meter.used(), 768 * 1024 + 1024 * 1024);
+    }
+
+    #[test]
+    fn test_page_size_label() {
+        assert_eq!(page_size_label(64), "64K");
+        assert_eq!(page_size_label(2048), "2M");
+        assert_eq!(page_size_label(1024 * 1024), "1G");
+        assert_eq!(page_size_label(1536), "1536K");
     }
 }
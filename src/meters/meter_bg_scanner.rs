@@ -26,7 +26,9 @@
 //!           │ ┌──────────────────────────────────────────────┐
 //!           └─│ Parallel collection:                         │
 //!             │ - Battery: pmset / sysfs reads               │
-//!             │ - GPU: nvidia-smi (future)                   │
+//!             │ - CPU temperature: hwmon / thermal sensors   │
+//!             │ - PSI: /proc/pressure reads                  │
+//!             │ - Systemd: D-Bus / systemctl                 │
 //!             │ - etc.                                       │
 //!             └──────────────────────────────────────────────┘
 //! ```
@@ -43,13 +45,30 @@ use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
 use std::thread::{self, JoinHandle};
 
+use super::apparmor_meter::AppArmorState;
 use super::battery_meter::ACPresence;
+use super::pressure_stall_meter::{PressureStallData, PressureStallResource};
+use super::selinux_meter::SELinuxState;
+use super::systemd_meter::SystemdState;
+use crate::core::CpuTemperatures;
 
 /// Unique identifier for meter types that need expensive data
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum MeterDataId {
     Battery,
-    // Future: Gpu, SystemdServices, etc.
+    /// Pressure stall information of one resource
+    PressureStall(PressureStallResource),
+    /// State of the systemd system manager
+    Systemd,
+    /// State of the systemd user manager
+    SystemdUser,
+    /// Temperatures of the CPU packages and cores
+    CpuTemperature,
+    /// SELinux mode and policy
+    SELinux,
+    /// AppArmor profile counts
+    AppArmor,
+    // Future: GPU via external tools, etc.
 }
 
 /// Expensive data collected for meters
@@ -61,7 +80,16 @@ pub enum MeterExpensiveData {
         ac_presence: ACPresence,
         available: bool,
     },
-    // Future variants for GPU, systemd, etc.
+    /// Pressure stall information (None if the kernel does not provide it)
+    PressureStall(PressureStallResource, Option<PressureStallData>),
+    /// Systemd manager state (None if neither D-Bus nor systemctl answered)
+    Systemd(Option<SystemdState>),
+    /// CPU temperatures (None if there is no CPU sensor)
+    CpuTemperature(Option<CpuTemperatures>),
+    /// SELinux state (None if SELinux is disabled)
+    SELinux(Option<SELinuxState>),
+    /// AppArmor state (None if AppArmor is disabled)
+    AppArmor(Option<AppArmorState>),
 }
 
 /// Result map from background collection
@@ -168,9 +196,164 @@ fn collect_meter_data(requested: &[MeterDataId]) -> MeterDataMap {
 fn collect_for_meter(id: MeterDataId) -> Option<MeterExpensiveData> {
     match id {
         MeterDataId::Battery => collect_battery_data(),
+        MeterDataId::PressureStall(resource) => collect_pressure_stall_data(resource),
+        MeterDataId::Systemd => collect_systemd_data(false),
+        MeterDataId::SystemdUser => collect_systemd_data(true),
+        MeterDataId::CpuTemperature => collect_cpu_temperature_data(),
+        MeterDataId::SELinux => collect_selinux_data(),
+        MeterDataId::AppArmor => collect_apparmor_data(),
     }
 }
 
+/// Collect the SELinux mode from selinuxfs and the policy from its config
+#[cfg(target_os = "linux")]
+fn collect_selinux_data() -> Option<MeterExpensiveData> {
+    use super::selinux_meter::{parse_selinux_policy, SELINUX_CONFIG_PATH, SELINUX_ENFORCE_PATH};
+
+    // The enforce file only exists while selinuxfs is mounted
+    let state = std::fs::read_to_string(SELINUX_ENFORCE_PATH)
+        .ok()
+        .map(|enforce| SELinuxState {
+            enforcing: enforce.trim() == "1",
+            policy: std::fs::read_to_string(SELINUX_CONFIG_PATH)
+                .ok()
+                .and_then(|config| parse_selinux_policy(&config)),
+        });
+    Some(MeterExpensiveData::SELinux(state))
+}
+
+#[cfg(not(target_os = "linux"))]
+fn collect_selinux_data() -> Option<MeterExpensiveData> {
+    Some(MeterExpensiveData::SELinux(None))
+}
+
+/// Collect the AppArmor profile counts from securityfs
+#[cfg(target_os = "linux")]
+fn collect_apparmor_data() -> Option<MeterExpensiveData> {
+    use super::apparmor_meter::{
+        parse_apparmor_profiles, APPARMOR_ENABLED_PATH, APPARMOR_PROFILES_PATH,
+    };
+
+    let enabled =
+        std::fs::read_to_string(APPARMOR_ENABLED_PATH).is_ok_and(|enabled| enabled.trim() == "Y");
+    let state = enabled.then(|| AppArmorState {
+        profiles: std::fs::read_to_string(APPARMOR_PROFILES_PATH)
+            .ok()
+            .map(|content| parse_apparmor_profiles(&content)),
+    });
+    Some(MeterExpensiveData::AppArmor(state))
+}
+
+#[cfg(not(target_os = "linux"))]
+fn collect_apparmor_data() -> Option<MeterExpensiveData> {
+    Some(MeterExpensiveData::AppArmor(None))
+}
+
+/// Collect CPU temperatures from the hwmon and thermal zone sensors
+#[cfg(target_os = "linux")]
+fn collect_cpu_temperature_data() -> Option<MeterExpensiveData> {
+    use crate::platform::linux_temperature::{
+        read_cpu_temperatures, CPU_PATH, HWMON_PATH, THERMAL_PATH,
+    };
+    use std::path::Path;
+
+    Some(MeterExpensiveData::CpuTemperature(read_cpu_temperatures(
+        Path::new(HWMON_PATH),
+        Path::new(THERMAL_PATH),
+        Path::new(CPU_PATH),
+    )))
+}
+
+#[cfg(not(target_os = "linux"))]
+fn collect_cpu_temperature_data() -> Option<MeterExpensiveData> {
+    Some(MeterExpensiveData::CpuTemperature(None))
+}
+
+/// Collect the state of the systemd system or user manager
+#[cfg(target_os = "linux")]
+fn collect_systemd_data(user: bool) -> Option<MeterExpensiveData> {
+    let state = read_systemd_dbus(user).or_else(|| read_systemd_systemctl(user));
+    Some(MeterExpensiveData::Systemd(state))
+}
+
+#[cfg(not(target_os = "linux"))]
+fn collect_systemd_data(_user: bool) -> Option<MeterExpensiveData> {
+    Some(MeterExpensiveData::Systemd(None))
+}
+
+/// Query org.freedesktop.systemd1.Manager on the system or session bus
+#[cfg(target_os = "linux")]
+fn read_systemd_dbus(user: bool) -> Option<SystemdState> {
+    use super::systemd_meter::SYSTEMD_PROPERTIES;
+    use crate::platform::linux_dbus::{DbusConnection, DbusValue};
+
+    let mut conn = if user {
+        DbusConnection::session()
+    } else {
+        DbusConnection::system()
+    }
+    .ok()?;
+
+    let mut state = SystemdState::default();
+    for property in SYSTEMD_PROPERTIES {
+        let value = conn
+            .get_property(
+                "org.freedesktop.systemd1",
+                "/org/freedesktop/systemd1",
+                "org.freedesktop.systemd1.Manager",
+                property,
+            )
+            .ok()?;
+        let value = match value {
+            DbusValue::String(s) => s,
+            DbusValue::U32(v) => v.to_string(),
+            _ => return None,
+        };
+        state.set_property(property, &value);
+    }
+    Some(state)
+}
+
+/// Fallback when the bus is unreachable: parse `systemctl show`
+#[cfg(target_os = "linux")]
+fn read_systemd_systemctl(user: bool) -> Option<SystemdState> {
+    use super::systemd_meter::{parse_systemctl_show, SYSTEMD_PROPERTIES};
+    use std::process::{Command, Stdio};
+
+    let mut cmd = Command::new("systemctl");
+    if user {
+        cmd.arg("--user");
+    }
+    let output = cmd
+        .arg("show")
+        .arg(format!("--property={}", SYSTEMD_PROPERTIES.join(",")))
+        .stdin(Stdio::null())
+        .stderr(Stdio::null())
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    parse_systemctl_show(&String::from_utf8_lossy(&output.stdout))
+}
+
+/// Collect pressure stall information from /proc/pressure
+#[cfg(target_os = "linux")]
+fn collect_pressure_stall_data(resource: PressureStallResource) -> Option<MeterExpensiveData> {
+    use super::pressure_stall_meter::parse_pressure_stall;
+
+    let path = format!("/proc/pressure/{}", resource.file_name());
+    let data = std::fs::read_to_string(path)
+        .ok()
+        .map(|content| parse_pressure_stall(&content));
+    Some(MeterExpensiveData::PressureStall(resource, data))
+}
+
+#[cfg(not(target_os = "linux"))]
+fn collect_pressure_stall_data(resource: PressureStallResource) -> Option<MeterExpensiveData> {
+    Some(MeterExpensiveData::PressureStall(resource, None))
+}
+
 /// Collect battery data (platform-specific)
 #[cfg(target_os = "macos")]
 fn collect_battery_data() -> Option<MeterExpensiveData> {
@@ -354,6 +537,9 @@ mod tests {
 
         let mut set = HashSet::new();
         set.insert(MeterDataId::Battery);
+        set.insert(MeterDataId::PressureStall(PressureStallResource::Cpu));
+        set.insert(MeterDataId::PressureStall(PressureStallResource::Cpu));
         assert!(set.contains(&MeterDataId::Battery));
+        assert_eq!(set.len(), 2);
     }
 }
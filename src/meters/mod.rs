@@ -5,6 +5,7 @@
 
 #![allow(dead_code)]
 
+mod apparmor_meter;
 mod battery_meter;
 mod blank_meter;
 mod clock_meter;
@@ -35,9 +36,12 @@ mod zram_meter;
 
 use std::time::Instant;
 
+#[cfg(target_os = "linux")]
+use crate::core::ScanFlags;
 use crate::core::{Machine, Settings};
 use crate::ui::Crt;
 
+pub use apparmor_meter::*;
 pub use battery_meter::*;
 pub use blank_meter::*;
 pub use clock_meter::*;
@@ -169,8 +173,8 @@ pub const STACKED_GRAPH_SEGMENTS: usize = 5;
 /// Number of segments in a stacked graph for CPU meter (user, nice, system, irq, softirq, steal, guest, iowait)
 pub const CPU_STACKED_GRAPH_SEGMENTS: usize = 8;
 
-/// Number of segments in a stacked graph for Swap meter (used, cache)
-pub const SWAP_STACKED_GRAPH_SEGMENTS: usize = 2;
+/// Number of segments in a stacked graph for Swap meter (used, zram, cache)
+pub const SWAP_STACKED_GRAPH_SEGMENTS: usize = 3;
 
 /// Stacked graph data storage for multi-value historical meter values
 /// Each time sample stores multiple values that stack on top of each other
@@ -657,6 +661,14 @@ pub trait Meter: std::fmt::Debug + Send {
     /// Called when background results are available.
     /// Default implementation does nothing.
     fn merge_expensive_data(&mut self, _data: &meter_bg_scanner::MeterExpensiveData) {}
+
+    /// Get the process scan flags this meter depends on.
+    /// Meters built from per-process data (e.g. GPU usage) need them even
+    /// when no matching column is shown. Default is none.
+    #[cfg(target_os = "linux")]
+    fn scan_flags(&self) -> ScanFlags {
+        ScanFlags::empty()
+    }
 }
 
 /// Meter type enum for creating meters by name
@@ -734,6 +746,7 @@ impl MeterType {
             "PressureStallMemoryFull" => Some(Box::new(PressureStallMemoryFullMeter::new())),
             "Zram" => Some(Box::new(ZramMeter::new())),
             "SELinux" => Some(Box::new(SELinuxMeter::new())),
+            "AppArmor" => Some(Box::new(AppArmorMeter::new())),
             "Systemd" => Some(Box::new(SystemdMeter::new())),
             "SystemdUser" => Some(Box::new(SystemdUserMeter::new())),
             "ZFSARC" => Some(Box::new(ZfsArcMeter::new())),
@@ -741,6 +754,18 @@ impl MeterType {
             _ => None,
         }
     }
+
+    /// Create a meter restricted to a single device, e.g. "DiskIO(sda)"
+    /// or "NetworkIO(eth*)".
+    /// Returns None for meters that do not take a device.
+    pub fn create_for_device(name: &str, device: &str) -> Option<Box<dyn Meter>> {
+        match name {
+            "DiskIO" => Some(Box::new(DiskIOMeter::for_device(device))),
+            "DiskIOTime" => Some(Box::new(DiskIOTimeMeter::for_device(device))),
+            "NetworkIO" => Some(Box::new(NetworkIOMeter::for_interfaces(device))),
+            _ => None,
+        }
+    }
 }
 
 // ============================================================================
@@ -2555,4 +2580,20 @@ mod tests {
         let memory = MeterType::create_from_name("memory", 0);
         assert!(memory.is_none());
     }
+
+    #[test]
+    fn test_meter_type_create_for_device() {
+        let disk = MeterType::create_for_device("DiskIO", "sda").unwrap();
+        assert_eq!(disk.name(), "DiskIO");
+        assert_eq!(disk.caption(), "sda IO");
+
+        let time = MeterType::create_for_device("DiskIOTime", "nvme0n1").unwrap();
+        assert_eq!(time.name(), "DiskIOTime");
+
+        let net = MeterType::create_for_device("NetworkIO", "wl*").unwrap();
+        assert_eq!(net.caption(), "wl*");
+
+        // Meters without a device parameter
+        assert!(MeterType::create_for_device("Memory", "sda").is_none());
+    }
 }
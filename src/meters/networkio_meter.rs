@@ -5,7 +5,7 @@
 use std::cell::RefCell;
 
 use super::{draw_bar_with_text, draw_graph, draw_led, BarSegment, GraphData, Meter, MeterMode};
-use crate::core::{Machine, Settings};
+use crate::core::{Machine, NetIoData, Settings};
 use crate::ui::ColorElement;
 use crate::ui::Crt;
 
@@ -18,13 +18,54 @@ enum RateStatus {
     NoData, // No data available (read failed)
 }
 
+/// Match an interface name against a shell-style glob ('*' and '?')
+fn glob_match(pattern: &str, name: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let name: Vec<char> = name.chars().collect();
+    let (mut p, mut n) = (0, 0);
+    // Position after the last '*' and the name position it was tried at
+    let mut backtrack: Option<(usize, usize)> = None;
+
+    while n < name.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                backtrack = Some((p + 1, n));
+                p += 1;
+            }
+            Some(&c) if c == '?' || c == name[n] => {
+                p += 1;
+                n += 1;
+            }
+            _ => match backtrack {
+                // Let the last '*' swallow one more character
+                Some((star_p, star_n)) => {
+                    backtrack = Some((star_p, star_n + 1));
+                    p = star_p;
+                    n = star_n + 1;
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
 /// Network IO Meter
 ///
 /// Displays network receive/transmit rates and packet counts.
 /// In text mode, shows "rx: XiB/s tx: YiB/s rx_pps/tx_pps pkt/s".
+///
+/// By default the totals leave out virtual interfaces (loopback, veth,
+/// bridges and bond slaves). Configured as "NetworkIO(eth0)" or
+/// "NetworkIO(enp*)" the meter sums the interfaces matching the name or glob.
 #[derive(Debug)]
 pub struct NetworkIOMeter {
     mode: MeterMode,
+    /// Interface name or glob, None for all non-virtual interfaces
+    interfaces: Option<String>,
+    caption: String,
+    /// Per-interface (name, receive rate, transmit rate) of the counted interfaces
+    breakdown: Vec<(String, f64, f64)>,
     status: RateStatus,
     /// Cached receive rate in bytes per second
     receive_rate: f64,
@@ -48,6 +89,9 @@ impl NetworkIOMeter {
     pub fn new() -> Self {
         NetworkIOMeter {
             mode: MeterMode::Text, // Default to text mode like C htop
+            interfaces: None,
+            caption: "Network".to_string(),
+            breakdown: Vec::new(),
             status: RateStatus::Init,
             receive_rate: 0.0,
             transmit_rate: 0.0,
@@ -57,6 +101,23 @@ impl NetworkIOMeter {
         }
     }
 
+    /// Create a meter for the interfaces matching a name or glob (e.g. "wl*")
+    pub fn for_interfaces(pattern: &str) -> Self {
+        NetworkIOMeter {
+            interfaces: Some(pattern.to_string()),
+            caption: pattern.to_string(),
+            ..Self::new()
+        }
+    }
+
+    /// Whether an interface is counted by this meter
+    fn includes(&self, net: &NetIoData) -> bool {
+        match &self.interfaces {
+            Some(pattern) => glob_match(pattern, &net.name),
+            None => !net.is_virtual,
+        }
+    }
+
     /// Format a rate value using human-readable units (like C htop's Meter_humanUnit)
     pub(crate) fn human_unit(bytes_per_sec: f64) -> String {
         const UNIT_PREFIXES: [char; 5] = ['K', 'M', 'G', 'T', 'P'];
@@ -85,7 +146,7 @@ impl Meter for NetworkIOMeter {
     }
 
     fn caption(&self) -> &str {
-        "Network"
+        &self.caption
     }
 
     fn update(&mut self, machine: &Machine) {
@@ -104,6 +165,30 @@ impl Meter for NetworkIOMeter {
             return;
         }
 
+        let selected: Vec<&NetIoData> = machine
+            .net_interfaces
+            .iter()
+            .filter(|net| self.includes(net))
+            .collect();
+        self.breakdown = selected
+            .iter()
+            .map(|net| (net.name.clone(), net.receive_rate, net.transmit_rate))
+            .collect();
+
+        if self.interfaces.is_some() {
+            // No interface matches (yet), e.g. a VPN that is not up
+            if selected.is_empty() {
+                self.status = RateStatus::NoData;
+                return;
+            }
+            self.status = RateStatus::Data;
+            self.receive_rate = selected.iter().map(|net| net.receive_rate).sum();
+            self.transmit_rate = selected.iter().map(|net| net.transmit_rate).sum();
+            self.receive_packets = selected.iter().map(|net| net.receive_packets).sum();
+            self.transmit_packets = selected.iter().map(|net| net.transmit_packets).sum();
+            return;
+        }
+
         // We have valid data
         self.status = RateStatus::Data;
         self.receive_rate = machine.net_io_receive_rate;
@@ -164,7 +249,12 @@ impl Meter for NetworkIOMeter {
                     },
                 ];
 
-                draw_bar_with_text(crt, x, y, width, "Net", &segments, 1.0, &text);
+                let caption = if self.interfaces.is_some() {
+                    self.caption()
+                } else {
+                    "Net"
+                };
+                draw_bar_with_text(crt, x, y, width, caption, &segments, 1.0, &text);
             }
             MeterMode::Text => {
                 // Extract all colors BEFORE with_window to avoid borrow conflicts
@@ -176,65 +266,61 @@ impl Meter for NetworkIOMeter {
                 let error_attr = crt.color(ColorElement::MeterValueError);
                 let reset_attr = crt.color(ColorElement::ResetColor);
 
-                // Pre-compute formatted strings
-                let status = self.status;
-                let rx_rate_str = Self::human_unit(self.receive_rate);
-                let tx_rate_str = Self::human_unit(self.transmit_rate);
-                let rx_packets_str = format!("{}", self.receive_packets);
-                let tx_packets_str = format!("{}", self.transmit_packets);
+                // "Net: rx: XiB/s tx: YiB/s rx_pps/tx_pps pkt/s", clipped to the
+                // meter width since the interface breakdown can be long
+                let mut segments: Vec<(String, u32)> =
+                    vec![(format!("{}: ", self.caption()), text_attr)];
+                if self.status != RateStatus::Data {
+                    let (text, attr) = match self.status {
+                        RateStatus::Init => ("initializing...", value_attr),
+                        RateStatus::Stale => ("stale data", warn_attr),
+                        RateStatus::NoData => ("no data", error_attr),
+                        RateStatus::Data => unreachable!(),
+                    };
+                    segments.push((text.to_string(), attr));
+                } else {
+                    segments.push(("rx: ".to_string(), text_attr));
+                    segments.push((
+                        format!("{}iB/s", Self::human_unit(self.receive_rate)),
+                        rx_attr,
+                    ));
+                    segments.push((" tx: ".to_string(), text_attr));
+                    segments.push((
+                        format!("{}iB/s", Self::human_unit(self.transmit_rate)),
+                        tx_attr,
+                    ));
+                    segments.push((" ".to_string(), text_attr));
+                    segments.push((self.receive_packets.to_string(), rx_attr));
+                    segments.push(("/".to_string(), text_attr));
+                    segments.push((self.transmit_packets.to_string(), tx_attr));
+                    segments.push((" pkt/s".to_string(), text_attr));
+
+                    // Optional per-interface breakdown: " eth0:<rx>/<tx>"
+                    if settings.show_network_interfaces {
+                        for (name, rx, tx) in &self.breakdown {
+                            segments.push((format!(" {}:", name), text_attr));
+                            segments.push((Self::human_unit(*rx), rx_attr));
+                            segments.push(("/".to_string(), text_attr));
+                            segments.push((Self::human_unit(*tx), tx_attr));
+                        }
+                    }
+                }
 
+                let max_x = x + width;
                 crt.with_window(|win| {
-                    let _ = win.mv(y, x);
-
-                    // Caption prefix
-                    let _ = win.attrset(text_attr);
-                    let _ = win.addstr("Network: ");
-
-                    // Handle non-data states
-                    if status != RateStatus::Data {
-                        let (text, attr) = match status {
-                            RateStatus::Init => ("initializing...", value_attr),
-                            RateStatus::Stale => ("stale data", warn_attr),
-                            RateStatus::NoData => ("no data", error_attr),
-                            RateStatus::Data => unreachable!(),
-                        };
-                        let _ = win.attrset(attr);
-                        let _ = win.addstr(text);
-                        let _ = win.attrset(reset_attr);
-                        return;
+                    let mut cur_x = x;
+
+                    for (s, attr) in &segments {
+                        if cur_x >= max_x {
+                            break;
+                        }
+                        let _ = win.attrset(*attr);
+                        let available = (max_x - cur_x) as usize;
+                        let to_print: String = s.chars().take(available).collect();
+                        let _ = win.mvaddstr(y, cur_x, &to_print);
+                        cur_x += to_print.chars().count() as i32;
                     }
 
-                    // "rx: XiB/s tx: YiB/s rx_pps/tx_pps pkt/s"
-                    let _ = win.attrset(text_attr);
-                    let _ = win.addstr("rx: ");
-
-                    let _ = win.attrset(rx_attr);
-                    let _ = win.addstr(&rx_rate_str);
-                    let _ = win.addstr("iB/s");
-
-                    let _ = win.attrset(text_attr);
-                    let _ = win.addstr(" tx: ");
-
-                    let _ = win.attrset(tx_attr);
-                    let _ = win.addstr(&tx_rate_str);
-                    let _ = win.addstr("iB/s");
-
-                    // Packet counts
-                    let _ = win.attrset(text_attr);
-                    let _ = win.addstr(" ");
-
-                    let _ = win.attrset(rx_attr);
-                    let _ = win.addstr(&rx_packets_str);
-
-                    let _ = win.attrset(text_attr);
-                    let _ = win.addstr("/");
-
-                    let _ = win.attrset(tx_attr);
-                    let _ = win.addstr(&tx_packets_str);
-
-                    let _ = win.attrset(text_attr);
-                    let _ = win.addstr(" pkt/s");
-
                     let _ = win.attrset(reset_attr);
                 });
             }
@@ -250,7 +336,7 @@ impl Meter for NetworkIOMeter {
                 }
 
                 let graph_data = self.graph_data.borrow();
-                draw_graph(crt, x, y, width, self.height(), &graph_data, "Network");
+                draw_graph(crt, x, y, width, self.height(), &graph_data, self.caption());
             }
             MeterMode::Led => {
                 // Format rate values for LED display
@@ -261,14 +347,14 @@ impl Meter for NetworkIOMeter {
                         RateStatus::NoData => "N/A",
                         RateStatus::Data => unreachable!(),
                     };
-                    draw_led(crt, x, y, width, "Network: ", text);
+                    draw_led(crt, x, y, width, &format!("{}: ", self.caption()), text);
                 } else {
                     let text = format!(
                         "rx:{}iB/s tx:{}iB/s",
                         Self::human_unit(self.receive_rate),
                         Self::human_unit(self.transmit_rate)
                     );
-                    draw_led(crt, x, y, width, "Network: ", &text);
+                    draw_led(crt, x, y, width, &format!("{}: ", self.caption()), &text);
                 }
             }
             MeterMode::StackedGraph => {
@@ -404,6 +490,75 @@ mod tests {
         assert_eq!(meter.status, RateStatus::Stale);
     }
 
+    fn machine_with_interfaces() -> Machine {
+        let net = |name: &str, is_virtual: bool, receive_rate: f64| NetIoData {
+            name: name.to_string(),
+            is_virtual,
+            receive_rate,
+            receive_packets: 10,
+            ..Default::default()
+        };
+        Machine {
+            net_io_last_update: 1000,
+            realtime_ms: 1500,
+            net_io_receive_rate: 3072.0,
+            net_interfaces: vec![
+                net("docker0", true, 4096.0),
+                net("enp1s0", false, 1024.0),
+                net("enp2s0", false, 2048.0),
+                net("lo", true, 8192.0),
+                net("veth1a2b3c", true, 4096.0),
+            ],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_networkio_meter_default_excludes_virtual() {
+        let mut meter = NetworkIOMeter::new();
+        meter.update(&machine_with_interfaces());
+
+        assert_eq!(meter.status, RateStatus::Data);
+        assert_eq!(meter.receive_rate, 3072.0);
+        let names: Vec<&str> = meter.breakdown.iter().map(|(n, _, _)| n.as_str()).collect();
+        assert_eq!(names, ["enp1s0", "enp2s0"]);
+    }
+
+    #[test]
+    fn test_networkio_meter_interface_pattern() {
+        let machine = machine_with_interfaces();
+
+        let mut glob = NetworkIOMeter::for_interfaces("enp*");
+        glob.update(&machine);
+        assert_eq!(glob.caption(), "enp*");
+        assert_eq!(glob.status, RateStatus::Data);
+        assert_eq!(glob.receive_rate, 3072.0);
+        assert_eq!(glob.receive_packets, 20);
+
+        // An explicit name selects virtual interfaces too
+        let mut lo = NetworkIOMeter::for_interfaces("lo");
+        lo.update(&machine);
+        assert_eq!(lo.receive_rate, 8192.0);
+
+        let mut missing = NetworkIOMeter::for_interfaces("wg0");
+        missing.update(&machine);
+        assert_eq!(missing.status, RateStatus::NoData);
+    }
+
+    #[test]
+    fn test_glob_match() {
+        assert!(glob_match("eth0", "eth0"));
+        assert!(!glob_match("eth0", "eth01"));
+        assert!(glob_match("eth*", "eth0"));
+        assert!(glob_match("eth*", "eth"));
+        assert!(glob_match("*0", "wlan0"));
+        assert!(!glob_match("en?s0", "enp1s0"));
+        assert!(glob_match("enp?s0", "enp1s0"));
+        assert!(glob_match("*p*s*", "enp1s0"));
+        assert!(!glob_match("wl*", "eth0"));
+        assert!(glob_match("*", ""));
+    }
+
     // ==================== Meter Trait Tests ====================
 
     #[test]
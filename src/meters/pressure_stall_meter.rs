@@ -1,37 +1,264 @@
 //! Pressure Stall Information (PSI) Meters
 //!
-//! Linux-specific meters for displaying CPU, IO, and memory pressure.
-
-use super::{Meter, MeterMode};
+//! Linux-specific meters for displaying CPU, IO, memory and IRQ pressure.
+//!
+//! The averages are read from `/proc/pressure/{cpu,io,memory,irq}` by the
+//! meter background scanner and arrive via `merge_expensive_data()`. Each
+//! file has a "some" line (at least one task stalled) and, except for CPU
+//! on older kernels, a "full" line (all non-idle tasks stalled):
+//!
+//! ```text
+//! some avg10=0.12 avg60=0.30 avg300=1.00 total=123456
+//! full avg10=0.00 avg60=0.10 avg300=0.50 total=65432
+//! ```
+
+use std::cell::RefCell;
+
+use super::meter_bg_scanner::{MeterDataId, MeterExpensiveData};
+use super::{
+    draw_bar_with_text, draw_graph, draw_text_segments, BarSegment, GraphData, Meter, MeterMode,
+    TextSegment,
+};
 use crate::core::{Machine, Settings};
 use crate::ui::{ColorElement, Crt};
 
-/// Helper function to draw "Not implemented" text
-fn draw_not_implemented(crt: &mut Crt, x: i32, y: i32, caption: &str) {
-    let caption_attr = crt.color(ColorElement::MeterText);
-    let text_attr = crt.color(ColorElement::MeterValueError);
-    let reset_attr = crt.color(ColorElement::ResetColor);
+/// Resource a pressure file describes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PressureStallResource {
+    Cpu,
+    Io,
+    Memory,
+    Irq,
+}
 
-    crt.with_window(|win| {
-        let _ = win.mv(y, x);
-        let _ = win.attrset(caption_attr);
-        let _ = win.addstr(caption);
-        let _ = win.attrset(text_attr);
-        let _ = win.addstr("Not implemented");
-        let _ = win.attrset(reset_attr);
-    });
+impl PressureStallResource {
+    /// File name under /proc/pressure
+    pub fn file_name(self) -> &'static str {
+        match self {
+            PressureStallResource::Cpu => "cpu",
+            PressureStallResource::Io => "io",
+            PressureStallResource::Memory => "memory",
+            PressureStallResource::Irq => "irq",
+        }
+    }
+}
+
+/// Which line of a pressure file a meter shows
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PressureStallKind {
+    Some,
+    Full,
 }
 
+/// Percentage of time stalled, averaged over 10s, 60s and 300s
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct PressureStallAverages {
+    pub avg10: f64,
+    pub avg60: f64,
+    pub avg300: f64,
+}
+
+/// Contents of one pressure file
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct PressureStallData {
+    pub some: Option<PressureStallAverages>,
+    pub full: Option<PressureStallAverages>,
+}
+
+/// Parse the contents of a /proc/pressure file
+pub fn parse_pressure_stall(content: &str) -> PressureStallData {
+    let mut data = PressureStallData::default();
+
+    for line in content.lines() {
+        let mut fields = line.split_whitespace();
+        let slot = match fields.next() {
+            Some("some") => &mut data.some,
+            Some("full") => &mut data.full,
+            _ => continue,
+        };
+
+        let mut averages = PressureStallAverages::default();
+        let mut found = 0;
+        for field in fields {
+            let Some((key, value)) = field.split_once('=') else {
+                continue;
+            };
+            let Ok(value) = value.parse::<f64>() else {
+                continue;
+            };
+            match key {
+                "avg10" => averages.avg10 = value,
+                "avg60" => averages.avg60 = value,
+                "avg300" => averages.avg300 = value,
+                _ => continue,
+            }
+            found += 1;
+        }
+        if found == 3 {
+            *slot = Some(averages);
+        }
+    }
+
+    data
+}
+
+/// State shared by all PSI meters
+#[derive(Debug)]
+struct PressureStall {
+    resource: PressureStallResource,
+    kind: PressureStallKind,
+    /// Latest averages, None if the kernel does not provide them
+    averages: Option<PressureStallAverages>,
+    /// Graph data for the avg10 history (RefCell for interior mutability)
+    graph_data: RefCell<GraphData>,
+}
+
+impl PressureStall {
+    fn new(resource: PressureStallResource, kind: PressureStallKind) -> Self {
+        PressureStall {
+            resource,
+            kind,
+            averages: None,
+            graph_data: RefCell::new(GraphData::new()),
+        }
+    }
+
+    fn data_id(&self) -> MeterDataId {
+        MeterDataId::PressureStall(self.resource)
+    }
+
+    fn merge(&mut self, data: &MeterExpensiveData) {
+        if let MeterExpensiveData::PressureStall(resource, data) = data {
+            if *resource == self.resource {
+                self.averages = data.and_then(|data| match self.kind {
+                    PressureStallKind::Some => data.some,
+                    PressureStallKind::Full => data.full,
+                });
+            }
+        }
+    }
+
+    /// Values as shown in Text, Bar and LED modes: "avg10% avg60% avg300%"
+    fn text(&self) -> String {
+        match self.averages {
+            Some(a) => format!("{:.2}% {:.2}% {:.2}%", a.avg10, a.avg60, a.avg300),
+            None => "N/A".to_string(),
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn draw(
+        &self,
+        mode: MeterMode,
+        caption: &str,
+        crt: &mut Crt,
+        settings: &Settings,
+        x: i32,
+        y: i32,
+        width: i32,
+        height: i32,
+    ) {
+        match mode {
+            MeterMode::Bar => {
+                // The bar shows the most recent average, the text all three
+                let segments = [BarSegment {
+                    value: self.averages.map_or(0.0, |a| a.avg10),
+                    attr: crt.color(ColorElement::PressureStallTen),
+                }];
+                draw_bar_with_text(
+                    crt,
+                    x,
+                    y,
+                    width,
+                    caption.trim_end(),
+                    &segments,
+                    100.0,
+                    &self.text(),
+                );
+            }
+            MeterMode::Graph => {
+                {
+                    let mut graph_data = self.graph_data.borrow_mut();
+                    let value = self.averages.map_or(0.0, |a| a.avg10 / 100.0);
+                    graph_data.record(value, settings.delay * 100);
+                }
+
+                let graph_data = self.graph_data.borrow();
+                draw_graph(crt, x, y, width, height, &graph_data, "PSI");
+            }
+            MeterMode::Led => {
+                let text = format!(":{}", self.text());
+                super::draw_led(crt, x, y, width, "PSI", &text);
+            }
+            _ => {
+                let text_attr = crt.color(ColorElement::MeterText);
+                let Some(a) = self.averages else {
+                    let error_attr = crt.color(ColorElement::MeterValueError);
+                    let segments = [
+                        TextSegment {
+                            text: caption,
+                            attr: text_attr,
+                        },
+                        TextSegment {
+                            text: "N/A",
+                            attr: error_attr,
+                        },
+                    ];
+                    draw_text_segments(crt, x, y, &segments);
+                    return;
+                };
+
+                let ten = format!("{:.2}% ", a.avg10);
+                let sixty = format!("{:.2}% ", a.avg60);
+                let three_hundred = format!("{:.2}% ", a.avg300);
+                let segments = [
+                    TextSegment {
+                        text: caption,
+                        attr: text_attr,
+                    },
+                    TextSegment {
+                        text: &ten,
+                        attr: crt.color(ColorElement::PressureStallTen),
+                    },
+                    TextSegment {
+                        text: &sixty,
+                        attr: crt.color(ColorElement::PressureStallSixty),
+                    },
+                    TextSegment {
+                        text: &three_hundred,
+                        attr: crt.color(ColorElement::PressureStallThreeHundred),
+                    },
+                ];
+                draw_text_segments(crt, x, y, &segments);
+            }
+        }
+    }
+}
+
+/// Modes supported by all PSI meters
+const PRESSURE_STALL_MODES: u32 = (1 << MeterMode::Bar as u32)
+    | (1 << MeterMode::Text as u32)
+    | (1 << MeterMode::Graph as u32)
+    | (1 << MeterMode::Led as u32);
+
 /// PressureStallCPUSome Meter - PSI some CPU
-#[derive(Debug, Default)]
+#[derive(Debug)]
 pub struct PressureStallCPUSomeMeter {
     mode: MeterMode,
+    psi: PressureStall,
+}
+
+impl Default for PressureStallCPUSomeMeter {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl PressureStallCPUSomeMeter {
     pub fn new() -> Self {
         Self {
             mode: MeterMode::Text,
+            psi: PressureStall::new(PressureStallResource::Cpu, PressureStallKind::Some),
         }
     }
 }
@@ -45,18 +272,44 @@ impl Meter for PressureStallCPUSomeMeter {
         "PSI some CPU:    "
     }
 
+    fn supported_modes(&self) -> u32 {
+        PRESSURE_STALL_MODES
+    }
+
+    fn default_mode(&self) -> MeterMode {
+        MeterMode::Text
+    }
+
     fn update(&mut self, _machine: &Machine) {}
 
+    fn expensive_data_id(&self) -> Option<MeterDataId> {
+        Some(self.psi.data_id())
+    }
+
+    fn merge_expensive_data(&mut self, data: &MeterExpensiveData) {
+        self.psi.merge(data);
+    }
+
     fn draw(
         &self,
         crt: &mut Crt,
         _machine: &Machine,
-        _settings: &Settings,
+        settings: &Settings,
         x: i32,
         y: i32,
-        _width: i32,
+        width: i32,
     ) {
-        draw_not_implemented(crt, x, y, self.caption());
+        let height = self.height();
+        self.psi.draw(
+            self.mode,
+            self.caption(),
+            crt,
+            settings,
+            x,
+            y,
+            width,
+            height,
+        );
     }
 
     fn mode(&self) -> MeterMode {
@@ -69,15 +322,23 @@ impl Meter for PressureStallCPUSomeMeter {
 }
 
 /// PressureStallIOSome Meter - PSI some IO
-#[derive(Debug, Default)]
+#[derive(Debug)]
 pub struct PressureStallIOSomeMeter {
     mode: MeterMode,
+    psi: PressureStall,
+}
+
+impl Default for PressureStallIOSomeMeter {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl PressureStallIOSomeMeter {
     pub fn new() -> Self {
         Self {
             mode: MeterMode::Text,
+            psi: PressureStall::new(PressureStallResource::Io, PressureStallKind::Some),
         }
     }
 }
@@ -91,18 +352,44 @@ impl Meter for PressureStallIOSomeMeter {
         "PSI some IO:     "
     }
 
+    fn supported_modes(&self) -> u32 {
+        PRESSURE_STALL_MODES
+    }
+
+    fn default_mode(&self) -> MeterMode {
+        MeterMode::Text
+    }
+
     fn update(&mut self, _machine: &Machine) {}
 
+    fn expensive_data_id(&self) -> Option<MeterDataId> {
+        Some(self.psi.data_id())
+    }
+
+    fn merge_expensive_data(&mut self, data: &MeterExpensiveData) {
+        self.psi.merge(data);
+    }
+
     fn draw(
         &self,
         crt: &mut Crt,
         _machine: &Machine,
-        _settings: &Settings,
+        settings: &Settings,
         x: i32,
         y: i32,
-        _width: i32,
+        width: i32,
     ) {
-        draw_not_implemented(crt, x, y, self.caption());
+        let height = self.height();
+        self.psi.draw(
+            self.mode,
+            self.caption(),
+            crt,
+            settings,
+            x,
+            y,
+            width,
+            height,
+        );
     }
 
     fn mode(&self) -> MeterMode {
@@ -115,15 +402,23 @@ impl Meter for PressureStallIOSomeMeter {
 }
 
 /// PressureStallIOFull Meter - PSI full IO
-#[derive(Debug, Default)]
+#[derive(Debug)]
 pub struct PressureStallIOFullMeter {
     mode: MeterMode,
+    psi: PressureStall,
+}
+
+impl Default for PressureStallIOFullMeter {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl PressureStallIOFullMeter {
     pub fn new() -> Self {
         Self {
             mode: MeterMode::Text,
+            psi: PressureStall::new(PressureStallResource::Io, PressureStallKind::Full),
         }
     }
 }
@@ -137,18 +432,44 @@ impl Meter for PressureStallIOFullMeter {
         "PSI full IO:     "
     }
 
+    fn supported_modes(&self) -> u32 {
+        PRESSURE_STALL_MODES
+    }
+
+    fn default_mode(&self) -> MeterMode {
+        MeterMode::Text
+    }
+
     fn update(&mut self, _machine: &Machine) {}
 
+    fn expensive_data_id(&self) -> Option<MeterDataId> {
+        Some(self.psi.data_id())
+    }
+
+    fn merge_expensive_data(&mut self, data: &MeterExpensiveData) {
+        self.psi.merge(data);
+    }
+
     fn draw(
         &self,
         crt: &mut Crt,
         _machine: &Machine,
-        _settings: &Settings,
+        settings: &Settings,
         x: i32,
         y: i32,
-        _width: i32,
+        width: i32,
     ) {
-        draw_not_implemented(crt, x, y, self.caption());
+        let height = self.height();
+        self.psi.draw(
+            self.mode,
+            self.caption(),
+            crt,
+            settings,
+            x,
+            y,
+            width,
+            height,
+        );
     }
 
     fn mode(&self) -> MeterMode {
@@ -161,15 +482,23 @@ impl Meter for PressureStallIOFullMeter {
 }
 
 /// PressureStallIRQFull Meter - PSI full IRQ
-#[derive(Debug, Default)]
+#[derive(Debug)]
 pub struct PressureStallIRQFullMeter {
     mode: MeterMode,
+    psi: PressureStall,
+}
+
+impl Default for PressureStallIRQFullMeter {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl PressureStallIRQFullMeter {
     pub fn new() -> Self {
         Self {
             mode: MeterMode::Text,
+            psi: PressureStall::new(PressureStallResource::Irq, PressureStallKind::Full),
         }
     }
 }
@@ -183,18 +512,44 @@ impl Meter for PressureStallIRQFullMeter {
         "PSI full IRQ:    "
     }
 
+    fn supported_modes(&self) -> u32 {
+        PRESSURE_STALL_MODES
+    }
+
+    fn default_mode(&self) -> MeterMode {
+        MeterMode::Text
+    }
+
     fn update(&mut self, _machine: &Machine) {}
 
+    fn expensive_data_id(&self) -> Option<MeterDataId> {
+        Some(self.psi.data_id())
+    }
+
+    fn merge_expensive_data(&mut self, data: &MeterExpensiveData) {
+        self.psi.merge(data);
+    }
+
     fn draw(
         &self,
         crt: &mut Crt,
         _machine: &Machine,
-        _settings: &Settings,
+        settings: &Settings,
         x: i32,
         y: i32,
-        _width: i32,
+        width: i32,
     ) {
-        draw_not_implemented(crt, x, y, self.caption());
+        let height = self.height();
+        self.psi.draw(
+            self.mode,
+            self.caption(),
+            crt,
+            settings,
+            x,
+            y,
+            width,
+            height,
+        );
     }
 
     fn mode(&self) -> MeterMode {
@@ -207,15 +562,23 @@ impl Meter for PressureStallIRQFullMeter {
 }
 
 /// PressureStallMemorySome Meter - PSI some memory
-#[derive(Debug, Default)]
+#[derive(Debug)]
 pub struct PressureStallMemorySomeMeter {
     mode: MeterMode,
+    psi: PressureStall,
+}
+
+impl Default for PressureStallMemorySomeMeter {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl PressureStallMemorySomeMeter {
     pub fn new() -> Self {
         Self {
             mode: MeterMode::Text,
+            psi: PressureStall::new(PressureStallResource::Memory, PressureStallKind::Some),
         }
     }
 }
@@ -229,18 +592,44 @@ impl Meter for PressureStallMemorySomeMeter {
         "PSI some memory: "
     }
 
+    fn supported_modes(&self) -> u32 {
+        PRESSURE_STALL_MODES
+    }
+
+    fn default_mode(&self) -> MeterMode {
+        MeterMode::Text
+    }
+
     fn update(&mut self, _machine: &Machine) {}
 
+    fn expensive_data_id(&self) -> Option<MeterDataId> {
+        Some(self.psi.data_id())
+    }
+
+    fn merge_expensive_data(&mut self, data: &MeterExpensiveData) {
+        self.psi.merge(data);
+    }
+
     fn draw(
         &self,
         crt: &mut Crt,
         _machine: &Machine,
-        _settings: &Settings,
+        settings: &Settings,
         x: i32,
         y: i32,
-        _width: i32,
+        width: i32,
     ) {
-        draw_not_implemented(crt, x, y, self.caption());
+        let height = self.height();
+        self.psi.draw(
+            self.mode,
+            self.caption(),
+            crt,
+            settings,
+            x,
+            y,
+            width,
+            height,
+        );
     }
 
     fn mode(&self) -> MeterMode {
@@ -253,15 +642,23 @@ impl Meter for PressureStallMemorySomeMeter {
 }
 
 /// PressureStallMemoryFull Meter - PSI full memory
-#[derive(Debug, Default)]
+#[derive(Debug)]
 pub struct PressureStallMemoryFullMeter {
     mode: MeterMode,
+    psi: PressureStall,
+}
+
+impl Default for PressureStallMemoryFullMeter {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl PressureStallMemoryFullMeter {
     pub fn new() -> Self {
         Self {
             mode: MeterMode::Text,
+            psi: PressureStall::new(PressureStallResource::Memory, PressureStallKind::Full),
         }
     }
 }
@@ -275,18 +672,44 @@ impl Meter for PressureStallMemoryFullMeter {
         "PSI full memory: "
     }
 
+    fn supported_modes(&self) -> u32 {
+        PRESSURE_STALL_MODES
+    }
+
+    fn default_mode(&self) -> MeterMode {
+        MeterMode::Text
+    }
+
     fn update(&mut self, _machine: &Machine) {}
 
+    fn expensive_data_id(&self) -> Option<MeterDataId> {
+        Some(self.psi.data_id())
+    }
+
+    fn merge_expensive_data(&mut self, data: &MeterExpensiveData) {
+        self.psi.merge(data);
+    }
+
     fn draw(
         &self,
         crt: &mut Crt,
         _machine: &Machine,
-        _settings: &Settings,
+        settings: &Settings,
         x: i32,
         y: i32,
-        _width: i32,
+        width: i32,
     ) {
-        draw_not_implemented(crt, x, y, self.caption());
+        let height = self.height();
+        self.psi.draw(
+            self.mode,
+            self.caption(),
+            crt,
+            settings,
+            x,
+            y,
+            width,
+            height,
+        );
     }
 
     fn mode(&self) -> MeterMode {
@@ -312,7 +735,7 @@ mod tests {
     #[test]
     fn test_psi_cpu_some_meter_default() {
         let meter = PressureStallCPUSomeMeter::default();
-        assert_eq!(meter.mode, MeterMode::Bar);
+        assert_eq!(meter.mode, MeterMode::Text);
     }
 
     #[test]
@@ -345,7 +768,7 @@ mod tests {
     #[test]
     fn test_psi_io_some_meter_default() {
         let meter = PressureStallIOSomeMeter::default();
-        assert_eq!(meter.mode, MeterMode::Bar);
+        assert_eq!(meter.mode, MeterMode::Text);
     }
 
     #[test]
@@ -378,7 +801,7 @@ mod tests {
     #[test]
     fn test_psi_io_full_meter_default() {
         let meter = PressureStallIOFullMeter::default();
-        assert_eq!(meter.mode, MeterMode::Bar);
+        assert_eq!(meter.mode, MeterMode::Text);
     }
 
     #[test]
@@ -411,7 +834,7 @@ mod tests {
     #[test]
     fn test_psi_irq_full_meter_default() {
         let meter = PressureStallIRQFullMeter::default();
-        assert_eq!(meter.mode, MeterMode::Bar);
+        assert_eq!(meter.mode, MeterMode::Text);
     }
 
     #[test]
@@ -444,7 +867,7 @@ mod tests {
     #[test]
     fn test_psi_memory_some_meter_default() {
         let meter = PressureStallMemorySomeMeter::default();
-        assert_eq!(meter.mode, MeterMode::Bar);
+        assert_eq!(meter.mode, MeterMode::Text);
     }
 
     #[test]
@@ -477,7 +900,7 @@ mod tests {
     #[test]
     fn test_psi_memory_full_meter_default() {
         let meter = PressureStallMemoryFullMeter::default();
-        assert_eq!(meter.mode, MeterMode::Bar);
+        assert_eq!(meter.mode, MeterMode::Text);
     }
 
     #[test]
@@ -518,4 +941,78 @@ mod tests {
         mem_some.update(&machine);
         mem_full.update(&machine);
     }
+
+    // Parsing and merging
+    const PRESSURE_IO: &str = "some avg10=1.50 avg60=0.75 avg300=0.25 total=123456\n\
+                               full avg10=0.50 avg60=0.20 avg300=0.05 total=65432\n";
+
+    #[test]
+    fn test_parse_pressure_stall() {
+        let data = parse_pressure_stall(PRESSURE_IO);
+        assert_eq!(
+            data.some,
+            Some(PressureStallAverages {
+                avg10: 1.5,
+                avg60: 0.75,
+                avg300: 0.25,
+            })
+        );
+        assert_eq!(data.full.map(|a| a.avg300), Some(0.05));
+
+        // Kernels before 5.13 have no "full" line for CPU
+        let cpu = parse_pressure_stall("some avg10=0.00 avg60=0.00 avg300=0.00 total=0\n");
+        assert!(cpu.some.is_some());
+        assert!(cpu.full.is_none());
+
+        assert_eq!(parse_pressure_stall(""), PressureStallData::default());
+        assert!(
+            parse_pressure_stall("some avg10=x avg60=0.00 avg300=0.00\n")
+                .some
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_psi_meter_merge_selects_line() {
+        let data = MeterExpensiveData::PressureStall(
+            PressureStallResource::Io,
+            Some(parse_pressure_stall(PRESSURE_IO)),
+        );
+
+        let mut some = PressureStallIOSomeMeter::new();
+        let mut full = PressureStallIOFullMeter::new();
+        assert_eq!(
+            some.expensive_data_id(),
+            Some(MeterDataId::PressureStall(PressureStallResource::Io))
+        );
+        some.merge_expensive_data(&data);
+        full.merge_expensive_data(&data);
+        assert_eq!(some.psi.text(), "1.50% 0.75% 0.25%");
+        assert_eq!(full.psi.text(), "0.50% 0.20% 0.05%");
+
+        // Data of another resource is ignored
+        let mut cpu = PressureStallCPUSomeMeter::new();
+        cpu.merge_expensive_data(&data);
+        assert_eq!(cpu.psi.text(), "N/A");
+    }
+
+    #[test]
+    fn test_psi_meter_unavailable() {
+        let mut meter = PressureStallIRQFullMeter::new();
+        meter.merge_expensive_data(&MeterExpensiveData::PressureStall(
+            PressureStallResource::Irq,
+            None,
+        ));
+        assert_eq!(meter.psi.averages, None);
+        assert_eq!(meter.psi.text(), "N/A");
+    }
+
+    #[test]
+    fn test_psi_meter_supported_modes() {
+        let meter = PressureStallMemorySomeMeter::new();
+        assert!(meter.supports_mode(MeterMode::Text));
+        assert!(meter.supports_mode(MeterMode::Bar));
+        assert!(meter.supports_mode(MeterMode::Graph));
+        assert!(!meter.supports_mode(MeterMode::StackedGraph));
+    }
 }
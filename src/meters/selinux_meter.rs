@@ -1,21 +1,78 @@
 //! SELinux Meter
 //!
-//! Displays SELinux state (Linux-specific).
+//! Displays SELinux state (Linux-specific), like C htop:
+//! "enabled; mode: enforcing; policy: targeted"
+//!
+//! The mode comes from `/sys/fs/selinux/enforce` (the file only exists
+//! when SELinux is enabled) and the policy type from `SELINUXTYPE` in
+//! `/etc/selinux/config`. Both are read by the background scanner and
+//! arrive via `merge_expensive_data()`.
 
-use super::{Meter, MeterMode};
+use super::meter_bg_scanner::{MeterDataId, MeterExpensiveData};
+use super::{draw_text_segments, Meter, MeterMode, TextSegment};
 use crate::core::{Machine, Settings};
 use crate::ui::{ColorElement, Crt};
 
+/// Enforcement switch of the SELinux filesystem
+pub const SELINUX_ENFORCE_PATH: &str = "/sys/fs/selinux/enforce";
+
+/// SELinux configuration naming the loaded policy
+pub const SELINUX_CONFIG_PATH: &str = "/etc/selinux/config";
+
+/// State of an enabled SELinux
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SELinuxState {
+    /// Enforcing or permissive mode
+    pub enforcing: bool,
+    /// Policy type, e.g. "targeted" or "mls" (None if not configured)
+    pub policy: Option<String>,
+}
+
+/// Parse the policy type from the contents of /etc/selinux/config
+pub fn parse_selinux_policy(config: &str) -> Option<String> {
+    config.lines().find_map(|line| {
+        let value = line.trim().strip_prefix("SELINUXTYPE=")?;
+        let value = value.trim().trim_matches('"');
+        (!value.is_empty()).then(|| value.to_string())
+    })
+}
+
 /// SELinux Meter - displays SELinux state overview
 #[derive(Debug, Default)]
 pub struct SELinuxMeter {
     mode: MeterMode,
+    /// Latest state: None before the first scan, Some(None) if SELinux
+    /// is disabled
+    state: Option<Option<SELinuxState>>,
 }
 
 impl SELinuxMeter {
     pub fn new() -> Self {
         Self {
             mode: MeterMode::Text,
+            state: None,
+        }
+    }
+
+    /// Text shown after the caption
+    fn text(&self) -> String {
+        match &self.state {
+            None => "N/A".to_string(),
+            Some(None) => "disabled".to_string(),
+            Some(Some(state)) => {
+                let mut text = format!(
+                    "enabled; mode: {}",
+                    if state.enforcing {
+                        "enforcing"
+                    } else {
+                        "permissive"
+                    }
+                );
+                if let Some(ref policy) = state.policy {
+                    text.push_str(&format!("; policy: {}", policy));
+                }
+                text
+            }
         }
     }
 }
@@ -38,7 +95,17 @@ impl Meter for SELinuxMeter {
     }
 
     fn update(&mut self, _machine: &Machine) {
-        // Not yet implemented
+        // State comes from the background scanner via merge_expensive_data()
+    }
+
+    fn expensive_data_id(&self) -> Option<MeterDataId> {
+        Some(MeterDataId::SELinux)
+    }
+
+    fn merge_expensive_data(&mut self, data: &MeterExpensiveData) {
+        if let MeterExpensiveData::SELinux(state) = data {
+            self.state = Some(state.clone());
+        }
     }
 
     fn draw(
@@ -50,19 +117,24 @@ impl Meter for SELinuxMeter {
         y: i32,
         _width: i32,
     ) {
-        let caption_attr = crt.color(ColorElement::MeterText);
-        let text_attr = crt.color(ColorElement::MeterValueError);
-        let reset_attr = crt.color(ColorElement::ResetColor);
-        let caption = self.caption();
-
-        crt.with_window(|win| {
-            let _ = win.mv(y, x);
-            let _ = win.attrset(caption_attr);
-            let _ = win.addstr(caption);
-            let _ = win.attrset(text_attr);
-            let _ = win.addstr("Not implemented");
-            let _ = win.attrset(reset_attr);
-        });
+        // Permissive mode only logs denials, which audits treat as a finding
+        let value_attr = match &self.state {
+            Some(Some(state)) if state.enforcing => crt.color(ColorElement::MeterValueOk),
+            Some(Some(_)) => crt.color(ColorElement::MeterValueWarn),
+            _ => crt.color(ColorElement::MeterValueError),
+        };
+        let text = self.text();
+        let segments = [
+            TextSegment {
+                text: self.caption(),
+                attr: crt.color(ColorElement::MeterText),
+            },
+            TextSegment {
+                text: &text,
+                attr: value_attr,
+            },
+        ];
+        draw_text_segments(crt, x, y, &segments);
     }
 
     fn mode(&self) -> MeterMode {
@@ -82,6 +154,7 @@ mod tests {
     fn test_selinux_meter_new() {
         let meter = SELinuxMeter::new();
         assert_eq!(meter.mode, MeterMode::Text);
+        assert_eq!(meter.state, None);
     }
 
     #[test]
@@ -131,10 +204,54 @@ mod tests {
     }
 
     #[test]
-    fn test_selinux_meter_update_does_nothing() {
+    fn test_selinux_meter_update_keeps_state() {
         let mut meter = SELinuxMeter::new();
-        let machine = Machine::default();
-        // Update is not yet implemented but should not panic
-        meter.update(&machine);
+        meter.merge_expensive_data(&MeterExpensiveData::SELinux(None));
+        // The state only comes from the background scanner
+        meter.update(&Machine::default());
+        assert_eq!(meter.state, Some(None));
+    }
+
+    #[test]
+    fn test_selinux_meter_expensive_data_id() {
+        let meter = SELinuxMeter::new();
+        assert_eq!(meter.expensive_data_id(), Some(MeterDataId::SELinux));
+    }
+
+    #[test]
+    fn test_selinux_meter_text() {
+        let mut meter = SELinuxMeter::new();
+        assert_eq!(meter.text(), "N/A");
+
+        meter.merge_expensive_data(&MeterExpensiveData::SELinux(None));
+        assert_eq!(meter.text(), "disabled");
+
+        meter.merge_expensive_data(&MeterExpensiveData::SELinux(Some(SELinuxState {
+            enforcing: true,
+            policy: Some("targeted".to_string()),
+        })));
+        assert_eq!(meter.text(), "enabled; mode: enforcing; policy: targeted");
+
+        meter.merge_expensive_data(&MeterExpensiveData::SELinux(Some(SELinuxState {
+            enforcing: false,
+            policy: None,
+        })));
+        assert_eq!(meter.text(), "enabled; mode: permissive");
+    }
+
+    #[test]
+    fn test_parse_selinux_policy() {
+        let config = "\
+# This file controls the state of SELinux on the system.
+SELINUX=enforcing
+# SELINUXTYPE= can take one of these three values:
+SELINUXTYPE=targeted
+";
+        assert_eq!(parse_selinux_policy(config), Some("targeted".to_string()));
+        assert_eq!(
+            parse_selinux_policy("SELINUXTYPE=\"mls\"\n"),
+            Some("mls".to_string())
+        );
+        assert_eq!(parse_selinux_policy("SELINUX=disabled\n"), None);
     }
 }
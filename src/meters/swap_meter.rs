@@ -16,10 +16,15 @@ use crate::ui::Crt;
 /// Displays swap usage exactly like C htop:
 /// Bar mode: "Swp[|||||||||     XXXM/YYYM]"
 /// The value text appears right-aligned INSIDE the bar
+///
+/// Swap on zram devices lives in (compressed) RAM rather than on disk,
+/// so it is shown as a separate segment.
 #[derive(Debug)]
 pub struct SwapMeter {
     mode: MeterMode,
     used: f64,
+    /// Part of `used` on zram devices (-1 if there is no zram swap)
+    zram: f64,
     cache: f64,
     total: f64,
     /// Graph data for historical display (RefCell for interior mutability)
@@ -39,12 +44,18 @@ impl SwapMeter {
         SwapMeter {
             mode: MeterMode::Bar,
             used: 0.0,
+            zram: -1.0,
             cache: 0.0,
             total: 0.0,
             graph_data: RefCell::new(GraphData::new()),
             stacked_graph_data: RefCell::new(SwapStackedGraphData::new()),
         }
     }
+
+    /// Used swap that is not on zram devices
+    fn disk_used(&self) -> f64 {
+        (self.used - self.zram.max(0.0)).max(0.0)
+    }
 }
 
 impl Meter for SwapMeter {
@@ -60,6 +71,11 @@ impl Meter for SwapMeter {
         self.total = machine.total_swap as f64;
         self.used = machine.used_swap as f64;
         self.cache = machine.cached_swap as f64;
+        self.zram = if machine.zram.swap_total > 0 {
+            machine.zram.swap_used as f64
+        } else {
+            -1.0
+        };
     }
 
     fn draw(
@@ -75,6 +91,7 @@ impl Meter for SwapMeter {
             MeterMode::Bar => {
                 // Get color attributes
                 let swap_attr = crt.color(ColorElement::Swap);
+                let swap_zram_attr = crt.color(ColorElement::SwapFrontswap);
                 let swap_cache_attr = crt.color(ColorElement::SwapCache);
 
                 let text = format!("{}/{}", human_unit(self.used), human_unit(self.total));
@@ -82,9 +99,13 @@ impl Meter for SwapMeter {
                 // Build bar segments
                 let segments = vec![
                     BarSegment {
-                        value: self.used,
+                        value: self.disk_used(),
                         attr: swap_attr,
                     },
+                    BarSegment {
+                        value: self.zram.max(0.0),
+                        attr: swap_zram_attr,
+                    },
                     BarSegment {
                         value: self.cache.max(0.0),
                         attr: swap_cache_attr,
@@ -97,18 +118,24 @@ impl Meter for SwapMeter {
                 // Extract all colors BEFORE building segments
                 let text_attr = crt.color(ColorElement::MeterText);
                 let value_attr = crt.color(ColorElement::MeterValue);
+                let zram_attr = crt.color(ColorElement::SwapFrontswap);
                 let cache_attr = crt.color(ColorElement::SwapCache);
 
                 // Pre-compute values
                 let total_str = human_unit(self.total);
                 let used_str = human_unit(self.used);
+                let zram_str = if self.zram >= 0.0 {
+                    Some(human_unit(self.zram))
+                } else {
+                    None
+                };
                 let cache_str = if self.cache >= 0.0 {
                     Some(human_unit(self.cache))
                 } else {
                     None
                 };
 
-                // Build segments: "Swp:TOTAL used:VALUE cache:VALUE"
+                // Build segments: "Swp:TOTAL used:VALUE zram:VALUE cache:VALUE"
                 let mut segments = vec![
                     TextSegment {
                         text: "Swp:",
@@ -128,6 +155,18 @@ impl Meter for SwapMeter {
                     },
                 ];
 
+                // Add zram segment if there is swap on zram devices
+                if let Some(ref zram) = zram_str {
+                    segments.push(TextSegment {
+                        text: " zram:",
+                        attr: text_attr,
+                    });
+                    segments.push(TextSegment {
+                        text: zram,
+                        attr: zram_attr,
+                    });
+                }
+
                 // Add cache segment if supported
                 if let Some(ref cache) = cache_str {
                     segments.push(TextSegment {
@@ -144,10 +183,13 @@ impl Meter for SwapMeter {
             }
             MeterMode::Led => {
                 // LED mode: show same detailed breakdown as Text mode, rendered with draw_led
-                // Format: "Swp:TOTAL used:VALUE cache:VALUE"
+                // Format: "Swp:TOTAL used:VALUE zram:VALUE cache:VALUE"
                 let mut text =
                     format!(":{} used:{}", human_unit(self.total), human_unit(self.used));
 
+                if self.zram >= 0.0 {
+                    text.push_str(&format!(" zram:{}", human_unit(self.zram)));
+                }
                 if self.cache >= 0.0 {
                     text.push_str(&format!(" cache:{}", human_unit(self.cache)));
                 }
@@ -173,27 +215,28 @@ impl Meter for SwapMeter {
                 draw_graph(crt, x, y, width, self.height(), &graph_data, "Swp");
             }
             MeterMode::StackedGraph => {
-                // StackedGraph mode: show used and cache as separate colored segments
+                // StackedGraph mode: show used, zram and cache as separate colored segments
                 use crate::ui::ColorElement;
 
                 // Pre-compute segment colors
                 let segment_colors: [u32; SWAP_STACKED_GRAPH_SEGMENTS] = [
-                    crt.color(ColorElement::Swap),      // used
-                    crt.color(ColorElement::SwapCache), // cache
+                    crt.color(ColorElement::Swap),          // used
+                    crt.color(ColorElement::SwapFrontswap), // zram
+                    crt.color(ColorElement::SwapCache),     // cache
                 ];
 
                 // Calculate segment values as ratio of total (0.0-1.0)
-                let (used_ratio, cache_ratio) = if self.total > 0.0 {
-                    (
-                        self.used / self.total,
-                        self.cache.max(0.0) / self.total, // Hide negative (unsupported) values
-                    )
+                // Hide negative (unsupported) values
+                let segments: [f64; SWAP_STACKED_GRAPH_SEGMENTS] = if self.total > 0.0 {
+                    [
+                        self.disk_used() / self.total,
+                        self.zram.max(0.0) / self.total,
+                        self.cache.max(0.0) / self.total,
+                    ]
                 } else {
-                    (0.0, 0.0)
+                    [0.0; SWAP_STACKED_GRAPH_SEGMENTS]
                 };
 
-                let segments: [f64; SWAP_STACKED_GRAPH_SEGMENTS] = [used_ratio, cache_ratio];
-
                 // Record the value in stacked graph data
                 {
                     let mut stacked_graph_data = self.stacked_graph_data.borrow_mut();
@@ -237,7 +280,7 @@ impl Meter for SwapMeter {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::core::Machine;
+    use crate::core::{Machine, ZramStats};
     use crate::meters::human_unit;
 
     // ==================== Constructor Tests ====================
@@ -304,6 +347,29 @@ mod tests {
         assert_eq!(meter.total, 8.0 * 1024.0 * 1024.0);
         assert_eq!(meter.used, 2.0 * 1024.0 * 1024.0);
         assert_eq!(meter.cache, 512.0 * 1024.0);
+        // No zram swap
+        assert_eq!(meter.zram, -1.0);
+        assert_eq!(meter.disk_used(), meter.used);
+    }
+
+    #[test]
+    fn test_swap_meter_update_zram() {
+        let mut meter = SwapMeter::new();
+        let machine = Machine {
+            total_swap: 6 * 1024 * 1024,
+            used_swap: 3 * 1024 * 1024,
+            zram: ZramStats {
+                swap_total: 4 * 1024 * 1024,
+                swap_used: 2 * 1024 * 1024,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        meter.update(&machine);
+
+        assert_eq!(meter.zram, 2.0 * 1024.0 * 1024.0);
+        assert_eq!(meter.disk_used(), 1024.0 * 1024.0);
     }
 
     #[test]
@@ -1,21 +1,180 @@
 //! Systemd Meters
 //!
-//! Displays Systemd state (Linux-specific).
+//! Displays Systemd state (Linux-specific), like C htop:
+//! "running (0/245 failed) (0/1342 jobs)"
+//!
+//! The state is read from the system or user manager over D-Bus (falling
+//! back to `systemctl show`) by the background scanner and arrives via
+//! `merge_expensive_data()`.
 
-use super::{Meter, MeterMode};
+use super::meter_bg_scanner::{MeterDataId, MeterExpensiveData};
+use super::{draw_text_segments, Meter, MeterMode, TextSegment};
 use crate::core::{Machine, Settings};
 use crate::ui::{ColorElement, Crt};
 
+/// Properties of org.freedesktop.systemd1.Manager shown by the meters
+pub const SYSTEMD_PROPERTIES: [&str; 5] = [
+    "SystemState",
+    "NFailedUnits",
+    "NNames",
+    "NJobs",
+    "NInstalledJobs",
+];
+
+/// State of a systemd manager
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SystemdState {
+    /// "running", "degraded", "starting", ...
+    pub system_state: String,
+    pub n_failed_units: u32,
+    /// Number of loaded units
+    pub n_names: u32,
+    /// Number of queued jobs
+    pub n_jobs: u32,
+    /// Number of jobs installed since boot
+    pub n_installed_jobs: u32,
+}
+
+impl SystemdState {
+    /// Set a property from its name and textual value
+    /// Returns false for unknown properties or unparsable values
+    pub fn set_property(&mut self, name: &str, value: &str) -> bool {
+        let target = match name {
+            "SystemState" => {
+                self.system_state = value.to_string();
+                return true;
+            }
+            "NFailedUnits" => &mut self.n_failed_units,
+            "NNames" => &mut self.n_names,
+            "NJobs" => &mut self.n_jobs,
+            "NInstalledJobs" => &mut self.n_installed_jobs,
+            _ => return false,
+        };
+        match value.parse() {
+            Ok(v) => {
+                *target = v;
+                true
+            }
+            Err(_) => false,
+        }
+    }
+}
+
+/// Parse the output of `systemctl show --property=...`
+/// Returns None unless the system state is present
+pub fn parse_systemctl_show(output: &str) -> Option<SystemdState> {
+    let mut state = SystemdState::default();
+    for line in output.lines() {
+        if let Some((name, value)) = line.split_once('=') {
+            state.set_property(name, value.trim());
+        }
+    }
+    (!state.system_state.is_empty()).then_some(state)
+}
+
+/// Draw "STATE (F/N failed) (J/I jobs)" after the caption
+fn draw_systemd_state(crt: &mut Crt, x: i32, y: i32, caption: &str, state: Option<&SystemdState>) {
+    let text_attr = crt.color(ColorElement::MeterText);
+    let value_attr = crt.color(ColorElement::MeterValue);
+    let error_attr = crt.color(ColorElement::MeterValueError);
+
+    let Some(state) = state else {
+        let segments = [
+            TextSegment {
+                text: caption,
+                attr: text_attr,
+            },
+            TextSegment {
+                text: "N/A",
+                attr: error_attr,
+            },
+        ];
+        draw_text_segments(crt, x, y, &segments);
+        return;
+    };
+
+    let state_attr = match state.system_state.as_str() {
+        "running" => crt.color(ColorElement::MeterValueOk),
+        "degraded" => error_attr,
+        _ => crt.color(ColorElement::MeterValueWarn),
+    };
+    let failed_attr = if state.n_failed_units > 0 {
+        error_attr
+    } else {
+        value_attr
+    };
+    let jobs_attr = if state.n_jobs > 0 {
+        crt.color(ColorElement::MeterValueNotice)
+    } else {
+        value_attr
+    };
+
+    let failed = state.n_failed_units.to_string();
+    let names = state.n_names.to_string();
+    let jobs = state.n_jobs.to_string();
+    let installed_jobs = state.n_installed_jobs.to_string();
+    let segments = [
+        TextSegment {
+            text: caption,
+            attr: text_attr,
+        },
+        TextSegment {
+            text: &state.system_state,
+            attr: state_attr,
+        },
+        TextSegment {
+            text: " (",
+            attr: text_attr,
+        },
+        TextSegment {
+            text: &failed,
+            attr: failed_attr,
+        },
+        TextSegment {
+            text: "/",
+            attr: text_attr,
+        },
+        TextSegment {
+            text: &names,
+            attr: value_attr,
+        },
+        TextSegment {
+            text: " failed) (",
+            attr: text_attr,
+        },
+        TextSegment {
+            text: &jobs,
+            attr: jobs_attr,
+        },
+        TextSegment {
+            text: "/",
+            attr: text_attr,
+        },
+        TextSegment {
+            text: &installed_jobs,
+            attr: value_attr,
+        },
+        TextSegment {
+            text: " jobs)",
+            attr: text_attr,
+        },
+    ];
+    draw_text_segments(crt, x, y, &segments);
+}
+
 /// Systemd Meter - displays Systemd system state and unit overview
 #[derive(Debug, Default)]
 pub struct SystemdMeter {
     mode: MeterMode,
+    /// Latest state, None if the manager could not be queried
+    state: Option<SystemdState>,
 }
 
 impl SystemdMeter {
     pub fn new() -> Self {
         Self {
             mode: MeterMode::Text,
+            state: None,
         }
     }
 }
@@ -38,7 +197,17 @@ impl Meter for SystemdMeter {
     }
 
     fn update(&mut self, _machine: &Machine) {
-        // Not yet implemented
+        // State comes from the background scanner via merge_expensive_data()
+    }
+
+    fn expensive_data_id(&self) -> Option<MeterDataId> {
+        Some(MeterDataId::Systemd)
+    }
+
+    fn merge_expensive_data(&mut self, data: &MeterExpensiveData) {
+        if let MeterExpensiveData::Systemd(state) = data {
+            self.state = state.clone();
+        }
     }
 
     fn draw(
@@ -50,19 +219,7 @@ impl Meter for SystemdMeter {
         y: i32,
         _width: i32,
     ) {
-        let caption_attr = crt.color(ColorElement::MeterText);
-        let text_attr = crt.color(ColorElement::MeterValueError);
-        let reset_attr = crt.color(ColorElement::ResetColor);
-        let caption = self.caption();
-
-        crt.with_window(|win| {
-            let _ = win.mv(y, x);
-            let _ = win.attrset(caption_attr);
-            let _ = win.addstr(caption);
-            let _ = win.attrset(text_attr);
-            let _ = win.addstr("Not implemented");
-            let _ = win.attrset(reset_attr);
-        });
+        draw_systemd_state(crt, x, y, self.caption(), self.state.as_ref());
     }
 
     fn mode(&self) -> MeterMode {
@@ -78,12 +235,15 @@ impl Meter for SystemdMeter {
 #[derive(Debug, Default)]
 pub struct SystemdUserMeter {
     mode: MeterMode,
+    /// Latest state, None if the manager could not be queried
+    state: Option<SystemdState>,
 }
 
 impl SystemdUserMeter {
     pub fn new() -> Self {
         Self {
             mode: MeterMode::Text,
+            state: None,
         }
     }
 }
@@ -106,7 +266,17 @@ impl Meter for SystemdUserMeter {
     }
 
     fn update(&mut self, _machine: &Machine) {
-        // Not yet implemented
+        // State comes from the background scanner via merge_expensive_data()
+    }
+
+    fn expensive_data_id(&self) -> Option<MeterDataId> {
+        Some(MeterDataId::SystemdUser)
+    }
+
+    fn merge_expensive_data(&mut self, data: &MeterExpensiveData) {
+        if let MeterExpensiveData::Systemd(state) = data {
+            self.state = state.clone();
+        }
     }
 
     fn draw(
@@ -118,19 +288,7 @@ impl Meter for SystemdUserMeter {
         y: i32,
         _width: i32,
     ) {
-        let caption_attr = crt.color(ColorElement::MeterText);
-        let text_attr = crt.color(ColorElement::MeterValueError);
-        let reset_attr = crt.color(ColorElement::ResetColor);
-        let caption = self.caption();
-
-        crt.with_window(|win| {
-            let _ = win.mv(y, x);
-            let _ = win.attrset(caption_attr);
-            let _ = win.addstr(caption);
-            let _ = win.attrset(text_attr);
-            let _ = win.addstr("Not implemented");
-            let _ = win.attrset(reset_attr);
-        });
+        draw_systemd_state(crt, x, y, self.caption(), self.state.as_ref());
     }
 
     fn mode(&self) -> MeterMode {
@@ -203,7 +361,7 @@ mod tests {
     fn test_systemd_meter_update_does_nothing() {
         let mut meter = SystemdMeter::new();
         let machine = Machine::default();
-        // Update is not yet implemented but should not panic
+        // State comes from the background scanner, update must not touch it
         meter.update(&machine);
     }
 
@@ -264,7 +422,60 @@ mod tests {
     fn test_systemd_user_meter_update_does_nothing() {
         let mut meter = SystemdUserMeter::new();
         let machine = Machine::default();
-        // Update is not yet implemented but should not panic
+        // State comes from the background scanner, update must not touch it
         meter.update(&machine);
     }
+
+    // State parsing and merging
+    #[test]
+    fn test_parse_systemctl_show() {
+        let output =
+            "SystemState=degraded\nNFailedUnits=2\nNNames=245\nNJobs=1\nNInstalledJobs=1342\n";
+        let state = parse_systemctl_show(output).unwrap();
+        assert_eq!(
+            state,
+            SystemdState {
+                system_state: "degraded".to_string(),
+                n_failed_units: 2,
+                n_names: 245,
+                n_jobs: 1,
+                n_installed_jobs: 1342,
+            }
+        );
+
+        // Without a system state (e.g. no user manager) there is nothing to show
+        assert_eq!(parse_systemctl_show("NNames=3\n"), None);
+        assert_eq!(parse_systemctl_show(""), None);
+    }
+
+    #[test]
+    fn test_systemd_state_set_property() {
+        let mut state = SystemdState::default();
+        assert!(state.set_property("NJobs", "4"));
+        assert!(!state.set_property("NJobs", "many"));
+        assert!(!state.set_property("Version", "255"));
+        assert_eq!(state.n_jobs, 4);
+    }
+
+    #[test]
+    fn test_systemd_meters_merge() {
+        let state = SystemdState {
+            system_state: "running".to_string(),
+            n_names: 10,
+            ..Default::default()
+        };
+        let mut meter = SystemdMeter::new();
+        let mut user_meter = SystemdUserMeter::new();
+        assert_eq!(meter.expensive_data_id(), Some(MeterDataId::Systemd));
+        assert_eq!(
+            user_meter.expensive_data_id(),
+            Some(MeterDataId::SystemdUser)
+        );
+
+        meter.merge_expensive_data(&MeterExpensiveData::Systemd(Some(state.clone())));
+        assert_eq!(meter.state, Some(state));
+
+        user_meter.merge_expensive_data(&MeterExpensiveData::Systemd(None));
+        assert_eq!(user_meter.state, None);
+    }
 }
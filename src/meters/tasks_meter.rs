@@ -98,12 +98,12 @@ impl Meter for TasksMeter {
                 let shadow_attr = crt.color(ColorElement::MeterShadow);
 
                 // Pre-compute conditional attrs based on settings
-                let (thr_text_attr, thr_value_attr) = if settings.hide_userland_threads {
+                let (thr_text_attr, thr_value_attr) = if settings.userland_threads_hidden() {
                     (shadow_attr, shadow_attr)
                 } else {
                     (text_attr, running_attr)
                 };
-                let (kthr_text_attr, kthr_value_attr) = if settings.hide_kernel_threads {
+                let (kthr_text_attr, kthr_value_attr) = if settings.kernel_threads_hidden() {
                     (shadow_attr, shadow_attr)
                 } else {
                     (text_attr, running_attr)
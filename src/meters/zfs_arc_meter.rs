@@ -1,21 +1,54 @@
 //! ZFS ARC Meters
 //!
 //! Displays ZFS ARC statistics (available where ZFS is supported).
+//! The statistics are read into `Machine::zfs` by the platform scan.
 
-use super::{Meter, MeterMode};
-use crate::core::{Machine, Settings};
+use std::cell::RefCell;
+
+use super::{
+    draw_bar_with_text, draw_graph, draw_text_segments, human_unit, BarSegment, GraphData, Meter,
+    MeterMode, TextSegment,
+};
+use crate::core::{Machine, Settings, ZfsArcStats};
 use crate::ui::{ColorElement, Crt};
 
+/// Modes supported by the ZFS meters
+const ZFS_METER_MODES: u32 =
+    (1 << MeterMode::Bar as u32) | (1 << MeterMode::Text as u32) | (1 << MeterMode::Graph as u32);
+
+/// Draw "ARC: N/A" (or another message) when there is nothing to show
+fn draw_unavailable(crt: &mut Crt, x: i32, y: i32, caption: &str, text: &str) {
+    let segments = [
+        TextSegment {
+            text: caption,
+            attr: crt.color(ColorElement::MeterText),
+        },
+        TextSegment {
+            text,
+            attr: crt.color(ColorElement::MeterValueError),
+        },
+    ];
+    draw_text_segments(crt, x, y, &segments);
+}
+
 /// ZFS ARC Meter - displays ZFS Adaptive Replacement Cache statistics
+///
+/// Bar mode: "ARC[|||||||||||      4.00G/8.00G]" with MFU, MRU, anon,
+/// header and other segments relative to the ARC maximum
 #[derive(Debug, Default)]
 pub struct ZfsArcMeter {
     mode: MeterMode,
+    stats: ZfsArcStats,
+    /// Graph data for historical display (RefCell for interior mutability)
+    graph_data: RefCell<GraphData>,
 }
 
 impl ZfsArcMeter {
     pub fn new() -> Self {
         Self {
             mode: MeterMode::Bar,
+            stats: ZfsArcStats::default(),
+            graph_data: RefCell::new(GraphData::new()),
         }
     }
 }
@@ -29,32 +62,120 @@ impl Meter for ZfsArcMeter {
         "ARC: "
     }
 
-    fn update(&mut self, _machine: &Machine) {
-        // Not yet implemented
+    fn supported_modes(&self) -> u32 {
+        ZFS_METER_MODES
+    }
+
+    fn update(&mut self, machine: &Machine) {
+        self.stats = machine.zfs.clone();
     }
 
     fn draw(
         &self,
         crt: &mut Crt,
         _machine: &Machine,
-        _settings: &Settings,
+        settings: &Settings,
         x: i32,
         y: i32,
-        _width: i32,
+        width: i32,
     ) {
-        let caption_attr = crt.color(ColorElement::MeterText);
-        let text_attr = crt.color(ColorElement::MeterValueError);
-        let reset_attr = crt.color(ColorElement::ResetColor);
-        let caption = self.caption();
+        let stats = &self.stats;
+        if !stats.enabled {
+            draw_unavailable(crt, x, y, self.caption(), "N/A");
+            return;
+        }
 
-        crt.with_window(|win| {
-            let _ = win.mv(y, x);
-            let _ = win.attrset(caption_attr);
-            let _ = win.addstr(caption);
-            let _ = win.attrset(text_attr);
-            let _ = win.addstr("Not implemented");
-            let _ = win.attrset(reset_attr);
-        });
+        match self.mode {
+            MeterMode::Graph => {
+                let normalized = if stats.max > 0 {
+                    stats.size as f64 / stats.max as f64
+                } else {
+                    0.0
+                };
+                {
+                    let mut graph_data = self.graph_data.borrow_mut();
+                    graph_data.record(normalized, settings.delay * 100);
+                }
+
+                let graph_data = self.graph_data.borrow();
+                draw_graph(crt, x, y, width, self.height(), &graph_data, "ARC");
+            }
+            MeterMode::Text => {
+                let text_attr = crt.color(ColorElement::MeterText);
+                let value_attr = crt.color(ColorElement::MeterValue);
+
+                let size = human_unit(stats.size as f64);
+                let mfu = human_unit(stats.mfu as f64);
+                let mru = human_unit(stats.mru as f64);
+                let anon = human_unit(stats.anon as f64);
+                let header = human_unit(stats.header as f64);
+                let other = human_unit(stats.other as f64);
+
+                // Format: "ARC: Used:SIZE MFU:X MRU:X Anon:X Hdr:X Oth:X"
+                let mut segments = vec![
+                    TextSegment {
+                        text: self.caption(),
+                        attr: text_attr,
+                    },
+                    TextSegment {
+                        text: "Used:",
+                        attr: text_attr,
+                    },
+                    TextSegment {
+                        text: &size,
+                        attr: value_attr,
+                    },
+                ];
+                for (label, value, element) in [
+                    (" MFU:", &mfu, ColorElement::ZfsMfu),
+                    (" MRU:", &mru, ColorElement::ZfsMru),
+                    (" Anon:", &anon, ColorElement::ZfsAnon),
+                    (" Hdr:", &header, ColorElement::ZfsHeader),
+                    (" Oth:", &other, ColorElement::ZfsOther),
+                ] {
+                    segments.push(TextSegment {
+                        text: label,
+                        attr: text_attr,
+                    });
+                    segments.push(TextSegment {
+                        text: value,
+                        attr: crt.color(element),
+                    });
+                }
+
+                draw_text_segments(crt, x, y, &segments);
+            }
+            _ => {
+                let segments = [
+                    BarSegment {
+                        value: stats.mfu as f64,
+                        attr: crt.color(ColorElement::ZfsMfu),
+                    },
+                    BarSegment {
+                        value: stats.mru as f64,
+                        attr: crt.color(ColorElement::ZfsMru),
+                    },
+                    BarSegment {
+                        value: stats.anon as f64,
+                        attr: crt.color(ColorElement::ZfsAnon),
+                    },
+                    BarSegment {
+                        value: stats.header as f64,
+                        attr: crt.color(ColorElement::ZfsHeader),
+                    },
+                    BarSegment {
+                        value: stats.other as f64,
+                        attr: crt.color(ColorElement::ZfsOther),
+                    },
+                ];
+                let text = format!(
+                    "{}/{}",
+                    human_unit(stats.size as f64),
+                    human_unit(stats.max as f64)
+                );
+                draw_bar_with_text(crt, x, y, width, "ARC", &segments, stats.max as f64, &text);
+            }
+        }
     }
 
     fn mode(&self) -> MeterMode {
@@ -67,17 +188,29 @@ impl Meter for ZfsArcMeter {
 }
 
 /// ZFS Compressed ARC Meter - displays ZFS Compressed ARC statistics
+///
+/// Bar mode: "ARC[||||||||              3.00:1]" showing the compressed
+/// size relative to the uncompressed size
 #[derive(Debug, Default)]
 pub struct ZfsCompressedArcMeter {
     mode: MeterMode,
+    stats: ZfsArcStats,
+    /// Graph data for historical display (RefCell for interior mutability)
+    graph_data: RefCell<GraphData>,
 }
 
 impl ZfsCompressedArcMeter {
     pub fn new() -> Self {
         Self {
             mode: MeterMode::Bar,
+            stats: ZfsArcStats::default(),
+            graph_data: RefCell::new(GraphData::new()),
         }
     }
+
+    fn ratio_text(&self) -> String {
+        format!("{:.2}:1", self.stats.compression_ratio())
+    }
 }
 
 impl Meter for ZfsCompressedArcMeter {
@@ -89,32 +222,100 @@ impl Meter for ZfsCompressedArcMeter {
         "ARC: "
     }
 
-    fn update(&mut self, _machine: &Machine) {
-        // Not yet implemented
+    fn supported_modes(&self) -> u32 {
+        ZFS_METER_MODES
+    }
+
+    fn update(&mut self, machine: &Machine) {
+        self.stats = machine.zfs.clone();
     }
 
     fn draw(
         &self,
         crt: &mut Crt,
         _machine: &Machine,
-        _settings: &Settings,
+        settings: &Settings,
         x: i32,
         y: i32,
-        _width: i32,
+        width: i32,
     ) {
-        let caption_attr = crt.color(ColorElement::MeterText);
-        let text_attr = crt.color(ColorElement::MeterValueError);
-        let reset_attr = crt.color(ColorElement::ResetColor);
-        let caption = self.caption();
+        let stats = &self.stats;
+        if !stats.enabled {
+            draw_unavailable(crt, x, y, self.caption(), "N/A");
+            return;
+        }
+        if !stats.is_compressed {
+            draw_unavailable(crt, x, y, self.caption(), "Compression Disabled");
+            return;
+        }
 
-        crt.with_window(|win| {
-            let _ = win.mv(y, x);
-            let _ = win.attrset(caption_attr);
-            let _ = win.addstr(caption);
-            let _ = win.attrset(text_attr);
-            let _ = win.addstr("Not implemented");
-            let _ = win.attrset(reset_attr);
-        });
+        match self.mode {
+            MeterMode::Graph => {
+                let normalized = if stats.uncompressed > 0 {
+                    stats.compressed as f64 / stats.uncompressed as f64
+                } else {
+                    0.0
+                };
+                {
+                    let mut graph_data = self.graph_data.borrow_mut();
+                    graph_data.record(normalized, settings.delay * 100);
+                }
+
+                let graph_data = self.graph_data.borrow();
+                draw_graph(crt, x, y, width, self.height(), &graph_data, "ARC");
+            }
+            MeterMode::Text => {
+                let text_attr = crt.color(ColorElement::MeterText);
+                let compressed = human_unit(stats.compressed as f64);
+                let uncompressed = human_unit(stats.uncompressed as f64);
+                let ratio = self.ratio_text();
+
+                // Format: "ARC: SIZE Uncompressed:SIZE Ratio:R:1"
+                let segments = [
+                    TextSegment {
+                        text: self.caption(),
+                        attr: text_attr,
+                    },
+                    TextSegment {
+                        text: &compressed,
+                        attr: crt.color(ColorElement::ZfsCompressed),
+                    },
+                    TextSegment {
+                        text: " Uncompressed:",
+                        attr: text_attr,
+                    },
+                    TextSegment {
+                        text: &uncompressed,
+                        attr: crt.color(ColorElement::MeterValue),
+                    },
+                    TextSegment {
+                        text: " Ratio:",
+                        attr: text_attr,
+                    },
+                    TextSegment {
+                        text: &ratio,
+                        attr: crt.color(ColorElement::ZfsRatio),
+                    },
+                ];
+                draw_text_segments(crt, x, y, &segments);
+            }
+            _ => {
+                let segments = [BarSegment {
+                    value: stats.compressed as f64,
+                    attr: crt.color(ColorElement::ZfsCompressed),
+                }];
+                draw_bar_with_text(
+                    crt,
+                    x,
+                    y,
+                    width,
+                    "ARC",
+                    &segments,
+                    stats.uncompressed as f64,
+                    &self.ratio_text(),
+                );
+            }
+        }
     }
 
     fn mode(&self) -> MeterMode {
@@ -169,8 +370,9 @@ mod tests {
     fn test_zfs_arc_meter_update_does_nothing() {
         let mut meter = ZfsArcMeter::new();
         let machine = Machine::default();
-        // Update is not yet implemented but should not panic
+        // Without ZFS the meter stays disabled
         meter.update(&machine);
+        assert!(!meter.stats.enabled);
     }
 
     // ZfsCompressedArcMeter tests
@@ -212,7 +414,50 @@ mod tests {
     fn test_zfs_compressed_arc_meter_update_does_nothing() {
         let mut meter = ZfsCompressedArcMeter::new();
         let machine = Machine::default();
-        // Update is not yet implemented but should not panic
+        // Without ZFS the meter stays disabled
         meter.update(&machine);
+        assert!(!meter.stats.enabled);
+    }
+
+    fn zfs_machine() -> Machine {
+        Machine {
+            zfs: ZfsArcStats {
+                enabled: true,
+                is_compressed: true,
+                max: 8 * 1024 * 1024,
+                size: 4 * 1024 * 1024,
+                mfu: 2 * 1024 * 1024,
+                compressed: 1024 * 1024,
+                uncompressed: 3 * 1024 * 1024,
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_zfs_meters_update_from_machine() {
+        let machine = zfs_machine();
+
+        let mut meter = ZfsArcMeter::new();
+        meter.update(&machine);
+        assert_eq!(meter.stats, machine.zfs);
+
+        let mut carc = ZfsCompressedArcMeter::new();
+        carc.update(&machine);
+        assert_eq!(carc.ratio_text(), "3.00:1");
+    }
+
+    #[test]
+    fn test_zfs_meters_supported_modes() {
+        for meter in [
+            &ZfsArcMeter::new() as &dyn Meter,
+            &ZfsCompressedArcMeter::new(),
+        ] {
+            assert!(meter.supports_mode(MeterMode::Bar));
+            assert!(meter.supports_mode(MeterMode::Text));
+            assert!(meter.supports_mode(MeterMode::Graph));
+            assert!(!meter.supports_mode(MeterMode::Led));
+        }
     }
 }
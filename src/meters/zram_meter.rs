@@ -1,23 +1,41 @@
 //! Zram Meter
 //!
-//! Displays Zram usage (Linux-specific).
+//! Displays Zram usage (Linux-specific). The statistics of all zram
+//! devices are summed into `Machine::zram` by the platform scan.
 
-use super::{Meter, MeterMode};
-use crate::core::{Machine, Settings};
+use std::cell::RefCell;
+
+use super::{
+    draw_bar_with_text, draw_graph, draw_text_segments, human_unit, BarSegment, GraphData, Meter,
+    MeterMode, TextSegment,
+};
+use crate::core::{Machine, Settings, ZramStats};
 use crate::ui::{ColorElement, Crt};
 
 /// Zram Meter - displays Zram compressed memory usage
+///
+/// Bar mode: "zrm[|||||||          1.50G/5.00G]" with the compressed size
+/// and the space saved by compression relative to the device size
 #[derive(Debug, Default)]
 pub struct ZramMeter {
     mode: MeterMode,
+    stats: ZramStats,
+    /// Graph data for historical display (RefCell for interior mutability)
+    graph_data: RefCell<GraphData>,
 }
 
 impl ZramMeter {
     pub fn new() -> Self {
         Self {
             mode: MeterMode::Bar,
+            stats: ZramStats::default(),
+            graph_data: RefCell::new(GraphData::new()),
         }
     }
+
+    fn ratio_text(&self) -> String {
+        format!("{:.2}:1", self.stats.compression_ratio())
+    }
 }
 
 impl Meter for ZramMeter {
@@ -29,32 +47,125 @@ impl Meter for ZramMeter {
         "zrm: "
     }
 
-    fn update(&mut self, _machine: &Machine) {
-        // Not yet implemented
+    fn supported_modes(&self) -> u32 {
+        (1 << MeterMode::Bar as u32)
+            | (1 << MeterMode::Text as u32)
+            | (1 << MeterMode::Graph as u32)
+    }
+
+    fn update(&mut self, machine: &Machine) {
+        self.stats = machine.zram.clone();
     }
 
     fn draw(
         &self,
         crt: &mut Crt,
         _machine: &Machine,
-        _settings: &Settings,
+        settings: &Settings,
         x: i32,
         y: i32,
-        _width: i32,
+        width: i32,
     ) {
-        let caption_attr = crt.color(ColorElement::MeterText);
-        let text_attr = crt.color(ColorElement::MeterValueError);
-        let reset_attr = crt.color(ColorElement::ResetColor);
-        let caption = self.caption();
+        let stats = &self.stats;
+        if stats.total == 0 {
+            let segments = [
+                TextSegment {
+                    text: self.caption(),
+                    attr: crt.color(ColorElement::MeterText),
+                },
+                TextSegment {
+                    text: "N/A",
+                    attr: crt.color(ColorElement::MeterValueError),
+                },
+            ];
+            draw_text_segments(crt, x, y, &segments);
+            return;
+        }
 
-        crt.with_window(|win| {
-            let _ = win.mv(y, x);
-            let _ = win.attrset(caption_attr);
-            let _ = win.addstr(caption);
-            let _ = win.attrset(text_attr);
-            let _ = win.addstr("Not implemented");
-            let _ = win.attrset(reset_attr);
-        });
+        match self.mode {
+            MeterMode::Graph => {
+                let normalized = stats.original as f64 / stats.total as f64;
+                {
+                    let mut graph_data = self.graph_data.borrow_mut();
+                    graph_data.record(normalized, settings.delay * 100);
+                }
+
+                let graph_data = self.graph_data.borrow();
+                draw_graph(crt, x, y, width, self.height(), &graph_data, "zrm");
+            }
+            MeterMode::Text => {
+                let text_attr = crt.color(ColorElement::MeterText);
+                let total = human_unit(stats.total as f64);
+                let original = human_unit(stats.original as f64);
+                let compressed = human_unit(stats.compressed as f64);
+                let ratio = self.ratio_text();
+
+                // Format: "zrm: SIZE used:ORIGINAL compressed:SIZE ratio:R:1"
+                let segments = [
+                    TextSegment {
+                        text: self.caption(),
+                        attr: text_attr,
+                    },
+                    TextSegment {
+                        text: &total,
+                        attr: crt.color(ColorElement::MeterValue),
+                    },
+                    TextSegment {
+                        text: " used:",
+                        attr: text_attr,
+                    },
+                    TextSegment {
+                        text: &original,
+                        attr: crt.color(ColorElement::ZramUncompressed),
+                    },
+                    TextSegment {
+                        text: " compressed:",
+                        attr: text_attr,
+                    },
+                    TextSegment {
+                        text: &compressed,
+                        attr: crt.color(ColorElement::ZramCompressed),
+                    },
+                    TextSegment {
+                        text: " ratio:",
+                        attr: text_attr,
+                    },
+                    TextSegment {
+                        text: &ratio,
+                        attr: crt.color(ColorElement::MeterValue),
+                    },
+                ];
+                draw_text_segments(crt, x, y, &segments);
+            }
+            _ => {
+                // The stored data is the compressed part plus what compression saved
+                let segments = [
+                    BarSegment {
+                        value: stats.compressed as f64,
+                        attr: crt.color(ColorElement::ZramCompressed),
+                    },
+                    BarSegment {
+                        value: stats.original.saturating_sub(stats.compressed) as f64,
+                        attr: crt.color(ColorElement::ZramUncompressed),
+                    },
+                ];
+                let text = format!(
+                    "{}/{}",
+                    human_unit(stats.original as f64),
+                    human_unit(stats.total as f64)
+                );
+                draw_bar_with_text(
+                    crt,
+                    x,
+                    y,
+                    width,
+                    "zrm",
+                    &segments,
+                    stats.total as f64,
+                    &text,
+                );
+            }
+        }
     }
 
     fn mode(&self) -> MeterMode {
@@ -108,7 +219,34 @@ mod tests {
     fn test_zram_meter_update_does_nothing() {
         let mut meter = ZramMeter::new();
         let machine = Machine::default();
-        // Update is not yet implemented but should not panic
+        // Without zram devices there is nothing to show
+        meter.update(&machine);
+        assert_eq!(meter.stats.total, 0);
+    }
+
+    #[test]
+    fn test_zram_meter_update_from_machine() {
+        let mut meter = ZramMeter::new();
+        let machine = Machine {
+            zram: ZramStats {
+                total: 4 * 1024 * 1024,
+                original: 1024 * 1024,
+                compressed: 256 * 1024,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
         meter.update(&machine);
+        assert_eq!(meter.stats, machine.zram);
+        assert_eq!(meter.ratio_text(), "4.00:1");
+    }
+
+    #[test]
+    fn test_zram_meter_supported_modes() {
+        let meter = ZramMeter::new();
+        assert!(meter.supports_mode(MeterMode::Bar));
+        assert!(meter.supports_mode(MeterMode::Text));
+        assert!(meter.supports_mode(MeterMode::Graph));
+        assert!(!meter.supports_mode(MeterMode::Led));
     }
 }
@@ -6,14 +6,24 @@
 use anyhow::Result;
 use procfs::{CpuTime, Current, CurrentSI, KernelStats, Meminfo};
 use std::collections::HashMap;
+use std::path::Path;
 use std::sync::Mutex;
 use std::time::Duration;
 
-use crate::core::{CpuData, Machine, Process, ProcessState, ScanFlags};
+use crate::core::{
+    Affinity, CpuData, DiskIoData, GpuEngineData, HugePageData, Machine, NetIoData, Process,
+    ProcessState, ScanFlags,
+};
 
 use super::linux_bg_scanner::{
-    start_linux_bg_scan, LinuxBackgroundScanner, LinuxExpensiveData, LinuxScanParams,
+    start_linux_bg_scan, update_net_rates, LinuxBackgroundScanner, LinuxExpensiveData,
+    LinuxExpensiveDataMap, LinuxScanParams,
 };
+use super::linux_gpu::{busy_percent, DrmUsage};
+use super::linux_hugepages::{read_hugepages, HUGEPAGES_PATH};
+use super::linux_net::{is_virtual_interface, SYS_CLASS_NET_PATH};
+use super::linux_zfs::{read_arcstats, ARCSTATS_PATH};
+use super::linux_zram::{parse_zram_swaps, read_zram, SWAPS_PATH, SYS_BLOCK_PATH};
 
 // IO Priority constants (from linux/ioprio.h)
 pub const IOPRIO_CLASS_NONE: i32 = 0;
@@ -39,6 +49,12 @@ pub fn ioprio_data(ioprio: i32) -> i32 {
     ioprio & IOPRIO_PRIO_MASK
 }
 
+/// Build an ioprio value from a class and a level (IOPRIO_PRIO_VALUE)
+#[inline]
+pub fn ioprio_value(class: i32, data: i32) -> i32 {
+    (class << IOPRIO_CLASS_SHIFT) | data
+}
+
 /// Get IO priority for a process using ioprio_get syscall
 /// Returns the IO priority value, or -1 on error
 pub fn get_io_priority(pid: i32) -> i32 {
@@ -96,6 +112,105 @@ pub fn get_io_priority(pid: i32) -> i32 {
     }
 }
 
+/// Set IO priority for a process using ioprio_set syscall
+/// Returns true on success
+pub fn set_io_priority(pid: i32, ioprio: i32) -> bool {
+    // SYS_ioprio_set is 251 on x86_64, 289 on i386
+    #[cfg(target_arch = "x86_64")]
+    const SYS_IOPRIO_SET: libc::c_long = 251;
+    #[cfg(target_arch = "x86")]
+    const SYS_IOPRIO_SET: libc::c_long = 289;
+    #[cfg(target_arch = "aarch64")]
+    const SYS_IOPRIO_SET: libc::c_long = 30;
+    #[cfg(target_arch = "arm")]
+    const SYS_IOPRIO_SET: libc::c_long = 314;
+
+    #[cfg(any(
+        target_arch = "x86_64",
+        target_arch = "x86",
+        target_arch = "aarch64",
+        target_arch = "arm"
+    ))]
+    {
+        let result = unsafe {
+            libc::syscall(
+                SYS_IOPRIO_SET,
+                IOPRIO_WHO_PROCESS as libc::c_int,
+                pid as libc::c_int,
+                ioprio as libc::c_int,
+            )
+        };
+        result == 0
+    }
+
+    #[cfg(not(any(
+        target_arch = "x86_64",
+        target_arch = "x86",
+        target_arch = "aarch64",
+        target_arch = "arm"
+    )))]
+    {
+        // Unsupported architecture
+        let _ = (pid, ioprio);
+        false
+    }
+}
+
+/// Get the CPU affinity of a process or thread using sched_getaffinity
+/// Returns None if the process is gone or not accessible
+pub fn get_affinity(pid: i32) -> Option<Affinity> {
+    let mut set: libc::cpu_set_t = unsafe { std::mem::zeroed() };
+    let result =
+        unsafe { libc::sched_getaffinity(pid, std::mem::size_of::<libc::cpu_set_t>(), &mut set) };
+    if result != 0 {
+        return None;
+    }
+    Some(
+        (0..libc::CPU_SETSIZE as usize)
+            .filter(|&cpu| unsafe { libc::CPU_ISSET(cpu, &set) })
+            .map(|cpu| cpu as u32)
+            .collect(),
+    )
+}
+
+/// Set the CPU affinity of a process or thread using sched_setaffinity
+/// Returns true on success
+pub fn set_affinity(pid: i32, affinity: &Affinity) -> bool {
+    if affinity.is_empty() {
+        return false;
+    }
+    let mut set: libc::cpu_set_t = unsafe { std::mem::zeroed() };
+    for &cpu in affinity.cpus() {
+        if cpu as usize >= libc::CPU_SETSIZE as usize {
+            return false;
+        }
+        unsafe { libc::CPU_SET(cpu as usize, &mut set) };
+    }
+    let result =
+        unsafe { libc::sched_setaffinity(pid, std::mem::size_of::<libc::cpu_set_t>(), &set) };
+    result == 0
+}
+
+/// Set the soft and hard limit of a resource (RLIMIT_*) of a process with
+/// prlimit(2), None meaning unlimited
+pub fn set_resource_limit(
+    pid: i32,
+    resource: u32,
+    soft: Option<u64>,
+    hard: Option<u64>,
+) -> Result<(), nix::errno::Errno> {
+    let limit = libc::rlimit {
+        rlim_cur: soft.unwrap_or(libc::RLIM_INFINITY),
+        rlim_max: hard.unwrap_or(libc::RLIM_INFINITY),
+    };
+    let result = unsafe { libc::prlimit(pid, resource as _, &limit, std::ptr::null_mut()) };
+    if result == 0 {
+        Ok(())
+    } else {
+        Err(nix::errno::Errno::last())
+    }
+}
+
 /// Previous CPU times for calculating deltas
 static PREV_CPU_TIMES: Mutex<Option<Vec<CpuTime>>> = Mutex::new(None);
 static PREV_TOTAL_CPU: Mutex<Option<CpuTime>> = Mutex::new(None);
@@ -326,6 +441,37 @@ pub fn scan_memory(machine: &mut Machine) {
         .unwrap_or(-1);
     machine.shared_mem = meminfo.shmem.map(|v| (v / 1024) as i64).unwrap_or(-1);
 
+    // The ZFS ARC is accounted as used kernel memory, but the part above
+    // c_min is given back under pressure, so count it as cache (like C htop)
+    machine.zfs = read_arcstats(Path::new(ARCSTATS_PATH));
+    let shrinkable = machine.zfs.shrinkable().min(machine.used_mem);
+    machine.used_mem -= shrinkable;
+    machine.cached_mem += shrinkable;
+
+    // Zram keeps its compressed data in RAM, which the kernel reports as
+    // used memory; show it as compressed memory instead
+    machine.zram = read_zram(Path::new(SYS_BLOCK_PATH));
+    if machine.zram.total > 0 {
+        let compressed = machine.zram.compressed.min(machine.used_mem);
+        machine.used_mem -= compressed;
+        machine.compressed_mem = compressed as i64;
+    } else {
+        machine.compressed_mem = -1;
+    }
+    if let Ok(swaps) = std::fs::read_to_string(SWAPS_PATH) {
+        (machine.zram.swap_total, machine.zram.swap_used) = parse_zram_swaps(&swaps);
+    }
+
+    // /proc/meminfo only knows the default page size, so prefer sysfs
+    machine.huge_pages = read_hugepages(Path::new(HUGEPAGES_PATH));
+    if machine.huge_pages.is_empty() && meminfo.hugepages_total.unwrap_or(0) > 0 {
+        machine.huge_pages.push(HugePageData {
+            size_kb: meminfo.hugepagesize.unwrap_or(0) / 1024,
+            total: meminfo.hugepages_total.unwrap_or(0),
+            free: meminfo.hugepages_free.unwrap_or(0),
+        });
+    }
+
     // Swap
     machine.total_swap = meminfo.swap_total / 1024;
     let swap_free = meminfo.swap_free / 1024;
@@ -399,6 +545,87 @@ fn merge_expensive_data(process: &mut Process, data: &LinuxExpensiveData) {
     if let Some(v) = data.uses_deleted_lib {
         process.uses_deleted_lib = v;
     }
+    if let Some(ref usage) = data.gpu {
+        let gpu_time = usage.total_time();
+        process.gpu_percent = if process.gpu_last_scan_time_ms > 0 {
+            let interval = data
+                .gpu_sampled_ms
+                .saturating_sub(process.gpu_last_scan_time_ms);
+            busy_percent(process.gpu_time, gpu_time, interval) as f32
+        } else {
+            f32::NAN
+        };
+        process.gpu_time = gpu_time;
+        process.gpu_last_scan_time_ms = data.gpu_sampled_ms;
+    }
+    if let Some(v) = data.socket_count {
+        process.socket_count = v;
+    }
+    if let Some((rx, tx)) = data.net_rates {
+        process.net_rx_rate_bps = rx;
+        process.net_tx_rate_bps = tx;
+    }
+}
+
+/// Sum the GPU usage of all processes per engine and compute each engine's
+/// busy percentage since the previous sample (feeds the GPU meter).
+///
+/// The busy time is summed from each process's delta against its own
+/// previous sample, so clients exiting or starting between two samples do
+/// not make the cumulative totals jump.
+fn update_gpu_engines(machine: &mut Machine, results: &LinuxExpensiveDataMap) {
+    let mut totals = DrmUsage::default();
+    let mut deltas = DrmUsage::default();
+    let mut usages = HashMap::new();
+    let mut sampled_ms = 0;
+    let prev_usages = std::mem::take(&mut machine.gpu_prev_usage);
+    for (pid, data) in results.iter() {
+        if let Some(ref usage) = data.gpu {
+            for (engine, ns) in &usage.engines {
+                totals.add(engine, *ns);
+            }
+            // A process without a previous sample has no delta yet
+            if let Some(prev) = prev_usages.get(pid) {
+                for (engine, ns) in &usage.delta_since(prev).engines {
+                    deltas.add(engine, *ns);
+                }
+            }
+            usages.insert(*pid, usage.clone());
+            sampled_ms = sampled_ms.max(data.gpu_sampled_ms);
+        }
+    }
+    // Processes that are gone are dropped with the old samples
+    machine.gpu_prev_usage = usages;
+    if sampled_ms == 0 {
+        return;
+    }
+
+    let interval = if machine.gpu_last_update > 0 {
+        sampled_ms.saturating_sub(machine.gpu_last_update)
+    } else {
+        0
+    };
+    let mut engines: Vec<GpuEngineData> = totals
+        .engines
+        .into_iter()
+        .map(|(name, time_ns)| {
+            let busy_ns = deltas
+                .engines
+                .iter()
+                .find(|(n, _)| *n == name)
+                .map_or(0, |(_, ns)| *ns);
+            GpuEngineData {
+                // Processes are sampled at slightly different times, which
+                // can push the sum over the interval a little
+                percent: busy_percent(0, busy_ns, interval).min(100.0),
+                name,
+                time_ns,
+            }
+        })
+        .collect();
+    engines.sort_by(|a, b| a.name.cmp(&b.name));
+    machine.gpu_engines = engines;
+    machine.gpu_last_update = sampled_ms;
 }
 
 /// Flags that should be handled by background scanner (expensive reads)
@@ -408,7 +635,9 @@ const BG_SCAN_FLAGS: ScanFlags = ScanFlags::from_bits_truncate(
         | ScanFlags::OOM.bits()
         | ScanFlags::SMAPS.bits()
         | ScanFlags::AUTOGROUP.bits()
-        | ScanFlags::SEC_ATTR.bits(),
+        | ScanFlags::SEC_ATTR.bits()
+        | ScanFlags::GPU.bits()
+        | ScanFlags::NET.bits(),
 );
 
 /// Scan all processes
@@ -433,17 +662,27 @@ pub fn scan_processes(machine: &mut Machine) {
     // Merge any completed background scan results into processes
     // This applies data from the previous frame's background scan
     if let Some(ref mut scanner) = machine.bg_scanner {
-        if let Some(bg_results) = scanner.try_take_results() {
+        if let Some(mut bg_results) = scanner.try_take_results() {
             if debug_timing {
                 eprintln!("[SCAN] Merging {} background results", bg_results.len());
             }
+            update_net_rates(&mut bg_results, &mut machine.net_namespace_counters);
             for (pid, data) in bg_results.iter() {
                 if let Some(process) = machine.processes.get_mut(*pid) {
                     merge_expensive_data(process, data);
                 }
             }
+            update_gpu_engines(machine, &bg_results);
         }
     }
+    if !flags.contains(ScanFlags::GPU) {
+        machine.gpu_engines.clear();
+        machine.gpu_last_update = 0;
+        machine.gpu_prev_usage.clear();
+    }
+    if !flags.contains(ScanFlags::NET) {
+        machine.net_namespace_counters.clear();
+    }
 
     // Reset auto-width fields at start of scan (matches C htop Row_resetFieldWidths)
     // This allows widths to shrink back when there are no longer processes with wide values
@@ -658,6 +897,11 @@ pub fn scan_processes(machine: &mut Machine) {
             process.io_priority = get_io_priority(pid);
         }
 
+        // CPU affinity - only read when AFFINITY column is displayed
+        if flags.contains(ScanFlags::AFFINITY) {
+            process.cpu_affinity = get_affinity(pid);
+        }
+
         // NOTE: CGroup reading is now handled by background scanner
         // Reading /proc/PID/cgroup for each process adds latency
 
@@ -832,6 +1076,11 @@ pub fn scan_processes(machine: &mut Machine) {
                     // IO Priority (thread-specific)
                     thread.io_priority = get_io_priority(tid);
 
+                    // CPU affinity (thread-specific)
+                    if flags.contains(ScanFlags::AFFINITY) {
+                        thread.cpu_affinity = get_affinity(tid);
+                    }
+
                     // Track max values
                     if tid > max_pid {
                         max_pid = tid;
@@ -921,6 +1170,22 @@ pub fn scan_processes(machine: &mut Machine) {
     }
 }
 
+/// Check if the pathname of a memory mapping refers to a deleted file
+pub fn is_deleted_mapping(pathname: &str) -> bool {
+    // Skip non-path entries
+    if !pathname.starts_with('/') {
+        return false;
+    }
+
+    // Skip false positives (matches C htop behavior)
+    if pathname.starts_with("/memfd:") || pathname == "/dev/zero (deleted)" {
+        return false;
+    }
+
+    // Check for " (deleted)" suffix
+    pathname.ends_with(" (deleted)")
+}
+
 /// Check if a process uses deleted libraries by scanning /proc/PID/maps
 /// Returns true if any executable memory-mapped file has " (deleted)" suffix
 pub fn check_deleted_libs(pid: i32) -> bool {
@@ -953,23 +1218,7 @@ pub fn check_deleted_libs(pid: i32) -> bool {
         }
 
         // Get the pathname (6th field, may contain spaces)
-        let pathname = parts[5].trim();
-
-        // Skip non-path entries
-        if !pathname.starts_with('/') {
-            continue;
-        }
-
-        // Skip false positives (matches C htop behavior)
-        if pathname.starts_with("/memfd:") {
-            continue;
-        }
-        if pathname == "/dev/zero (deleted)" {
-            continue;
-        }
-
-        // Check for " (deleted)" suffix
-        if pathname.ends_with(" (deleted)") {
+        if is_deleted_mapping(parts[5].trim()) {
             return true;
         }
     }
@@ -1145,6 +1394,13 @@ pub fn scan_disk_io(machine: &mut Machine) {
         Err(_) => return,
     };
 
+    let elapsed_ms = if machine.disk_io_last_update > 0 {
+        now_ms.saturating_sub(machine.disk_io_last_update)
+    } else {
+        0
+    };
+    let prev_disks = std::mem::take(&mut machine.disks);
+
     let mut last_top_disk = String::new();
     let mut read_sum: u64 = 0;
     let mut write_sum: u64 = 0;
@@ -1152,6 +1408,20 @@ pub fn scan_disk_io(machine: &mut Machine) {
     let mut num_disks: u64 = 0;
 
     for stat in stats {
+        // Keep every device (including partitions and virtual devices) so
+        // meters can be configured for a single one, e.g. DiskIO(sda1)
+        let mut disk = DiskIoData {
+            name: stat.name.clone(),
+            read_bytes: stat.sectors_read * 512,
+            write_bytes: stat.sectors_written * 512,
+            ms_time_spend: stat.time_in_progress,
+            ..Default::default()
+        };
+        if let Some(prev) = prev_disks.iter().find(|prev| prev.name == disk.name) {
+            disk.update_rates(prev, elapsed_ms);
+        }
+        machine.disks.push(disk);
+
         let diskname = &stat.name;
 
         // Skip dm-*, loop*, md*, zram* devices (like C htop)
@@ -1183,23 +1453,20 @@ pub fn scan_disk_io(machine: &mut Machine) {
     let total_bytes_written = write_sum * 512;
 
     // Calculate rates if we have previous data
-    if machine.disk_io_last_update > 0 {
-        let elapsed_ms = now_ms.saturating_sub(machine.disk_io_last_update);
-        if elapsed_ms > 0 {
-            // Calculate read/write rates in bytes per second
-            let read_delta = total_bytes_read.saturating_sub(machine.disk_io_read_bytes);
-            let write_delta = total_bytes_written.saturating_sub(machine.disk_io_write_bytes);
-            let time_delta = time_spend_sum.saturating_sub(machine.disk_io_ms_time_spend);
-
-            // Rate = bytes_delta * 1000 / elapsed_ms (to get bytes/second)
-            machine.disk_io_read_rate = (read_delta as f64) * 1000.0 / (elapsed_ms as f64);
-            machine.disk_io_write_rate = (write_delta as f64) * 1000.0 / (elapsed_ms as f64);
-
-            // Utilization = 100 * ms_io_delta / elapsed_ms
-            // But we normalize by number of disks since utilization can exceed 100%
-            // when multiple disks are busy
-            machine.disk_io_utilization = (time_delta as f64) * 100.0 / (elapsed_ms as f64);
-        }
+    if elapsed_ms > 0 {
+        // Calculate read/write rates in bytes per second
+        let read_delta = total_bytes_read.saturating_sub(machine.disk_io_read_bytes);
+        let write_delta = total_bytes_written.saturating_sub(machine.disk_io_write_bytes);
+        let time_delta = time_spend_sum.saturating_sub(machine.disk_io_ms_time_spend);
+
+        // Rate = bytes_delta * 1000 / elapsed_ms (to get bytes/second)
+        machine.disk_io_read_rate = (read_delta as f64) * 1000.0 / (elapsed_ms as f64);
+        machine.disk_io_write_rate = (write_delta as f64) * 1000.0 / (elapsed_ms as f64);
+
+        // Utilization = 100 * ms_io_delta / elapsed_ms
+        // But we normalize by number of disks since utilization can exceed 100%
+        // when multiple disks are busy
+        machine.disk_io_utilization = (time_delta as f64) * 100.0 / (elapsed_ms as f64);
     }
 
     // Store current values for next iteration
@@ -1229,14 +1496,37 @@ pub fn scan_network_io(machine: &mut Machine) {
         Err(_) => return,
     };
 
+    let elapsed_ms = if machine.net_io_last_update > 0 {
+        now_ms.saturating_sub(machine.net_io_last_update)
+    } else {
+        0
+    };
+    let prev_interfaces = std::mem::take(&mut machine.net_interfaces);
+
     let mut bytes_received: u64 = 0;
     let mut packets_received: u64 = 0;
     let mut bytes_transmitted: u64 = 0;
     let mut packets_transmitted: u64 = 0;
 
     for (interface, status) in dev_stats {
-        // Skip loopback interface (like C htop)
-        if interface == "lo" {
+        let mut net = NetIoData {
+            is_virtual: is_virtual_interface(Path::new(SYS_CLASS_NET_PATH), &interface),
+            name: interface,
+            bytes_received: status.recv_bytes,
+            bytes_transmitted: status.sent_bytes,
+            packets_received: status.recv_packets,
+            packets_transmitted: status.sent_packets,
+            ..Default::default()
+        };
+        if let Some(prev) = prev_interfaces.iter().find(|prev| prev.name == net.name) {
+            net.update_rates(prev, elapsed_ms);
+        }
+        let is_virtual = net.is_virtual;
+        machine.net_interfaces.push(net);
+
+        // Skip loopback (like C htop) and interfaces whose traffic is
+        // already counted on a physical interface
+        if is_virtual {
             continue;
         }
 
@@ -1246,27 +1536,26 @@ pub fn scan_network_io(machine: &mut Machine) {
         packets_transmitted += status.sent_packets;
     }
 
+    machine.net_interfaces.sort_by(|a, b| a.name.cmp(&b.name));
+
     // Calculate rates if we have previous data
-    if machine.net_io_last_update > 0 {
-        let elapsed_ms = now_ms.saturating_sub(machine.net_io_last_update);
-        if elapsed_ms > 0 {
-            // Calculate byte rates in bytes per second
-            let rx_bytes_delta = bytes_received.saturating_sub(machine.net_io_bytes_received);
-            let tx_bytes_delta = bytes_transmitted.saturating_sub(machine.net_io_bytes_transmitted);
-
-            // Calculate packet rates in packets per second
-            let rx_packets_delta = packets_received.saturating_sub(machine.net_io_packets_received);
-            let tx_packets_delta =
-                packets_transmitted.saturating_sub(machine.net_io_packets_transmitted);
-
-            // Rate = delta * 1000 / elapsed_ms (to get per-second rate)
-            machine.net_io_receive_rate = (rx_bytes_delta as f64) * 1000.0 / (elapsed_ms as f64);
-            machine.net_io_transmit_rate = (tx_bytes_delta as f64) * 1000.0 / (elapsed_ms as f64);
-            machine.net_io_receive_packets =
-                ((rx_packets_delta as f64) * 1000.0 / (elapsed_ms as f64)) as u64;
-            machine.net_io_transmit_packets =
-                ((tx_packets_delta as f64) * 1000.0 / (elapsed_ms as f64)) as u64;
-        }
+    if elapsed_ms > 0 {
+        // Calculate byte rates in bytes per second
+        let rx_bytes_delta = bytes_received.saturating_sub(machine.net_io_bytes_received);
+        let tx_bytes_delta = bytes_transmitted.saturating_sub(machine.net_io_bytes_transmitted);
+
+        // Calculate packet rates in packets per second
+        let rx_packets_delta = packets_received.saturating_sub(machine.net_io_packets_received);
+        let tx_packets_delta =
+            packets_transmitted.saturating_sub(machine.net_io_packets_transmitted);
+
+        // Rate = delta * 1000 / elapsed_ms (to get per-second rate)
+        machine.net_io_receive_rate = (rx_bytes_delta as f64) * 1000.0 / (elapsed_ms as f64);
+        machine.net_io_transmit_rate = (tx_bytes_delta as f64) * 1000.0 / (elapsed_ms as f64);
+        machine.net_io_receive_packets =
+            ((rx_packets_delta as f64) * 1000.0 / (elapsed_ms as f64)) as u64;
+        machine.net_io_transmit_packets =
+            ((tx_packets_delta as f64) * 1000.0 / (elapsed_ms as f64)) as u64;
     }
 
     // Store current values for next iteration
@@ -1635,6 +1924,43 @@ pub fn filter_container(cgroup: &str) -> Option<String> {
         }
     }
 
+    // containerd (CRI plugin or nerdctl) with systemd cgroup driver
+    // Pattern: cri-containerd-<id>.scope or nerdctl-<id>.scope
+    for prefix in ["cri-containerd-", "nerdctl-"] {
+        if let Some(start) = cgroup.find(prefix) {
+            let rest = &cgroup[start + prefix.len()..];
+            if let Some(end) = rest.find('.') {
+                let id = &rest[..end];
+                let short_id = if id.len() > 12 { &id[..12] } else { id };
+                return Some(format!("containerd:{}", short_id));
+            }
+        }
+    }
+
+    // CRI-O with systemd cgroup driver
+    // Pattern: crio-<id>.scope (crio-conmon-<id>.scope is the container monitor)
+    if cgroup.contains("/crio-") && !cgroup.contains("/crio-conmon-") {
+        if let Some(start) = cgroup.find("/crio-") {
+            let rest = &cgroup[start + 6..];
+            if let Some(end) = rest.find('.') {
+                let id = &rest[..end];
+                let short_id = if id.len() > 12 { &id[..12] } else { id };
+                return Some(format!("crio:{}", short_id));
+            }
+        }
+    }
+
+    // Kubernetes with cgroupfs driver, the runtime is not part of the path
+    // Pattern: /kubepods/<qos>/pod<uid>/<id>
+    if cgroup.contains("/kubepods/") {
+        if let Some((pod, id)) = cgroup.rsplit_once('/') {
+            if pod.rsplit('/').next().is_some_and(|p| p.starts_with("pod")) && !id.is_empty() {
+                let short_id = if id.len() > 12 { &id[..12] } else { id };
+                return Some(format!("k8s:{}", short_id));
+            }
+        }
+    }
+
     // Podman container detection
     if cgroup.contains("/libpod-") {
         if let Some(start) = cgroup.find("/libpod-") {
@@ -1675,3 +2001,101 @@ pub fn filter_container(cgroup: &str) -> Option<String> {
 
     None
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONTAINER_ID: &str = "4b1c8f0e9d2a7c3b5e6f8a9d0c1b2e3f4a5b6c7d8e9f0a1b2c3d4e5f6a7b8c9d";
+    const POD_UID: &str = "5c9a7d2e-3b1f-4e8a-9c6d-0f1e2a3b4c5d";
+
+    #[test]
+    fn test_filter_container() {
+        let id = CONTAINER_ID;
+        let uid = POD_UID;
+        let systemd_uid = uid.replace('-', "_");
+        let burstable_pod = format!(
+            "/kubepods.slice/kubepods-burstable.slice/kubepods-burstable-pod{}.slice",
+            systemd_uid
+        );
+        let besteffort_pod = format!(
+            "/kubepods.slice/kubepods-besteffort.slice/kubepods-besteffort-pod{}.slice",
+            systemd_uid
+        );
+        let cases: Vec<(String, Option<&str>)> = vec![
+            // Docker, cgroupfs and systemd drivers
+            (format!("/docker/{}", id), Some("docker:4b1c8f0e9d2a")),
+            (
+                format!("/system.slice/docker-{}.scope", id),
+                Some("docker:4b1c8f0e9d2a"),
+            ),
+            // containerd, systemd driver
+            (
+                format!("{}/cri-containerd-{}.scope", burstable_pod, id),
+                Some("containerd:4b1c8f0e9d2a"),
+            ),
+            (
+                format!("/system.slice/nerdctl-{}.scope", id),
+                Some("containerd:4b1c8f0e9d2a"),
+            ),
+            // CRI-O, systemd driver; the conmon scope is not the container
+            (
+                format!("{}/crio-{}.scope", besteffort_pod, id),
+                Some("crio:4b1c8f0e9d2a"),
+            ),
+            (format!("{}/crio-conmon-{}.scope", besteffort_pod, id), None),
+            // Kubernetes, cgroupfs driver
+            (
+                format!("/kubepods/burstable/pod{}/{}", uid, id),
+                Some("k8s:4b1c8f0e9d2a"),
+            ),
+            (
+                format!("/kubepods/besteffort/pod{}/{}", uid, id),
+                Some("k8s:4b1c8f0e9d2a"),
+            ),
+            // Guaranteed QoS pods have no QoS segment
+            (
+                format!("/kubepods/pod{}/{}", uid, id),
+                Some("k8s:4b1c8f0e9d2a"),
+            ),
+            // Pod and QoS level cgroups hold no container
+            (format!("/kubepods/burstable/pod{}", uid), None),
+            (format!("/kubepods/pod{}", uid), None),
+            ("/kubepods/burstable".to_string(), None),
+            (burstable_pod.clone(), None),
+            // Podman, LXC and systemd-nspawn
+            (
+                format!(
+                    "/user.slice/user-1000.slice/user@1000.service/user.slice/libpod-{}.scope",
+                    id
+                ),
+                Some("podman:4b1c8f0e9d2a"),
+            ),
+            ("/lxc/web01".to_string(), Some("lxc:web01")),
+            (
+                "/lxc.payload.web01/init.scope".to_string(),
+                Some("lxc:web01"),
+            ),
+            (
+                "/machine.slice/machine-fedora.scope".to_string(),
+                Some("nspawn:fedora"),
+            ),
+            // Regular services and sessions
+            ("/system.slice/sshd.service".to_string(), None),
+            (
+                "/user.slice/user-1000.slice/session-2.scope".to_string(),
+                None,
+            ),
+            ("/".to_string(), None),
+        ];
+
+        for (cgroup, expected) in cases {
+            assert_eq!(
+                filter_container(&cgroup).as_deref(),
+                expected,
+                "cgroup {}",
+                cgroup
+            );
+        }
+    }
+}
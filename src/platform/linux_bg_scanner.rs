@@ -4,11 +4,19 @@
 //! using the generic BackgroundScanner framework and rayon's thread pool.
 //! Results are collected in the background and merged on the next scan cycle.
 
+use procfs::ProcessCGroup;
 use rayon::prelude::*;
 use std::collections::HashMap;
+use std::path::Path;
 
 use super::bg_scanner::BackgroundScanner;
 use super::linux::{check_deleted_libs, filter_cgroup_name, filter_container};
+use super::linux_container::container_display_name;
+use super::linux_gpu::{read_drm_usage, DrmUsage};
+use super::linux_net::{is_virtual_interface, SYS_CLASS_NET_PATH};
+use super::linux_sockets::{
+    byte_rate, parse_netns_link, read_process_sockets, NetNamespace, ProcessSockets,
+};
 use crate::core::ScanFlags;
 
 /// Data collected from expensive /proc reads for a single process
@@ -28,11 +36,11 @@ pub struct LinuxExpensiveData {
     pub container_short: Option<String>,
     /// OOM score
     pub oom_score: Option<i32>,
-    /// PSS memory (from smaps_rollup)
+    /// PSS memory in KB (from smaps_rollup)
     pub m_pss: Option<i64>,
-    /// Swap memory (from smaps_rollup)
+    /// Swap memory in KB (from smaps_rollup)
     pub m_swap: Option<i64>,
-    /// SwapPss memory (from smaps_rollup)
+    /// SwapPss memory in KB (from smaps_rollup)
     pub m_psswp: Option<i64>,
     /// Autogroup ID
     pub autogroup_id: Option<i64>,
@@ -42,6 +50,19 @@ pub struct LinuxExpensiveData {
     pub secattr: Option<String>,
     /// Uses deleted library
     pub uses_deleted_lib: Option<bool>,
+    /// GPU engine busy times (from DRM fdinfo)
+    pub gpu: Option<DrmUsage>,
+    /// Time the GPU usage was sampled, in ms since the epoch
+    pub gpu_sampled_ms: u64,
+    /// Network namespace and socket inodes (from /proc/PID/fd)
+    pub sockets: Option<ProcessSockets>,
+    /// Number of TCP, UDP and Unix sockets
+    pub socket_count: Option<i64>,
+    /// Traffic of the network namespace and the process's share of it
+    pub net_traffic: Option<NetTraffic>,
+    /// Estimated network receive and transmit rates in bytes per second,
+    /// filled in from `net_traffic` by `update_net_rates()`
+    pub net_rates: Option<(f64, f64)>,
 }
 
 impl LinuxExpensiveData {
@@ -54,6 +75,8 @@ impl LinuxExpensiveData {
             || self.autogroup_id.is_some()
             || self.secattr.is_some()
             || self.uses_deleted_lib.is_some()
+            || self.gpu.is_some()
+            || self.sockets.is_some()
     }
 }
 
@@ -91,7 +114,8 @@ fn collect_expensive_data(
     should_check_deleted_libs: bool,
     page_size: i64,
 ) -> LinuxExpensiveDataMap {
-    pids.par_iter()
+    let mut results: LinuxExpensiveDataMap = pids
+        .par_iter()
         .filter_map(|&pid| {
             let data = collect_for_pid(pid, flags, should_check_deleted_libs, page_size);
             // Only include if we got some data
@@ -101,7 +125,129 @@ fn collect_expensive_data(
                 None
             }
         })
-        .collect()
+        .collect();
+
+    if flags.contains(ScanFlags::NET) {
+        attribute_net_traffic(&mut results);
+    }
+    results
+}
+
+/// Traffic counters of a network namespace at one scan
+#[derive(Clone, Debug, Default)]
+pub struct NetCounters {
+    pub rx_bytes: u64,
+    pub tx_bytes: u64,
+    /// Sample time in ms since the epoch
+    pub sampled_ms: u64,
+}
+
+/// Traffic of the network namespace of a process
+#[derive(Clone, Debug)]
+pub struct NetTraffic {
+    pub netns: u64,
+    pub counters: NetCounters,
+    /// Share of the namespace traffic attributed to the process
+    pub share: f64,
+}
+
+/// Count the sockets of every process and record the traffic counters of
+/// its network namespace with the process's share of them, by its number
+/// of active internet sockets (see `linux_sockets`)
+fn attribute_net_traffic(results: &mut LinuxExpensiveDataMap) {
+    let own_netns = std::fs::read_link("/proc/self/ns/net")
+        .ok()
+        .and_then(|target| parse_netns_link(target.to_str()?));
+
+    // The tables of a namespace are read through any process inside it
+    let mut readers: HashMap<u64, i32> = HashMap::new();
+    for (&pid, data) in results.iter() {
+        if let Some(ref sockets) = data.sockets {
+            readers.entry(sockets.netns).or_insert(pid);
+        }
+    }
+    let sampled_ms = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0);
+    let namespaces: HashMap<u64, NetNamespace> = readers
+        .into_iter()
+        .filter_map(|(netns, pid)| {
+            let net_dir = format!("/proc/{}/net", pid);
+            let namespace = if Some(netns) == own_netns {
+                // Container traffic on bridges and veth pairs is counted
+                // in the containers' own namespaces
+                NetNamespace::read(Path::new(&net_dir), |name| {
+                    is_virtual_interface(Path::new(SYS_CLASS_NET_PATH), name)
+                })
+            } else {
+                NetNamespace::read(Path::new(&net_dir), |name| name == "lo")
+            };
+            Some((netns, namespace?))
+        })
+        .collect();
+
+    // Active sockets per process and per namespace
+    let mut active_sockets: HashMap<i32, (u64, u32)> = HashMap::new();
+    let mut namespace_active: HashMap<u64, u32> = HashMap::new();
+    for (&pid, data) in results.iter_mut() {
+        let Some(ref sockets) = data.sockets else {
+            continue;
+        };
+        // The reading process may have exited in the meantime
+        let Some(namespace) = namespaces.get(&sockets.netns) else {
+            continue;
+        };
+        let (count, active) = namespace.count(&sockets.inodes);
+        data.socket_count = Some(count as i64);
+        active_sockets.insert(pid, (sockets.netns, active));
+        *namespace_active.entry(sockets.netns).or_default() += active;
+    }
+
+    for (pid, (netns, active)) in active_sockets {
+        let total = namespace_active[&netns];
+        let share = if total > 0 {
+            active as f64 / total as f64
+        } else {
+            0.0
+        };
+        let namespace = &namespaces[&netns];
+        if let Some(data) = results.get_mut(&pid) {
+            data.net_traffic = Some(NetTraffic {
+                netns,
+                counters: NetCounters {
+                    rx_bytes: namespace.rx_bytes,
+                    tx_bytes: namespace.tx_bytes,
+                    sampled_ms,
+                },
+                share,
+            });
+        }
+    }
+}
+
+/// Estimate the network rates of every process from the traffic of its
+/// namespace since the previous scan. `previous` holds the namespace
+/// counters of the previous scan and is replaced with the current ones,
+/// which drops the namespaces that are no longer in use.
+pub fn update_net_rates(
+    results: &mut LinuxExpensiveDataMap,
+    previous: &mut HashMap<u64, NetCounters>,
+) {
+    let mut current = HashMap::new();
+    for data in results.values_mut() {
+        let Some(ref traffic) = data.net_traffic else {
+            continue;
+        };
+        let counters = &traffic.counters;
+        let prev = previous.get(&traffic.netns);
+        let interval = prev.map_or(0, |p| counters.sampled_ms.saturating_sub(p.sampled_ms));
+        let rx = byte_rate(prev.map(|p| p.rx_bytes), counters.rx_bytes, interval);
+        let tx = byte_rate(prev.map(|p| p.tx_bytes), counters.tx_bytes, interval);
+        data.net_rates = Some((rx * traffic.share, tx * traffic.share));
+        current.insert(traffic.netns, counters.clone());
+    }
+    *previous = current;
 }
 
 /// Collect expensive data for a single PID
@@ -130,10 +276,10 @@ fn collect_for_pid(
     // CGROUP
     if flags.contains(ScanFlags::CGROUP) {
         if let Ok(cgroups) = proc.cgroups() {
-            if let Some(cgroup) = cgroups.0.first() {
+            if let Some(cgroup) = process_cgroup(&cgroups.0) {
                 let cgroup_path = cgroup.pathname.clone();
                 data.cgroup_short = Some(filter_cgroup_name(&cgroup_path));
-                data.container_short = filter_container(&cgroup_path);
+                data.container_short = filter_container(&cgroup_path).map(container_display_name);
                 data.cgroup = Some(cgroup_path);
             }
         }
@@ -151,21 +297,7 @@ fn collect_for_pid(
     // SMAPS (PSS, Swap, SwapPss)
     if flags.contains(ScanFlags::SMAPS) {
         if let Ok(content) = std::fs::read_to_string(format!("/proc/{}/smaps_rollup", pid)) {
-            for line in content.lines() {
-                if let Some(rest) = line.strip_prefix("Pss:") {
-                    if let Some(kb) = parse_kb_value(rest) {
-                        data.m_pss = Some(kb * 1024);
-                    }
-                } else if let Some(rest) = line.strip_prefix("Swap:") {
-                    if let Some(kb) = parse_kb_value(rest) {
-                        data.m_swap = Some(kb * 1024);
-                    }
-                } else if let Some(rest) = line.strip_prefix("SwapPss:") {
-                    if let Some(kb) = parse_kb_value(rest) {
-                        data.m_psswp = Some(kb * 1024);
-                    }
-                }
-            }
+            parse_smaps_rollup(&content, &mut data);
         }
     }
 
@@ -199,6 +331,20 @@ fn collect_for_pid(
         }
     }
 
+    // GPU usage (reads every /proc/PID/fdinfo entry)
+    if flags.contains(ScanFlags::GPU) {
+        data.gpu = read_drm_usage(std::path::Path::new(&format!("/proc/{}/fdinfo", pid)));
+        data.gpu_sampled_ms = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_millis() as u64)
+            .unwrap_or(0);
+    }
+
+    // Sockets (reads every /proc/PID/fd link)
+    if flags.contains(ScanFlags::NET) {
+        data.sockets = read_process_sockets(Path::new(&format!("/proc/{}", pid)));
+    }
+
     // Deleted library check (expensive - reads /proc/PID/maps)
     if should_check_deleted_libs {
         data.uses_deleted_lib = Some(check_deleted_libs(pid));
@@ -208,6 +354,16 @@ fn collect_for_pid(
 }
 
 /// Parse a value like "  12345 kB" -> Some(12345)
+/// The cgroup of a process. On hybrid v1/v2 hosts the first line of
+/// /proc/PID/cgroup belongs to a v1 controller hierarchy, so the unified
+/// ("0::") entry is used when there is one.
+fn process_cgroup(cgroups: &[ProcessCGroup]) -> Option<&ProcessCGroup> {
+    cgroups
+        .iter()
+        .find(|cgroup| cgroup.hierarchy == 0)
+        .or_else(|| cgroups.first())
+}
+
 fn parse_kb_value(s: &str) -> Option<i64> {
     s.trim()
         .strip_suffix("kB")
@@ -215,9 +371,31 @@ fn parse_kb_value(s: &str) -> Option<i64> {
         .and_then(|v| v.trim().parse().ok())
 }
 
+/// Parse PSS, Swap and SwapPss from smaps_rollup. The values are kept in
+/// KB, like the other memory fields of a process.
+fn parse_smaps_rollup(content: &str, data: &mut LinuxExpensiveData) {
+    for line in content.lines() {
+        if let Some(rest) = line.strip_prefix("Pss:") {
+            if let Some(kb) = parse_kb_value(rest) {
+                data.m_pss = Some(kb);
+            }
+        } else if let Some(rest) = line.strip_prefix("Swap:") {
+            if let Some(kb) = parse_kb_value(rest) {
+                data.m_swap = Some(kb);
+            }
+        } else if let Some(rest) = line.strip_prefix("SwapPss:") {
+            if let Some(kb) = parse_kb_value(rest) {
+                data.m_psswp = Some(kb);
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use procfs::FromBufRead;
+    use procfs::ProcessCGroups;
 
     #[test]
     fn test_parse_kb_value() {
@@ -225,4 +403,89 @@ mod tests {
         assert_eq!(parse_kb_value("0 kB"), Some(0));
         assert_eq!(parse_kb_value("invalid"), None);
     }
+
+    #[test]
+    fn test_parse_smaps_rollup_keeps_kb() {
+        let content = "\
+55a4c0e2d000-7ffd3a1f6000 ---p 00000000 00:00 0                          [rollup]
+Rss:               12288 kB
+Pss:                4104 kB
+Pss_Anon:           2048 kB
+Swap:                512 kB
+SwapPss:             256 kB
+";
+        let mut data = LinuxExpensiveData::default();
+        parse_smaps_rollup(content, &mut data);
+        // Not scaled to bytes: the PSS/SWAP/SWAPPSS columns expect KB
+        assert_eq!(data.m_pss, Some(4104));
+        assert_eq!(data.m_swap, Some(512));
+        assert_eq!(data.m_psswp, Some(256));
+    }
+
+    #[test]
+    fn test_process_cgroup() {
+        let parse = |content: &str| ProcessCGroups::from_buf_read(content.as_bytes()).unwrap().0;
+
+        // Unified hierarchy only
+        let cgroups = parse("0::/system.slice/sshd.service\n");
+        let cgroup = process_cgroup(&cgroups).unwrap();
+        assert_eq!(cgroup.pathname, "/system.slice/sshd.service");
+
+        // Hybrid host, the v1 controllers come first
+        let cgroups = parse(
+            "12:freezer:/\n\
+             11:cpu,cpuacct:/\n\
+             1:name=systemd:/system.slice/docker-abc.scope\n\
+             0::/system.slice/docker-abc.scope\n",
+        );
+        let cgroup = process_cgroup(&cgroups).unwrap();
+        assert_eq!(cgroup.pathname, "/system.slice/docker-abc.scope");
+
+        // Legacy v1 host without a unified entry
+        let cgroups = parse("12:freezer:/lxc/web01\n11:cpu,cpuacct:/lxc/web01\n");
+        assert_eq!(process_cgroup(&cgroups).unwrap().pathname, "/lxc/web01");
+
+        assert!(process_cgroup(&[]).is_none());
+    }
+
+    #[test]
+    fn test_update_net_rates() {
+        let traffic = |netns, rx_bytes, tx_bytes, sampled_ms, share| LinuxExpensiveData {
+            net_traffic: Some(NetTraffic {
+                netns,
+                counters: NetCounters {
+                    rx_bytes,
+                    tx_bytes,
+                    sampled_ms,
+                },
+                share,
+            }),
+            ..Default::default()
+        };
+        let mut previous = HashMap::new();
+
+        // No rates without a previous sample
+        let mut results = HashMap::from([(1, traffic(10, 1000, 500, 1000, 1.0))]);
+        update_net_rates(&mut results, &mut previous);
+        let (rx, tx) = results[&1].net_rates.unwrap();
+        assert!(rx.is_nan() && tx.is_nan());
+
+        // The namespace rates are shared among its processes
+        let mut results = HashMap::from([
+            (1, traffic(10, 3000, 1500, 3000, 0.75)),
+            (2, traffic(10, 3000, 1500, 3000, 0.25)),
+            (3, traffic(20, 100, 100, 3000, 1.0)),
+        ]);
+        update_net_rates(&mut results, &mut previous);
+        assert_eq!(results[&1].net_rates, Some((750.0, 375.0)));
+        assert_eq!(results[&2].net_rates, Some((250.0, 125.0)));
+        assert!(results[&3].net_rates.unwrap().0.is_nan());
+        assert_eq!(previous.len(), 2);
+
+        // Namespaces without processes are dropped
+        let mut results = HashMap::from([(3, traffic(20, 300, 100, 5000, 1.0))]);
+        update_net_rates(&mut results, &mut previous);
+        assert_eq!(results[&3].net_rates, Some((100.0, 0.0)));
+        assert_eq!(previous.keys().collect::<Vec<_>>(), vec![&20]);
+    }
 }
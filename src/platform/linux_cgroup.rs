@@ -0,0 +1,105 @@
+//! Cgroup v2 accounting from the unified hierarchy
+//!
+//! Every cgroup is a directory below `/sys/fs/cgroup` with the kernel's own
+//! accounting of all its tasks, including ones that already exited:
+//!
+//! - `memory.current`: memory charged to the cgroup (page cache included)
+//! - `memory.max`: the memory limit, "max" when unlimited
+//! - `cpu.stat`: "key value" lines with `usage_usec` and `nr_throttled`
+//!
+//! The root cgroup has no memory files, and controllers that are not
+//! enabled for a subtree leave their files out.
+
+use std::fs;
+use std::path::Path;
+
+use super::linux_sysfs::read_trimmed;
+use crate::core::CgroupStats;
+
+/// Mount point of the cgroup v2 hierarchy
+pub const CGROUP_PATH: &str = "/sys/fs/cgroup";
+
+/// Parse a memory limit ("max" means unlimited)
+fn parse_memory_max(value: &str) -> Option<u64> {
+    if value == "max" {
+        Some(u64::MAX)
+    } else {
+        value.parse().ok()
+    }
+}
+
+/// Get a value from the contents of cpu.stat
+fn parse_cpu_stat(content: &str, key: &str) -> Option<u64> {
+    content.lines().find_map(|line| {
+        let (name, value) = line.split_once(' ')?;
+        if name == key {
+            value.trim().parse().ok()
+        } else {
+            None
+        }
+    })
+}
+
+/// Read the accounting of the cgroup with the given path (as found in
+/// /proc/PID/cgroup) below the hierarchy mounted at `root`
+pub fn read_cgroup_stats(root: &Path, cgroup: &str) -> CgroupStats {
+    let dir = root.join(cgroup.trim_start_matches('/'));
+    let cpu_stat = fs::read_to_string(dir.join("cpu.stat")).ok();
+    CgroupStats {
+        memory_current: read_trimmed(&dir.join("memory.current")).and_then(|v| v.parse().ok()),
+        memory_max: read_trimmed(&dir.join("memory.max")).and_then(|v| parse_memory_max(&v)),
+        cpu_usage_usec: cpu_stat
+            .as_deref()
+            .and_then(|s| parse_cpu_stat(s, "usage_usec")),
+        nr_throttled: cpu_stat
+            .as_deref()
+            .and_then(|s| parse_cpu_stat(s, "nr_throttled")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::platform::testdata;
+
+    #[test]
+    fn test_read_cgroup_stats() {
+        let stats = read_cgroup_stats(&testdata("cgroup"), "/system.slice/nginx.service");
+        assert_eq!(
+            stats,
+            CgroupStats {
+                memory_current: Some(52_428_800),
+                memory_max: Some(268_435_456),
+                cpu_usage_usec: Some(123_456_789),
+                nr_throttled: Some(17),
+            }
+        );
+    }
+
+    #[test]
+    fn test_read_cgroup_stats_unlimited() {
+        let stats = read_cgroup_stats(&testdata("cgroup"), "/user.slice");
+        assert_eq!(stats.memory_current, Some(1_073_741_824));
+        assert_eq!(stats.memory_max, Some(u64::MAX));
+        // cpu controller not enabled for this subtree
+        assert_eq!(stats.cpu_usage_usec, None);
+        assert_eq!(stats.nr_throttled, None);
+    }
+
+    #[test]
+    fn test_read_cgroup_stats_root() {
+        // The root cgroup only has cpu.stat, without throttling counters
+        let stats = read_cgroup_stats(&testdata("cgroup"), "/");
+        assert_eq!(
+            stats,
+            CgroupStats {
+                cpu_usage_usec: Some(987_654_321_000),
+                ..Default::default()
+            }
+        );
+        assert_eq!(
+            read_cgroup_stats(&testdata("cgroup"), "/missing.slice"),
+            CgroupStats::default()
+        );
+    }
+}
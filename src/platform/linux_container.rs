@@ -0,0 +1,354 @@
+//! Container names from the state files of local container runtimes
+//!
+//! `filter_container()` only knows the runtime and the (short) container ID
+//! from the cgroup path. The runtimes keep the human-readable name in their
+//! state directories:
+//!
+//! - Docker: `Name` in `/var/lib/docker/containers/<id>/config.v2.json`
+//! - Podman: `names` of the container in
+//!   `/var/lib/containers/storage/overlay-containers/containers.json`
+//! - containerd: annotations of the OCI spec in
+//!   `/run/containerd/io.containerd.runtime.v2.task/<namespace>/<id>/config.json`
+//! - CRI-O: annotations of the OCI spec in
+//!   `/run/containers/storage/overlay-containers/<id>/userdata/config.json`
+//!
+//! Kubernetes containers are named "namespace/pod/container". The state
+//! directories are only readable by root, without access the ID is kept.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+const DOCKER_CONTAINERS: &str = "var/lib/docker/containers";
+const PODMAN_CONTAINERS_JSON: &str =
+    "var/lib/containers/storage/overlay-containers/containers.json";
+const CONTAINERD_TASKS: &str = "run/containerd/io.containerd.runtime.v2.task";
+const CRIO_CONTAINERS: &str = "run/containers/storage/overlay-containers";
+
+/// How long a container whose name could not be resolved is not looked up
+/// again (its state files may not have been written yet)
+const RETRY_INTERVAL: Duration = Duration::from_secs(10);
+/// Cached names not used for this long belong to containers that are gone
+const EXPIRE_AFTER: Duration = Duration::from_secs(60);
+
+/// Resolved container names
+static CONTAINER_NAMES: Mutex<Option<ContainerNameCache>> = Mutex::new(None);
+
+/// A cached lookup of one container
+struct CachedName {
+    /// None if the name could not be resolved
+    name: Option<String>,
+    resolved: Instant,
+    used: Instant,
+}
+
+/// Container names by container ("runtime:id"). Names are kept while the
+/// container is seen, failed lookups are retried after `RETRY_INTERVAL`.
+#[derive(Default)]
+struct ContainerNameCache {
+    entries: HashMap<String, CachedName>,
+    last_prune: Option<Instant>,
+}
+
+impl ContainerNameCache {
+    /// Get the name of a container, resolving it if it is not cached or
+    /// the last attempt failed a while ago
+    fn lookup(
+        &mut self,
+        container: &str,
+        now: Instant,
+        resolve: impl FnOnce(&str) -> Option<String>,
+    ) -> Option<String> {
+        self.prune(now);
+        let stale = self.entries.get(container).is_none_or(|entry| {
+            entry.name.is_none() && now.duration_since(entry.resolved) >= RETRY_INTERVAL
+        });
+        if stale {
+            let entry = CachedName {
+                name: resolve(container),
+                resolved: now,
+                used: now,
+            };
+            self.entries.insert(container.to_string(), entry);
+        }
+        let entry = self.entries.get_mut(container)?;
+        entry.used = now;
+        entry.name.clone()
+    }
+
+    /// Drop the containers that have not been looked up for a while
+    fn prune(&mut self, now: Instant) {
+        if self
+            .last_prune
+            .is_some_and(|last| now.duration_since(last) < EXPIRE_AFTER)
+        {
+            return;
+        }
+        self.entries
+            .retain(|_, entry| now.duration_since(entry.used) < EXPIRE_AFTER);
+        self.last_prune = Some(now);
+    }
+}
+
+/// Read a JSON string literal starting at the opening quote of `text`
+fn json_string_literal(text: &str) -> Option<String> {
+    let mut chars = text.strip_prefix('"')?.chars();
+    let mut value = String::new();
+    loop {
+        match chars.next()? {
+            '"' => return Some(value),
+            '\\' => match chars.next()? {
+                'n' => value.push('\n'),
+                't' => value.push('\t'),
+                c => value.push(c),
+            },
+            c => value.push(c),
+        }
+    }
+}
+
+/// Find the string value of the first `"key": "value"` pair. The state
+/// files are written by the runtimes themselves, so a key lookup is
+/// enough and no full JSON parser is needed.
+fn json_string(content: &str, key: &str) -> Option<String> {
+    let pattern = format!("\"{}\"", key);
+    let mut rest = content;
+    while let Some(pos) = rest.find(&pattern) {
+        rest = &rest[pos + pattern.len()..];
+        if let Some(value) = rest.trim_start().strip_prefix(':') {
+            return json_string_literal(value.trim_start());
+        }
+    }
+    None
+}
+
+/// Entry of `dir` whose name starts with the (short) container ID
+fn find_by_id(dir: &Path, id: &str) -> Option<PathBuf> {
+    fs::read_dir(dir)
+        .ok()?
+        .flatten()
+        .find(|entry| {
+            entry
+                .file_name()
+                .to_str()
+                .is_some_and(|n| n.starts_with(id))
+        })
+        .map(|entry| entry.path())
+}
+
+fn docker_name(root: &Path, id: &str) -> Option<String> {
+    let dir = find_by_id(&root.join(DOCKER_CONTAINERS), id)?;
+    let config = fs::read_to_string(dir.join("config.v2.json")).ok()?;
+    Some(
+        json_string(&config, "Name")?
+            .trim_start_matches('/')
+            .to_string(),
+    )
+}
+
+fn podman_name(root: &Path, id: &str) -> Option<String> {
+    let containers = fs::read_to_string(root.join(PODMAN_CONTAINERS_JSON)).ok()?;
+    let start = containers.find(&format!("\"id\":\"{}", id))?;
+    // The names list follows the ID within the same container object
+    let object = &containers[start..];
+    let object = &object[..object.find('}').unwrap_or(object.len())];
+    let names = object.find("\"names\"")?;
+    let list = object[names..].split_once('[')?.1.trim_start();
+    json_string_literal(list)
+}
+
+/// Name from the annotations of an OCI runtime spec written for a
+/// Kubernetes pod (by containerd's CRI plugin or CRI-O) or by nerdctl
+fn oci_spec_name(config: &str) -> Option<String> {
+    let kubernetes = json_string(config, "io.kubernetes.cri.sandbox-namespace")
+        .zip(json_string(config, "io.kubernetes.cri.sandbox-name"))
+        .map(|(namespace, pod)| {
+            (
+                namespace,
+                pod,
+                json_string(config, "io.kubernetes.cri.container-name"),
+            )
+        })
+        .or_else(|| {
+            let namespace = json_string(config, "io.kubernetes.pod.namespace")?;
+            let pod = json_string(config, "io.kubernetes.pod.name")?;
+            // CRI-O names the pause container of the pod "POD"
+            let container =
+                json_string(config, "io.kubernetes.container.name").filter(|c| c != "POD");
+            Some((namespace, pod, container))
+        });
+
+    match kubernetes {
+        // The sandbox (pause) container stands for the pod itself
+        Some((namespace, pod, None)) => Some(format!("{}/{}", namespace, pod)),
+        Some((namespace, pod, Some(container))) => {
+            Some(format!("{}/{}/{}", namespace, pod, container))
+        }
+        None => json_string(config, "nerdctl/name"),
+    }
+}
+
+fn containerd_name(root: &Path, id: &str) -> Option<String> {
+    // Tasks are grouped by containerd namespace ("k8s.io", "moby", "default")
+    let namespaces = fs::read_dir(root.join(CONTAINERD_TASKS)).ok()?;
+    namespaces.flatten().find_map(|namespace| {
+        let dir = find_by_id(&namespace.path(), id)?;
+        oci_spec_name(&fs::read_to_string(dir.join("config.json")).ok()?)
+    })
+}
+
+fn crio_name(root: &Path, id: &str) -> Option<String> {
+    let dir = find_by_id(&root.join(CRIO_CONTAINERS), id)?;
+    oci_spec_name(&fs::read_to_string(dir.join("userdata/config.json")).ok()?)
+}
+
+/// Resolve the name of a container as returned by `filter_container()`
+/// ("runtime:id") from the runtime state below `root`
+pub fn resolve_container_name(root: &Path, container: &str) -> Option<String> {
+    let (runtime, id) = container.split_once(':')?;
+    if id.is_empty() {
+        return None;
+    }
+    match runtime {
+        "docker" => docker_name(root, id).or_else(|| containerd_name(root, id)),
+        "podman" => podman_name(root, id),
+        "containerd" => containerd_name(root, id),
+        "crio" => crio_name(root, id),
+        // Kubernetes with the cgroupfs driver does not tell the runtime
+        "k8s" => containerd_name(root, id).or_else(|| crio_name(root, id)),
+        _ => None,
+    }
+}
+
+/// Container label for display: "runtime:name" if the name can be
+/// resolved from the local runtime state, the given "runtime:id" otherwise.
+pub fn container_display_name(container: String) -> String {
+    let mut cache = CONTAINER_NAMES.lock().unwrap_or_else(|e| e.into_inner());
+    let name = cache
+        .get_or_insert_with(ContainerNameCache::default)
+        .lookup(&container, Instant::now(), |container| {
+            resolve_container_name(Path::new("/"), container)
+        });
+
+    match (name, container.split_once(':')) {
+        (Some(name), Some((runtime, _))) => format!("{}:{}", runtime, name),
+        _ => container,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::platform::testdata;
+
+    #[test]
+    fn test_json_string() {
+        let json = r#"{"Name": "/web-1", "Path":"a\"b\\c", "Empty":""}"#;
+        assert_eq!(json_string(json, "Name"), Some("/web-1".to_string()));
+        assert_eq!(json_string(json, "Path"), Some("a\"b\\c".to_string()));
+        assert_eq!(json_string(json, "Empty"), Some(String::new()));
+        assert_eq!(json_string(json, "Missing"), None);
+        // A key that only appears as a value is skipped
+        assert_eq!(
+            json_string(r#"{"a":"Name","Name":"x"}"#, "Name"),
+            Some("x".to_string())
+        );
+    }
+
+    #[test]
+    fn test_resolve_docker() {
+        assert_eq!(
+            resolve_container_name(&testdata("container"), "docker:3f4e5d6c7b8a"),
+            Some("web-1".to_string())
+        );
+        assert_eq!(
+            resolve_container_name(&testdata("container"), "docker:000000000000"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_resolve_podman() {
+        assert_eq!(
+            resolve_container_name(&testdata("container"), "podman:b2c3d4e5f6a7"),
+            Some("db".to_string())
+        );
+    }
+
+    #[test]
+    fn test_resolve_containerd_kubernetes() {
+        assert_eq!(
+            resolve_container_name(&testdata("container"), "containerd:c0ffee000001"),
+            Some("default/web-5d9f7/nginx".to_string())
+        );
+        // Pause container of the pod
+        assert_eq!(
+            resolve_container_name(&testdata("container"), "k8s:c0ffee000002"),
+            Some("default/web-5d9f7".to_string())
+        );
+    }
+
+    #[test]
+    fn test_resolve_crio() {
+        assert_eq!(
+            resolve_container_name(&testdata("container"), "crio:dddd00000001"),
+            Some("kube-system/coredns-abc12/coredns".to_string())
+        );
+        assert_eq!(
+            resolve_container_name(&testdata("container"), "k8s:dddd00000002"),
+            Some("kube-system/coredns-abc12".to_string())
+        );
+    }
+
+    #[test]
+    fn test_resolve_unknown() {
+        assert_eq!(
+            resolve_container_name(&testdata("container"), "lxc:web"),
+            None
+        );
+        assert_eq!(
+            resolve_container_name(&testdata("container"), "docker:"),
+            None
+        );
+        assert_eq!(
+            resolve_container_name(&testdata("container"), "invalid"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_name_cache() {
+        let mut cache = ContainerNameCache::default();
+        let start = Instant::now();
+
+        assert_eq!(cache.lookup("docker:a", start, |_| None), None);
+        assert_eq!(
+            cache.lookup("docker:b", start, |_| Some("web".to_string())),
+            Some("web".to_string())
+        );
+
+        // Names are not resolved again, misses only after RETRY_INTERVAL
+        let soon = start + Duration::from_secs(1);
+        assert_eq!(
+            cache.lookup("docker:a", soon, |_| Some("db".to_string())),
+            None
+        );
+        assert_eq!(
+            cache.lookup("docker:b", soon, |_| Some("other".to_string())),
+            Some("web".to_string())
+        );
+        let later = start + RETRY_INTERVAL;
+        assert_eq!(
+            cache.lookup("docker:a", later, |_| Some("db".to_string())),
+            Some("db".to_string())
+        );
+
+        // Containers that are no longer looked up are dropped
+        let gone = later + EXPIRE_AFTER;
+        cache.lookup("docker:a", gone, |_| None);
+        assert!(cache.entries.contains_key("docker:a"));
+        assert!(!cache.entries.contains_key("docker:b"));
+    }
+}
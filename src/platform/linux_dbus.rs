@@ -0,0 +1,547 @@
+//! Minimal blocking D-Bus client
+//!
+//! Just enough of the D-Bus wire protocol to read properties of a service
+//! on the system or session bus (used by the Systemd meters). It speaks
+//! the protocol directly over the bus's unix socket, authenticating with
+//! SASL EXTERNAL, so no libdbus or async runtime is needed.
+//!
+//! Only the value types used by the properties we read are supported.
+
+use std::io::{self, Read, Write};
+use std::os::unix::net::UnixStream;
+use std::time::Duration;
+
+/// Default system bus socket (when DBUS_SYSTEM_BUS_ADDRESS is unset)
+const SYSTEM_BUS_ADDRESS: &str = "unix:path=/var/run/dbus/system_bus_socket";
+
+/// Maximum message size allowed by the specification
+const MAX_MESSAGE_LEN: usize = 128 * 1024 * 1024;
+
+/// Timeout for socket reads and writes
+const IO_TIMEOUT: Duration = Duration::from_millis(500);
+
+const METHOD_CALL: u8 = 1;
+const METHOD_RETURN: u8 = 2;
+const ERROR: u8 = 3;
+
+// Header field codes
+const FIELD_PATH: u8 = 1;
+const FIELD_INTERFACE: u8 = 2;
+const FIELD_MEMBER: u8 = 3;
+const FIELD_ERROR_NAME: u8 = 4;
+const FIELD_REPLY_SERIAL: u8 = 5;
+const FIELD_DESTINATION: u8 = 6;
+const FIELD_SIGNATURE: u8 = 8;
+
+/// A property value read from the bus
+#[derive(Debug, Clone, PartialEq)]
+pub enum DbusValue {
+    String(String),
+    Bool(bool),
+    U32(u32),
+    I32(i32),
+    U64(u64),
+}
+
+fn invalid_data(msg: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg.to_string())
+}
+
+/// Where a bus address points to
+#[derive(Debug, PartialEq, Eq)]
+enum BusAddress {
+    Path(String),
+    Abstract(String),
+}
+
+/// Parse a D-Bus server address ("unix:path=/run/dbus/system_bus_socket"),
+/// taking the first unix transport of a ';'-separated list
+fn parse_address(address: &str) -> Option<BusAddress> {
+    address.split(';').find_map(|entry| {
+        let params = entry.strip_prefix("unix:")?;
+        params
+            .split(',')
+            .find_map(|param| match param.split_once('=')? {
+                ("path", path) => Some(BusAddress::Path(path.to_string())),
+                ("abstract", name) => Some(BusAddress::Abstract(name.to_string())),
+                _ => None,
+            })
+    })
+}
+
+/// Marshalling buffer (little endian)
+#[derive(Default)]
+struct Writer {
+    buf: Vec<u8>,
+}
+
+impl Writer {
+    fn align(&mut self, n: usize) {
+        while !self.buf.len().is_multiple_of(n) {
+            self.buf.push(0);
+        }
+    }
+
+    fn u8(&mut self, v: u8) {
+        self.buf.push(v);
+    }
+
+    fn u32(&mut self, v: u32) {
+        self.align(4);
+        self.buf.extend_from_slice(&v.to_le_bytes());
+    }
+
+    fn string(&mut self, s: &str) {
+        self.u32(s.len() as u32);
+        self.buf.extend_from_slice(s.as_bytes());
+        self.buf.push(0);
+    }
+
+    fn signature(&mut self, s: &str) {
+        self.u8(s.len() as u8);
+        self.buf.extend_from_slice(s.as_bytes());
+        self.buf.push(0);
+    }
+
+    /// Header field: struct of (code, variant)
+    fn header_field(&mut self, code: u8, signature: &str, value: &str) {
+        self.align(8);
+        self.u8(code);
+        self.signature(signature);
+        if signature == "g" {
+            self.signature(value);
+        } else {
+            self.string(value);
+        }
+    }
+}
+
+/// Build a method call message
+fn method_call(
+    serial: u32,
+    destination: &str,
+    path: &str,
+    interface: &str,
+    member: &str,
+    args: &[&str],
+) -> Vec<u8> {
+    let mut body = Writer::default();
+    for arg in args {
+        body.string(arg);
+    }
+    let body_signature = "s".repeat(args.len());
+
+    let mut w = Writer::default();
+    w.u8(b'l');
+    w.u8(METHOD_CALL);
+    w.u8(0);
+    w.u8(1);
+    w.u32(body.buf.len() as u32);
+    w.u32(serial);
+
+    // Header fields array, its length is patched in afterwards
+    let len_pos = w.buf.len();
+    w.u32(0);
+    w.align(8);
+    let start = w.buf.len();
+    w.header_field(FIELD_PATH, "o", path);
+    w.header_field(FIELD_INTERFACE, "s", interface);
+    w.header_field(FIELD_MEMBER, "s", member);
+    w.header_field(FIELD_DESTINATION, "s", destination);
+    if !body_signature.is_empty() {
+        w.header_field(FIELD_SIGNATURE, "g", &body_signature);
+    }
+    let len = (w.buf.len() - start) as u32;
+    w.buf[len_pos..len_pos + 4].copy_from_slice(&len.to_le_bytes());
+
+    w.align(8);
+    w.buf.extend_from_slice(&body.buf);
+    w.buf
+}
+
+/// Unmarshalling cursor
+struct Reader<'a> {
+    buf: &'a [u8],
+    pos: usize,
+    big_endian: bool,
+}
+
+impl<'a> Reader<'a> {
+    fn align(&mut self, n: usize) {
+        self.pos = self.pos.div_ceil(n) * n;
+    }
+
+    fn bytes(&mut self, n: usize) -> Option<&'a [u8]> {
+        let bytes = self.buf.get(self.pos..self.pos.checked_add(n)?)?;
+        self.pos += n;
+        Some(bytes)
+    }
+
+    fn u8(&mut self) -> Option<u8> {
+        self.bytes(1).map(|b| b[0])
+    }
+
+    fn u32(&mut self) -> Option<u32> {
+        self.align(4);
+        let b: [u8; 4] = self.bytes(4)?.try_into().ok()?;
+        Some(if self.big_endian {
+            u32::from_be_bytes(b)
+        } else {
+            u32::from_le_bytes(b)
+        })
+    }
+
+    fn u64(&mut self) -> Option<u64> {
+        self.align(8);
+        let b: [u8; 8] = self.bytes(8)?.try_into().ok()?;
+        Some(if self.big_endian {
+            u64::from_be_bytes(b)
+        } else {
+            u64::from_le_bytes(b)
+        })
+    }
+
+    fn string(&mut self) -> Option<String> {
+        let len = self.u32()? as usize;
+        let s = String::from_utf8(self.bytes(len)?.to_vec()).ok();
+        self.pos += 1; // nul terminator
+        s
+    }
+
+    fn signature(&mut self) -> Option<String> {
+        let len = self.u8()? as usize;
+        let s = String::from_utf8(self.bytes(len)?.to_vec()).ok();
+        self.pos += 1;
+        s
+    }
+
+    fn variant(&mut self) -> Option<DbusValue> {
+        match self.signature()?.as_str() {
+            "s" | "o" => self.string().map(DbusValue::String),
+            "g" => self.signature().map(DbusValue::String),
+            "b" => self.u32().map(|v| DbusValue::Bool(v != 0)),
+            "u" => self.u32().map(DbusValue::U32),
+            "i" => self.u32().map(|v| DbusValue::I32(v as i32)),
+            "t" => self.u64().map(DbusValue::U64),
+            _ => None,
+        }
+    }
+}
+
+/// A received message, reduced to what replies need
+#[derive(Debug)]
+struct Message {
+    msg_type: u8,
+    reply_serial: Option<u32>,
+    error_name: Option<String>,
+    signature: String,
+    big_endian: bool,
+    body: Vec<u8>,
+}
+
+impl Message {
+    /// Total message length from the first 16 bytes of a message
+    fn length(prefix: &[u8; 16]) -> usize {
+        let read = |b: [u8; 4]| {
+            if prefix[0] == b'B' {
+                u32::from_be_bytes(b)
+            } else {
+                u32::from_le_bytes(b)
+            }
+        };
+        let body_len = read(prefix[4..8].try_into().unwrap()) as usize;
+        let fields_len = read(prefix[12..16].try_into().unwrap()) as usize;
+        (16 + fields_len).div_ceil(8) * 8 + body_len
+    }
+
+    /// Parse a complete message
+    fn parse(buf: &[u8]) -> Option<Message> {
+        let big_endian = match *buf.first()? {
+            b'l' => false,
+            b'B' => true,
+            _ => return None,
+        };
+        let mut r = Reader {
+            buf,
+            pos: 1,
+            big_endian,
+        };
+        let msg_type = r.u8()?;
+        r.pos = 4;
+        let body_len = r.u32()? as usize;
+        let _serial = r.u32()?;
+        let fields_len = r.u32()? as usize;
+        let fields_end = 16 + fields_len;
+
+        let mut message = Message {
+            msg_type,
+            reply_serial: None,
+            error_name: None,
+            signature: String::new(),
+            big_endian,
+            body: Vec::new(),
+        };
+
+        while r.pos < fields_end {
+            r.align(8);
+            let code = r.u8()?;
+            let value = r.variant()?;
+            match (code, value) {
+                (FIELD_REPLY_SERIAL, DbusValue::U32(serial)) => message.reply_serial = Some(serial),
+                (FIELD_ERROR_NAME, DbusValue::String(name)) => message.error_name = Some(name),
+                (FIELD_SIGNATURE, DbusValue::String(sig)) => message.signature = sig,
+                _ => {}
+            }
+        }
+
+        let body_start = fields_end.div_ceil(8) * 8;
+        message.body = buf.get(body_start..body_start + body_len)?.to_vec();
+        Some(message)
+    }
+}
+
+/// Connection to a message bus
+#[derive(Debug)]
+pub struct DbusConnection {
+    stream: UnixStream,
+    serial: u32,
+}
+
+impl DbusConnection {
+    /// Connect to the system bus
+    pub fn system() -> io::Result<Self> {
+        let address = std::env::var("DBUS_SYSTEM_BUS_ADDRESS")
+            .unwrap_or_else(|_| SYSTEM_BUS_ADDRESS.to_string());
+        Self::connect(&address)
+    }
+
+    /// Connect to the session bus of the current user
+    pub fn session() -> io::Result<Self> {
+        let address = std::env::var("DBUS_SESSION_BUS_ADDRESS")
+            .unwrap_or_else(|_| format!("unix:path=/run/user/{}/bus", unsafe { libc::geteuid() }));
+        Self::connect(&address)
+    }
+
+    fn connect(address: &str) -> io::Result<Self> {
+        let stream = match parse_address(address) {
+            Some(BusAddress::Path(path)) => UnixStream::connect(path)?,
+            Some(BusAddress::Abstract(name)) => {
+                use std::os::linux::net::SocketAddrExt;
+                let addr = std::os::unix::net::SocketAddr::from_abstract_name(name)?;
+                UnixStream::connect_addr(&addr)?
+            }
+            None => return Err(invalid_data("unsupported bus address")),
+        };
+        stream.set_read_timeout(Some(IO_TIMEOUT))?;
+        stream.set_write_timeout(Some(IO_TIMEOUT))?;
+
+        let mut conn = DbusConnection { stream, serial: 0 };
+        conn.authenticate()?;
+        // The bus requires Hello before any other call
+        conn.call(
+            "org.freedesktop.DBus",
+            "/org/freedesktop/DBus",
+            "org.freedesktop.DBus",
+            "Hello",
+            &[],
+        )?;
+        Ok(conn)
+    }
+
+    /// SASL EXTERNAL authentication with our uid
+    fn authenticate(&mut self) -> io::Result<()> {
+        let uid = unsafe { libc::geteuid() }.to_string();
+        let hex_uid: String = uid.bytes().map(|b| format!("{:02x}", b)).collect();
+        self.stream
+            .write_all(format!("\0AUTH EXTERNAL {}\r\n", hex_uid).as_bytes())?;
+
+        let mut line = Vec::new();
+        let mut byte = [0u8; 1];
+        while !line.ends_with(b"\r\n") {
+            self.stream.read_exact(&mut byte)?;
+            line.push(byte[0]);
+        }
+        if !line.starts_with(b"OK ") {
+            return Err(io::Error::new(
+                io::ErrorKind::PermissionDenied,
+                "D-Bus authentication rejected",
+            ));
+        }
+        self.stream.write_all(b"BEGIN\r\n")
+    }
+
+    /// Call a method and wait for its reply
+    fn call(
+        &mut self,
+        destination: &str,
+        path: &str,
+        interface: &str,
+        member: &str,
+        args: &[&str],
+    ) -> io::Result<Message> {
+        self.serial += 1;
+        let serial = self.serial;
+        self.stream.write_all(&method_call(
+            serial,
+            destination,
+            path,
+            interface,
+            member,
+            args,
+        ))?;
+
+        // Skip signals and anything else that is not our reply
+        loop {
+            let mut prefix = [0u8; 16];
+            self.stream.read_exact(&mut prefix)?;
+            let len = Message::length(&prefix);
+            if len > MAX_MESSAGE_LEN {
+                return Err(invalid_data("message too long"));
+            }
+            let mut buf = vec![0u8; len];
+            buf[..16].copy_from_slice(&prefix);
+            self.stream.read_exact(&mut buf[16..])?;
+
+            let message = Message::parse(&buf).ok_or_else(|| invalid_data("malformed message"))?;
+            if message.reply_serial != Some(serial) {
+                continue;
+            }
+            return match message.msg_type {
+                METHOD_RETURN => Ok(message),
+                ERROR => Err(io::Error::other(
+                    message
+                        .error_name
+                        .unwrap_or_else(|| "D-Bus error".to_string()),
+                )),
+                _ => continue,
+            };
+        }
+    }
+
+    /// Read a property via org.freedesktop.DBus.Properties.Get
+    pub fn get_property(
+        &mut self,
+        destination: &str,
+        path: &str,
+        interface: &str,
+        property: &str,
+    ) -> io::Result<DbusValue> {
+        let reply = self.call(
+            destination,
+            path,
+            "org.freedesktop.DBus.Properties",
+            "Get",
+            &[interface, property],
+        )?;
+        if reply.signature != "v" {
+            return Err(invalid_data("unexpected reply signature"));
+        }
+        let mut r = Reader {
+            buf: &reply.body,
+            pos: 0,
+            big_endian: reply.big_endian,
+        };
+        r.variant()
+            .ok_or_else(|| invalid_data("unsupported property type"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_address() {
+        assert_eq!(
+            parse_address("unix:path=/run/dbus/system_bus_socket"),
+            Some(BusAddress::Path("/run/dbus/system_bus_socket".to_string()))
+        );
+        assert_eq!(
+            parse_address("tcp:host=localhost,port=1;unix:abstract=/tmp/dbus-x,guid=12"),
+            Some(BusAddress::Abstract("/tmp/dbus-x".to_string()))
+        );
+        assert_eq!(parse_address("tcp:host=localhost,port=1"), None);
+    }
+
+    #[test]
+    fn test_method_call_roundtrip() {
+        let msg = method_call(
+            7,
+            "org.freedesktop.systemd1",
+            "/org/freedesktop/systemd1",
+            "org.freedesktop.DBus.Properties",
+            "Get",
+            &["org.freedesktop.systemd1.Manager", "SystemState"],
+        );
+        let mut prefix = [0u8; 16];
+        prefix.copy_from_slice(&msg[..16]);
+        assert_eq!(Message::length(&prefix), msg.len());
+
+        let parsed = Message::parse(&msg).unwrap();
+        assert_eq!(parsed.msg_type, METHOD_CALL);
+        assert_eq!(parsed.signature, "ss");
+        let mut r = Reader {
+            buf: &parsed.body,
+            pos: 0,
+            big_endian: false,
+        };
+        assert_eq!(
+            r.string().as_deref(),
+            Some("org.freedesktop.systemd1.Manager")
+        );
+        assert_eq!(r.string().as_deref(), Some("SystemState"));
+    }
+
+    /// Build a big-endian method return carrying a variant, as a bus would
+    fn reply(reply_serial: u32, variant_sig: &str, value: &[u8]) -> Vec<u8> {
+        let mut fields = vec![FIELD_REPLY_SERIAL, 1, b'u', 0];
+        fields.extend_from_slice(&reply_serial.to_be_bytes());
+        fields.extend_from_slice(&[FIELD_SIGNATURE, 1, b'g', 0, 1, b'v', 0]);
+
+        let mut body = vec![variant_sig.len() as u8];
+        body.extend_from_slice(variant_sig.as_bytes());
+        body.push(0);
+        while !body.len().is_multiple_of(4) {
+            body.push(0);
+        }
+        body.extend_from_slice(value);
+
+        let mut msg = vec![b'B', METHOD_RETURN, 0, 1];
+        msg.extend_from_slice(&(body.len() as u32).to_be_bytes());
+        msg.extend_from_slice(&9u32.to_be_bytes());
+        msg.extend_from_slice(&(fields.len() as u32).to_be_bytes());
+        msg.extend_from_slice(&fields);
+        msg.push(0); // pad the header to 8 bytes
+        msg.extend_from_slice(&body);
+        msg
+    }
+
+    #[test]
+    fn test_parse_big_endian_reply() {
+        let msg = reply(3, "u", &42u32.to_be_bytes());
+        let parsed = Message::parse(&msg).unwrap();
+        assert_eq!(parsed.msg_type, METHOD_RETURN);
+        assert_eq!(parsed.reply_serial, Some(3));
+        assert_eq!(parsed.signature, "v");
+
+        let mut r = Reader {
+            buf: &parsed.body,
+            pos: 0,
+            big_endian: true,
+        };
+        assert_eq!(r.variant(), Some(DbusValue::U32(42)));
+
+        let mut value = 8u32.to_be_bytes().to_vec();
+        value.extend_from_slice(b"degraded\0");
+        let parsed = Message::parse(&reply(4, "s", &value)).unwrap();
+        let mut r = Reader {
+            buf: &parsed.body,
+            pos: 0,
+            big_endian: true,
+        };
+        assert_eq!(r.variant(), Some(DbusValue::String("degraded".to_string())));
+
+        // Truncated messages are rejected instead of panicking
+        assert!(Message::parse(&msg[..msg.len() - 2]).is_none());
+    }
+}
@@ -0,0 +1,221 @@
+//! Per-process GPU usage from DRM fdinfo
+//!
+//! DRM drivers that implement the kernel's drm-usage-stats interface
+//! (amdgpu, i915, xe, nouveau, panfrost, msm, ...) report the busy time of
+//! each engine class in `/proc/PID/fdinfo/FD` for every open DRM file:
+//!
+//! ```text
+//! drm-driver:     amdgpu
+//! drm-client-id:  12
+//! drm-pdev:       0000:03:00.0
+//! drm-engine-gfx: 1500000000 ns
+//! ```
+//!
+//! A client can be reachable through several file descriptors (dup'ed or
+//! inherited fds), so clients are counted once per (device, client id),
+//! like C htop's linux/GPU.c.
+
+use std::collections::HashSet;
+use std::fs;
+use std::path::Path;
+
+/// GPU usage of one process, summed over its DRM clients
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct DrmUsage {
+    /// Busy time per engine name in nanoseconds, in first-seen order
+    pub engines: Vec<(String, u64)>,
+}
+
+impl DrmUsage {
+    /// Total busy time over all engines in nanoseconds
+    pub fn total_time(&self) -> u64 {
+        self.engines.iter().map(|(_, ns)| ns).sum()
+    }
+
+    /// Add busy time to an engine
+    pub fn add(&mut self, engine: &str, ns: u64) {
+        match self.engines.iter_mut().find(|(name, _)| name == engine) {
+            Some((_, total)) => *total += ns,
+            None => self.engines.push((engine.to_string(), ns)),
+        }
+    }
+
+    /// Busy time per engine since an earlier sample of the same process.
+    /// Engines that are new or whose counter went backwards (a client was
+    /// closed) count from zero, so they never show negative usage.
+    pub fn delta_since(&self, prev: &DrmUsage) -> DrmUsage {
+        let engines = self
+            .engines
+            .iter()
+            .map(|(name, ns)| {
+                let prev_ns = prev
+                    .engines
+                    .iter()
+                    .find(|(n, _)| n == name)
+                    .map_or(0, |(_, p)| *p);
+                (name.clone(), ns.saturating_sub(prev_ns))
+            })
+            .collect();
+        DrmUsage { engines }
+    }
+}
+
+/// One DRM client as described by an fdinfo file
+#[derive(Debug, Default, PartialEq)]
+struct DrmClient {
+    device: String,
+    id: u64,
+    engines: Vec<(String, u64)>,
+}
+
+/// Parse an fdinfo file, returning None if it does not describe a DRM client
+fn parse_drm_fdinfo(content: &str) -> Option<DrmClient> {
+    let mut client = DrmClient::default();
+    let mut has_id = false;
+
+    for line in content.lines() {
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        let value = value.trim();
+        match key {
+            "drm-client-id" => {
+                client.id = value.parse().ok()?;
+                has_id = true;
+            }
+            // Older kernels only report the driver, which is good enough
+            // to tell clients of different GPUs apart
+            "drm-pdev" => client.device = value.to_string(),
+            "drm-driver" if client.device.is_empty() => client.device = value.to_string(),
+            _ => {
+                let Some(engine) = key.strip_prefix("drm-engine-") else {
+                    continue;
+                };
+                // drm-engine-capacity-<name> is the number of engines in a class
+                if engine.starts_with("capacity-") {
+                    continue;
+                }
+                if let Some(ns) = value
+                    .strip_suffix("ns")
+                    .and_then(|v| v.trim().parse::<u64>().ok())
+                {
+                    client.engines.push((engine.to_string(), ns));
+                }
+            }
+        }
+    }
+
+    has_id.then_some(client)
+}
+
+/// Read the GPU usage of a process from its fdinfo directory
+/// (`/proc/PID/fdinfo`). Returns None if the directory cannot be read.
+pub fn read_drm_usage(fdinfo_dir: &Path) -> Option<DrmUsage> {
+    let entries = fs::read_dir(fdinfo_dir).ok()?;
+    let mut usage = DrmUsage::default();
+    let mut seen = HashSet::new();
+
+    for entry in entries.flatten() {
+        // fds can be closed while we iterate
+        let Ok(content) = fs::read_to_string(entry.path()) else {
+            continue;
+        };
+        let Some(client) = parse_drm_fdinfo(&content) else {
+            continue;
+        };
+        if !seen.insert((client.device, client.id)) {
+            continue;
+        }
+        for (engine, ns) in &client.engines {
+            usage.add(engine, *ns);
+        }
+    }
+
+    Some(usage)
+}
+
+/// Busy percentage of an engine (or process) between two samples
+pub fn busy_percent(prev_ns: u64, cur_ns: u64, interval_ms: u64) -> f64 {
+    if interval_ms == 0 || cur_ns < prev_ns {
+        return 0.0;
+    }
+    // ns / (ms * 1e6) * 100
+    (cur_ns - prev_ns) as f64 / (interval_ms as f64 * 10_000.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::platform::testdata;
+
+    #[test]
+    fn test_parse_drm_fdinfo() {
+        let content = fs::read_to_string(testdata("drm-fdinfo/9")).unwrap();
+        let client = parse_drm_fdinfo(&content).unwrap();
+        assert_eq!(client.device, "0000:00:02.0");
+        assert_eq!(client.id, 3);
+        // drm-engine-capacity-video is skipped
+        assert_eq!(
+            client.engines,
+            vec![
+                ("render".to_string(), 700_000_000),
+                ("copy".to_string(), 0),
+                ("video".to_string(), 50_000_000),
+            ]
+        );
+
+        let plain = fs::read_to_string(testdata("drm-fdinfo/0")).unwrap();
+        assert_eq!(parse_drm_fdinfo(&plain), None);
+    }
+
+    #[test]
+    fn test_read_drm_usage_dedups_clients() {
+        let usage = read_drm_usage(&testdata("drm-fdinfo")).unwrap();
+        let engine = |name: &str| {
+            usage
+                .engines
+                .iter()
+                .find(|(n, _)| n == name)
+                .map(|(_, ns)| *ns)
+        };
+        // fds 5 and 6 refer to the same amdgpu client and are counted once
+        assert_eq!(engine("gfx"), Some(1_500_000_000));
+        assert_eq!(engine("dec"), Some(250_000_000));
+        assert_eq!(engine("render"), Some(700_000_000));
+        assert_eq!(usage.total_time(), 2_500_000_000);
+
+        assert_eq!(read_drm_usage(&testdata("drm-fdinfo/missing")), None);
+    }
+
+    #[test]
+    fn test_delta_since() {
+        let prev = DrmUsage {
+            engines: vec![("gfx".to_string(), 1_000), ("dec".to_string(), 500)],
+        };
+        let cur = DrmUsage {
+            engines: vec![
+                ("gfx".to_string(), 1_800),
+                ("dec".to_string(), 200),
+                ("video".to_string(), 50),
+            ],
+        };
+        assert_eq!(
+            cur.delta_since(&prev).engines,
+            vec![
+                ("gfx".to_string(), 800),
+                ("dec".to_string(), 0),
+                ("video".to_string(), 50),
+            ]
+        );
+        assert_eq!(cur.delta_since(&cur).total_time(), 0);
+    }
+
+    #[test]
+    fn test_busy_percent() {
+        // 500ms of GPU time over a 1s interval
+        assert_eq!(busy_percent(1_000_000_000, 1_500_000_000, 1000), 50.0);
+        assert_eq!(busy_percent(0, 1_000, 0), 0.0);
+        // Counters going backwards (client closed) never show negative usage
+        assert_eq!(busy_percent(2_000, 1_000, 1000), 0.0);
+    }
+}
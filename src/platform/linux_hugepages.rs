@@ -0,0 +1,79 @@
+//! Huge page pools from sysfs
+//!
+//! Every supported huge page size has a directory
+//! `/sys/kernel/mm/hugepages/hugepages-<size>kB` with the number of pages
+//! in the pool (`nr_hugepages`) and the number of unused pages
+//! (`free_hugepages`). /proc/meminfo only reports the default size.
+
+use std::fs;
+use std::path::Path;
+
+use super::linux_sysfs::read_u64;
+use crate::core::HugePageData;
+
+/// Location of the huge page pool directories
+pub const HUGEPAGES_PATH: &str = "/sys/kernel/mm/hugepages";
+
+/// Read all huge page pools, sorted by page size. Returns an empty list if
+/// the directory does not exist (kernels without hugetlbfs).
+pub fn read_hugepages(dir: &Path) -> Vec<HugePageData> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+
+    let mut pools: Vec<HugePageData> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let name = entry.file_name();
+            let size_kb = name
+                .to_str()?
+                .strip_prefix("hugepages-")?
+                .strip_suffix("kB")?
+                .parse()
+                .ok()?;
+            let pool = entry.path();
+            Some(HugePageData {
+                size_kb,
+                total: read_u64(&pool.join("nr_hugepages"))?,
+                free: read_u64(&pool.join("free_hugepages"))?,
+            })
+        })
+        .collect();
+
+    pools.sort_by_key(|pool| pool.size_kb);
+    pools
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::platform::testdata;
+
+    #[test]
+    fn test_read_hugepages() {
+        let pools = read_hugepages(&testdata("hugepages"));
+        assert_eq!(
+            pools,
+            vec![
+                HugePageData {
+                    size_kb: 2048,
+                    total: 512,
+                    free: 128,
+                },
+                HugePageData {
+                    size_kb: 1024 * 1024,
+                    total: 2,
+                    free: 2,
+                },
+            ]
+        );
+        assert_eq!(pools[0].total_kb(), 1024 * 1024);
+        assert_eq!(pools[0].used_kb(), 768 * 1024);
+        assert_eq!(pools[1].used_kb(), 0);
+    }
+
+    #[test]
+    fn test_read_hugepages_missing() {
+        assert!(read_hugepages(&testdata("hugepages/missing")).is_empty());
+    }
+}
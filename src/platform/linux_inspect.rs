@@ -0,0 +1,527 @@
+//! Details of one process from /proc/PID for the process inspector
+//!
+//! Collects what is otherwise read with several `cat`s: the interesting
+//! fields of `status`, the security settings (capabilities decoded to
+//! names, seccomp, no_new_privs), cwd/root/exe, wchan, the OOM score,
+//! resource limits, cgroup membership and namespaces.
+
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// Fields of /proc/PID/status shown in the status section
+const STATUS_KEYS: &[&str] = &[
+    "Name",
+    "State",
+    "Tgid",
+    "PPid",
+    "TracerPid",
+    "Uid",
+    "Gid",
+    "Groups",
+    "Threads",
+    "VmPeak",
+    "VmSize",
+    "VmHWM",
+    "VmRSS",
+    "VmSwap",
+    "voluntary_ctxt_switches",
+    "nonvoluntary_ctxt_switches",
+    "Cpus_allowed_list",
+    "Mems_allowed_list",
+];
+
+/// Capability sets of /proc/PID/status
+const CAPABILITY_KEYS: &[&str] = &["CapInh", "CapPrm", "CapEff", "CapBnd", "CapAmb"];
+
+/// Capability names by bit (include/uapi/linux/capability.h)
+const CAPABILITIES: &[&str] = &[
+    "cap_chown",
+    "cap_dac_override",
+    "cap_dac_read_search",
+    "cap_fowner",
+    "cap_fsetid",
+    "cap_kill",
+    "cap_setgid",
+    "cap_setuid",
+    "cap_setpcap",
+    "cap_linux_immutable",
+    "cap_net_bind_service",
+    "cap_net_broadcast",
+    "cap_net_admin",
+    "cap_net_raw",
+    "cap_ipc_lock",
+    "cap_ipc_owner",
+    "cap_sys_module",
+    "cap_sys_rawio",
+    "cap_sys_chroot",
+    "cap_sys_ptrace",
+    "cap_sys_pacct",
+    "cap_sys_admin",
+    "cap_sys_boot",
+    "cap_sys_nice",
+    "cap_sys_resource",
+    "cap_sys_time",
+    "cap_sys_tty_config",
+    "cap_mknod",
+    "cap_lease",
+    "cap_audit_write",
+    "cap_audit_control",
+    "cap_setfcap",
+    "cap_mac_override",
+    "cap_mac_admin",
+    "cap_syslog",
+    "cap_wake_alarm",
+    "cap_block_suspend",
+    "cap_audit_read",
+    "cap_perfmon",
+    "cap_bpf",
+    "cap_checkpoint_restore",
+];
+
+/// Titled group of (name, value) entries
+#[derive(Debug, Clone, PartialEq)]
+pub struct DetailSection {
+    pub title: &'static str,
+    pub entries: Vec<(String, String)>,
+}
+
+impl DetailSection {
+    fn new(title: &'static str) -> Self {
+        DetailSection {
+            title,
+            entries: Vec::new(),
+        }
+    }
+
+    fn add(&mut self, name: impl Into<String>, value: impl Into<String>) {
+        self.entries.push((name.into(), value.into()));
+    }
+}
+
+/// One row of /proc/PID/limits. The rows are in the order of the
+/// RLIMIT_* resource numbers.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResourceLimit {
+    /// RLIMIT_* resource number
+    pub resource: u32,
+    /// Description like "Max open files"
+    pub name: String,
+    /// Soft limit, None if unlimited
+    pub soft: Option<u64>,
+    /// Hard limit, None if unlimited
+    pub hard: Option<u64>,
+    /// Units like "files" (empty for counts without a unit)
+    pub units: String,
+}
+
+/// Format a limit value like the kernel does
+pub fn format_limit(value: Option<u64>) -> String {
+    value.map_or_else(|| "unlimited".to_string(), |v| v.to_string())
+}
+
+/// Parse /proc/PID/limits
+pub fn parse_limits(content: &str) -> Vec<ResourceLimit> {
+    // Columns are printed with "%-25s %-20s %-20s %-10s"
+    let column = |line: &str, range: std::ops::Range<usize>| {
+        line.get(range.start..range.end.min(line.len()))
+            .unwrap_or("")
+            .trim()
+            .to_string()
+    };
+    let value = |text: String| text.parse::<u64>().ok();
+
+    content
+        .lines()
+        .skip(1)
+        .enumerate()
+        .map(|(resource, line)| ResourceLimit {
+            resource: resource as u32,
+            name: column(line, 0..26),
+            soft: value(column(line, 26..47)),
+            hard: value(column(line, 47..68)),
+            units: column(line, 68..line.len()),
+        })
+        .collect()
+}
+
+/// Parse one limit value: a number or "unlimited"
+fn parse_limit_value(text: &str) -> Result<Option<u64>, String> {
+    match text {
+        "unlimited" | "infinity" => Ok(None),
+        _ => text
+            .parse()
+            .map(Some)
+            .map_err(|_| format!("invalid limit \"{}\"", text)),
+    }
+}
+
+/// New limits of a resource, as entered in the limits editor
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LimitChange {
+    /// New soft limit, None if unlimited
+    pub soft: Option<u64>,
+    /// New hard limit (None if unlimited), or None to keep the current
+    /// hard limit of each process
+    pub hard: Option<Option<u64>>,
+}
+
+impl LimitChange {
+    /// Parse "SOFT HARD", or "SOFT" to keep the hard limit
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let values: Vec<&str> = text.split_whitespace().collect();
+        match values[..] {
+            [soft] => Ok(LimitChange {
+                soft: parse_limit_value(soft)?,
+                hard: None,
+            }),
+            [soft, hard] => Ok(LimitChange {
+                soft: parse_limit_value(soft)?,
+                hard: Some(parse_limit_value(hard)?),
+            }),
+            _ => Err("expected soft and hard limit".to_string()),
+        }
+    }
+
+    /// The (soft, hard) limits to set for a process whose limits are
+    /// currently `current`
+    pub fn resolve(&self, current: &ResourceLimit) -> Result<(Option<u64>, Option<u64>), String> {
+        let hard = self.hard.unwrap_or(current.hard);
+        // None (unlimited) is the largest value
+        if hard.is_some() && self.soft.is_none_or(|soft| Some(soft) > hard) {
+            return Err(format!(
+                "soft limit exceeds hard limit {}",
+                format_limit(hard)
+            ));
+        }
+        Ok((self.soft, hard))
+    }
+}
+
+/// Read the resource limits of a process from its `/proc/PID` directory
+pub fn read_limits(proc_dir: &Path) -> io::Result<Vec<ResourceLimit>> {
+    Ok(parse_limits(&fs::read_to_string(proc_dir.join("limits"))?))
+}
+
+/// Names of the capabilities in a capability set
+pub fn capability_names(mask: u64) -> Vec<String> {
+    (0..64)
+        .filter(|bit| mask & (1 << bit) != 0)
+        .map(|bit| match CAPABILITIES.get(bit) {
+            Some(name) => name.to_string(),
+            None => format!("cap_{}", bit),
+        })
+        .collect()
+}
+
+/// Describe a capability set: "none", "all" or the capability names
+pub fn format_capabilities(mask: u64) -> String {
+    let all = (1u64 << CAPABILITIES.len()) - 1;
+    match mask {
+        0 => "none".to_string(),
+        mask if mask & all == all => "all".to_string(),
+        mask => capability_names(mask).join(","),
+    }
+}
+
+/// Describe the Seccomp field of /proc/PID/status
+fn seccomp_mode(value: &str) -> String {
+    let mode = match value {
+        "0" => "disabled",
+        "1" => "strict",
+        "2" => "filter",
+        _ => return value.to_string(),
+    };
+    format!("{} ({})", value, mode)
+}
+
+/// Value to show for a file that could not be read
+fn unreadable(err: &io::Error) -> String {
+    match err.kind() {
+        io::ErrorKind::PermissionDenied => "(permission denied)".to_string(),
+        _ => "(unavailable)".to_string(),
+    }
+}
+
+/// Read a file, or a placeholder describing why it is not readable
+fn read_value(path: &Path) -> String {
+    match fs::read_to_string(path) {
+        Ok(content) => content.trim().to_string(),
+        Err(err) => unreadable(&err),
+    }
+}
+
+/// Read a symbolic link, or a placeholder describing why it is not readable
+fn read_link_value(path: &Path) -> String {
+    match fs::read_link(path) {
+        Ok(target) => target.to_string_lossy().into_owned(),
+        Err(err) => unreadable(&err),
+    }
+}
+
+/// Status and security sections from the contents of /proc/PID/status
+fn status_sections(status: &str) -> (DetailSection, DetailSection) {
+    let fields: Vec<(&str, String)> = status
+        .lines()
+        .filter_map(|line| {
+            let (key, value) = line.split_once(':')?;
+            // Tabs separate the values of Uid, Gid and Groups
+            Some((key, value.split_whitespace().collect::<Vec<_>>().join(" ")))
+        })
+        .collect();
+    let field = |key: &str| fields.iter().find(|(k, _)| *k == key).map(|(_, v)| v);
+
+    let mut general = DetailSection::new("Status");
+    for &key in STATUS_KEYS {
+        if let Some(value) = field(key) {
+            general.add(key, value.clone());
+        }
+    }
+
+    let mut security = DetailSection::new("Security");
+    if let Some(value) = field("NoNewPrivs") {
+        security.add("NoNewPrivs", value.clone());
+    }
+    if let Some(value) = field("Seccomp") {
+        security.add("Seccomp", seccomp_mode(value));
+    }
+    if let Some(value) = field("Seccomp_filters") {
+        security.add("Seccomp_filters", value.clone());
+    }
+    for &key in CAPABILITY_KEYS {
+        if let Some(value) = field(key) {
+            security.add(key, value.clone());
+        }
+    }
+    if let Some(mask) = field("CapEff").and_then(|v| u64::from_str_radix(v, 16).ok()) {
+        security.add("CapEff names", format_capabilities(mask));
+    }
+    (general, security)
+}
+
+/// Cgroup section from the contents of /proc/PID/cgroup
+fn cgroup_section(content: &str) -> DetailSection {
+    let mut section = DetailSection::new("Cgroups");
+    for line in content.lines() {
+        // hierarchy-ID:controllers:path, no controllers for cgroup v2
+        let mut parts = line.splitn(3, ':');
+        let (Some(id), Some(controllers), Some(path)) = (parts.next(), parts.next(), parts.next())
+        else {
+            continue;
+        };
+        let name = if controllers.is_empty() {
+            format!("{} (unified)", id)
+        } else {
+            format!("{} {}", id, controllers)
+        };
+        section.add(name, path);
+    }
+    section
+}
+
+/// Read the details of a process from its `/proc/PID` directory. Fails
+/// only if the process does not exist, other files that cannot be read
+/// are shown as unavailable.
+pub fn read_process_details(proc_dir: &Path) -> io::Result<Vec<DetailSection>> {
+    let status = fs::read_to_string(proc_dir.join("status"))?;
+    let (general, security) = status_sections(&status);
+
+    let mut files = DetailSection::new("Files");
+    for link in ["cwd", "root", "exe"] {
+        files.add(link, read_link_value(&proc_dir.join(link)));
+    }
+
+    let mut scheduling = DetailSection::new("Scheduling");
+    // The kernel shows "0" for running processes
+    let wchan = read_value(&proc_dir.join("wchan"));
+    scheduling.add("wchan", if wchan == "0" { "-".to_string() } else { wchan });
+    scheduling.add("oom_score", read_value(&proc_dir.join("oom_score")));
+    scheduling.add("oom_score_adj", read_value(&proc_dir.join("oom_score_adj")));
+
+    let mut limits = DetailSection::new("Limits");
+    match read_limits(proc_dir) {
+        Ok(rows) => {
+            for limit in rows {
+                let value = format!(
+                    "{} / {} {}",
+                    format_limit(limit.soft),
+                    format_limit(limit.hard),
+                    limit.units
+                );
+                limits.add(limit.name, value.trim_end());
+            }
+        }
+        Err(err) => limits.add("limits", unreadable(&err)),
+    }
+
+    let cgroups = match fs::read_to_string(proc_dir.join("cgroup")) {
+        Ok(content) => cgroup_section(&content),
+        Err(err) => {
+            let mut section = DetailSection::new("Cgroups");
+            section.add("cgroup", unreadable(&err));
+            section
+        }
+    };
+
+    let mut namespaces = DetailSection::new("Namespaces");
+    match fs::read_dir(proc_dir.join("ns")) {
+        Ok(entries) => {
+            let mut names: Vec<String> = entries
+                .flatten()
+                .map(|entry| entry.file_name().to_string_lossy().into_owned())
+                .collect();
+            names.sort();
+            for name in names {
+                let target = read_link_value(&proc_dir.join("ns").join(&name));
+                namespaces.add(name, target);
+            }
+        }
+        Err(err) => namespaces.add("ns", unreadable(&err)),
+    }
+
+    Ok(vec![
+        general, security, files, scheduling, limits, cgroups, namespaces,
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::platform::testdata;
+
+    fn entry<'a>(section: &'a DetailSection, name: &str) -> Option<&'a str> {
+        section
+            .entries
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, v)| v.as_str())
+    }
+
+    #[test]
+    fn test_capabilities() {
+        assert_eq!(format_capabilities(0), "none");
+        assert_eq!(format_capabilities(0x1ff_ffff_ffff), "all");
+        assert_eq!(
+            format_capabilities((1 << 10) | (1 << 12)),
+            "cap_net_bind_service,cap_net_admin"
+        );
+        assert_eq!(capability_names(1 << 63), vec!["cap_63"]);
+    }
+
+    #[test]
+    fn test_parse_limits() {
+        let limits = parse_limits(&fs::read_to_string(testdata("proc-pid/limits")).unwrap());
+        assert_eq!(limits.len(), 16);
+
+        let nofile = &limits[7];
+        assert_eq!(nofile.resource, 7);
+        assert_eq!(nofile.name, "Max open files");
+        assert_eq!(nofile.soft, Some(1024));
+        assert_eq!(nofile.hard, Some(524288));
+        assert_eq!(nofile.units, "files");
+
+        assert_eq!(limits[0].name, "Max cpu time");
+        assert_eq!(limits[0].soft, None);
+        assert_eq!(limits[13].units, "");
+        assert_eq!(format_limit(None), "unlimited");
+    }
+
+    #[test]
+    fn test_limit_change() {
+        let nofile = ResourceLimit {
+            resource: 7,
+            name: "Max open files".to_string(),
+            soft: Some(1024),
+            hard: Some(524288),
+            units: "files".to_string(),
+        };
+        let resolve = |text: &str| LimitChange::parse(text)?.resolve(&nofile);
+
+        // Only the soft limit keeps the hard limit of the process
+        assert_eq!(
+            LimitChange::parse("65536"),
+            Ok(LimitChange {
+                soft: Some(65536),
+                hard: None
+            })
+        );
+        assert_eq!(resolve("65536"), Ok((Some(65536), Some(524288))));
+        assert_eq!(resolve(" 4096  8192 "), Ok((Some(4096), Some(8192))));
+        assert_eq!(resolve("unlimited unlimited"), Ok((None, None)));
+        assert_eq!(resolve("100 unlimited"), Ok((Some(100), None)));
+
+        assert!(LimitChange::parse("").is_err());
+        assert!(LimitChange::parse("1 2 3").is_err());
+        assert!(LimitChange::parse("many").is_err());
+        assert!(LimitChange::parse("-1").is_err());
+        assert!(resolve("1000000").is_err());
+        assert!(resolve("unlimited 10").is_err());
+
+        // The same change is checked against the hard limit of each process
+        let unlimited = ResourceLimit {
+            hard: None,
+            ..nofile.clone()
+        };
+        let change = LimitChange::parse("1000000").unwrap();
+        assert_eq!(change.resolve(&unlimited), Ok((Some(1000000), None)));
+    }
+
+    #[test]
+    fn test_read_process_details() {
+        let sections = read_process_details(&testdata("proc-pid")).unwrap();
+        let titles: Vec<&str> = sections.iter().map(|s| s.title).collect();
+        assert_eq!(
+            titles,
+            vec![
+                "Status",
+                "Security",
+                "Files",
+                "Scheduling",
+                "Limits",
+                "Cgroups",
+                "Namespaces"
+            ]
+        );
+
+        let status = &sections[0];
+        assert_eq!(entry(status, "Name"), Some("java"));
+        assert_eq!(entry(status, "Uid"), Some("1000 1000 1000 1000"));
+        assert_eq!(entry(status, "voluntary_ctxt_switches"), Some("1523"));
+        assert_eq!(entry(status, "nonvoluntary_ctxt_switches"), Some("87"));
+        // Fields not shown in the inspector
+        assert_eq!(entry(status, "SigQ"), None);
+
+        let security = &sections[1];
+        assert_eq!(entry(security, "Seccomp"), Some("2 (filter)"));
+        assert_eq!(entry(security, "NoNewPrivs"), Some("1"));
+        assert_eq!(
+            entry(security, "CapEff names"),
+            Some("cap_net_bind_service,cap_sys_ptrace")
+        );
+
+        // Missing links and files
+        assert_eq!(entry(&sections[2], "exe"), Some("(unavailable)"));
+        assert_eq!(entry(&sections[3], "wchan"), Some("do_epoll_wait"));
+        assert_eq!(entry(&sections[3], "oom_score_adj"), Some("-500"));
+        assert_eq!(
+            entry(&sections[4], "Max open files"),
+            Some("1024 / 524288 files")
+        );
+        assert_eq!(
+            entry(&sections[5], "0 (unified)"),
+            Some("/system.slice/app.service")
+        );
+        assert_eq!(entry(&sections[5], "3 cpu,cpuacct"), Some("/app"));
+
+        assert!(read_process_details(&testdata("proc-pid/missing")).is_err());
+    }
+
+    #[test]
+    fn test_read_process_details_self() {
+        let sections = read_process_details(Path::new("/proc/self")).unwrap();
+        let files = sections.iter().find(|s| s.title == "Files").unwrap();
+        assert!(entry(files, "cwd").is_some_and(|cwd| cwd.starts_with('/')));
+        let namespaces = sections.iter().find(|s| s.title == "Namespaces").unwrap();
+        assert!(entry(namespaces, "net").is_some_and(|ns| ns.starts_with("net:[")));
+    }
+}
@@ -0,0 +1,48 @@
+//! Network interface classification from sysfs
+//!
+//! Traffic that passes through virtual interfaces is also seen on the
+//! physical interface it ends up on: a container's veth pair is a port of
+//! a bridge whose traffic leaves through the host NIC, and a bond master
+//! carries what its slaves carry. Summing all interfaces counts such
+//! traffic two or three times, so the NetworkIO totals leave them out.
+
+use std::path::Path;
+
+/// Location of the network interface directories
+pub const SYS_CLASS_NET_PATH: &str = "/sys/class/net";
+
+/// Interface name prefixes of virtual interfaces created by container runtimes
+const VIRTUAL_PREFIXES: &[&str] = &["veth", "docker", "br-"];
+
+/// Check whether an interface only carries traffic that is also counted
+/// on another interface (loopback, veth, bridges and bond slaves)
+pub fn is_virtual_interface(sys_class_net: &Path, name: &str) -> bool {
+    if name == "lo" || VIRTUAL_PREFIXES.iter().any(|p| name.starts_with(p)) {
+        return true;
+    }
+    let dir = sys_class_net.join(name);
+    dir.join("bridge").is_dir() || dir.join("bonding_slave").is_dir()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::platform::testdata;
+
+    #[test]
+    fn test_is_virtual_interface() {
+        let dir = testdata("net");
+        // Physical interfaces and the bond master carry the real traffic
+        assert!(!is_virtual_interface(&dir, "eth0"));
+        assert!(!is_virtual_interface(&dir, "wlan0"));
+        assert!(!is_virtual_interface(&dir, "bond0"));
+
+        assert!(is_virtual_interface(&dir, "lo"));
+        assert!(is_virtual_interface(&dir, "eth1")); // bond slave
+        assert!(is_virtual_interface(&dir, "br0"));
+        assert!(is_virtual_interface(&dir, "docker0"));
+        assert!(is_virtual_interface(&dir, "veth1a2b3c"));
+        // Docker bridges are recognized by name even without sysfs
+        assert!(is_virtual_interface(&dir, "br-5f2d0c1e9a7b"));
+    }
+}
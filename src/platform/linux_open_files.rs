@@ -0,0 +1,616 @@
+//! Open files of a process from /proc, without lsof
+//!
+//! Lists the same entries as `lsof -p PID`: the working directory, root
+//! directory and executable, every file descriptor in `/proc/PID/fd` and
+//! the files mapped into memory. File descriptors get their offset and
+//! open flags from `/proc/PID/fdinfo/FD`; sockets are resolved through the
+//! socket tables of the process's network namespace in `/proc/PID/net`.
+
+use std::collections::{HashMap, HashSet};
+use std::fs::{self, Metadata};
+use std::io;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::os::unix::fs::{FileTypeExt, MetadataExt};
+use std::path::Path;
+
+use super::linux_sockets::parse_socket_link;
+
+/// One open file, with the columns of `lsof`
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct OpenFile {
+    /// File descriptor number, or "cwd", "rtd", "txt" and "mem"
+    pub fd: String,
+    /// File type: REG, DIR, CHR, FIFO, IPv4, IPv6, unix, a_inode, ...
+    pub file_type: String,
+    /// Access mode: "r", "w" or "u" (read and write), empty if unknown
+    pub mode: String,
+    /// Device as "major,minor"
+    pub device: String,
+    /// Size of regular files in bytes
+    pub size: Option<u64>,
+    /// File offset of file descriptors
+    pub offset: Option<u64>,
+    /// Inode number, or the protocol of internet sockets
+    pub node: String,
+    /// Open flags other than the access mode, in lsof's abbreviations
+    pub flags: String,
+    /// Path, socket addresses or description of the file
+    pub name: String,
+}
+
+/// Contents of a `/proc/PID/fdinfo/FD` file
+#[derive(Debug, Default, PartialEq)]
+struct FdInfo {
+    pos: Option<u64>,
+    /// Open flags (printed in octal by the kernel)
+    flags: u32,
+    /// Counter of an eventfd
+    eventfd_count: Option<u64>,
+}
+
+impl FdInfo {
+    fn parse(content: &str) -> Self {
+        let mut info = FdInfo::default();
+        for line in content.lines() {
+            let Some((key, value)) = line.split_once(':') else {
+                continue;
+            };
+            let value = value.trim();
+            match key {
+                "pos" => info.pos = value.parse().ok(),
+                "flags" => info.flags = u32::from_str_radix(value, 8).unwrap_or(0),
+                "eventfd-count" => info.eventfd_count = u64::from_str_radix(value, 16).ok(),
+                _ => {}
+            }
+        }
+        info
+    }
+}
+
+/// Open flags shown in the FLAGS column, with lsof's abbreviations.
+/// O_LARGEFILE is left out: the kernel sets it on every file of a 64-bit
+/// process.
+const OPEN_FLAGS: [(i32, &str); 8] = [
+    (libc::O_APPEND, "AP"),
+    (libc::O_CLOEXEC, "CX"),
+    (libc::O_NONBLOCK, "NB"),
+    (libc::O_SYNC, "SYN"),
+    (libc::O_DIRECT, "DIR"),
+    (libc::O_NOATIME, "NA"),
+    (libc::O_PATH, "PA"),
+    (libc::O_ASYNC, "ASYN"),
+];
+
+/// Access mode of open flags as shown by lsof
+fn access_mode(flags: u32) -> &'static str {
+    match flags as i32 & libc::O_ACCMODE {
+        libc::O_RDONLY => "r",
+        libc::O_WRONLY => "w",
+        _ => "u",
+    }
+}
+
+/// Open flags other than the access mode, e.g. "AP,CX"
+fn format_flags(flags: u32) -> String {
+    OPEN_FLAGS
+        .iter()
+        .filter(|(flag, _)| {
+            // O_SYNC includes the O_DSYNC bit, require all of its bits
+            flags as i32 & flag == *flag
+        })
+        .map(|(_, name)| *name)
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// Format a device number as "major,minor"
+fn format_device(dev: u64) -> String {
+    let major = ((dev >> 8) & 0xfff) | ((dev >> 32) & !0xfff);
+    let minor = (dev & 0xff) | ((dev >> 12) & !0xff);
+    format!("{},{}", major, minor)
+}
+
+/// lsof type of a file
+fn file_type(metadata: &Metadata) -> &'static str {
+    let file_type = metadata.file_type();
+    if file_type.is_file() {
+        "REG"
+    } else if file_type.is_dir() {
+        "DIR"
+    } else if file_type.is_char_device() {
+        "CHR"
+    } else if file_type.is_block_device() {
+        "BLK"
+    } else if file_type.is_fifo() {
+        "FIFO"
+    } else if file_type.is_socket() {
+        "sock"
+    } else if file_type.is_symlink() {
+        "LINK"
+    } else {
+        "unknown"
+    }
+}
+
+/// Type, node and name of a socket, as shown by lsof
+#[derive(Debug, Clone, PartialEq)]
+struct SocketDetails {
+    file_type: &'static str,
+    node: String,
+    name: String,
+}
+
+/// TCP state names by the hex state of the socket tables
+/// (include/net/tcp_states.h)
+fn tcp_state(state: &str) -> Option<&'static str> {
+    Some(match state {
+        "01" => "ESTABLISHED",
+        "02" => "SYN_SENT",
+        "03" => "SYN_RECV",
+        "04" => "FIN_WAIT1",
+        "05" => "FIN_WAIT2",
+        "06" => "TIME_WAIT",
+        "07" => "CLOSE",
+        "08" => "CLOSE_WAIT",
+        "09" => "LAST_ACK",
+        "0A" => "LISTEN",
+        "0B" => "CLOSING",
+        "0C" => "NEW_SYN_RECV",
+        _ => return None,
+    })
+}
+
+/// Parse an "ADDRESS:PORT" of the socket tables. Addresses are printed as
+/// 32-bit words in host byte order.
+fn parse_inet_address(hex: &str) -> Option<SocketAddr> {
+    let (address, port) = hex.split_once(':')?;
+    let port = u16::from_str_radix(port, 16).ok()?;
+    let ip: IpAddr = match address.len() {
+        8 => Ipv4Addr::from(u32::from_str_radix(address, 16).ok()?.to_ne_bytes()).into(),
+        32 => {
+            let mut bytes = [0u8; 16];
+            for (i, chunk) in bytes.chunks_mut(4).enumerate() {
+                let word = u32::from_str_radix(address.get(i * 8..i * 8 + 8)?, 16).ok()?;
+                chunk.copy_from_slice(&word.to_ne_bytes());
+            }
+            Ipv6Addr::from(bytes).into()
+        }
+        _ => return None,
+    };
+    Some(SocketAddr::new(ip, port))
+}
+
+/// Format a socket address like lsof, with "*" for the wildcard address
+fn format_inet_address(address: &SocketAddr) -> String {
+    if address.ip().is_unspecified() {
+        format!("*:{}", address.port())
+    } else {
+        address.to_string()
+    }
+}
+
+/// Parse the sockets of a tcp/tcp6/udp/udp6 table
+fn parse_inet_table(
+    content: &str,
+    protocol: &'static str,
+    file_type: &'static str,
+) -> Vec<(u64, SocketDetails)> {
+    // sl local_address rem_address st tx_queue:rx_queue tr:tm->when retrnsmt uid timeout inode
+    content
+        .lines()
+        .skip(1)
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let inode: u64 = fields.get(9)?.parse().ok()?;
+            let local = parse_inet_address(fields.get(1)?)?;
+            let remote = parse_inet_address(fields.get(2)?)?;
+
+            let mut name = format_inet_address(&local);
+            if !remote.ip().is_unspecified() || remote.port() != 0 {
+                name.push_str("->");
+                name.push_str(&format_inet_address(&remote));
+            }
+            // UDP sockets reuse the TCP states, but only to tell connected ones
+            if protocol == "TCP" {
+                if let Some(state) = tcp_state(fields.get(3)?) {
+                    name.push_str(&format!(" ({})", state));
+                }
+            }
+            let details = SocketDetails {
+                file_type,
+                node: protocol.to_string(),
+                name,
+            };
+            Some((inode, details))
+        })
+        .collect()
+}
+
+/// Parse the sockets of the unix table
+fn parse_unix_table(content: &str) -> Vec<(u64, SocketDetails)> {
+    // Num RefCount Protocol Flags Type St Inode Path
+    content
+        .lines()
+        .skip(1)
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let inode: u64 = fields.get(6)?.parse().ok()?;
+            let socket_type = match *fields.get(4)? {
+                "0001" => "STREAM",
+                "0002" => "DGRAM",
+                "0005" => "SEQPACKET",
+                _ => "UNKNOWN",
+            };
+            let name = match fields.get(7) {
+                Some(path) => format!("{} type={}", path, socket_type),
+                None => format!("type={}", socket_type),
+            };
+            let details = SocketDetails {
+                file_type: "unix",
+                node: inode.to_string(),
+                name,
+            };
+            Some((inode, details))
+        })
+        .collect()
+}
+
+/// Read the socket tables seen through a `/proc/PID/net` directory
+fn read_socket_tables(net_dir: &Path) -> HashMap<u64, SocketDetails> {
+    let tables = [
+        ("tcp", "TCP", "IPv4"),
+        ("tcp6", "TCP", "IPv6"),
+        ("udp", "UDP", "IPv4"),
+        ("udp6", "UDP", "IPv6"),
+    ];
+    let mut sockets = HashMap::new();
+    for (table, protocol, file_type) in tables {
+        if let Ok(content) = fs::read_to_string(net_dir.join(table)) {
+            sockets.extend(parse_inet_table(&content, protocol, file_type));
+        }
+    }
+    if let Ok(content) = fs::read_to_string(net_dir.join("unix")) {
+        sockets.extend(parse_unix_table(&content));
+    }
+    sockets
+}
+
+/// Describe the target of a file descriptor link
+fn describe_fd(
+    file: &mut OpenFile,
+    target: &str,
+    metadata: Option<&Metadata>,
+    info: &FdInfo,
+    sockets: &HashMap<u64, SocketDetails>,
+) {
+    if let Some(inode) = parse_socket_link(target) {
+        match sockets.get(&inode) {
+            Some(details) => {
+                file.file_type = details.file_type.to_string();
+                file.node = details.node.clone();
+                file.name = details.name.clone();
+            }
+            // Netlink, packet and other sockets without a table entry
+            None => {
+                file.file_type = "sock".to_string();
+                file.name = "protocol: unknown".to_string();
+            }
+        }
+    } else if target.starts_with("pipe:[") {
+        file.file_type = "FIFO".to_string();
+        file.name = "pipe".to_string();
+    } else if let Some(kind) = target.strip_prefix("anon_inode:") {
+        // eventfd, eventpoll, timerfd, signalfd, inotify, ...
+        file.file_type = "a_inode".to_string();
+        let kind = kind.trim_start_matches('[').trim_end_matches(']');
+        file.name = match info.eventfd_count {
+            Some(count) => format!("[{}] count={}", kind, count),
+            None => format!("[{}]", kind),
+        };
+    } else {
+        // Paths, including memfds ("/memfd:NAME (deleted)")
+        file.file_type = metadata.map_or("unknown", file_type).to_string();
+        file.name = target.to_string();
+        if let Some(metadata) = metadata {
+            if metadata.file_type().is_file() {
+                file.size = Some(metadata.size());
+            } else if metadata.file_type().is_char_device()
+                || metadata.file_type().is_block_device()
+            {
+                file.device = format_device(metadata.rdev());
+            }
+        }
+    }
+}
+
+/// Read one file descriptor, None if it was closed in the meantime
+fn read_fd(proc_dir: &Path, fd: u32, sockets: &HashMap<u64, SocketDetails>) -> Option<OpenFile> {
+    let link = proc_dir.join("fd").join(fd.to_string());
+    let target = fs::read_link(&link).ok()?;
+    let target = target.to_string_lossy();
+    let info = fs::read_to_string(proc_dir.join("fdinfo").join(fd.to_string()))
+        .map(|content| FdInfo::parse(&content))
+        .unwrap_or_default();
+    // Follows the link, which also works for sockets, pipes and deleted files
+    let metadata = fs::metadata(&link).ok();
+
+    let mut file = OpenFile {
+        fd: fd.to_string(),
+        mode: access_mode(info.flags).to_string(),
+        flags: format_flags(info.flags),
+        offset: info.pos,
+        ..Default::default()
+    };
+    if let Some(ref metadata) = metadata {
+        file.device = format_device(metadata.dev());
+        file.node = metadata.ino().to_string();
+    }
+    describe_fd(&mut file, &target, metadata.as_ref(), &info, sockets);
+    Some(file)
+}
+
+/// Entry for the working directory, root directory or executable
+fn special_file(proc_dir: &Path, link: &str, fd: &str) -> Option<OpenFile> {
+    let path = proc_dir.join(link);
+    let target = fs::read_link(&path).ok()?;
+    let metadata = fs::metadata(&path).ok();
+    let mut file = OpenFile {
+        fd: fd.to_string(),
+        name: target.to_string_lossy().into_owned(),
+        file_type: metadata.as_ref().map_or("unknown", file_type).to_string(),
+        ..Default::default()
+    };
+    if let Some(metadata) = metadata {
+        file.device = format_device(metadata.dev());
+        file.node = metadata.ino().to_string();
+        if metadata.is_file() {
+            file.size = Some(metadata.size());
+        }
+    }
+    Some(file)
+}
+
+/// Files mapped into memory (from /proc/PID/maps), each listed once
+fn mapped_files(maps: &str, exe: Option<&str>) -> Vec<OpenFile> {
+    let mut seen: HashSet<&str> = HashSet::new();
+    let mut files = Vec::new();
+    // address perms offset dev inode path
+    for line in maps.lines() {
+        let mut fields = line.splitn(6, ' ');
+        let (Some(_), Some(_), Some(_), Some(dev), Some(inode), Some(path)) = (
+            fields.next(),
+            fields.next(),
+            fields.next(),
+            fields.next(),
+            fields.next(),
+            fields.next(),
+        ) else {
+            continue;
+        };
+        let path = path.trim_start();
+        // Anonymous mappings ([heap], [stack], ...) have inode 0
+        if inode == "0" || !path.starts_with('/') || Some(path) == exe || !seen.insert(path) {
+            continue;
+        }
+        let device = dev
+            .split_once(':')
+            .and_then(|(major, minor)| {
+                let major = u32::from_str_radix(major, 16).ok()?;
+                let minor = u32::from_str_radix(minor, 16).ok()?;
+                Some(format!("{},{}", major, minor))
+            })
+            .unwrap_or_default();
+        files.push(OpenFile {
+            fd: "mem".to_string(),
+            file_type: "REG".to_string(),
+            device,
+            size: fs::metadata(path).ok().map(|m| m.size()),
+            node: inode.to_string(),
+            name: path.to_string(),
+            ..Default::default()
+        });
+    }
+    files
+}
+
+/// List the open files of the process whose `/proc/PID` directory is
+/// `proc_dir`. Fails if the file descriptors are not readable, which
+/// needs the same user or root.
+pub fn read_open_files(proc_dir: &Path) -> io::Result<Vec<OpenFile>> {
+    let mut fds: Vec<u32> = fs::read_dir(proc_dir.join("fd"))?
+        .flatten()
+        .filter_map(|entry| entry.file_name().to_str()?.parse().ok())
+        .collect();
+    fds.sort_unstable();
+
+    let mut files: Vec<OpenFile> = [("cwd", "cwd"), ("root", "rtd"), ("exe", "txt")]
+        .into_iter()
+        .filter_map(|(link, fd)| special_file(proc_dir, link, fd))
+        .collect();
+
+    let sockets = read_socket_tables(&proc_dir.join("net"));
+    files.extend(
+        fds.into_iter()
+            .filter_map(|fd| read_fd(proc_dir, fd, &sockets)),
+    );
+
+    if let Ok(maps) = fs::read_to_string(proc_dir.join("maps")) {
+        let exe = files.iter().find(|f| f.fd == "txt").map(|f| f.name.clone());
+        files.extend(mapped_files(&maps, exe.as_deref()));
+    }
+    Ok(files)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::platform::testdata;
+    use std::io::{Seek, SeekFrom, Write};
+
+    #[test]
+    fn test_parse_fdinfo() {
+        let info = FdInfo::parse("pos:\t4096\nflags:\t02102002\nmnt_id:\t25\nino:\t1234\n");
+        assert_eq!(info.pos, Some(4096));
+        assert_eq!(access_mode(info.flags), "u");
+        assert_eq!(format_flags(info.flags), "AP,CX");
+        assert_eq!(info.eventfd_count, None);
+
+        let info = FdInfo::parse("pos:\t0\nflags:\t02004000\neventfd-count:  1f\n");
+        assert_eq!(access_mode(info.flags), "r");
+        assert_eq!(format_flags(info.flags), "CX,NB");
+        assert_eq!(info.eventfd_count, Some(31));
+
+        assert_eq!(access_mode(0o1), "w");
+        assert_eq!(FdInfo::parse(""), FdInfo::default());
+    }
+
+    #[test]
+    fn test_format_device() {
+        assert_eq!(format_device(0x0801), "8,1");
+        assert_eq!(format_device(0x8800), "136,0");
+        // Major numbers above 255 and minor numbers above 255 use more bits
+        assert_eq!(format_device(0x1_0301), "259,1");
+        assert_eq!(format_device(0x10_0800), "8,256");
+    }
+
+    #[test]
+    fn test_parse_inet_address() {
+        assert_eq!(
+            parse_inet_address("0100007F:0050"),
+            Some("127.0.0.1:80".parse().unwrap())
+        );
+        assert_eq!(
+            parse_inet_address("00000000000000000000000001000000:0016"),
+            Some("[::1]:22".parse().unwrap())
+        );
+        assert_eq!(parse_inet_address("0100007F"), None);
+        assert_eq!(parse_inet_address("7F:0050"), None);
+    }
+
+    #[test]
+    fn test_read_socket_tables() {
+        let sockets = read_socket_tables(&testdata("net-proc"));
+        let name = |inode: u64| sockets.get(&inode).map(|s| s.name.as_str());
+
+        assert_eq!(name(1001), Some("*:80 (LISTEN)"));
+        assert_eq!(
+            name(1002),
+            Some("127.0.0.1:80->127.0.0.1:41652 (ESTABLISHED)")
+        );
+        assert_eq!(
+            name(1003),
+            Some("15.0.0.10:22->1.0.0.10:54209 (ESTABLISHED)")
+        );
+        assert_eq!(sockets[&1001].file_type, "IPv4");
+        assert_eq!(sockets[&1001].node, "TCP");
+
+        assert_eq!(name(2001), Some("*:22 (LISTEN)"));
+        assert_eq!(sockets[&2001].file_type, "IPv6");
+
+        // UDP sockets have no state
+        assert_eq!(name(3001), Some("*:68"));
+        assert_eq!(sockets[&3001].node, "UDP");
+
+        assert_eq!(name(4001), Some("/run/systemd/notify type=DGRAM"));
+        assert_eq!(name(4002), Some("type=STREAM"));
+        assert_eq!(sockets[&4002].file_type, "unix");
+        assert_eq!(sockets[&4002].node, "4002");
+    }
+
+    #[test]
+    fn test_describe_fd() {
+        let sockets = read_socket_tables(&testdata("net-proc"));
+        let describe = |target: &str, info: &FdInfo| {
+            let mut file = OpenFile::default();
+            describe_fd(&mut file, target, None, info, &sockets);
+            (file.file_type, file.name)
+        };
+        let info = FdInfo::default();
+
+        assert_eq!(
+            describe("socket:[1002]", &info),
+            (
+                "IPv4".to_string(),
+                "127.0.0.1:80->127.0.0.1:41652 (ESTABLISHED)".to_string()
+            )
+        );
+        assert_eq!(
+            describe("socket:[77]", &info),
+            ("sock".to_string(), "protocol: unknown".to_string())
+        );
+        assert_eq!(
+            describe("pipe:[5123]", &info),
+            ("FIFO".to_string(), "pipe".to_string())
+        );
+        assert_eq!(
+            describe("anon_inode:[eventpoll]", &info),
+            ("a_inode".to_string(), "[eventpoll]".to_string())
+        );
+        assert_eq!(
+            describe("anon_inode:inotify", &info),
+            ("a_inode".to_string(), "[inotify]".to_string())
+        );
+        let eventfd = FdInfo {
+            eventfd_count: Some(3),
+            ..Default::default()
+        };
+        assert_eq!(
+            describe("anon_inode:[eventfd]", &eventfd),
+            ("a_inode".to_string(), "[eventfd] count=3".to_string())
+        );
+        assert_eq!(
+            describe("/memfd:wayland-shm (deleted)", &info),
+            (
+                "unknown".to_string(),
+                "/memfd:wayland-shm (deleted)".to_string()
+            )
+        );
+    }
+
+    #[test]
+    fn test_mapped_files() {
+        let maps = "\
+55d0c0a00000-55d0c0a28000 r--p 00000000 08:01 1311 /usr/bin/cat
+7f1e2c000000-7f1e2c028000 r--p 00000000 08:01 2622 /usr/lib/x86_64-linux-gnu/libc.so.6
+7f1e2c028000-7f1e2c1bd000 r-xp 00028000 08:01 2622 /usr/lib/x86_64-linux-gnu/libc.so.6
+7f1e2c200000-7f1e2c221000 rw-p 00000000 00:00 0    [heap]
+7f1e2c300000-7f1e2c301000 rw-s 00000000 00:01 4242 /memfd:pulseaudio (deleted)
+";
+        let files = mapped_files(maps, Some("/usr/bin/cat"));
+        let names: Vec<&str> = files.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec![
+                "/usr/lib/x86_64-linux-gnu/libc.so.6",
+                "/memfd:pulseaudio (deleted)"
+            ]
+        );
+        assert_eq!(files[0].fd, "mem");
+        assert_eq!(files[0].device, "8,1");
+        assert_eq!(files[0].node, "2622");
+    }
+
+    #[test]
+    fn test_read_open_files_self() {
+        let path = std::env::temp_dir().join(format!("htop-open-files-{}", std::process::id()));
+        let mut file = fs::File::create(&path).unwrap();
+        file.write_all(b"0123456789").unwrap();
+        file.seek(SeekFrom::Start(4)).unwrap();
+
+        let files = read_open_files(Path::new("/proc/self")).unwrap();
+        let entry = files
+            .iter()
+            .find(|f| f.name == path.to_string_lossy())
+            .expect("temporary file is listed");
+        assert_eq!(entry.file_type, "REG");
+        assert_eq!(entry.mode, "w");
+        assert_eq!(entry.size, Some(10));
+        assert_eq!(entry.offset, Some(4));
+        assert!(entry.flags.contains("CX"));
+        assert!(files.iter().any(|f| f.fd == "cwd" && f.file_type == "DIR"));
+
+        drop(file);
+        fs::remove_file(&path).unwrap();
+        assert!(read_open_files(Path::new("/proc/self/missing")).is_err());
+    }
+}
@@ -0,0 +1,310 @@
+//! Memory mappings of a process from /proc/PID/smaps
+//!
+//! The background scanner only keeps the totals of `smaps_rollup`. This
+//! module reads every mapping of `/proc/PID/smaps` with its own RSS, PSS,
+//! dirty, swap and transparent huge page usage, so the mappings behind a
+//! large PSS can be found. Mappings can be merged per backing file.
+
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use super::linux::is_deleted_mapping;
+
+/// Name shown for mappings without a path
+pub const ANONYMOUS_NAME: &str = "[anon]";
+
+/// One mapping of `/proc/PID/smaps`, or all mappings of one file after
+/// `group_by_file`. Sizes are in kB.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct MemoryMapping {
+    /// Start address (of the first mapping when grouped)
+    pub start: u64,
+    /// End address (exclusive)
+    pub end: u64,
+    /// Permissions like "r-xp" (combined over all mappings when grouped)
+    pub perms: String,
+    /// Offset into the backing file
+    pub offset: u64,
+    /// Path of the backing file, or a pseudo name like "[heap]"
+    pub path: String,
+    pub size: u64,
+    pub rss: u64,
+    pub pss: u64,
+    /// Shared and private dirty pages
+    pub dirty: u64,
+    pub swap: u64,
+    /// Transparent huge pages (anonymous, shmem and file backed)
+    pub thp: u64,
+    /// The backing file has been deleted (or replaced) since it was mapped
+    pub deleted: bool,
+    /// Number of mappings merged into this one
+    pub count: usize,
+}
+
+impl MemoryMapping {
+    /// Path for display, anonymous mappings have no path
+    pub fn name(&self) -> &str {
+        if self.path.is_empty() {
+            ANONYMOUS_NAME
+        } else {
+            &self.path
+        }
+    }
+
+    /// Parse a mapping header line:
+    /// `7f1234-7f5678 r-xp 00000000 08:01 12345   /lib/libc.so.6`
+    fn parse_header(line: &str) -> Option<Self> {
+        let mut fields = line.splitn(6, ' ');
+        let (start, end) = fields.next()?.split_once('-')?;
+        let perms = fields.next()?;
+        let offset = fields.next()?;
+        // Device and inode
+        fields.next()?;
+        fields.next()?;
+        let path = fields.next().unwrap_or("").trim_start();
+
+        Some(MemoryMapping {
+            start: u64::from_str_radix(start, 16).ok()?,
+            end: u64::from_str_radix(end, 16).ok()?,
+            perms: perms.to_string(),
+            offset: u64::from_str_radix(offset, 16).ok()?,
+            path: path.to_string(),
+            deleted: is_deleted_mapping(path),
+            count: 1,
+            ..Default::default()
+        })
+    }
+
+    /// Add the value of a `Name:   1234 kB` line to the matching field
+    fn add_field(&mut self, key: &str, value: &str) {
+        let Some(kb) = value
+            .split_whitespace()
+            .next()
+            .and_then(|v| v.parse::<u64>().ok())
+        else {
+            return;
+        };
+        match key {
+            "Size" => self.size += kb,
+            "Rss" => self.rss += kb,
+            "Pss" => self.pss += kb,
+            "Shared_Dirty" | "Private_Dirty" => self.dirty += kb,
+            "Swap" => self.swap += kb,
+            "AnonHugePages" | "ShmemPmdMapped" | "FilePmdMapped" => self.thp += kb,
+            _ => {}
+        }
+    }
+
+    /// Merge another mapping of the same file into this one
+    fn merge(&mut self, other: &MemoryMapping) {
+        self.start = self.start.min(other.start);
+        self.end = self.end.max(other.end);
+        self.offset = self.offset.min(other.offset);
+        self.perms = self
+            .perms
+            .chars()
+            .zip(other.perms.chars())
+            .map(|(a, b)| if a == '-' { b } else { a })
+            .collect();
+        self.size += other.size;
+        self.rss += other.rss;
+        self.pss += other.pss;
+        self.dirty += other.dirty;
+        self.swap += other.swap;
+        self.thp += other.thp;
+        self.deleted |= other.deleted;
+        self.count += other.count;
+    }
+}
+
+/// Order of the mappings
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MappingSortKey {
+    Address,
+    Size,
+    Rss,
+    #[default]
+    Pss,
+    Dirty,
+    Swap,
+    Thp,
+    Path,
+}
+
+impl MappingSortKey {
+    /// All sort keys in cycling order
+    pub const ALL: [MappingSortKey; 8] = [
+        MappingSortKey::Address,
+        MappingSortKey::Size,
+        MappingSortKey::Rss,
+        MappingSortKey::Pss,
+        MappingSortKey::Dirty,
+        MappingSortKey::Swap,
+        MappingSortKey::Thp,
+        MappingSortKey::Path,
+    ];
+
+    /// The sort key after this one
+    pub fn next(self) -> Self {
+        let index = Self::ALL.iter().position(|&k| k == self).unwrap_or(0);
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+
+    /// Title of the sort key
+    pub fn name(self) -> &'static str {
+        match self {
+            MappingSortKey::Address => "address",
+            MappingSortKey::Size => "size",
+            MappingSortKey::Rss => "RSS",
+            MappingSortKey::Pss => "PSS",
+            MappingSortKey::Dirty => "dirty",
+            MappingSortKey::Swap => "swap",
+            MappingSortKey::Thp => "THP",
+            MappingSortKey::Path => "path",
+        }
+    }
+
+    fn compare(self, a: &MemoryMapping, b: &MemoryMapping) -> Ordering {
+        // Largest first, ties ordered by address
+        let by_value = match self {
+            MappingSortKey::Address => Ordering::Equal,
+            MappingSortKey::Size => b.size.cmp(&a.size),
+            MappingSortKey::Rss => b.rss.cmp(&a.rss),
+            MappingSortKey::Pss => b.pss.cmp(&a.pss),
+            MappingSortKey::Dirty => b.dirty.cmp(&a.dirty),
+            MappingSortKey::Swap => b.swap.cmp(&a.swap),
+            MappingSortKey::Thp => b.thp.cmp(&a.thp),
+            MappingSortKey::Path => a.name().cmp(b.name()),
+        };
+        by_value.then_with(|| a.start.cmp(&b.start))
+    }
+
+    pub fn sort(self, mappings: &mut [MemoryMapping]) {
+        mappings.sort_by(|a, b| self.compare(a, b));
+    }
+}
+
+/// Parse the contents of a smaps file
+pub fn parse_smaps(content: &str) -> Vec<MemoryMapping> {
+    let mut mappings: Vec<MemoryMapping> = Vec::new();
+    for line in content.lines() {
+        // Field lines start with "Name:", header lines with an address range
+        let first = line.split(' ').next().unwrap_or("");
+        if let Some(key) = first.strip_suffix(':') {
+            if let Some(mapping) = mappings.last_mut() {
+                mapping.add_field(key, &line[first.len()..]);
+            }
+        } else if let Some(mapping) = MemoryMapping::parse_header(line) {
+            mappings.push(mapping);
+        }
+    }
+    mappings
+}
+
+/// Merge the mappings by backing file (all anonymous mappings together),
+/// in order of their first mapping
+pub fn group_by_file(mappings: &[MemoryMapping]) -> Vec<MemoryMapping> {
+    let mut groups: Vec<MemoryMapping> = Vec::new();
+    let mut index: HashMap<&str, usize> = HashMap::new();
+    for mapping in mappings {
+        match index.get(mapping.name()) {
+            Some(&i) => groups[i].merge(mapping),
+            None => {
+                index.insert(mapping.name(), groups.len());
+                groups.push(mapping.clone());
+            }
+        }
+    }
+    groups
+}
+
+/// Read the mappings of a process from its `/proc/PID` directory
+pub fn read_memory_maps(proc_dir: &Path) -> io::Result<Vec<MemoryMapping>> {
+    Ok(parse_smaps(&fs::read_to_string(proc_dir.join("smaps"))?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::platform::testdata;
+
+    fn fixture() -> Vec<MemoryMapping> {
+        parse_smaps(&fs::read_to_string(testdata("smaps")).unwrap())
+    }
+
+    #[test]
+    fn test_parse_smaps() {
+        let mappings = fixture();
+        assert_eq!(mappings.len(), 6);
+
+        let text = &mappings[0];
+        assert_eq!(text.start, 0x5555_5555_4000);
+        assert_eq!(text.end, 0x5555_5557_8000);
+        assert_eq!(text.perms, "r-xp");
+        assert_eq!(text.offset, 0x1000);
+        assert_eq!(text.path, "/usr/bin/java");
+        assert_eq!((text.size, text.rss, text.pss), (144, 140, 70));
+        assert_eq!(text.count, 1);
+        assert!(!text.deleted);
+
+        let heap = &mappings[2];
+        assert_eq!(heap.name(), "[heap]");
+        assert_eq!(heap.dirty, 2048 + 100);
+        assert_eq!(heap.thp, 2048);
+
+        // Anonymous mapping without a path
+        assert_eq!(mappings[3].path, "");
+        assert_eq!(mappings[3].name(), ANONYMOUS_NAME);
+        assert_eq!(mappings[3].swap, 512);
+
+        assert!(mappings[4].deleted);
+        assert_eq!(mappings[4].path, "/tmp/libjffi-1234.so (deleted)");
+        // memfd mappings are always "deleted"
+        assert!(!mappings[5].deleted);
+    }
+
+    #[test]
+    fn test_group_by_file() {
+        let groups = group_by_file(&fixture());
+        assert_eq!(groups.len(), 5);
+
+        let java = &groups[0];
+        assert_eq!(java.path, "/usr/bin/java");
+        assert_eq!(java.count, 2);
+        assert_eq!(java.perms, "rwxp");
+        assert_eq!(java.size, 144 + 8);
+        assert_eq!(java.pss, 70 + 8);
+        assert_eq!(java.start, 0x5555_5555_4000);
+        assert_eq!(java.end, 0x5555_5557_a000);
+
+        assert_eq!(groups[1].name(), "[heap]");
+        assert_eq!(groups[2].name(), ANONYMOUS_NAME);
+    }
+
+    #[test]
+    fn test_sort() {
+        let mut mappings = fixture();
+        MappingSortKey::Pss.sort(&mut mappings);
+        assert_eq!(mappings[0].name(), "[heap]");
+
+        MappingSortKey::Address.sort(&mut mappings);
+        assert!(mappings.windows(2).all(|w| w[0].start < w[1].start));
+
+        MappingSortKey::Path.sort(&mut mappings);
+        assert_eq!(mappings[0].name(), "/memfd:jit (deleted)");
+
+        assert_eq!(MappingSortKey::Path.next(), MappingSortKey::Address);
+        assert_eq!(MappingSortKey::default().next(), MappingSortKey::Dirty);
+    }
+
+    #[test]
+    fn test_read_memory_maps_self() {
+        let mappings = read_memory_maps(Path::new("/proc/self")).unwrap();
+        assert!(!mappings.is_empty());
+        assert!(mappings.iter().any(|m| m.rss > 0));
+        assert!(read_memory_maps(Path::new("/proc/self/missing")).is_err());
+    }
+}
@@ -0,0 +1,227 @@
+//! Per-process sockets and network traffic
+//!
+//! The kernel does not account network traffic per process. What it does
+//! provide is the sockets each process holds (`socket:[INODE]` links in
+//! `/proc/PID/fd`), the socket tables of its network namespace
+//! (`/proc/PID/net/{tcp,tcp6,udp,udp6,unix}`) and the interface counters of
+//! that namespace (`/proc/PID/net/dev`).
+//!
+//! The traffic of a namespace is attributed to the processes in it by
+//! their share of the namespace's active internet sockets (connected TCP
+//! sockets and UDP sockets, but not listening ones). For a container with
+//! a single server this is exact; on a busy host it is an estimate that
+//! still points at the process generating the traffic.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+/// Socket tables of a network namespace, relative to `/proc/PID/net`
+const INET_TABLES: [&str; 4] = ["tcp", "tcp6", "udp", "udp6"];
+
+/// TCP state of a listening socket (TCP_LISTEN in include/net/tcp_states.h)
+const TCP_LISTEN: &str = "0A";
+
+/// Sockets held by one process
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct ProcessSockets {
+    /// Inode of the network namespace (from `/proc/PID/ns/net`)
+    pub netns: u64,
+    /// Inodes of the open sockets
+    pub inodes: Vec<u64>,
+}
+
+/// Socket tables and traffic counters of one network namespace
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct NetNamespace {
+    /// Known TCP, UDP and Unix sockets by inode, true if the socket
+    /// counts towards the traffic share (an active internet socket)
+    pub sockets: HashMap<u64, bool>,
+    /// Bytes received over all counted interfaces
+    pub rx_bytes: u64,
+    /// Bytes transmitted over all counted interfaces
+    pub tx_bytes: u64,
+}
+
+impl NetNamespace {
+    /// Read the namespace seen through `net_dir` (a `/proc/PID/net`
+    /// directory). Interfaces for which `skip_interface` returns true are
+    /// not counted. Returns None if the interface counters are not
+    /// readable, e.g. because the process has exited.
+    pub fn read(net_dir: &Path, skip_interface: impl Fn(&str) -> bool) -> Option<Self> {
+        let dev = fs::read_to_string(net_dir.join("dev")).ok()?;
+        let mut namespace = NetNamespace::default();
+        for table in INET_TABLES {
+            if let Ok(content) = fs::read_to_string(net_dir.join(table)) {
+                let is_tcp = table.starts_with("tcp");
+                namespace
+                    .sockets
+                    .extend(parse_inet_sockets(&content, is_tcp));
+            }
+        }
+        if let Ok(content) = fs::read_to_string(net_dir.join("unix")) {
+            namespace
+                .sockets
+                .extend(parse_unix_sockets(&content).map(|inode| (inode, false)));
+        }
+        for (name, rx, tx) in parse_net_dev(&dev) {
+            if !skip_interface(name) {
+                namespace.rx_bytes += rx;
+                namespace.tx_bytes += tx;
+            }
+        }
+        Some(namespace)
+    }
+
+    /// Number of TCP, UDP and Unix sockets among `inodes`, and how many of
+    /// them are active internet sockets
+    pub fn count(&self, inodes: &[u64]) -> (u32, u32) {
+        inodes
+            .iter()
+            .filter_map(|inode| self.sockets.get(inode))
+            .fold((0, 0), |(sockets, active), &is_active| {
+                (sockets + 1, active + is_active as u32)
+            })
+    }
+}
+
+/// Parse the inode of a `socket:[INODE]` fd link target
+pub fn parse_socket_link(target: &str) -> Option<u64> {
+    target
+        .strip_prefix("socket:[")?
+        .strip_suffix(']')?
+        .parse()
+        .ok()
+}
+
+/// Parse the inode of a `net:[INODE]` namespace link target
+pub fn parse_netns_link(target: &str) -> Option<u64> {
+    target
+        .strip_prefix("net:[")?
+        .strip_suffix(']')?
+        .parse()
+        .ok()
+}
+
+/// Read the sockets of a process from its `/proc/PID` directory.
+/// Returns None if the fd directory is not readable (other users'
+/// processes without root).
+pub fn read_process_sockets(proc_dir: &Path) -> Option<ProcessSockets> {
+    let netns = fs::read_link(proc_dir.join("ns/net")).ok()?;
+    let netns = parse_netns_link(netns.to_str()?)?;
+    let inodes = fs::read_dir(proc_dir.join("fd"))
+        .ok()?
+        .flatten()
+        .filter_map(|entry| fs::read_link(entry.path()).ok())
+        .filter_map(|target| parse_socket_link(target.to_str()?))
+        .collect();
+    Some(ProcessSockets { netns, inodes })
+}
+
+/// Parse a tcp/tcp6/udp/udp6 table into (inode, active) pairs. TCP
+/// sockets are active unless listening, UDP sockets are always active.
+fn parse_inet_sockets(content: &str, is_tcp: bool) -> impl Iterator<Item = (u64, bool)> + '_ {
+    // sl local_address rem_address st tx_queue:rx_queue tr:tm->when retrnsmt uid timeout inode
+    content.lines().skip(1).filter_map(move |line| {
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        let inode: u64 = fields.get(9)?.parse().ok()?;
+        // Sockets in TIME_WAIT no longer belong to a process
+        if inode == 0 {
+            return None;
+        }
+        let active = !is_tcp || *fields.get(3)? != TCP_LISTEN;
+        Some((inode, active))
+    })
+}
+
+/// Parse the inodes of the unix socket table
+fn parse_unix_sockets(content: &str) -> impl Iterator<Item = u64> + '_ {
+    // Num RefCount Protocol Flags Type St Inode Path
+    content
+        .lines()
+        .skip(1)
+        .filter_map(|line| line.split_whitespace().nth(6)?.parse().ok())
+        .filter(|&inode| inode != 0)
+}
+
+/// Parse /proc/net/dev into (interface, rx bytes, tx bytes)
+fn parse_net_dev(content: &str) -> impl Iterator<Item = (&str, u64, u64)> {
+    content.lines().skip(2).filter_map(|line| {
+        let (name, counters) = line.split_once(':')?;
+        let counters: Vec<u64> = counters
+            .split_whitespace()
+            .map(|v| v.parse().unwrap_or(0))
+            .collect();
+        Some((name.trim(), *counters.first()?, *counters.get(8)?))
+    })
+}
+
+/// Byte rate between two counter samples taken `interval_ms` apart
+/// (NaN without a previous sample or interval)
+pub fn byte_rate(prev: Option<u64>, current: u64, interval_ms: u64) -> f64 {
+    match prev {
+        Some(prev) if interval_ms > 0 => {
+            current.saturating_sub(prev) as f64 * 1000.0 / interval_ms as f64
+        }
+        _ => f64::NAN,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::platform::testdata;
+
+    #[test]
+    fn test_parse_links() {
+        assert_eq!(parse_socket_link("socket:[1002]"), Some(1002));
+        assert_eq!(parse_socket_link("pipe:[1002]"), None);
+        assert_eq!(parse_socket_link("/dev/null"), None);
+        assert_eq!(parse_netns_link("net:[4026531840]"), Some(4026531840));
+        assert_eq!(parse_netns_link("mnt:[4026531841]"), None);
+    }
+
+    #[test]
+    fn test_read_namespace() {
+        let namespace = NetNamespace::read(&testdata("net-proc"), |name| name == "lo").unwrap();
+        // Listening TCP sockets are known but not active
+        assert_eq!(namespace.sockets.get(&1001), Some(&false));
+        assert_eq!(namespace.sockets.get(&1002), Some(&true));
+        assert_eq!(namespace.sockets.get(&2001), Some(&false));
+        assert_eq!(namespace.sockets.get(&3001), Some(&true));
+        assert_eq!(namespace.sockets.get(&4002), Some(&false));
+        // TIME_WAIT sockets have no inode
+        assert!(!namespace.sockets.contains_key(&0));
+        assert_eq!(namespace.sockets.len(), 7);
+
+        assert_eq!(namespace.rx_bytes, 1048576 + 40000);
+        assert_eq!(namespace.tx_bytes, 524288 + 30000);
+    }
+
+    #[test]
+    fn test_namespace_count() {
+        let namespace = NetNamespace::read(&testdata("net-proc"), |_| false).unwrap();
+        assert_eq!(namespace.rx_bytes, 500000 + 1048576 + 40000);
+        // Listening socket, connection, unix socket and an unknown inode
+        assert_eq!(namespace.count(&[1001, 1002, 4001, 9999]), (3, 1));
+        assert_eq!(namespace.count(&[]), (0, 0));
+    }
+
+    #[test]
+    fn test_read_missing() {
+        assert_eq!(read_process_sockets(&testdata("net-proc/missing")), None);
+        assert_eq!(
+            NetNamespace::read(&testdata("net-proc/missing"), |_| false),
+            None
+        );
+    }
+
+    #[test]
+    fn test_byte_rate() {
+        assert_eq!(byte_rate(Some(1000), 3000, 2000), 1000.0);
+        // Counters reset when an interface goes away
+        assert_eq!(byte_rate(Some(3000), 1000, 1000), 0.0);
+        assert!(byte_rate(None, 3000, 1000).is_nan());
+        assert!(byte_rate(Some(1000), 3000, 0).is_nan());
+    }
+}
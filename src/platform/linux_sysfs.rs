@@ -0,0 +1,43 @@
+//! Helpers for reading single-value sysfs and procfs attribute files
+//!
+//! Attributes like `/sys/block/zram0/disksize` hold one value followed by a
+//! newline. Missing or unreadable files are common (optional drivers,
+//! permissions), so the helpers return None instead of an error.
+
+use std::fs;
+use std::path::Path;
+
+/// Read an attribute without the trailing newline
+pub fn read_trimmed(path: &Path) -> Option<String> {
+    Some(fs::read_to_string(path).ok()?.trim().to_string())
+}
+
+/// Read an attribute holding an unsigned number
+pub fn read_u64(path: &Path) -> Option<u64> {
+    fs::read_to_string(path).ok()?.trim().parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::platform::testdata;
+
+    #[test]
+    fn test_read_trimmed() {
+        let hwmon = testdata("temperature/coretemp/hwmon/hwmon3");
+        assert_eq!(
+            read_trimmed(&hwmon.join("name")),
+            Some("coretemp".to_string())
+        );
+        assert_eq!(read_trimmed(&hwmon.join("missing")), None);
+    }
+
+    #[test]
+    fn test_read_u64() {
+        let pool = testdata("hugepages/hugepages-2048kB");
+        assert_eq!(read_u64(&pool.join("nr_hugepages")), Some(512));
+        assert_eq!(read_u64(&pool.join("missing")), None);
+        // Not a number
+        assert_eq!(read_u64(&testdata("zram/swaps")), None);
+    }
+}
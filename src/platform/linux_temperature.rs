@@ -0,0 +1,314 @@
+//! CPU temperatures from hwmon and thermal zone sensors
+//!
+//! CPU sensor drivers register a hwmon device whose `name` identifies the
+//! driver and whose `tempN_input` files hold readings in millidegrees
+//! Celsius, optionally described by `tempN_label`:
+//!
+//! - coretemp (Intel): one device per package with "Package id P" and a
+//!   "Core C" reading per physical core
+//! - k10temp / zenpower (AMD): "Tctl"/"Tdie" for the package and a
+//!   "TccdN" reading per core complex die
+//! - cpu_thermal (ARM SoCs): a single reading for the whole SoC
+//!
+//! Without any of these the `x86_pkg_temp` and `cpu-thermal` thermal zones
+//! provide a package temperature. Logical CPUs are mapped to their sensor
+//! through `topology/physical_package_id` and `topology/core_id`.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::linux_sysfs::read_trimmed;
+use crate::core::CpuTemperatures;
+
+/// Location of the hwmon devices
+pub const HWMON_PATH: &str = "/sys/class/hwmon";
+
+/// Location of the thermal zones
+pub const THERMAL_PATH: &str = "/sys/class/thermal";
+
+/// Location of the per-CPU topology directories
+pub const CPU_PATH: &str = "/sys/devices/system/cpu";
+
+/// Thermal zone types that measure the CPU package
+const CPU_THERMAL_ZONES: &[&str] = &["x86_pkg_temp", "cpu-thermal", "cpu_thermal"];
+
+/// Readings of one CPU package in Celsius
+#[derive(Debug, Default)]
+struct PackageSensors {
+    package: Option<f32>,
+    /// Per physical core, keyed by core id
+    cores: BTreeMap<u32, f32>,
+    /// Per core complex die, in die order
+    ccds: Vec<f32>,
+}
+
+/// Package and core id of a logical CPU
+#[derive(Debug)]
+struct CpuTopology {
+    cpu: usize,
+    package: u32,
+    core: u32,
+}
+
+/// Read a millidegree value and convert it to Celsius
+fn read_millidegrees(path: &Path) -> Option<f32> {
+    let value: i64 = read_trimmed(path)?.parse().ok()?;
+    Some(value as f32 / 1000.0)
+}
+
+/// Subdirectories of `dir` whose name is `prefix` followed by a number,
+/// sorted by that number
+fn numbered_dirs(dir: &Path, prefix: &str) -> Vec<(usize, PathBuf)> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut dirs: Vec<(usize, PathBuf)> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let index = entry
+                .file_name()
+                .to_str()?
+                .strip_prefix(prefix)?
+                .parse()
+                .ok()?;
+            Some((index, entry.path()))
+        })
+        .collect();
+    dirs.sort_by_key(|(index, _)| *index);
+    dirs
+}
+
+/// All labelled temperature readings of a hwmon device, in input order.
+/// Inputs without a label file get an empty label.
+fn read_hwmon_temps(dir: &Path) -> Vec<(String, f32)> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut inputs: Vec<(u32, String, f32)> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let name = entry.file_name();
+            let index: u32 = name
+                .to_str()?
+                .strip_prefix("temp")?
+                .strip_suffix("_input")?
+                .parse()
+                .ok()?;
+            let value = read_millidegrees(&entry.path())?;
+            let label = read_trimmed(&dir.join(format!("temp{}_label", index))).unwrap_or_default();
+            Some((index, label, value))
+        })
+        .collect();
+    inputs.sort_by_key(|(index, _, _)| *index);
+    inputs
+        .into_iter()
+        .map(|(_, label, value)| (label, value))
+        .collect()
+}
+
+/// Collect the readings of all CPU sensor drivers, keyed by package id
+fn read_hwmon(hwmon: &Path) -> BTreeMap<u32, PackageSensors> {
+    let mut packages: BTreeMap<u32, PackageSensors> = BTreeMap::new();
+    // AMD drivers do not report the package id, their devices are
+    // registered in package order
+    let mut amd_packages = 0;
+
+    for (_, dir) in numbered_dirs(hwmon, "hwmon") {
+        let Some(driver) = read_trimmed(&dir.join("name")) else {
+            continue;
+        };
+        let temps = read_hwmon_temps(&dir);
+        match driver.as_str() {
+            "coretemp" => {
+                let package_id = temps
+                    .iter()
+                    .find_map(|(label, _)| label.strip_prefix("Package id ")?.parse().ok())
+                    .unwrap_or(packages.len() as u32);
+                let sensors = packages.entry(package_id).or_default();
+                for (label, value) in temps {
+                    if label.starts_with("Package id ") {
+                        sensors.package = Some(value);
+                    } else if let Some(core) =
+                        label.strip_prefix("Core ").and_then(|c| c.parse().ok())
+                    {
+                        sensors.cores.insert(core, value);
+                    }
+                }
+            }
+            "k10temp" | "zenpower" => {
+                let sensors = packages.entry(amd_packages).or_default();
+                amd_packages += 1;
+                let mut tctl = None;
+                for (label, value) in temps {
+                    match label.as_str() {
+                        // Tctl may carry an offset for fan control, Tdie does not
+                        "Tdie" => sensors.package = Some(value),
+                        "Tctl" => tctl = Some(value),
+                        _ if label.starts_with("Tccd") => sensors.ccds.push(value),
+                        _ => {}
+                    }
+                }
+                sensors.package = sensors.package.or(tctl);
+            }
+            "cpu_thermal" | "cpu-thermal" => {
+                if let Some((_, value)) = temps.first() {
+                    packages.entry(0).or_default().package = Some(*value);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    packages
+}
+
+/// Package temperatures from the thermal zones, used when no hwmon
+/// driver is available
+fn read_thermal_zones(thermal: &Path) -> BTreeMap<u32, PackageSensors> {
+    let mut packages: BTreeMap<u32, PackageSensors> = BTreeMap::new();
+
+    for (_, dir) in numbered_dirs(thermal, "thermal_zone") {
+        let Some(zone_type) = read_trimmed(&dir.join("type")) else {
+            continue;
+        };
+        if !CPU_THERMAL_ZONES.contains(&zone_type.as_str()) {
+            continue;
+        }
+        if let Some(value) = read_millidegrees(&dir.join("temp")) {
+            // x86_pkg_temp zones are registered once per package
+            let package = packages.len() as u32;
+            packages.entry(package).or_default().package = Some(value);
+        }
+    }
+
+    packages
+}
+
+fn read_topology(cpu_dir: &Path) -> Vec<CpuTopology> {
+    numbered_dirs(cpu_dir, "cpu")
+        .into_iter()
+        .map(|(cpu, dir)| {
+            let topology = dir.join("topology");
+            let read_id = |file: &str| -> Option<u32> {
+                // Some ARM kernels report -1 for an unknown package
+                let id: i64 = read_trimmed(&topology.join(file))?.parse().ok()?;
+                u32::try_from(id).ok()
+            };
+            CpuTopology {
+                cpu,
+                package: read_id("physical_package_id").unwrap_or(0),
+                core: read_id("core_id").unwrap_or(cpu as u32),
+            }
+        })
+        .collect()
+}
+
+/// Read the CPU temperatures and map them to the logical CPUs.
+/// CPUs without a core or die sensor get the temperature of their
+/// package. Returns None if no CPU sensor was found.
+pub fn read_cpu_temperatures(
+    hwmon: &Path,
+    thermal: &Path,
+    cpu_dir: &Path,
+) -> Option<CpuTemperatures> {
+    let mut packages = read_hwmon(hwmon);
+    if packages.is_empty() {
+        packages = read_thermal_zones(thermal);
+    }
+    if packages.is_empty() {
+        return None;
+    }
+
+    let topology = read_topology(cpu_dir);
+    let cpu_count = topology.iter().map(|t| t.cpu + 1).max().unwrap_or(0);
+    let mut cpus = vec![None; cpu_count];
+
+    for t in &topology {
+        let Some(sensors) = packages.get(&t.package) else {
+            continue;
+        };
+        let ccd_temp = || {
+            if sensors.ccds.is_empty() {
+                return None;
+            }
+            // Dies hold consecutive core ids
+            let max_core = topology
+                .iter()
+                .filter(|other| other.package == t.package)
+                .map(|other| other.core)
+                .max()
+                .unwrap_or(0) as usize;
+            let cores_per_ccd = (max_core + 1).div_ceil(sensors.ccds.len());
+            sensors.ccds.get(t.core as usize / cores_per_ccd).copied()
+        };
+        cpus[t.cpu] = sensors
+            .cores
+            .get(&t.core)
+            .copied()
+            .or_else(ccd_temp)
+            .or(sensors.package);
+    }
+
+    // The hottest package stands for the whole machine
+    let package = packages
+        .values()
+        .filter_map(|s| s.package)
+        .reduce(f32::max)
+        .or_else(|| cpus.iter().flatten().copied().reduce(f32::max));
+
+    Some(CpuTemperatures { package, cpus })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::platform::testdata;
+
+    fn read_fixture(name: &str) -> Option<CpuTemperatures> {
+        let dir = testdata("temperature").join(name);
+        read_cpu_temperatures(&dir.join("hwmon"), &dir.join("thermal"), &dir.join("cpu"))
+    }
+
+    #[test]
+    fn test_coretemp() {
+        // cpu2 and cpu3 are the hyperthreads of cores 0 and 1; acpitz is ignored
+        let temps = read_fixture("coretemp").unwrap();
+        assert_eq!(temps.package, Some(52.0));
+        assert_eq!(
+            temps.cpus,
+            vec![Some(50.0), Some(55.0), Some(50.0), Some(55.0)]
+        );
+    }
+
+    #[test]
+    fn test_k10temp() {
+        // Tctl is the package reading, cores 0-1 are on the first die
+        let temps = read_fixture("k10temp").unwrap();
+        assert_eq!(temps.package, Some(61.25));
+        assert_eq!(
+            temps.cpus,
+            vec![Some(55.0), Some(55.0), Some(58.5), Some(58.5)]
+        );
+    }
+
+    #[test]
+    fn test_cpu_thermal() {
+        // physical_package_id is -1 on older ARM kernels
+        let temps = read_fixture("cpu_thermal").unwrap();
+        assert_eq!(temps.package, Some(48.312));
+        assert_eq!(temps.cpus, vec![Some(48.312), Some(48.312)]);
+    }
+
+    #[test]
+    fn test_thermal_zone_fallback() {
+        let temps = read_fixture("thermal_zone").unwrap();
+        assert_eq!(temps.package, Some(47.0));
+        assert_eq!(temps.cpus, vec![Some(47.0), Some(47.0)]);
+    }
+
+    #[test]
+    fn test_no_sensors() {
+        assert_eq!(read_fixture("missing"), None);
+    }
+}
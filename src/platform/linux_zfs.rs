@@ -0,0 +1,110 @@
+//! ZFS ARC statistics from the SPL kstat interface
+//!
+//! ZFS on Linux exports its ARC counters in `/proc/spl/kstat/zfs/arcstats`:
+//! two header lines followed by one `name type data` row per counter,
+//! with sizes in bytes.
+
+use std::fs;
+use std::path::Path;
+
+use crate::core::ZfsArcStats;
+
+/// Location of the arcstats kstat
+pub const ARCSTATS_PATH: &str = "/proc/spl/kstat/zfs/arcstats";
+
+/// Parse the contents of an arcstats kstat (sizes are converted to KB)
+pub fn parse_arcstats(content: &str) -> ZfsArcStats {
+    let mut stats = ZfsArcStats::default();
+    let mut dbuf_dnode_bonus = 0;
+    let mut has_dbuf = false;
+    let mut other_size = 0;
+
+    // Skip the kstat header and the column names
+    for line in content.lines().skip(2) {
+        let mut fields = line.split_whitespace();
+        let (Some(name), Some(_type), Some(data)) = (fields.next(), fields.next(), fields.next())
+        else {
+            continue;
+        };
+        let Ok(bytes) = data.parse::<u64>() else {
+            continue;
+        };
+        let kb = bytes / 1024;
+        match name {
+            "c_min" => stats.min = kb,
+            "c_max" => stats.max = kb,
+            "size" => {
+                stats.size = kb;
+                stats.enabled = true;
+            }
+            "mfu_size" => stats.mfu = kb,
+            "mru_size" => stats.mru = kb,
+            "anon_size" => stats.anon = kb,
+            "hdr_size" => stats.header = kb,
+            "dbuf_size" | "dnode_size" | "bonus_size" => {
+                dbuf_dnode_bonus += kb;
+                has_dbuf = true;
+            }
+            "other_size" => other_size = kb,
+            "compressed_size" => stats.compressed = kb,
+            "uncompressed_size" => stats.uncompressed = kb,
+            _ => {}
+        }
+    }
+
+    stats.other = if has_dbuf {
+        dbuf_dnode_bonus
+    } else {
+        other_size
+    };
+    stats.is_compressed = stats.compressed > 0;
+    stats
+}
+
+/// Read the ARC statistics, returning disabled stats if ZFS is not loaded
+pub fn read_arcstats(path: &Path) -> ZfsArcStats {
+    fs::read_to_string(path)
+        .map(|content| parse_arcstats(&content))
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::platform::testdata;
+
+    #[test]
+    fn test_read_arcstats() {
+        let stats = read_arcstats(&testdata("zfs/arcstats"));
+        assert!(stats.enabled);
+        assert!(stats.is_compressed);
+        assert_eq!(stats.min, 1024 * 1024);
+        assert_eq!(stats.max, 8 * 1024 * 1024);
+        assert_eq!(stats.size, 4 * 1024 * 1024);
+        assert_eq!(stats.mfu, 2 * 1024 * 1024);
+        assert_eq!(stats.mru, 1536 * 1024);
+        assert_eq!(stats.anon, 20 * 1024);
+        assert_eq!(stats.header, 100 * 1024);
+        // dbuf + dnode + bonus
+        assert_eq!(stats.other, 100 * 1024);
+        assert_eq!(stats.compression_ratio(), 3.0);
+        assert_eq!(stats.shrinkable(), 3 * 1024 * 1024);
+    }
+
+    #[test]
+    fn test_read_arcstats_old_format() {
+        // Older ZFS versions report other_size and no compression stats
+        let stats = read_arcstats(&testdata("zfs/arcstats-uncompressed"));
+        assert!(stats.enabled);
+        assert!(!stats.is_compressed);
+        assert_eq!(stats.other, 3 * 1024);
+        assert_eq!(stats.compression_ratio(), 1.0);
+    }
+
+    #[test]
+    fn test_read_arcstats_missing() {
+        let stats = read_arcstats(&testdata("zfs/missing"));
+        assert!(!stats.enabled);
+        assert_eq!(stats.shrinkable(), 0);
+    }
+}
@@ -0,0 +1,112 @@
+//! Zram statistics from sysfs
+//!
+//! Each zram device exports its size in `/sys/block/zramN/disksize` and
+//! its memory statistics in `/sys/block/zramN/mm_stat`, whose first two
+//! columns are the uncompressed and compressed size of the stored data in
+//! bytes. Kernels before 4.2 have separate `orig_data_size` and
+//! `compr_data_size` files instead.
+
+use std::fs;
+use std::path::Path;
+
+use super::linux_sysfs::read_u64;
+use crate::core::ZramStats;
+
+/// Location of the block device directories
+pub const SYS_BLOCK_PATH: &str = "/sys/block";
+
+/// Location of the active swap areas
+pub const SWAPS_PATH: &str = "/proc/swaps";
+
+/// Uncompressed and compressed data size of one device in bytes
+fn read_data_sizes(device: &Path) -> Option<(u64, u64)> {
+    if let Ok(mm_stat) = fs::read_to_string(device.join("mm_stat")) {
+        let mut fields = mm_stat.split_whitespace().map(|f| f.parse::<u64>().ok());
+        return Some((fields.next()??, fields.next()??));
+    }
+    Some((
+        read_u64(&device.join("orig_data_size"))?,
+        read_u64(&device.join("compr_data_size"))?,
+    ))
+}
+
+/// Sum the statistics of all zram devices below a sysfs block directory
+/// (sizes are converted to KB). Swap usage is not filled in.
+pub fn read_zram(sys_block: &Path) -> ZramStats {
+    let mut stats = ZramStats::default();
+    let Ok(entries) = fs::read_dir(sys_block) else {
+        return stats;
+    };
+
+    for entry in entries.flatten() {
+        if !entry.file_name().to_string_lossy().starts_with("zram") {
+            continue;
+        }
+        let device = entry.path();
+        // Unconfigured devices have a disksize of 0
+        let Some(disksize) = read_u64(&device.join("disksize")).filter(|&size| size > 0) else {
+            continue;
+        };
+        let (original, compressed) = read_data_sizes(&device).unwrap_or((0, 0));
+        stats.total += disksize / 1024;
+        stats.original += original / 1024;
+        stats.compressed += compressed / 1024;
+    }
+
+    stats
+}
+
+/// Total and used size in KB of the swap areas on zram devices,
+/// parsed from the contents of /proc/swaps
+pub fn parse_zram_swaps(content: &str) -> (u64, u64) {
+    let mut total = 0;
+    let mut used = 0;
+
+    // Skip the column names
+    for line in content.lines().skip(1) {
+        let mut fields = line.split_whitespace();
+        let (Some(name), Some(_type), Some(size), Some(in_use)) =
+            (fields.next(), fields.next(), fields.next(), fields.next())
+        else {
+            continue;
+        };
+        if !name.starts_with("/dev/zram") {
+            continue;
+        }
+        total += size.parse::<u64>().unwrap_or(0);
+        used += in_use.parse::<u64>().unwrap_or(0);
+    }
+
+    (total, used)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::platform::testdata;
+
+    #[test]
+    fn test_read_zram() {
+        // zram0 has mm_stat, zram1 the pre-4.2 files, sda is skipped
+        let stats = read_zram(&testdata("zram/block"));
+        assert_eq!(stats.total, 5 * 1024 * 1024);
+        assert_eq!(stats.original, 1536 * 1024);
+        assert_eq!(stats.compressed, 512 * 1024);
+        assert_eq!(stats.compression_ratio(), 3.0);
+    }
+
+    #[test]
+    fn test_read_zram_missing() {
+        let stats = read_zram(&testdata("zram/missing"));
+        assert_eq!(stats, ZramStats::default());
+        assert_eq!(stats.compression_ratio(), 1.0);
+    }
+
+    #[test]
+    fn test_parse_zram_swaps() {
+        let content = fs::read_to_string(testdata("zram/swaps")).unwrap();
+        // The swap file is not on zram
+        assert_eq!(parse_zram_swaps(&content), (4194300 + 1048572, 1572864));
+        assert_eq!(parse_zram_swaps(""), (0, 0));
+    }
+}
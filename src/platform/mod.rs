@@ -5,7 +5,7 @@
 
 use anyhow::Result;
 
-use crate::core::Machine;
+use crate::core::{Affinity, CgroupStats, Machine};
 
 #[cfg(target_os = "linux")]
 pub mod linux;
@@ -13,6 +13,48 @@ pub mod linux;
 #[cfg(target_os = "linux")]
 pub mod linux_bg_scanner;
 
+#[cfg(target_os = "linux")]
+pub mod linux_cgroup;
+
+#[cfg(target_os = "linux")]
+pub mod linux_container;
+
+#[cfg(target_os = "linux")]
+pub mod linux_dbus;
+
+#[cfg(target_os = "linux")]
+pub mod linux_gpu;
+
+#[cfg(target_os = "linux")]
+pub mod linux_hugepages;
+
+#[cfg(target_os = "linux")]
+pub mod linux_inspect;
+
+#[cfg(target_os = "linux")]
+pub mod linux_net;
+
+#[cfg(target_os = "linux")]
+pub mod linux_open_files;
+
+#[cfg(target_os = "linux")]
+pub mod linux_smaps;
+
+#[cfg(target_os = "linux")]
+pub mod linux_sockets;
+
+#[cfg(target_os = "linux")]
+pub mod linux_sysfs;
+
+#[cfg(target_os = "linux")]
+pub mod linux_temperature;
+
+#[cfg(target_os = "linux")]
+pub mod linux_zfs;
+
+#[cfg(target_os = "linux")]
+pub mod linux_zram;
+
 #[cfg(target_os = "macos")]
 mod darwin;
 
@@ -23,6 +65,14 @@ pub mod darwin_bg_scanner;
 #[cfg(unix)]
 pub mod bg_scanner;
 
+/// Path of a test fixture below `src/platform/testdata`
+#[cfg(all(test, target_os = "linux"))]
+pub(crate) fn testdata(rel: &str) -> std::path::PathBuf {
+    std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("src/platform/testdata")
+        .join(rel)
+}
+
 /// Initialize platform-specific resources
 pub fn init() -> Result<()> {
     #[cfg(target_os = "linux")]
@@ -64,6 +114,131 @@ pub fn get_uid_for_username(name: &str) -> Option<u32> {
     }
 }
 
+/// Get the CPU affinity of a process (None if unsupported or not accessible)
+pub fn get_process_affinity(pid: i32) -> Option<Affinity> {
+    #[cfg(target_os = "linux")]
+    {
+        linux::get_affinity(pid)
+    }
+    #[cfg(not(target_os = "linux"))]
+    {
+        let _ = pid;
+        None
+    }
+}
+
+/// Set the CPU affinity of a process, returns true on success
+pub fn set_process_affinity(pid: i32, affinity: &Affinity) -> bool {
+    #[cfg(target_os = "linux")]
+    {
+        linux::set_affinity(pid, affinity)
+    }
+    #[cfg(not(target_os = "linux"))]
+    {
+        let _ = (pid, affinity);
+        false
+    }
+}
+
+/// Read the environment of a process as sorted `NAME=value` strings.
+/// The error is a message suitable for display.
+pub fn get_process_environ(pid: i32) -> Result<Vec<String>, String> {
+    get_process_environ_raw(pid).map(|vars| {
+        vars.iter()
+            .map(|var| crate::core::decode_variable(var))
+            .collect()
+    })
+}
+
+/// Read the undecoded `NAME=value` variables of a process, in the order of
+/// `get_process_environ()`
+pub fn get_process_environ_raw(pid: i32) -> Result<Vec<Vec<u8>>, String> {
+    #[cfg(target_os = "linux")]
+    {
+        use std::io::ErrorKind;
+        match std::fs::read(format!("/proc/{}/environ", pid)) {
+            Ok(bytes) => {
+                let vars = crate::core::split_environ(&bytes);
+                if vars.is_empty() {
+                    // Kernel threads and zombies have no environment
+                    Err(format!("Process {} has no environment.", pid))
+                } else {
+                    Ok(vars)
+                }
+            }
+            Err(e) if e.kind() == ErrorKind::PermissionDenied => Err(format!(
+                "Permission denied reading the environment of process {}. \
+                 Run as root or as the owner of the process.",
+                pid
+            )),
+            Err(e) if e.kind() == ErrorKind::NotFound => {
+                Err(format!("Process {} no longer exists.", pid))
+            }
+            Err(e) => Err(format!("Could not read process environment: {}", e)),
+        }
+    }
+    #[cfg(target_os = "macos")]
+    {
+        use std::process::Command;
+        Command::new("ps")
+            .args(["-p", &pid.to_string(), "-E", "-o", "command="])
+            .output()
+            .ok()
+            .and_then(|o| String::from_utf8(o.stdout).ok())
+            .map(|s| {
+                let mut vars: Vec<String> = Vec::new();
+                if let Some(pos) = s.find(' ') {
+                    let env_part = &s[pos + 1..];
+                    for part in env_part.split_whitespace() {
+                        if part.contains('=') {
+                            vars.push(part.to_string());
+                        }
+                    }
+                }
+                vars.sort();
+                vars.into_iter().map(String::into_bytes).collect()
+            })
+            .ok_or_else(|| "Could not read process environment.".to_string())
+    }
+    #[cfg(not(any(target_os = "macos", target_os = "linux")))]
+    {
+        let _ = pid;
+        Err("Environment reading not supported on this platform.".to_string())
+    }
+}
+
+/// Set a resource limit (RLIMIT_* number) of a process, None meaning
+/// unlimited. The error is a message suitable for display.
+pub fn set_process_limit(
+    pid: i32,
+    resource: u32,
+    soft: Option<u64>,
+    hard: Option<u64>,
+) -> Result<(), String> {
+    #[cfg(target_os = "linux")]
+    {
+        linux::set_resource_limit(pid, resource, soft, hard).map_err(|errno| errno.to_string())
+    }
+    #[cfg(not(target_os = "linux"))]
+    {
+        let _ = (pid, resource, soft, hard);
+        Err("not supported on this platform".to_string())
+    }
+}
+
+/// Read the kernel's accounting of a cgroup (empty where unsupported)
+pub fn get_cgroup_stats(cgroup: &str) -> CgroupStats {
+    #[cfg(target_os = "linux")]
+    {
+        linux_cgroup::read_cgroup_stats(std::path::Path::new(linux_cgroup::CGROUP_PATH), cgroup)
+    }
+    #[cfg(not(target_os = "linux"))]
+    {
+        let _ = cgroup;
+        CgroupStats::default()
+    }
+}
+
 /// Scan all processes and update the machine state
 pub fn scan_processes(machine: &mut Machine) {
     #[cfg(target_os = "linux")]
@@ -0,0 +1,233 @@
+//! CgroupPanel - Process list aggregated by cgroup (like systemd-cgtop)
+//!
+//! Replaces the process list while shown and is rebuilt from the process
+//! list after every scan. Each row is a cgroup with the summed CPU%, RSS,
+//! IO rates and task count of its subtree, next to the kernel's own
+//! memory.current, memory.max and cpu.stat accounting.
+
+use std::collections::HashSet;
+
+use super::crt::{
+    KEY_DOT, KEY_F10, KEY_F6, KEY_GT, KEY_LC_C, KEY_LC_I, KEY_LC_M, KEY_LC_P, KEY_LC_Q, KEY_LC_T,
+    KEY_LINEFEED, KEY_MINUS, KEY_PLUS, KEY_Q, KEY_RETURN, KEY_SPACE, KEY_STAR,
+};
+use super::function_bar::FunctionBar;
+use super::panel::{HandlerResult, Panel};
+use super::Crt;
+use crate::core::{CgroupNode, CgroupSortKey, CgroupTree, Machine, Process};
+use crate::platform;
+
+/// Column titles, aligned with the fields of `format_row`
+const HEADER: &str =
+    " CPU% TASKS    RSS MEMORY  LIMIT     IO READ    IO WRITE  CPU TIME THRTL CGROUP";
+
+/// Format a byte count like the memory columns of the process list
+fn format_bytes(bytes: Option<u64>) -> String {
+    match bytes {
+        Some(u64::MAX) => format!("{:>6}", "max"),
+        Some(bytes) => Process::format_memory((bytes / 1024) as i64),
+        None => format!("{:>6}", "N/A"),
+    }
+}
+
+/// Format one cgroup row
+fn format_row(node: &CgroupNode, collapsed: bool) -> String {
+    let cpu_time = node
+        .stats
+        .cpu_usage_usec
+        .map(|usec| Process::format_time(usec / 10_000))
+        .unwrap_or_else(|| "N/A".to_string());
+    let throttled = node
+        .stats
+        .nr_throttled
+        .map(|n| n.to_string())
+        .unwrap_or_else(|| "N/A".to_string());
+    let marker = if node.children.is_empty() {
+        "  "
+    } else if collapsed {
+        "+ "
+    } else {
+        "- "
+    };
+
+    format!(
+        "{:>5.1} {:>5} {} {} {} {} {} {:>9} {:>5} {}{}{}",
+        node.usage.percent_cpu,
+        node.usage.tasks,
+        Process::format_memory(node.usage.m_resident),
+        format_bytes(node.stats.memory_current),
+        format_bytes(node.stats.memory_max),
+        Process::format_rate(node.usage.io_read_bps),
+        Process::format_rate(node.usage.io_write_bps),
+        cpu_time,
+        throttled,
+        "  ".repeat(node.depth),
+        marker,
+        node.name()
+    )
+}
+
+/// Title of a sort key in the panel header
+fn sort_key_name(key: CgroupSortKey) -> &'static str {
+    match key {
+        CgroupSortKey::Path => "path",
+        CgroupSortKey::Cpu => "CPU%",
+        CgroupSortKey::Memory => "memory",
+        CgroupSortKey::Tasks => "tasks",
+        CgroupSortKey::Io => "IO",
+    }
+}
+
+/// Panel showing the cgroup tree
+pub struct CgroupPanel {
+    panel: Panel,
+    tree: CgroupTree,
+    /// Node index of every row
+    rows: Vec<usize>,
+    /// Paths of the collapsed cgroups
+    collapsed: HashSet<String>,
+    sort_key: CgroupSortKey,
+    pub function_bar: FunctionBar,
+}
+
+impl CgroupPanel {
+    pub fn new() -> Self {
+        CgroupPanel {
+            panel: Panel::new(0, 0, 80, 24),
+            tree: CgroupTree::default(),
+            rows: Vec::new(),
+            collapsed: HashSet::new(),
+            sort_key: CgroupSortKey::default(),
+            function_bar: FunctionBar::with_functions(vec![
+                ("F1".to_string(), "Help  ".to_string()),
+                ("Space".to_string(), "Fold  ".to_string()),
+                ("F6".to_string(), "SortBy".to_string()),
+                ("G".to_string(), "Procs ".to_string()),
+                ("F10".to_string(), "Quit  ".to_string()),
+            ]),
+        }
+    }
+
+    pub fn move_to(&mut self, x: i32, y: i32) {
+        self.panel.move_to(x, y);
+    }
+
+    pub fn resize(&mut self, w: i32, h: i32) {
+        self.panel.resize(w, h);
+    }
+
+    /// Rebuild the tree from the current process list. The kernel
+    /// accounting is only read from /sys/fs/cgroup when `read_stats` is
+    /// set, a replayed recording has no matching cgroups on this system.
+    pub fn update(&mut self, machine: &Machine, read_stats: bool) {
+        self.tree = CgroupTree::build(&machine.processes.processes);
+        if read_stats {
+            for node in &mut self.tree.nodes {
+                node.stats = platform::get_cgroup_stats(&node.path);
+            }
+        }
+        self.rebuild_rows();
+    }
+
+    /// Sort the tree and rebuild the rows, keeping the selected cgroup
+    fn rebuild_rows(&mut self) {
+        let selected_path = self
+            .rows
+            .get(self.panel.get_selected() as usize)
+            .and_then(|&index| self.tree.nodes.get(index))
+            .map(|node| node.path.clone());
+
+        self.tree.sort(self.sort_key);
+        self.rows = self.tree.flatten(&self.collapsed);
+
+        let selected = self.panel.get_selected();
+        self.panel.prune();
+        self.panel.set_header(&format!(
+            "{}  (sorted by {})",
+            HEADER,
+            sort_key_name(self.sort_key)
+        ));
+        for &index in &self.rows {
+            let node = &self.tree.nodes[index];
+            let collapsed = self.collapsed.contains(&node.path);
+            self.panel.add_text(&format_row(node, collapsed));
+        }
+
+        let selected = selected_path
+            .and_then(|path| {
+                self.rows
+                    .iter()
+                    .position(|&index| self.tree.nodes[index].path == path)
+            })
+            .map_or(selected, |row| row as i32);
+        self.panel.set_selected(selected);
+    }
+
+    /// Collapse or expand the selected cgroup
+    fn set_collapsed(&mut self, collapsed: Option<bool>) {
+        let Some(node) = self
+            .rows
+            .get(self.panel.get_selected() as usize)
+            .map(|&index| &self.tree.nodes[index])
+        else {
+            return;
+        };
+        if node.children.is_empty() {
+            return;
+        }
+        let collapse = collapsed.unwrap_or(!self.collapsed.contains(&node.path));
+        if collapse {
+            self.collapsed.insert(node.path.clone());
+        } else {
+            self.collapsed.remove(&node.path);
+        }
+        self.rebuild_rows();
+    }
+
+    fn set_sort_key(&mut self, key: CgroupSortKey) {
+        self.sort_key = key;
+        self.rebuild_rows();
+    }
+
+    pub fn draw(&mut self, crt: &mut Crt, focus: bool) {
+        self.panel.draw(crt, focus, true);
+    }
+
+    /// Handle a key event
+    pub fn on_key(&mut self, key: i32) -> HandlerResult {
+        match key {
+            KEY_SPACE | KEY_RETURN | KEY_LINEFEED => self.set_collapsed(None),
+            KEY_PLUS => self.set_collapsed(Some(false)),
+            KEY_MINUS => self.set_collapsed(Some(true)),
+            KEY_STAR => {
+                // Expand everything, or collapse all top level cgroups
+                if self.collapsed.is_empty() {
+                    self.collapsed = self.tree.nodes[0]
+                        .children
+                        .iter()
+                        .map(|&index| self.tree.nodes[index].path.clone())
+                        .collect();
+                } else {
+                    self.collapsed.clear();
+                }
+                self.rebuild_rows();
+            }
+            KEY_F6 | KEY_DOT | KEY_GT => self.set_sort_key(self.sort_key.next()),
+            // Sort keys of systemd-cgtop
+            KEY_LC_P => self.set_sort_key(CgroupSortKey::Path),
+            KEY_LC_C => self.set_sort_key(CgroupSortKey::Cpu),
+            KEY_LC_M => self.set_sort_key(CgroupSortKey::Memory),
+            KEY_LC_T => self.set_sort_key(CgroupSortKey::Tasks),
+            KEY_LC_I => self.set_sort_key(CgroupSortKey::Io),
+            KEY_F10 | KEY_LC_Q | KEY_Q => return HandlerResult::BreakLoop,
+            _ => return self.panel.on_key(key),
+        }
+        HandlerResult::Handled
+    }
+}
+
+impl Default for CgroupPanel {
+    fn default() -> Self {
+        Self::new()
+    }
+}
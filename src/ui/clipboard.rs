@@ -0,0 +1,91 @@
+//! Clipboard - Copy text to the system clipboard
+//!
+//! Uses the clipboard tool of the desktop session (wl-copy, xclip, xsel or
+//! pbcopy). Without one, e.g. over SSH, the text is sent to the terminal as
+//! an OSC 52 sequence, which most terminal emulators put on the clipboard.
+
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+/// Clipboard tools to try, with the environment variable they depend on
+const CLIPBOARD_TOOLS: &[(&str, &[&str], Option<&str>)] = &[
+    ("pbcopy", &[], None),
+    ("wl-copy", &[], Some("WAYLAND_DISPLAY")),
+    ("xclip", &["-selection", "clipboard"], Some("DISPLAY")),
+    ("xsel", &["--clipboard", "--input"], Some("DISPLAY")),
+];
+
+/// Pipe the text into a clipboard tool, returns true on success
+fn copy_with_tool(program: &str, args: &[&str], text: &str) -> bool {
+    let Ok(mut child) = Command::new(program)
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+    else {
+        return false;
+    };
+    let written = child
+        .stdin
+        .take()
+        .is_some_and(|mut stdin| stdin.write_all(text.as_bytes()).is_ok());
+    child.wait().is_ok_and(|status| status.success()) && written
+}
+
+/// Standard base64 encoding, as required by OSC 52
+fn base64(data: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut encoded = String::with_capacity(data.len().div_ceil(3) * 4);
+    for chunk in data.chunks(3) {
+        let n = chunk
+            .iter()
+            .enumerate()
+            .fold(0u32, |n, (i, &b)| n | (b as u32) << (16 - 8 * i));
+        for i in 0..4 {
+            if i <= chunk.len() {
+                encoded.push(ALPHABET[(n >> (18 - 6 * i) & 0x3f) as usize] as char);
+            } else {
+                encoded.push('=');
+            }
+        }
+    }
+    encoded
+}
+
+/// Copy text to the clipboard. Returns how it was copied, for a status
+/// message.
+pub fn copy_to_clipboard(text: &str) -> &'static str {
+    for &(program, args, env) in CLIPBOARD_TOOLS {
+        // pbcopy is only available (and only needed) on macOS
+        if cfg!(target_os = "macos") != (program == "pbcopy") {
+            continue;
+        }
+        if env.is_none_or(|var| std::env::var_os(var).is_some())
+            && copy_with_tool(program, args, text)
+        {
+            return program;
+        }
+    }
+
+    let mut stdout = std::io::stdout();
+    let _ = write!(stdout, "\x1b]52;c;{}\x07", base64(text.as_bytes()));
+    let _ = stdout.flush();
+    "terminal"
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_base64_rfc4648_vectors() {
+        assert_eq!(base64(b""), "");
+        assert_eq!(base64(b"f"), "Zg==");
+        assert_eq!(base64(b"fo"), "Zm8=");
+        assert_eq!(base64(b"foo"), "Zm9v");
+        assert_eq!(base64(b"foob"), "Zm9vYg==");
+        assert_eq!(base64(b"fooba"), "Zm9vYmE=");
+        assert_eq!(base64(b"foobar"), "Zm9vYmFy");
+    }
+}
@@ -226,6 +226,11 @@ pub enum ColorElement {
     CpuSoftIrq,
     CpuSteal,
     CpuGuest,
+    GpuEngine1,
+    GpuEngine2,
+    GpuEngine3,
+    GpuEngine4,
+    GpuResidue,
     ScreensOthBorder,
     ScreensOthText,
     ScreensCurBorder,
@@ -320,7 +325,9 @@ pub const KEY_QUESTION: i32 = 0x3F; // '?'
 
 // Uppercase letters
 pub const KEY_C: i32 = 0x43; // 'C'
+pub const KEY_E: i32 = 0x45; // 'E'
 pub const KEY_F: i32 = 0x46; // 'F'
+pub const KEY_G: i32 = 0x47; // 'G'
 pub const KEY_H: i32 = 0x48; // 'H'
 pub const KEY_I: i32 = 0x49; // 'I'
 pub const KEY_K: i32 = 0x4B; // 'K'
@@ -338,20 +345,28 @@ pub const KEY_BACKSLASH: i32 = 0x5C; // '\\'
 pub const KEY_RBRACKET: i32 = 0x5D; // ']'
 
 // Lowercase letters
+pub const KEY_LC_A: i32 = 0x61; // 'a'
 pub const KEY_LC_C: i32 = 0x63; // 'c'
+pub const KEY_LC_D: i32 = 0x64; // 'd'
 pub const KEY_LC_E: i32 = 0x65; // 'e'
 pub const KEY_LC_F: i32 = 0x66; // 'f'
+pub const KEY_LC_G: i32 = 0x67; // 'g'
 pub const KEY_LC_H: i32 = 0x68; // 'h'
+pub const KEY_LC_I: i32 = 0x69; // 'i'
 pub const KEY_LC_K: i32 = 0x6B; // 'k'
 pub const KEY_LC_L: i32 = 0x6C; // 'l'
 pub const KEY_LC_M: i32 = 0x6D; // 'm'
+pub const KEY_LC_O: i32 = 0x6F; // 'o'
 pub const KEY_LC_P: i32 = 0x70; // 'p'
 pub const KEY_LC_Q: i32 = 0x71; // 'q'
+pub const KEY_LC_R: i32 = 0x72; // 'r'
 pub const KEY_LC_S: i32 = 0x73; // 's'
 pub const KEY_LC_T: i32 = 0x74; // 't'
 pub const KEY_LC_U: i32 = 0x75; // 'u'
 pub const KEY_LC_W: i32 = 0x77; // 'w'
 pub const KEY_LC_X: i32 = 0x78; // 'x'
+pub const KEY_LBRACE: i32 = 0x7B; // '{'
+pub const KEY_RBRACE: i32 = 0x7D; // '}'
 
 // Character ranges (for matching)
 pub const KEY_PRINTABLE_START: i32 = 0x20;
@@ -866,6 +881,32 @@ impl Crt {
         self.colors[ColorElement::CpuSoftIrq as usize] = color_pair(COLOR_MAGENTA, COLOR_BLACK);
         self.colors[ColorElement::CpuSteal as usize] = color_pair(COLOR_CYAN, COLOR_BLACK);
         self.colors[ColorElement::CpuGuest as usize] = color_pair(COLOR_CYAN, COLOR_BLACK);
+        self.colors[ColorElement::GpuEngine1 as usize] = color_pair(COLOR_GREEN, COLOR_BLACK);
+        self.colors[ColorElement::GpuEngine2 as usize] = color_pair(COLOR_YELLOW, COLOR_BLACK);
+        self.colors[ColorElement::GpuEngine3 as usize] = color_pair(COLOR_RED, COLOR_BLACK);
+        self.colors[ColorElement::GpuEngine4 as usize] =
+            color_pair(COLOR_BLUE, COLOR_BLACK) | A_BOLD;
+        self.colors[ColorElement::GpuResidue as usize] = color_pair(COLOR_MAGENTA, COLOR_BLACK);
+        self.colors[ColorElement::PressureStallTen as usize] =
+            color_pair(COLOR_WHITE, COLOR_BLACK) | A_BOLD;
+        self.colors[ColorElement::PressureStallSixty as usize] =
+            color_pair(COLOR_CYAN, COLOR_BLACK) | A_BOLD;
+        self.colors[ColorElement::PressureStallThreeHundred as usize] =
+            color_pair(COLOR_CYAN, COLOR_BLACK);
+        self.colors[ColorElement::ZfsMfu as usize] = color_pair(COLOR_BLUE, COLOR_BLACK);
+        self.colors[ColorElement::ZfsMru as usize] = color_pair(COLOR_YELLOW, COLOR_BLACK);
+        self.colors[ColorElement::ZfsAnon as usize] = color_pair(COLOR_MAGENTA, COLOR_BLACK);
+        self.colors[ColorElement::ZfsHeader as usize] = color_pair(COLOR_CYAN, COLOR_BLACK);
+        self.colors[ColorElement::ZfsOther as usize] = color_pair(COLOR_MAGENTA, COLOR_BLACK);
+        self.colors[ColorElement::ZfsCompressed as usize] = color_pair(COLOR_BLUE, COLOR_BLACK);
+        self.colors[ColorElement::ZfsRatio as usize] = color_pair(COLOR_MAGENTA, COLOR_BLACK);
+        self.colors[ColorElement::HugePage1 as usize] = color_pair(COLOR_GREEN, COLOR_BLACK);
+        self.colors[ColorElement::HugePage2 as usize] = color_pair(COLOR_YELLOW, COLOR_BLACK);
+        self.colors[ColorElement::HugePage3 as usize] = color_pair(COLOR_RED, COLOR_BLACK);
+        self.colors[ColorElement::HugePage4 as usize] = color_pair(COLOR_BLUE, COLOR_BLACK);
+        self.colors[ColorElement::ZramCompressed as usize] = color_pair(COLOR_BLUE, COLOR_BLACK);
+        self.colors[ColorElement::ZramUncompressed as usize] =
+            color_pair(COLOR_YELLOW, COLOR_BLACK);
 
         // Screen tabs colors
         self.colors[ColorElement::ScreensOthBorder as usize] = color_pair(COLOR_BLUE, COLOR_BLUE);
@@ -1003,6 +1044,11 @@ impl Crt {
         self.colors[ColorElement::CpuSoftIrq as usize] = A_BOLD;
         self.colors[ColorElement::CpuSteal as usize] = A_DIM;
         self.colors[ColorElement::CpuGuest as usize] = A_DIM;
+        self.colors[ColorElement::GpuEngine1 as usize] = A_BOLD;
+        self.colors[ColorElement::GpuEngine2 as usize] = A_NORMAL;
+        self.colors[ColorElement::GpuEngine3 as usize] = A_REVERSE | A_BOLD;
+        self.colors[ColorElement::GpuEngine4 as usize] = A_REVERSE;
+        self.colors[ColorElement::GpuResidue as usize] = A_DIM;
 
         // Screen tabs - THIS IS THE KEY FIX
         self.colors[ColorElement::ScreensOthBorder as usize] = A_DIM;
@@ -1020,11 +1066,15 @@ impl Crt {
         self.colors[ColorElement::FileDescriptorMax as usize] = A_BOLD;
 
         // ZFS elements
+        self.colors[ColorElement::ZfsMfu as usize] = A_NORMAL;
+        self.colors[ColorElement::ZfsMru as usize] = A_BOLD;
         self.colors[ColorElement::ZfsAnon as usize] = A_DIM;
         self.colors[ColorElement::ZfsHeader as usize] = A_BOLD;
         self.colors[ColorElement::ZfsOther as usize] = A_DIM;
         self.colors[ColorElement::ZfsCompressed as usize] = A_BOLD;
         self.colors[ColorElement::ZfsRatio as usize] = A_BOLD;
+        self.colors[ColorElement::ZramCompressed as usize] = A_NORMAL;
+        self.colors[ColorElement::ZramUncompressed as usize] = A_BOLD;
 
         // Dynamic colors
         self.colors[ColorElement::DynamicGray as usize] = A_DIM;
@@ -1138,6 +1188,31 @@ impl Crt {
         self.colors[ColorElement::CpuSoftIrq as usize] = color_pair(COLOR_BLUE, COLOR_WHITE);
         self.colors[ColorElement::CpuSteal as usize] = color_pair(COLOR_CYAN, COLOR_WHITE);
         self.colors[ColorElement::CpuGuest as usize] = color_pair(COLOR_CYAN, COLOR_WHITE);
+        self.colors[ColorElement::GpuEngine1 as usize] = color_pair(COLOR_GREEN, COLOR_WHITE);
+        self.colors[ColorElement::GpuEngine2 as usize] = color_pair(COLOR_YELLOW, COLOR_WHITE);
+        self.colors[ColorElement::GpuEngine3 as usize] = color_pair(COLOR_RED, COLOR_WHITE);
+        self.colors[ColorElement::GpuEngine4 as usize] = color_pair(COLOR_BLUE, COLOR_WHITE);
+        self.colors[ColorElement::GpuResidue as usize] = color_pair(COLOR_MAGENTA, COLOR_WHITE);
+        self.colors[ColorElement::PressureStallTen as usize] =
+            color_pair(COLOR_BLACK, COLOR_WHITE) | A_BOLD;
+        self.colors[ColorElement::PressureStallSixty as usize] =
+            color_pair(COLOR_BLACK, COLOR_WHITE);
+        self.colors[ColorElement::PressureStallThreeHundred as usize] =
+            color_pair(COLOR_BLACK, COLOR_WHITE);
+        self.colors[ColorElement::ZfsMfu as usize] = color_pair(COLOR_BLUE, COLOR_WHITE);
+        self.colors[ColorElement::ZfsMru as usize] = color_pair(COLOR_YELLOW, COLOR_WHITE);
+        self.colors[ColorElement::ZfsAnon as usize] = color_pair(COLOR_MAGENTA, COLOR_WHITE);
+        self.colors[ColorElement::ZfsHeader as usize] = color_pair(COLOR_CYAN, COLOR_WHITE);
+        self.colors[ColorElement::ZfsOther as usize] = color_pair(COLOR_MAGENTA, COLOR_WHITE);
+        self.colors[ColorElement::ZfsCompressed as usize] = color_pair(COLOR_BLUE, COLOR_WHITE);
+        self.colors[ColorElement::ZfsRatio as usize] = color_pair(COLOR_MAGENTA, COLOR_WHITE);
+        self.colors[ColorElement::HugePage1 as usize] = color_pair(COLOR_GREEN, COLOR_WHITE);
+        self.colors[ColorElement::HugePage2 as usize] = color_pair(COLOR_YELLOW, COLOR_WHITE);
+        self.colors[ColorElement::HugePage3 as usize] = color_pair(COLOR_RED, COLOR_WHITE);
+        self.colors[ColorElement::HugePage4 as usize] = color_pair(COLOR_BLUE, COLOR_WHITE);
+        self.colors[ColorElement::ZramCompressed as usize] = color_pair(COLOR_BLUE, COLOR_WHITE);
+        self.colors[ColorElement::ZramUncompressed as usize] =
+            color_pair(COLOR_YELLOW, COLOR_WHITE);
         self.colors[ColorElement::ScreensOthBorder as usize] =
             color_pair(COLOR_BLACK, COLOR_WHITE) | A_BOLD;
         self.colors[ColorElement::ScreensOthText as usize] =
@@ -1262,6 +1337,31 @@ impl Crt {
         self.colors[ColorElement::CpuSoftIrq as usize] = color_pair(COLOR_BLUE, COLOR_BLACK);
         self.colors[ColorElement::CpuSteal as usize] = color_pair(COLOR_BLACK, COLOR_BLACK);
         self.colors[ColorElement::CpuGuest as usize] = color_pair(COLOR_BLACK, COLOR_BLACK);
+        self.colors[ColorElement::GpuEngine1 as usize] = color_pair(COLOR_GREEN, COLOR_BLACK);
+        self.colors[ColorElement::GpuEngine2 as usize] = color_pair(COLOR_YELLOW, COLOR_BLACK);
+        self.colors[ColorElement::GpuEngine3 as usize] = color_pair(COLOR_RED, COLOR_BLACK);
+        self.colors[ColorElement::GpuEngine4 as usize] = color_pair(COLOR_BLUE, COLOR_BLACK);
+        self.colors[ColorElement::GpuResidue as usize] = color_pair(COLOR_MAGENTA, COLOR_BLACK);
+        self.colors[ColorElement::PressureStallTen as usize] =
+            color_pair(COLOR_BLACK, COLOR_BLACK) | A_BOLD;
+        self.colors[ColorElement::PressureStallSixty as usize] =
+            color_pair(COLOR_BLACK, COLOR_BLACK);
+        self.colors[ColorElement::PressureStallThreeHundred as usize] =
+            color_pair(COLOR_BLACK, COLOR_BLACK);
+        self.colors[ColorElement::ZfsMfu as usize] = color_pair(COLOR_BLUE, COLOR_BLACK);
+        self.colors[ColorElement::ZfsMru as usize] = color_pair(COLOR_YELLOW, COLOR_BLACK);
+        self.colors[ColorElement::ZfsAnon as usize] = color_pair(COLOR_MAGENTA, COLOR_BLACK);
+        self.colors[ColorElement::ZfsHeader as usize] = color_pair(COLOR_CYAN, COLOR_BLACK);
+        self.colors[ColorElement::ZfsOther as usize] = color_pair(COLOR_MAGENTA, COLOR_BLACK);
+        self.colors[ColorElement::ZfsCompressed as usize] = color_pair(COLOR_BLUE, COLOR_BLACK);
+        self.colors[ColorElement::ZfsRatio as usize] = color_pair(COLOR_MAGENTA, COLOR_BLACK);
+        self.colors[ColorElement::HugePage1 as usize] = color_pair(COLOR_GREEN, COLOR_BLACK);
+        self.colors[ColorElement::HugePage2 as usize] = color_pair(COLOR_YELLOW, COLOR_BLACK);
+        self.colors[ColorElement::HugePage3 as usize] = color_pair(COLOR_RED, COLOR_BLACK);
+        self.colors[ColorElement::HugePage4 as usize] = color_pair(COLOR_BLUE, COLOR_BLACK);
+        self.colors[ColorElement::ZramCompressed as usize] = color_pair(COLOR_BLUE, COLOR_BLACK);
+        self.colors[ColorElement::ZramUncompressed as usize] =
+            color_pair(COLOR_YELLOW, COLOR_BLACK);
         self.colors[ColorElement::ScreensOthBorder as usize] = color_pair(COLOR_BLUE, COLOR_BLACK);
         self.colors[ColorElement::ScreensOthText as usize] = color_pair(COLOR_BLUE, COLOR_BLACK);
         self.colors[ColorElement::ScreensCurBorder as usize] = color_pair(COLOR_GREEN, COLOR_GREEN);
@@ -1400,6 +1500,43 @@ impl Crt {
         self.colors[ColorElement::CpuSoftIrq as usize] = color_pair(COLOR_BLACK, COLOR_BLUE);
         self.colors[ColorElement::CpuSteal as usize] = color_pair(COLOR_WHITE, COLOR_BLUE);
         self.colors[ColorElement::CpuGuest as usize] = color_pair(COLOR_WHITE, COLOR_BLUE);
+        self.colors[ColorElement::GpuEngine1 as usize] =
+            color_pair(COLOR_GREEN, COLOR_BLUE) | A_BOLD;
+        self.colors[ColorElement::GpuEngine2 as usize] =
+            color_pair(COLOR_YELLOW, COLOR_BLUE) | A_BOLD;
+        self.colors[ColorElement::GpuEngine3 as usize] = color_pair(COLOR_RED, COLOR_BLUE) | A_BOLD;
+        self.colors[ColorElement::GpuEngine4 as usize] =
+            color_pair(COLOR_CYAN, COLOR_BLUE) | A_BOLD;
+        self.colors[ColorElement::GpuResidue as usize] =
+            color_pair(COLOR_MAGENTA, COLOR_BLUE) | A_BOLD;
+        self.colors[ColorElement::PressureStallTen as usize] =
+            color_pair(COLOR_WHITE, COLOR_BLUE) | A_BOLD;
+        self.colors[ColorElement::PressureStallSixty as usize] =
+            color_pair(COLOR_WHITE, COLOR_BLUE);
+        self.colors[ColorElement::PressureStallThreeHundred as usize] =
+            color_pair(COLOR_BLACK, COLOR_BLUE) | A_BOLD;
+        self.colors[ColorElement::ZfsMfu as usize] = color_pair(COLOR_WHITE, COLOR_BLUE) | A_BOLD;
+        self.colors[ColorElement::ZfsMru as usize] = color_pair(COLOR_YELLOW, COLOR_BLUE) | A_BOLD;
+        self.colors[ColorElement::ZfsAnon as usize] =
+            color_pair(COLOR_MAGENTA, COLOR_BLUE) | A_BOLD;
+        self.colors[ColorElement::ZfsHeader as usize] = color_pair(COLOR_CYAN, COLOR_BLUE) | A_BOLD;
+        self.colors[ColorElement::ZfsOther as usize] =
+            color_pair(COLOR_MAGENTA, COLOR_BLUE) | A_BOLD;
+        self.colors[ColorElement::ZfsCompressed as usize] =
+            color_pair(COLOR_WHITE, COLOR_BLUE) | A_BOLD;
+        self.colors[ColorElement::ZfsRatio as usize] =
+            color_pair(COLOR_MAGENTA, COLOR_BLUE) | A_BOLD;
+        self.colors[ColorElement::HugePage1 as usize] =
+            color_pair(COLOR_GREEN, COLOR_BLUE) | A_BOLD;
+        self.colors[ColorElement::HugePage2 as usize] =
+            color_pair(COLOR_YELLOW, COLOR_BLUE) | A_BOLD;
+        self.colors[ColorElement::HugePage3 as usize] = color_pair(COLOR_RED, COLOR_BLUE) | A_BOLD;
+        self.colors[ColorElement::HugePage4 as usize] =
+            color_pair(COLOR_WHITE, COLOR_BLUE) | A_BOLD;
+        self.colors[ColorElement::ZramCompressed as usize] =
+            color_pair(COLOR_CYAN, COLOR_BLUE) | A_BOLD;
+        self.colors[ColorElement::ZramUncompressed as usize] =
+            color_pair(COLOR_YELLOW, COLOR_BLUE) | A_BOLD;
         self.colors[ColorElement::ScreensOthBorder as usize] =
             color_pair(COLOR_YELLOW, COLOR_BLUE) | A_BOLD;
         self.colors[ColorElement::ScreensOthText as usize] = color_pair(COLOR_CYAN, COLOR_BLUE);
@@ -1535,6 +1672,32 @@ impl Crt {
         self.colors[ColorElement::CpuSoftIrq as usize] = color_pair(COLOR_BLUE, COLOR_BLACK);
         self.colors[ColorElement::CpuSteal as usize] = color_pair(COLOR_CYAN, COLOR_BLACK);
         self.colors[ColorElement::CpuGuest as usize] = color_pair(COLOR_CYAN, COLOR_BLACK);
+        self.colors[ColorElement::GpuEngine1 as usize] = color_pair(COLOR_GREEN, COLOR_BLACK);
+        self.colors[ColorElement::GpuEngine2 as usize] = color_pair(COLOR_YELLOW, COLOR_BLACK);
+        self.colors[ColorElement::GpuEngine3 as usize] = color_pair(COLOR_RED, COLOR_BLACK);
+        self.colors[ColorElement::GpuEngine4 as usize] =
+            color_pair(COLOR_BLUE, COLOR_BLACK) | A_BOLD;
+        self.colors[ColorElement::GpuResidue as usize] = color_pair(COLOR_MAGENTA, COLOR_BLACK);
+        self.colors[ColorElement::PressureStallTen as usize] =
+            color_pair(COLOR_GREEN, COLOR_BLACK) | A_BOLD;
+        self.colors[ColorElement::PressureStallSixty as usize] =
+            color_pair(COLOR_GREEN, COLOR_BLACK);
+        self.colors[ColorElement::PressureStallThreeHundred as usize] =
+            color_pair(COLOR_GREEN, COLOR_BLACK);
+        self.colors[ColorElement::ZfsMfu as usize] = color_pair(COLOR_BLUE, COLOR_BLACK);
+        self.colors[ColorElement::ZfsMru as usize] = color_pair(COLOR_YELLOW, COLOR_BLACK);
+        self.colors[ColorElement::ZfsAnon as usize] = color_pair(COLOR_MAGENTA, COLOR_BLACK);
+        self.colors[ColorElement::ZfsHeader as usize] = color_pair(COLOR_YELLOW, COLOR_BLACK);
+        self.colors[ColorElement::ZfsOther as usize] = color_pair(COLOR_MAGENTA, COLOR_BLACK);
+        self.colors[ColorElement::ZfsCompressed as usize] = color_pair(COLOR_BLUE, COLOR_BLACK);
+        self.colors[ColorElement::ZfsRatio as usize] = color_pair(COLOR_MAGENTA, COLOR_BLACK);
+        self.colors[ColorElement::HugePage1 as usize] = color_pair(COLOR_GREEN, COLOR_BLACK);
+        self.colors[ColorElement::HugePage2 as usize] = color_pair(COLOR_YELLOW, COLOR_BLACK);
+        self.colors[ColorElement::HugePage3 as usize] = color_pair(COLOR_RED, COLOR_BLACK);
+        self.colors[ColorElement::HugePage4 as usize] = color_pair(COLOR_BLUE, COLOR_BLACK);
+        self.colors[ColorElement::ZramCompressed as usize] = color_pair(COLOR_BLUE, COLOR_BLACK);
+        self.colors[ColorElement::ZramUncompressed as usize] =
+            color_pair(COLOR_YELLOW, COLOR_BLACK);
         self.colors[ColorElement::ScreensOthBorder as usize] = color_pair(COLOR_WHITE, COLOR_BLACK);
         self.colors[ColorElement::ScreensOthText as usize] = color_pair(COLOR_CYAN, COLOR_BLACK);
         self.colors[ColorElement::ScreensCurBorder as usize] =
@@ -1698,6 +1861,29 @@ impl Crt {
         self.colors[ColorElement::CpuSoftIrq as usize] = A_BOLD;
         self.colors[ColorElement::CpuSteal as usize] = color_pair_attr(PAIR_GRAY_BLACK) | A_BOLD;
         self.colors[ColorElement::CpuGuest as usize] = color_pair_attr(PAIR_GRAY_BLACK) | A_BOLD;
+        self.colors[ColorElement::GpuEngine1 as usize] = A_BOLD;
+        self.colors[ColorElement::GpuEngine2 as usize] = color_pair_attr(PAIR_YELLOW_BLACK);
+        self.colors[ColorElement::GpuEngine3 as usize] =
+            color_pair_attr(PAIR_YELLOW_BLACK) | A_BOLD;
+        self.colors[ColorElement::GpuEngine4 as usize] = color_pair_attr(PAIR_CYAN_BLACK);
+        self.colors[ColorElement::GpuResidue as usize] = color_pair_attr(PAIR_GRAY_BLACK) | A_BOLD;
+        self.colors[ColorElement::PressureStallTen as usize] = A_BOLD;
+        self.colors[ColorElement::PressureStallSixty as usize] = A_NORMAL;
+        self.colors[ColorElement::PressureStallThreeHundred as usize] =
+            color_pair_attr(PAIR_GRAY_BLACK) | A_BOLD;
+        self.colors[ColorElement::ZfsMfu as usize] = A_BOLD;
+        self.colors[ColorElement::ZfsMru as usize] = color_pair_attr(PAIR_YELLOW_BLACK);
+        self.colors[ColorElement::ZfsAnon as usize] = A_NORMAL;
+        self.colors[ColorElement::ZfsHeader as usize] = color_pair_attr(PAIR_GRAY_BLACK) | A_BOLD;
+        self.colors[ColorElement::ZfsOther as usize] = A_DIM;
+        self.colors[ColorElement::ZfsCompressed as usize] = A_BOLD;
+        self.colors[ColorElement::ZfsRatio as usize] = color_pair_attr(PAIR_YELLOW_BLACK);
+        self.colors[ColorElement::HugePage1 as usize] = A_BOLD;
+        self.colors[ColorElement::HugePage2 as usize] = A_NORMAL;
+        self.colors[ColorElement::HugePage3 as usize] = color_pair_attr(PAIR_YELLOW_BLACK);
+        self.colors[ColorElement::HugePage4 as usize] = color_pair_attr(PAIR_GRAY_BLACK) | A_BOLD;
+        self.colors[ColorElement::ZramCompressed as usize] = A_BOLD;
+        self.colors[ColorElement::ZramUncompressed as usize] = color_pair_attr(PAIR_YELLOW_BLACK);
         self.colors[ColorElement::ScreensOthBorder as usize] =
             color_pair_attr(PAIR_GRAY_BLACK) | A_BOLD;
         self.colors[ColorElement::ScreensOthText as usize] =
@@ -6,8 +6,10 @@ use rayon::prelude::*;
 
 use super::crt::{ColorElement, A_NORMAL};
 use super::Crt;
+#[cfg(target_os = "linux")]
+use crate::core::ScanFlags;
 use crate::core::{HeaderLayout, Machine, Settings};
-use crate::meters::meter_bg_scanner::{MeterBackgroundScanner, MeterDataId};
+use crate::meters::meter_bg_scanner::{MeterBackgroundScanner, MeterDataId, MeterExpensiveData};
 use crate::meters::{Meter, MeterType};
 
 /// Header containing meters
@@ -90,9 +92,11 @@ impl Header {
 
         for (col_idx, column_config) in settings.header_columns.iter().enumerate() {
             for meter_config in column_config {
-                if let Some(mut meter) =
-                    MeterType::create_from_name(&meter_config.name, meter_config.param)
-                {
+                let meter = match &meter_config.device {
+                    Some(device) => MeterType::create_for_device(&meter_config.name, device),
+                    None => MeterType::create_from_name(&meter_config.name, meter_config.param),
+                };
+                if let Some(mut meter) = meter {
                     // Apply the mode from config
                     meter.set_mode(meter_config.mode.into());
                     if col_idx < self.columns.len() {
@@ -137,9 +141,16 @@ impl Header {
     /// 1. Merge any completed background results into meters
     /// 2. Update meters with fast data from Machine (parallel)
     /// 3. Start background scan for expensive meter data
-    pub fn update(&mut self, machine: &Machine) {
+    pub fn update(&mut self, machine: &mut Machine) {
         // 1. Merge completed background results from previous frame
         if let Some(bg_results) = self.bg_scanner.try_take_results() {
+            // Temperatures are per CPU, so they go to the machine where the
+            // CPU meters read them along with the usage of each CPU
+            if let Some(MeterExpensiveData::CpuTemperature(temperatures)) =
+                bg_results.get(&MeterDataId::CpuTemperature)
+            {
+                machine.set_cpu_temperatures(temperatures.as_ref());
+            }
             for column in &mut self.columns {
                 for meter in column {
                     if let Some(id) = meter.expensive_data_id() {
@@ -152,6 +163,7 @@ impl Header {
         }
 
         // 2. Update all meters with fast data from Machine (parallel)
+        let machine = &*machine;
         self.columns.par_iter_mut().for_each(|column| {
             column.par_iter_mut().for_each(|meter| {
                 meter.update(machine);
@@ -177,6 +189,17 @@ impl Header {
         self.calculate_height();
     }
 
+    /// Process scan flags needed by the meters in the header
+    #[cfg(target_os = "linux")]
+    pub fn scan_flags(&self) -> ScanFlags {
+        self.columns
+            .iter()
+            .flatten()
+            .fold(ScanFlags::empty(), |flags, meter| {
+                flags | meter.scan_flags()
+            })
+    }
+
     /// Draw the header (matches C htop Header_draw)
     pub fn draw(&self, crt: &mut Crt, machine: &Machine, settings: &Settings) {
         let screen_width = crt.width();
@@ -6,10 +6,13 @@
 //! - Selectable line list with scrolling
 //! - Search/filter support
 //! - Standard navigation keys
+//! - Optional copying of the selected line to the clipboard
+//! - Screen-specific keys through `InfoScreenContent`
 
+use super::clipboard::copy_to_clipboard;
 use super::crt::{
-    ColorElement, Crt, A_NORMAL, KEY_CTRL_L, KEY_CTRL_N, KEY_CTRL_P, KEY_DOWN, KEY_END, KEY_F10,
-    KEY_F5, KEY_HOME, KEY_NPAGE, KEY_PPAGE, KEY_UP, KEY_WHEELDOWN, KEY_WHEELUP,
+    ColorElement, Crt, A_NORMAL, ERR, KEY_CTRL_L, KEY_CTRL_N, KEY_CTRL_P, KEY_DOWN, KEY_END,
+    KEY_F10, KEY_F5, KEY_HOME, KEY_LC_C, KEY_NPAGE, KEY_PPAGE, KEY_UP, KEY_WHEELDOWN, KEY_WHEELUP,
 };
 use super::search_filter::{self, HandleResult, SearchFilterState};
 
@@ -21,6 +24,95 @@ pub struct InfoScreenConfig<'a> {
     pub header: Option<&'a str>,
     /// Whether to use needs_redraw optimization (for screens that may not change often)
     pub use_redraw_optimization: bool,
+    /// Text to copy for the selected line with 'c' (None disables copying)
+    pub copy_text: Option<fn(&str) -> &str>,
+}
+
+/// Content of an info screen, for screens that handle keys of their own
+/// (e.g. to change the sort order)
+pub trait InfoScreenContent {
+    /// Lines to display
+    fn lines(&self) -> &[String];
+
+    /// Header row, replacing the one of the configuration
+    fn header(&self) -> Option<String> {
+        None
+    }
+
+    /// Re-read the content (F5)
+    fn refresh(&mut self);
+
+    /// Whether to refresh the content every update interval
+    fn live(&self) -> bool {
+        false
+    }
+
+    /// Additional (key, label) entries of the function bar
+    fn function_keys(&self) -> &[(&'static str, &'static str)] {
+        &[]
+    }
+
+    /// Prompt and text of a value being entered. While set, all keys go
+    /// to `on_key` and the function bar shows the input line.
+    fn input(&self) -> Option<(&str, &str)> {
+        None
+    }
+
+    /// Text to copy with 'c' for the line at `index`, by default the line
+    /// as mapped by the configuration's `copy_text`. None if the line
+    /// cannot be copied.
+    fn copy_text(&self, index: usize, copy_text: fn(&str) -> &str) -> Option<String> {
+        Some(copy_text(&self.lines()[index]).to_string())
+    }
+
+    /// Handle a key not used by the info screen, with the index of the
+    /// selected line. Returns true if handled.
+    fn on_key(&mut self, _key: i32, _selected: Option<usize>) -> bool {
+        false
+    }
+}
+
+/// Draw the input line of a content's prompt in place of the function bar
+fn draw_input_bar(crt: &mut Crt, y: i32, screen_width: i32, prompt: &str, text: &str) {
+    let bar_attr = crt.color(ColorElement::FunctionBar);
+    let key_attr = crt.color(ColorElement::FunctionKey);
+
+    crt.mv(y, 0);
+    crt.attrset(bar_attr);
+    crt.hline(y, 0, ' ' as u32, screen_width);
+    crt.mv(y, 0);
+    crt.attrset(key_attr);
+    crt.addstr_raw("Enter");
+    crt.attrset(bar_attr);
+    crt.addstr_raw("Apply ");
+    crt.attrset(key_attr);
+    crt.addstr_raw("Esc");
+    crt.attrset(bar_attr);
+    crt.addstr_raw("Cancel ");
+    crt.attrset(key_attr);
+    crt.addstr_raw(prompt);
+    crt.addstr_raw(": ");
+    crt.attrset(bar_attr);
+    crt.addstr_raw(text);
+    crt.attrset(A_NORMAL);
+}
+
+/// Fixed lines with an optional refresh callback
+struct LineContent<'a, F> {
+    lines: &'a mut Vec<String>,
+    refresh_fn: Option<F>,
+}
+
+impl<F: Fn() -> Vec<String>> InfoScreenContent for LineContent<'_, F> {
+    fn lines(&self) -> &[String] {
+        self.lines
+    }
+
+    fn refresh(&mut self) {
+        if let Some(ref refresh) = self.refresh_fn {
+            *self.lines = refresh();
+        }
+    }
 }
 
 /// Run an info screen with the given configuration
@@ -38,17 +130,31 @@ pub fn run_info_screen<F>(
 ) where
     F: Fn() -> Vec<String>,
 {
+    run_info_screen_content(crt, config, &mut LineContent { lines, refresh_fn });
+}
+
+/// Run an info screen showing `content`
+pub fn run_info_screen_content(
+    crt: &mut Crt,
+    config: &InfoScreenConfig,
+    content: &mut dyn InfoScreenContent,
+) {
     let mut selected = 0i32;
     let mut scroll_v = 0i32;
     let mut sf_state = SearchFilterState::new();
     let mut needs_redraw = true;
-
-    let has_header = config.header.is_some();
-    let panel_y = if has_header { 2 } else { 1 };
-    let height_offset = if has_header { 3 } else { 2 }; // Title + header? + function bar
+    // Result of the last copy, shown in the title until the next key
+    let mut status: Option<String> = None;
 
     loop {
-        let filtered_indices = sf_state.filter_indices(lines);
+        let header = content
+            .header()
+            .or_else(|| config.header.map(str::to_string));
+        let has_header = header.is_some();
+        let panel_y = if has_header { 2 } else { 1 };
+        let height_offset = if has_header { 3 } else { 2 }; // Title + header? + function bar
+
+        let filtered_indices = sf_state.filter_indices(content.lines());
         let panel_height = crt.height() - height_offset;
 
         // Clamp selection and scroll
@@ -70,7 +176,11 @@ pub fn run_info_screen<F>(
 
             // Draw title
             let title_attr = crt.color(ColorElement::MeterText);
-            let title_display: String = config.title.chars().take(screen_width as usize).collect();
+            let title = match &status {
+                Some(status) => format!("{} - {}", config.title, status),
+                None => config.title.clone(),
+            };
+            let title_display: String = title.chars().take(screen_width as usize).collect();
             crt.mv(0, 0);
             crt.attrset(title_attr);
             crt.hline(0, 0, ' ' as u32, screen_width);
@@ -78,7 +188,7 @@ pub fn run_info_screen<F>(
             crt.attrset(A_NORMAL);
 
             // Draw header if present
-            if let Some(header) = config.header {
+            if let Some(header) = &header {
                 let header_attr = crt.color(ColorElement::PanelHeaderFocus);
                 let header_display: String = header.chars().take(screen_width as usize).collect();
                 crt.mv(1, 0);
@@ -98,7 +208,7 @@ pub fn run_info_screen<F>(
 
                 if line_idx < filtered_indices.len() {
                     let actual_idx = filtered_indices[line_idx];
-                    let line = &lines[actual_idx];
+                    let line = &content.lines()[actual_idx];
                     let is_selected = (scroll_v + row) == selected;
 
                     let attr = if is_selected {
@@ -122,15 +232,42 @@ pub fn run_info_screen<F>(
 
             // Draw function bar or search/filter bar
             let fb_y = crt.height() - 1;
-            sf_state.draw_bar(crt, fb_y, screen_width);
+            if let Some((prompt, text)) = content.input() {
+                draw_input_bar(crt, fb_y, screen_width, prompt, text);
+            } else {
+                let mut extra = content.function_keys().to_vec();
+                if config.copy_text.is_some() {
+                    extra.push(("c", "Copy  "));
+                }
+                sf_state.draw_bar_with(crt, fb_y, screen_width, &extra);
+            }
 
             crt.refresh();
         }
 
         // Handle input
-        crt.set_blocking(true);
+        if content.live() {
+            // Wait at most one update interval
+            crt.enable_delay();
+        } else {
+            crt.set_blocking(true);
+        }
         let mut ch = crt.getch();
         needs_redraw = true; // Assume we need redraw
+        if ch == ERR {
+            // Update interval elapsed without a key
+            content.refresh();
+            continue;
+        }
+        status = None;
+
+        let selected_line = filtered_indices.get(selected as usize).copied();
+
+        // Keys go to the content while it reads a value
+        if content.input().is_some() {
+            content.on_key(ch, selected_line);
+            continue;
+        }
 
         // Handle mouse events
         ch = search_filter::process_mouse_event(crt, &sf_state, ch);
@@ -139,7 +276,8 @@ pub fn run_info_screen<F>(
         if sf_state.is_active() {
             match sf_state.handle_input(ch) {
                 HandleResult::SearchChanged => {
-                    if let Some(idx) = sf_state.find_first_match(lines, &filtered_indices) {
+                    if let Some(idx) = sf_state.find_first_match(content.lines(), &filtered_indices)
+                    {
                         selected = idx as i32;
                     }
                 }
@@ -148,16 +286,20 @@ pub fn run_info_screen<F>(
                     scroll_v = 0;
                 }
                 HandleResult::SearchNext => {
-                    if let Some(idx) =
-                        sf_state.find_next_match(lines, &filtered_indices, selected as usize)
-                    {
+                    if let Some(idx) = sf_state.find_next_match(
+                        content.lines(),
+                        &filtered_indices,
+                        selected as usize,
+                    ) {
                         selected = idx as i32;
                     }
                 }
                 HandleResult::SearchPrev => {
-                    if let Some(idx) =
-                        sf_state.find_prev_match(lines, &filtered_indices, selected as usize)
-                    {
+                    if let Some(idx) = sf_state.find_prev_match(
+                        content.lines(),
+                        &filtered_indices,
+                        selected as usize,
+                    ) {
                         selected = idx as i32;
                     }
                 }
@@ -176,18 +318,25 @@ pub fn run_info_screen<F>(
             27 | 113 | KEY_F10 => break, // Escape, 'q', or F10 - exit
             x if x == KEY_F5 => {
                 // F5 - refresh
-                if let Some(ref refresh) = refresh_fn {
-                    let saved_selected = selected;
-                    *lines = refresh();
-                    let max_idx = (lines.len() as i32 - 1).max(0);
-                    selected = saved_selected.min(max_idx);
-                    crt.clear();
-                }
+                let saved_selected = selected;
+                content.refresh();
+                let max_idx = (content.lines().len() as i32 - 1).max(0);
+                selected = saved_selected.min(max_idx);
+                crt.clear();
             }
             KEY_CTRL_L => {
                 // Ctrl+L - refresh screen
                 crt.clear();
             }
+            KEY_LC_C if config.copy_text.is_some() => {
+                // 'c' - copy the selected line
+                if let (Some(copy_text), Some(idx)) = (config.copy_text, selected_line) {
+                    status = Some(match content.copy_text(idx, copy_text) {
+                        Some(text) => format!("copied via {}", copy_to_clipboard(&text)),
+                        None => "nothing to copy".to_string(),
+                    });
+                }
+            }
             KEY_UP | KEY_CTRL_P => {
                 if selected > 0 {
                     selected -= 1;
@@ -214,6 +363,7 @@ pub fn run_info_screen<F>(
             KEY_WHEELDOWN => {
                 selected = (selected + 3).min(max_selected);
             }
+            _ if content.on_key(ch, selected_line) => {}
             _ => {
                 // Unknown key, no redraw needed if using optimization
                 if config.use_redraw_optimization {
@@ -18,8 +18,11 @@ use super::row_print::{
     print_count, print_kbytes, print_left_aligned, print_percentage, print_time,
 };
 use super::Crt;
+#[cfg(target_os = "linux")]
+use crate::core::ScanFlags;
 use crate::core::{
-    highlight_flags, FieldWidths, Machine, Process, ProcessField, ProcessState, Settings,
+    highlight_flags, FieldWidths, Machine, Process, ProcessField, ProcessFilter, ProcessState,
+    Settings,
 };
 #[cfg(target_os = "linux")]
 use crate::platform::linux::{
@@ -96,6 +99,20 @@ impl IncSearch {
     pub fn clear(&mut self) {
         self.text.clear();
     }
+
+    /// Apply a line editing key (printable ASCII, Backspace or Ctrl+U) to
+    /// the text, other keys are ignored. Multi-byte characters arrive one
+    /// byte at a time and are dropped.
+    pub fn edit_key(&mut self, ch: i32) {
+        match ch {
+            KEY_CTRL_U => self.clear(),
+            KEY_BACKSPACE | KEY_DEL_MAC | KEY_CTRL_BS => self.backspace(),
+            ch if (KEY_PRINTABLE_START..KEY_PRINTABLE_END).contains(&ch) => {
+                self.add_char(ch as u8 as char)
+            }
+            _ => {}
+        }
+    }
 }
 
 /// Main process list panel
@@ -117,7 +134,7 @@ pub struct MainPanel {
 
     // Search/filter
     pub inc_search: IncSearch,
-    pub filter: Option<String>,
+    pub filter: Option<ProcessFilter>,
 
     // Following state (for filter/search - shows yellow highlight)
     pub following: bool,
@@ -195,7 +212,7 @@ impl MainPanel {
         if filter.is_empty() {
             self.filter = None;
         } else {
-            self.filter = Some(filter.to_string());
+            self.filter = Some(ProcessFilter::new(filter));
         }
         self.invalidate_display_list();
     }
@@ -215,6 +232,12 @@ impl MainPanel {
         self.filter.is_some()
     }
 
+    /// Check if the filter text is not a valid query, so it is only
+    /// matched as a substring of the command
+    pub fn is_filter_invalid(&self) -> bool {
+        self.filter.as_ref().is_some_and(|f| f.error().is_some())
+    }
+
     /// Update function bar labels based on current state
     /// Matches C htop MainPanel_updateLabels behavior
     pub fn update_labels(&mut self, tree_view: bool, has_filter: bool) {
@@ -568,6 +591,7 @@ impl MainPanel {
                 settings.show_thread_names,
                 settings.show_merged_command,
                 settings.highlight_deleted_exe,
+                settings.highlight_unconfined,
                 settings.shadow_dist_path_prefix,
                 settings.find_comm_in_cmdline,
                 settings.strip_exe_from_cmdline,
@@ -644,6 +668,7 @@ impl MainPanel {
         _show_thread_names: bool,
         _show_merged_command: bool,
         highlight_deleted_exe: bool,
+        highlight_unconfined: bool,
         shadow_dist_path_prefix: bool,
         _find_comm_in_cmdline: bool,
         _strip_exe_from_cmdline: bool,
@@ -792,6 +817,25 @@ impl MainPanel {
                 str.append(&format!("{:>3} ", process.processor), base_color);
             }
             ProcessField::Command => {
+                // Label the tree of a container with its summed up usage
+                if self.tree_view {
+                    if let Some((container, usage)) = process
+                        .container_short
+                        .as_ref()
+                        .zip(process.container_usage.as_ref())
+                    {
+                        str.append(
+                            &format!(
+                                "[{} {:.1}% {}] ",
+                                container,
+                                usage.percent_cpu,
+                                Process::format_memory(usage.m_resident).trim()
+                            ),
+                            crt.color(ColorElement::ProcessTree),
+                        );
+                    }
+                }
+
                 // Draw tree indentation if in tree view mode
                 if self.tree_view && process.indent != 0 {
                     let tree_attr = crt.color(ColorElement::ProcessTree);
@@ -1440,7 +1484,13 @@ impl MainPanel {
             ProcessField::SecAttr => {
                 // Security Attribute (18 chars)
                 let sec_attr = process.sec_attr.as_deref().unwrap_or("?");
-                print_left_aligned(str, base_color, sec_attr, 18);
+                // Red like deleted executables: both are worth a second look
+                let color = if highlight_unconfined && !is_shadowed && process.is_unconfined() {
+                    crt.color(ColorElement::FailedRead)
+                } else {
+                    base_color
+                };
+                print_left_aligned(str, color, sec_attr, 18);
             }
             #[cfg(target_os = "linux")]
             ProcessField::AutogroupId => {
@@ -1498,13 +1548,22 @@ impl MainPanel {
             }
             #[cfg(target_os = "linux")]
             ProcessField::GpuTime => {
-                // GPU_TIME (9 chars) - requires DRM/GPU support
-                str.append("     N/A ", shadow_color);
+                // GPU_TIME (9 chars) - busy time from DRM fdinfo, in nanoseconds
+                print_time(
+                    str,
+                    process.gpu_time / 10_000_000,
+                    coloring && !is_shadowed,
+                    crt,
+                );
             }
             #[cfg(target_os = "linux")]
             ProcessField::GpuPercent => {
-                // GPU% (6 chars) - requires DRM/GPU support
-                str.append("  N/A ", shadow_color);
+                // GPU% (6 chars) - like C htop Row_printPercentage with width 5
+                if is_shadowed && process.gpu_percent.is_finite() {
+                    str.append(&format!("{:>5.1} ", process.gpu_percent), shadow_color);
+                } else {
+                    print_percentage(str, process.gpu_percent, 5, crt);
+                }
             }
             #[cfg(target_os = "linux")]
             ProcessField::IsContainer => {
@@ -1521,6 +1580,35 @@ impl MainPanel {
                     str.append("NO   ", shadow_color);
                 }
             }
+            #[cfg(target_os = "linux")]
+            ProcessField::Affinity => {
+                // AFFINITY (10 chars) - CPU list from sched_getaffinity
+                match process.cpu_affinity {
+                    Some(ref affinity) => {
+                        print_left_aligned(str, base_color, &affinity.to_string(), 10)
+                    }
+                    None => print_left_aligned(str, shadow_color, "N/A", 10),
+                }
+            }
+            #[cfg(target_os = "linux")]
+            ProcessField::NetRxRate => {
+                // Estimated network receive rate in bytes per second
+                print_rate(str, process.net_rx_rate_bps, coloring && !is_shadowed, crt);
+            }
+            #[cfg(target_os = "linux")]
+            ProcessField::NetTxRate => {
+                // Estimated network transmit rate in bytes per second
+                print_rate(str, process.net_tx_rate_bps, coloring && !is_shadowed, crt);
+            }
+            #[cfg(target_os = "linux")]
+            ProcessField::Sockets => {
+                // SOCK: open TCP, UDP and Unix sockets (5 chars)
+                if process.socket_count < 0 {
+                    str.append("  N/A ", shadow_color);
+                } else {
+                    str.append(&format!("{:>5} ", process.socket_count), base_color);
+                }
+            }
 
             // === macOS-specific fields ===
             #[cfg(target_os = "macos")]
@@ -1706,13 +1794,17 @@ impl MainPanel {
 
     /// Check if a process matches the current filter
     fn matches_filter(&self, process: &Process) -> bool {
-        if let Some(ref filter) = self.filter {
-            let cmd = process.get_command().to_lowercase();
-            let filter_lower = filter.to_lowercase();
-            cmd.contains(&filter_lower)
-        } else {
-            true
-        }
+        self.filter
+            .as_ref()
+            .is_none_or(|filter| filter.matches(process))
+    }
+
+    /// Scan flags needed by the current filter expression
+    #[cfg(target_os = "linux")]
+    pub fn filter_scan_flags(&self) -> ScanFlags {
+        self.filter
+            .as_ref()
+            .map_or(ScanFlags::empty(), |filter| filter.scan_flags())
     }
 
     /// Check if a process should be visible based on settings
@@ -1735,12 +1827,12 @@ impl MainPanel {
         }
 
         // Check kernel threads filter
-        if settings.hide_kernel_threads && process.is_kernel_thread {
+        if settings.kernel_threads_hidden() && process.is_kernel_thread {
             return false;
         }
 
         // Check userland threads filter
-        if settings.hide_userland_threads && process.is_userland_thread {
+        if settings.userland_threads_hidden() && process.is_userland_thread {
             return false;
         }
 
@@ -1800,7 +1892,7 @@ impl MainPanel {
                 // Start filter mode with existing filter text (if any)
                 // Matches C htop actionIncFilter - always opens filter mode
                 self.inc_search
-                    .start(IncType::Filter, self.filter.as_deref());
+                    .start(IncType::Filter, self.filter.as_ref().map(|f| f.text()));
                 HandlerResult::Handled
             }
             KEY_LEFT | KEY_CTRL_B => {
@@ -1897,7 +1989,7 @@ impl MainPanel {
                     self.filter = if self.inc_search.text.is_empty() {
                         None
                     } else {
-                        Some(self.inc_search.text.clone())
+                        Some(ProcessFilter::new(&self.inc_search.text))
                     };
                     self.invalidate_display_list();
                 }
@@ -1908,7 +2000,7 @@ impl MainPanel {
                 self.inc_search.add_char(ch as u8 as char);
                 if is_filter {
                     // Update filter in real-time
-                    self.filter = Some(self.inc_search.text.clone());
+                    self.filter = Some(ProcessFilter::new(&self.inc_search.text));
                     self.invalidate_display_list();
                 }
                 self.do_incremental_search(machine);
@@ -1922,7 +2014,7 @@ impl MainPanel {
                     self.filter = if self.inc_search.text.is_empty() {
                         None
                     } else {
-                        Some(self.inc_search.text.clone())
+                        Some(ProcessFilter::new(&self.inc_search.text))
                     };
                     self.invalidate_display_list();
                     // Keep yellow "following" selection when filter is active
@@ -2069,6 +2161,22 @@ impl MainPanel {
         self.needs_redraw = true;
     }
 
+    /// Get the processes currently shown, in display order
+    /// (filtered, sorted and tree-ordered exactly like the drawn rows)
+    pub fn get_displayed_processes<'a>(
+        &mut self,
+        machine: &'a Machine,
+        settings: &Settings,
+    ) -> Vec<&'a Process> {
+        if !self.display_list_valid {
+            self.rebuild_display_list(machine, settings);
+        }
+        self.cached_display_indices
+            .iter()
+            .filter_map(|&idx| machine.processes.processes.get(idx))
+            .collect()
+    }
+
     /// Get count of visible processes
     /// Uses cached display list which includes all filtering
     fn get_visible_count(&self, _machine: &Machine) -> i32 {
@@ -2191,20 +2299,19 @@ impl MainPanel {
 
         // Get the search string (non-mutable borrow)
         let search_str = self.pid_search.as_ref().unwrap().clone();
-        let filter = self.filter.clone();
 
         // Find process matching the PID prefix
         let processes: Vec<&Process> = if self.tree_view {
             machine
                 .processes
                 .iter_tree()
-                .filter(|p| self.matches_filter_with(p, &filter))
+                .filter(|p| self.matches_filter(p))
                 .collect()
         } else {
             machine
                 .processes
                 .iter()
-                .filter(|p| self.matches_filter_with(p, &filter))
+                .filter(|p| self.matches_filter(p))
                 .collect()
         };
 
@@ -2222,17 +2329,6 @@ impl MainPanel {
         // In C htop, there's a timeout - we'll simplify by clearing on next non-digit
     }
 
-    /// Helper for filtering with explicit filter
-    fn matches_filter_with(&self, process: &Process, filter: &Option<String>) -> bool {
-        if let Some(ref f) = filter {
-            let cmd = process.get_command().to_lowercase();
-            let filter_lower = f.to_lowercase();
-            cmd.contains(&filter_lower)
-        } else {
-            true
-        }
-    }
-
     /// Clear PID search state
     pub fn clear_pid_search(&mut self) {
         self.pid_search = None;
@@ -5,14 +5,20 @@
 //! - Kill signal menu (F9/k)
 //! - Sort column menu (F6/>.)
 //! - User filter menu (u)
-
-use super::crt::ColorElement;
+//! - CPU affinity menu (a)
+//! - IO priority menu (i, Linux only)
+//! - Text prompt on the function bar line (used by CSV export)
+
+use super::crt::{
+    ColorElement, A_NORMAL, CURSOR_INVISIBLE, CURSOR_VISIBLE, KEY_ENTER, KEY_ESC, KEY_LINEFEED,
+    KEY_RETURN,
+};
 use super::function_bar::FunctionBar;
 use super::header::Header;
-use super::main_panel::MainPanel;
+use super::main_panel::{IncSearch, MainPanel};
 use super::panel::Panel;
 use super::Crt;
-use crate::core::{Machine, ProcessField, Settings};
+use crate::core::{Affinity, Machine, ProcessField, Settings};
 
 /// Convert SPDX license identifier to display string
 fn license_display() -> &'static str {
@@ -80,6 +86,7 @@ pub fn show_help(crt: &mut Crt, settings: &Settings) {
         ("      H: ", "hide/show user process threads", false),
         ("      K: ", "hide/show kernel threads", false),
         ("      O: ", "hide/show processes in containers", false),
+        ("      G: ", "group processes by cgroup", false),
         ("      F: ", "cursor follows process", false),
         ("  + - *: ", "expand/collapse tree/toggle all", false),
         ("N P M T: ", "sort by PID, CPU%, MEM% or TIME", false),
@@ -96,10 +103,16 @@ pub fn show_help(crt: &mut Crt, settings: &Settings) {
         ("   F9 k: ", "kill process/tagged processes", true),
         ("   F7 ]: ", "higher priority (- nice)", true),
         ("   F8 [: ", "lower priority (+ nice)", true),
-        ("      e: ", "show process environment", false),
+        ("      d: ", "show process details", false),
+        ("      e: ", "show environment (diff of 2 tagged)", false),
         ("      i: ", "set IO priority", true),
+        ("      a: ", "set CPU affinity", true),
         ("      l: ", "list open files with lsof", true),
+        ("      o: ", "show memory maps (smaps)", false),
+        ("      r: ", "edit resource limits (prlimit)", true),
         ("      x: ", "list file locks of process", false),
+        ("      E: ", "export visible rows to CSV", false),
+        ("    { }: ", "previous/next frame of a replay", false),
         ("      s: ", "trace syscalls with strace", true),
         ("      w: ", "wrap process command in multiple lines", false),
         (" F2 C S: ", "setup", false),
@@ -642,3 +655,209 @@ pub fn show_user_menu(
 
     UserMenuResult { user_id: None }
 }
+
+/// Context for CPU affinity menu
+pub struct AffinityMenuContext<'a> {
+    pub main_panel: &'a mut MainPanel,
+    pub header: &'a Header,
+    pub settings: &'a Settings,
+    pub hide_meters: bool,
+}
+
+/// Show CPU affinity menu (like C htop AffinityPanel without hwloc)
+/// One checkbox per CPU, pre-checked from `current`; Space toggles, Enter sets.
+///
+/// Returns the chosen CPU set, or None if cancelled or no CPU was checked.
+pub fn show_affinity_menu(
+    crt: &mut Crt,
+    machine: &mut Machine,
+    ctx: &mut AffinityMenuContext,
+    current: &Affinity,
+) -> Option<Affinity> {
+    // C htop uses width 15 for the affinity panel
+    let affinity_panel_width = 15i32;
+    let panel_y = ctx.main_panel.y;
+    let panel_height = crt.height() - panel_y - 1; // Leave room for function bar
+
+    let mut affinity_panel = Panel::new(0, panel_y, affinity_panel_width, panel_height);
+    affinity_panel.set_header("Use CPUs:");
+    affinity_panel.function_bar = FunctionBar::new_enter_esc("Set    ", "Cancel ");
+
+    let cpu_count = machine.existing_cpus.max(1);
+    for cpu in 0..cpu_count {
+        affinity_panel.add_check_item(&format!("cpu {}", cpu), cpu as i32, current.contains(cpu));
+    }
+
+    // Run the side panel menu
+    let mut side_ctx = super::side_panel_menu::SidePanelContext {
+        main_panel: ctx.main_panel,
+        header: ctx.header,
+        settings: ctx.settings,
+        hide_meters: ctx.hide_meters,
+    };
+
+    let result = super::side_panel_menu::run_side_panel_menu(
+        crt,
+        machine,
+        &mut side_ctx,
+        &mut affinity_panel,
+    );
+
+    if let super::side_panel_menu::SidePanelResult::Selected(_) = result {
+        let affinity: Affinity = (0..cpu_count)
+            .filter(|&cpu| affinity_panel.is_checked(cpu as usize) == Some(true))
+            .collect();
+        if !affinity.is_empty() {
+            return Some(affinity);
+        }
+    }
+
+    None
+}
+
+/// Context for IO priority menu
+#[cfg(target_os = "linux")]
+pub struct IOPriorityMenuContext<'a> {
+    pub main_panel: &'a mut MainPanel,
+    pub header: &'a Header,
+    pub settings: &'a Settings,
+    pub hide_meters: bool,
+}
+
+/// Show IO priority menu (matches C htop IOPriorityPanel)
+/// Lists "None", the realtime and best-effort levels 0-7, and idle,
+/// with the current ioprio value pre-selected.
+///
+/// Returns the chosen ioprio value, or None if cancelled.
+#[cfg(target_os = "linux")]
+pub fn show_io_priority_menu(
+    crt: &mut Crt,
+    machine: &mut Machine,
+    ctx: &mut IOPriorityMenuContext,
+    current: i32,
+) -> Option<i32> {
+    use crate::platform::linux::{
+        ioprio_value, IOPRIO_CLASS_BE, IOPRIO_CLASS_IDLE, IOPRIO_CLASS_NONE, IOPRIO_CLASS_RT,
+    };
+
+    // C htop uses width 21 for the IO priority panel
+    let io_priority_panel_width = 21i32;
+    let panel_y = ctx.main_panel.y;
+    let panel_height = crt.height() - panel_y - 1; // Leave room for function bar
+
+    let mut priorities: Vec<(String, i32)> = vec![(
+        "None (based on nice)".to_string(),
+        ioprio_value(IOPRIO_CLASS_NONE, 0),
+    )];
+    for (class, class_name) in [
+        (IOPRIO_CLASS_RT, "Realtime"),
+        (IOPRIO_CLASS_BE, "Best-effort"),
+    ] {
+        for level in 0..8 {
+            let suffix = match level {
+                0 => " (High)",
+                7 => " (Low)",
+                _ => "",
+            };
+            priorities.push((
+                format!("{} {}{}", class_name, level, suffix),
+                ioprio_value(class, level),
+            ));
+        }
+    }
+    // C htop's IOPriority_Idle uses level 7
+    priorities.push(("Idle".to_string(), ioprio_value(IOPRIO_CLASS_IDLE, 7)));
+
+    let mut io_priority_panel = Panel::new(0, panel_y, io_priority_panel_width, panel_height);
+    io_priority_panel.set_header("IO Priority:");
+    io_priority_panel.function_bar = FunctionBar::new_enter_esc("Set    ", "Cancel ");
+
+    let mut current_selection = 0i32;
+    for (i, (name, ioprio)) in priorities.iter().enumerate() {
+        io_priority_panel.add_list_item(name, *ioprio);
+        if *ioprio == current {
+            current_selection = i as i32;
+        }
+    }
+    io_priority_panel.set_selected(current_selection);
+
+    // Run the side panel menu
+    let mut side_ctx = super::side_panel_menu::SidePanelContext {
+        main_panel: ctx.main_panel,
+        header: ctx.header,
+        settings: ctx.settings,
+        hide_meters: ctx.hide_meters,
+    };
+
+    let result = super::side_panel_menu::run_side_panel_menu(
+        crt,
+        machine,
+        &mut side_ctx,
+        &mut io_priority_panel,
+    );
+
+    if let super::side_panel_menu::SidePanelResult::Selected(selected_idx) = result {
+        return priorities.get(selected_idx).map(|(_, ioprio)| *ioprio);
+    }
+
+    None
+}
+
+/// Draw a key/label pair on the function bar line (like FunctionBar_draw)
+fn draw_bar_key(crt: &mut Crt, key: &str, label: &str) {
+    let key_color = crt.color(ColorElement::FunctionKey);
+    let bar_color = crt.color(ColorElement::FunctionBar);
+    crt.attrset(key_color);
+    crt.addstr_raw(key);
+    crt.attrset(bar_color);
+    crt.addstr_raw(label);
+    crt.attrset(A_NORMAL);
+}
+
+/// Prompt for a line of text on the function bar line
+/// ("Enter Done  Esc Cancel   label: text", styled like the filter bar).
+/// Returns None if the prompt was cancelled with Esc.
+pub fn prompt_line(crt: &mut Crt, label: &str, initial: &str) -> Option<String> {
+    let mut line = IncSearch::new();
+    line.text = initial.to_string();
+    let y = crt.height() - 1;
+    crt.set_blocking(true);
+
+    let result = loop {
+        let bar_color = crt.color(ColorElement::FunctionBar);
+        crt.fill_line(y, bar_color);
+        crt.mv(y, 0);
+        draw_bar_key(crt, "Enter", "Done  ");
+        draw_bar_key(crt, "Esc", "Cancel ");
+        draw_bar_key(crt, "  ", &format!(" {}: ", label));
+        crt.attrset(bar_color);
+        crt.addstr_raw(&line.text);
+        crt.attrset(A_NORMAL);
+        crt.curs_set(CURSOR_VISIBLE);
+        crt.refresh();
+
+        match crt.getch() {
+            KEY_ESC => break None,
+            KEY_RETURN | KEY_LINEFEED | KEY_ENTER => break Some(line.text),
+            ch => line.edit_key(ch),
+        }
+    };
+
+    crt.curs_set(CURSOR_INVISIBLE);
+    crt.enable_delay();
+    result
+}
+
+/// Show a one-line message on the function bar line until a key is pressed
+pub fn show_bar_message(crt: &mut Crt, message: &str) {
+    let y = crt.height() - 1;
+    let bar_color = crt.color(ColorElement::FunctionBar);
+    crt.fill_line(y, bar_color);
+    crt.mv(y, 0);
+    draw_bar_key(crt, "  ", &format!(" {} ", message));
+    crt.refresh();
+
+    crt.set_blocking(true);
+    crt.getch();
+    crt.enable_delay();
+}
@@ -8,9 +8,13 @@
 //! - Header: Meter display area
 //! - FunctionBar: F1-F10 key labels
 //! - MainPanel: Main process list panel
+//! - CgroupPanel: Process list aggregated by cgroup
 //! - RowPrint: Row printing utilities matching C htop
 //! - SetupScreen: F2 configuration screen
+//! - Clipboard: copying text from info screens
 
+mod cgroup_panel;
+mod clipboard;
 mod crt;
 mod function_bar;
 mod header;
@@ -30,6 +30,16 @@ pub trait PanelItem {
 
     /// Get the sort key for typing search
     fn sort_key(&self) -> &str;
+
+    /// Checkbox state, for items that have one (like C htop's CheckItem)
+    fn is_checked(&self) -> Option<bool> {
+        None
+    }
+
+    /// Flip the checkbox state; returns false if the item has no checkbox
+    fn toggle(&mut self) -> bool {
+        false
+    }
 }
 
 /// A simple text item
@@ -91,6 +101,45 @@ impl PanelItem for ListItem {
     }
 }
 
+/// A checkbox item with a label and an integer key (like C htop's CheckItem)
+#[derive(Debug, Clone)]
+pub struct CheckItem {
+    pub text: String,
+    pub key: i32,
+    pub checked: bool,
+}
+
+impl CheckItem {
+    pub fn new(text: &str, key: i32, checked: bool) -> Self {
+        CheckItem {
+            text: text.to_string(),
+            key,
+            checked,
+        }
+    }
+}
+
+impl PanelItem for CheckItem {
+    fn display(&self, buffer: &mut RichString, _highlighted: bool) {
+        let mark = if self.checked { "[x] " } else { "[ ] " };
+        buffer.append(mark, A_NORMAL);
+        buffer.append(&self.text, A_NORMAL);
+    }
+
+    fn sort_key(&self) -> &str {
+        &self.text
+    }
+
+    fn is_checked(&self) -> Option<bool> {
+        Some(self.checked)
+    }
+
+    fn toggle(&mut self) -> bool {
+        self.checked = !self.checked;
+        true
+    }
+}
+
 /// Panel widget
 pub struct Panel {
     // Position and size
@@ -167,6 +216,29 @@ impl Panel {
         self.add(Box::new(ListItem::new(value, key)));
     }
 
+    /// Add a checkbox item (with label, key and initial state)
+    pub fn add_check_item(&mut self, text: &str, key: i32, checked: bool) {
+        self.add(Box::new(CheckItem::new(text, key, checked)));
+    }
+
+    /// Toggle the checkbox of the selected item
+    /// Returns false if the selected item has no checkbox
+    pub fn toggle_selected(&mut self) -> bool {
+        let toggled = self
+            .items
+            .get_mut(self.selected as usize)
+            .is_some_and(|item| item.toggle());
+        if toggled {
+            self.needs_redraw = true;
+        }
+        toggled
+    }
+
+    /// Get the checkbox state of an item (None if it has no checkbox)
+    pub fn is_checked(&self, index: usize) -> Option<bool> {
+        self.items.get(index).and_then(|item| item.is_checked())
+    }
+
     /// Clear all items
     pub fn prune(&mut self) {
         self.items.clear();
@@ -4,7 +4,10 @@
 //! process-related information:
 //! - Environment variables (EnvScreen)
 //! - File locks (ProcessLocksScreen)
-//! - Open files via lsof (OpenFilesScreen)
+//! - Open files from /proc, or via lsof elsewhere (OpenFilesScreen)
+//! - Memory mappings from /proc/PID/smaps (MemoryMapsScreen)
+//! - Process details from /proc/PID (InspectorScreen)
+//! - Resource limits editor using prlimit(2) (LimitsScreen)
 //! - Strace output (TraceScreen)
 //! - Command line (CommandScreen)
 
@@ -18,11 +21,14 @@ use super::crt::{
     KEY_UP, KEY_WHEELDOWN, KEY_WHEELUP,
 };
 use super::function_bar::FunctionBar;
-use super::info_screen::{run_info_screen, InfoScreenConfig};
+use super::info_screen::{
+    run_info_screen, run_info_screen_content, InfoScreenConfig, InfoScreenContent,
+};
 use super::search_filter::{process_mouse_event, HandleResult, SearchFilterState};
 use super::Crt;
+use crate::core::{diff_environ, EnvChange};
 
-/// Parsed lsof file entry (from lsof -F output)
+/// Parsed lsof file entry (from lsof -F output or read from /proc)
 #[derive(Default)]
 struct LsofFileEntry {
     fd: String,        // File descriptor
@@ -32,65 +38,124 @@ struct LsofFileEntry {
     size: String,      // File size
     offset: String,    // File offset
     inode: String,     // Inode number
+    flags: String,     // Open flags (AP, CX, NB, ...)
     name: String,      // File name/path
 }
 
-pub fn show_process_env(crt: &mut Crt, pid: i32, command: &str) {
-    // Helper to read environment
-    let read_env = || -> Vec<String> {
-        #[cfg(target_os = "macos")]
-        {
-            use std::process::Command;
-            Command::new("ps")
-                .args(["-p", &pid.to_string(), "-E", "-o", "command="])
-                .output()
-                .ok()
-                .and_then(|o| String::from_utf8(o.stdout).ok())
-                .map(|s| {
-                    let mut vars: Vec<String> = Vec::new();
-                    if let Some(pos) = s.find(' ') {
-                        let env_part = &s[pos + 1..];
-                        for part in env_part.split_whitespace() {
-                            if part.contains('=') {
-                                vars.push(part.to_string());
-                            }
-                        }
-                    }
-                    vars.sort();
-                    vars
-                })
-                .unwrap_or_else(|| vec!["Could not read process environment.".to_string()])
+#[cfg(target_os = "linux")]
+impl From<crate::platform::linux_open_files::OpenFile> for LsofFileEntry {
+    fn from(file: crate::platform::linux_open_files::OpenFile) -> Self {
+        let number = |value: Option<u64>| value.map(|v| v.to_string()).unwrap_or_default();
+        LsofFileEntry {
+            fd: file.fd,
+            file_type: file.file_type,
+            mode: file.mode,
+            device: file.device,
+            size: number(file.size),
+            offset: number(file.offset),
+            inode: file.node,
+            flags: file.flags,
+            name: file.name,
         }
-        #[cfg(target_os = "linux")]
-        {
-            use std::fs;
-            fs::read_to_string(format!("/proc/{}/environ", pid))
-                .map(|s| {
-                    let mut vars: Vec<String> = s
-                        .split('\0')
-                        .filter(|s| !s.is_empty())
-                        .map(|s| s.to_string())
-                        .collect();
-                    vars.sort();
-                    vars
-                })
-                .unwrap_or_else(|_| vec!["Could not read process environment.".to_string()])
-        }
-        #[cfg(not(any(target_os = "macos", target_os = "linux")))]
-        {
-            vec!["Environment reading not supported on this platform.".to_string()]
-        }
-    };
+    }
+}
 
-    let mut lines = read_env();
+/// Environment of a process, displayed escaped and copied undecoded
+struct EnvContent {
+    pid: i32,
+    vars: Result<Vec<Vec<u8>>, String>,
+    lines: Vec<String>,
+}
+
+impl InfoScreenContent for EnvContent {
+    fn lines(&self) -> &[String] {
+        &self.lines
+    }
+
+    fn refresh(&mut self) {
+        self.vars = crate::platform::get_process_environ_raw(self.pid);
+        self.lines = match &self.vars {
+            Ok(vars) => vars
+                .iter()
+                .map(|var| crate::core::decode_variable(var))
+                .collect(),
+            Err(msg) => vec![msg.clone()],
+        };
+    }
+
+    fn copy_text(&self, index: usize, _copy_text: fn(&str) -> &str) -> Option<String> {
+        // The line is an error message if the environment is unreadable
+        let var = self.vars.as_ref().ok()?.get(index)?;
+        Some(String::from_utf8_lossy(var).into_owned())
+    }
+}
+
+/// Show the environment of a process (like C htop EnvScreen)
+pub fn show_process_env(crt: &mut Crt, pid: i32, command: &str) {
+    let mut content = EnvContent {
+        pid,
+        vars: Ok(Vec::new()),
+        lines: Vec::new(),
+    };
+    content.refresh();
 
+    let copy_text: fn(&str) -> &str = |line| line;
     let config = InfoScreenConfig {
         title: format!("Environment of process {} - {}", pid, command),
         header: None,
         use_redraw_optimization: false,
+        copy_text: content.vars.is_ok().then_some(copy_text),
     };
 
-    run_info_screen(crt, &config, &mut lines, Some(&read_env));
+    run_info_screen_content(crt, &config, &mut content);
+}
+
+/// Compare the environments of two processes, as lines of a unified diff
+/// ("-" only or differently set in the first, "+" in the second process)
+fn env_diff_lines(first: i32, second: i32) -> Vec<String> {
+    let (first_env, second_env) = match (
+        crate::platform::get_process_environ(first),
+        crate::platform::get_process_environ(second),
+    ) {
+        (Ok(first_env), Ok(second_env)) => (first_env, second_env),
+        (Err(msg), _) | (_, Err(msg)) => return vec![msg],
+    };
+
+    let (changes, identical) = diff_environ(&first_env, &second_env);
+    let mut lines = Vec::with_capacity(changes.len() + 1);
+    for change in changes {
+        match change {
+            EnvChange::Removed { name, value } => lines.push(format!("- {}={}", name, value)),
+            EnvChange::Added { name, value } => lines.push(format!("+ {}={}", name, value)),
+            EnvChange::Changed { name, old, new } => {
+                lines.push(format!("- {}={}", name, old));
+                lines.push(format!("+ {}={}", name, new));
+            }
+        }
+    }
+    lines.push(format!("  ({} identical variables not shown)", identical));
+    lines
+}
+
+/// Show the differences between the environments of two processes
+pub fn show_process_env_diff(crt: &mut Crt, first: (i32, &str), second: (i32, &str)) {
+    let read_diff = || env_diff_lines(first.0, second.0);
+
+    let mut lines = read_diff();
+    let header = format!("- {} {}    + {} {}", first.0, first.1, second.0, second.1);
+
+    let config = InfoScreenConfig {
+        title: format!(
+            "Environment differences of processes {} and {}",
+            first.0, second.0
+        ),
+        header: Some(&header),
+        use_redraw_optimization: false,
+        // Copy the variable without the diff marker
+        copy_text: Some(|line| line.get(2..).unwrap_or(line)),
+    };
+
+    run_info_screen(crt, &config, &mut lines, Some(&read_diff));
 }
 
 /// Show file locks for process (like C htop ProcessLocksScreen)
@@ -116,6 +181,7 @@ pub fn show_file_locks(crt: &mut Crt, pid: i32, command: &str) {
         title: format!("Snapshot of file locks of process {} - {}", pid, command),
         header: Some(header_str),
         use_redraw_optimization: false,
+        copy_text: None,
     };
 
     run_info_screen(crt, &config, &mut lines, Some(&read_locks));
@@ -235,28 +301,26 @@ fn get_process_locks(pid: i32) -> Result<Vec<String>, String> {
     }
 }
 
-/// Show lsof output for process (like C htop OpenFilesScreen)
-pub fn show_lsof(crt: &mut Crt, pid: i32, command: &str) {
-    // Parse lsof output using -F (machine-readable format)
-    let lsof_data = run_lsof(pid);
-
-    // Build lines from parsed data
+/// Format the open files as screen lines, returning the header and lines
+fn format_open_files(data: &Result<Vec<LsofFileEntry>, String>) -> (String, Vec<String>) {
     let mut lines: Vec<String> = Vec::new();
-    let mut col_widths = [5usize, 7, 4, 6, 8, 8, 8]; // FD, TYPE, MODE, DEVICE, SIZE, OFFSET, NODE
+    // FD, TYPE, MODE, DEVICE, SIZE, OFFSET, NODE, FLAGS
+    let mut col_widths = [5usize, 7, 4, 6, 8, 8, 8, 5];
 
-    match &lsof_data {
+    match data {
         Ok(files) => {
             // Calculate dynamic column widths
             for file in files {
                 col_widths[4] = col_widths[4].max(file.size.len());
                 col_widths[5] = col_widths[5].max(file.offset.len());
                 col_widths[6] = col_widths[6].max(file.inode.len());
+                col_widths[7] = col_widths[7].max(file.flags.len());
             }
 
             // Build formatted lines
             for file in files {
                 let line = format!(
-                    "{:>5} {:7} {:4} {:>6} {:>width_s$} {:>width_o$} {:>width_i$}  {}",
+                    "{:>5} {:7} {:4} {:>6} {:>width_s$} {:>width_o$} {:>width_i$} {:width_f$}  {}",
                     file.fd,
                     file.file_type,
                     file.mode,
@@ -264,10 +328,12 @@ pub fn show_lsof(crt: &mut Crt, pid: i32, command: &str) {
                     file.size,
                     file.offset,
                     file.inode,
+                    file.flags,
                     file.name,
                     width_s = col_widths[4],
                     width_o = col_widths[5],
                     width_i = col_widths[6],
+                    width_f = col_widths[7],
                 );
                 lines.push(line);
             }
@@ -279,7 +345,7 @@ pub fn show_lsof(crt: &mut Crt, pid: i32, command: &str) {
 
     // Build header with dynamic column widths
     let header_str = format!(
-        "{:>5} {:7} {:4} {:>6} {:>width_s$} {:>width_o$} {:>width_i$}  {}",
+        "{:>5} {:7} {:4} {:>6} {:>width_s$} {:>width_o$} {:>width_i$} {:width_f$}  {}",
         "FD",
         "TYPE",
         "MODE",
@@ -287,11 +353,19 @@ pub fn show_lsof(crt: &mut Crt, pid: i32, command: &str) {
         "SIZE",
         "OFFSET",
         "NODE",
+        "FLAGS",
         "NAME",
         width_s = col_widths[4],
         width_o = col_widths[5],
         width_i = col_widths[6],
+        width_f = col_widths[7],
     );
+    (header_str, lines)
+}
+
+/// Show the open files of a process (like C htop OpenFilesScreen)
+pub fn show_lsof(crt: &mut Crt, pid: i32, command: &str) {
+    let (mut header_str, mut lines) = format_open_files(&list_open_files(pid));
 
     // State for the info screen
     let mut selected = 0i32;
@@ -437,39 +511,7 @@ pub fn show_lsof(crt: &mut Crt, pid: i32, command: &str) {
             x if x == KEY_F5 => {
                 // F5 - refresh (preserve selected index like C htop)
                 let saved_selected = selected;
-                let new_data = run_lsof(pid);
-                lines.clear();
-                match new_data {
-                    Ok(files) => {
-                        // Recalculate column widths
-                        col_widths = [5, 7, 4, 6, 8, 8, 8];
-                        for file in &files {
-                            col_widths[4] = col_widths[4].max(file.size.len());
-                            col_widths[5] = col_widths[5].max(file.offset.len());
-                            col_widths[6] = col_widths[6].max(file.inode.len());
-                        }
-                        for file in files {
-                            let line = format!(
-                                "{:>5} {:7} {:4} {:>6} {:>width_s$} {:>width_o$} {:>width_i$}  {}",
-                                file.fd,
-                                file.file_type,
-                                file.mode,
-                                file.device,
-                                file.size,
-                                file.offset,
-                                file.inode,
-                                file.name,
-                                width_s = col_widths[4],
-                                width_o = col_widths[5],
-                                width_i = col_widths[6],
-                            );
-                            lines.push(line);
-                        }
-                    }
-                    Err(msg) => {
-                        lines.push(msg);
-                    }
-                }
+                (header_str, lines) = format_open_files(&list_open_files(pid));
                 // Recalculate filtered indices and restore selection
                 let new_filtered = sf_state.filter_indices(&lines);
                 let max_idx = (new_filtered.len() as i32 - 1).max(0);
@@ -521,6 +563,27 @@ pub fn show_lsof(crt: &mut Crt, pid: i32, command: &str) {
     crt.enable_delay();
 }
 
+/// List the open files of a process. Linux reads them from /proc and only
+/// falls back to lsof if that fails.
+fn list_open_files(pid: i32) -> Result<Vec<LsofFileEntry>, String> {
+    #[cfg(target_os = "linux")]
+    {
+        let proc_dir = std::path::PathBuf::from(format!("/proc/{}", pid));
+        match crate::platform::linux_open_files::read_open_files(&proc_dir) {
+            Ok(files) if !files.is_empty() => {
+                Ok(files.into_iter().map(LsofFileEntry::from).collect())
+            }
+            Ok(_) => Err("No open files found.".to_string()),
+            Err(err) => run_lsof(pid).map_err(|_| format!("Failed listing open files: {}", err)),
+        }
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    {
+        run_lsof(pid)
+    }
+}
+
 fn run_lsof(pid: i32) -> Result<Vec<LsofFileEntry>, String> {
     use std::process::Command;
 
@@ -565,6 +628,8 @@ fn run_lsof(pid: i32) -> Result<Vec<LsofFileEntry>, String> {
     // o = offset
     // i = inode
     // n = name
+    // File flags (G) are only reported with +fg, the fallback leaves
+    // the FLAGS column empty
 
     let mut files: Vec<LsofFileEntry> = Vec::new();
     let mut current_file: Option<LsofFileEntry> = None;
@@ -660,6 +725,400 @@ fn run_lsof(pid: i32) -> Result<Vec<LsofFileEntry>, String> {
     Ok(files)
 }
 
+/// Memory mappings of a process, sortable and groupable by file
+#[cfg(target_os = "linux")]
+struct MemoryMapsContent {
+    pid: i32,
+    sort_key: crate::platform::linux_smaps::MappingSortKey,
+    /// Merge the mappings of each file into one line
+    grouped: bool,
+    mappings: Result<Vec<crate::platform::linux_smaps::MemoryMapping>, String>,
+    lines: Vec<String>,
+}
+
+#[cfg(target_os = "linux")]
+impl MemoryMapsContent {
+    fn new(pid: i32) -> Self {
+        let mut content = MemoryMapsContent {
+            pid,
+            sort_key: Default::default(),
+            grouped: false,
+            mappings: Ok(Vec::new()),
+            lines: Vec::new(),
+        };
+        content.refresh();
+        content
+    }
+
+    /// Rebuild the lines after a change of sort order or grouping
+    fn rebuild_lines(&mut self) {
+        use crate::core::Process;
+        use crate::platform::linux_smaps::group_by_file;
+
+        let mappings = match &self.mappings {
+            Ok(mappings) => mappings,
+            Err(msg) => {
+                self.lines = vec![msg.clone()];
+                return;
+            }
+        };
+        let mut mappings = if self.grouped {
+            group_by_file(mappings)
+        } else {
+            mappings.clone()
+        };
+        self.sort_key.sort(&mut mappings);
+
+        self.lines = mappings
+            .iter()
+            .map(|mapping| {
+                let first = if self.grouped {
+                    format!("{:>12}", mapping.count)
+                } else {
+                    format!("{:>12x}", mapping.start)
+                };
+                format!(
+                    "{} {:4} {} {} {} {} {} {} {:3} {}",
+                    first,
+                    mapping.perms,
+                    Process::format_memory(mapping.size as i64),
+                    Process::format_memory(mapping.rss as i64),
+                    Process::format_memory(mapping.pss as i64),
+                    Process::format_memory(mapping.dirty as i64),
+                    Process::format_memory(mapping.swap as i64),
+                    Process::format_memory(mapping.thp as i64),
+                    if mapping.deleted { "DEL" } else { "" },
+                    mapping.name()
+                )
+            })
+            .collect();
+    }
+}
+
+#[cfg(target_os = "linux")]
+impl InfoScreenContent for MemoryMapsContent {
+    fn lines(&self) -> &[String] {
+        &self.lines
+    }
+
+    fn header(&self) -> Option<String> {
+        Some(format!(
+            "{:>12} PERM   SIZE    RSS    PSS  DIRTY   SWAP    THP DEL PATH  (sorted by {})",
+            if self.grouped { "MAPS" } else { "START" },
+            self.sort_key.name()
+        ))
+    }
+
+    fn refresh(&mut self) {
+        let proc_dir = std::path::PathBuf::from(format!("/proc/{}", self.pid));
+        self.mappings = crate::platform::linux_smaps::read_memory_maps(&proc_dir)
+            .map_err(|err| format!("Could not read memory maps: {}", err));
+        self.rebuild_lines();
+    }
+
+    fn function_keys(&self) -> &[(&'static str, &'static str)] {
+        if self.grouped {
+            &[("F6", "SortBy"), ("g", "Split ")]
+        } else {
+            &[("F6", "SortBy"), ("g", "Group ")]
+        }
+    }
+
+    fn on_key(&mut self, key: i32, _selected: Option<usize>) -> bool {
+        use super::crt::{KEY_DOT, KEY_F6, KEY_GT, KEY_LC_G};
+
+        match key {
+            KEY_F6 | KEY_DOT | KEY_GT => self.sort_key = self.sort_key.next(),
+            KEY_LC_G => self.grouped = !self.grouped,
+            _ => return false,
+        }
+        self.rebuild_lines();
+        true
+    }
+}
+
+/// Show the memory mappings of a process with their RSS, PSS, dirty,
+/// swap and huge page usage from /proc/PID/smaps
+pub fn show_memory_maps(crt: &mut Crt, pid: i32, command: &str) {
+    let config = InfoScreenConfig {
+        title: format!("Memory maps of process {} - {}", pid, command),
+        header: None,
+        use_redraw_optimization: false,
+        copy_text: None,
+    };
+
+    #[cfg(target_os = "linux")]
+    {
+        run_info_screen_content(crt, &config, &mut MemoryMapsContent::new(pid));
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    {
+        let mut lines = vec!["Memory maps are not supported on this platform.".to_string()];
+        run_info_screen(crt, &config, &mut lines, None::<fn() -> Vec<String>>);
+    }
+}
+
+/// Details of a process, re-read every update interval
+#[cfg(target_os = "linux")]
+struct InspectorContent {
+    pid: i32,
+    lines: Vec<String>,
+}
+
+#[cfg(target_os = "linux")]
+impl InfoScreenContent for InspectorContent {
+    fn lines(&self) -> &[String] {
+        &self.lines
+    }
+
+    fn refresh(&mut self) {
+        let proc_dir = std::path::PathBuf::from(format!("/proc/{}", self.pid));
+        self.lines = match crate::platform::linux_inspect::read_process_details(&proc_dir) {
+            Ok(sections) => {
+                let mut lines = Vec::new();
+                for section in sections {
+                    if !lines.is_empty() {
+                        lines.push(String::new());
+                    }
+                    lines.push(section.title.to_string());
+                    for (name, value) in section.entries {
+                        lines.push(format!("  {:<28} {}", name, value));
+                    }
+                }
+                lines
+            }
+            Err(_) => vec![format!("Process {} no longer exists.", self.pid)],
+        };
+    }
+
+    fn live(&self) -> bool {
+        true
+    }
+}
+
+/// Show the status, security settings, limits, cgroups and namespaces of
+/// a process, updated every update interval
+pub fn show_process_inspector(crt: &mut Crt, pid: i32, command: &str) {
+    let config = InfoScreenConfig {
+        title: format!("Details of process {} - {}", pid, command),
+        header: None,
+        use_redraw_optimization: false,
+        copy_text: None,
+    };
+
+    #[cfg(target_os = "linux")]
+    {
+        let mut content = InspectorContent {
+            pid,
+            lines: Vec::new(),
+        };
+        content.refresh();
+        run_info_screen_content(crt, &config, &mut content);
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    {
+        let mut lines = vec!["Process details are not supported on this platform.".to_string()];
+        run_info_screen(crt, &config, &mut lines, None::<fn() -> Vec<String>>);
+    }
+}
+
+/// Resource limits of a process, edited with Enter and applied to all
+/// target processes
+#[cfg(target_os = "linux")]
+struct LimitsContent {
+    /// Process whose limits are shown, one of the targets
+    pid: i32,
+    /// Processes the new limits are applied to (tagged or selected)
+    targets: Vec<i32>,
+    limits: Result<Vec<crate::platform::linux_inspect::ResourceLimit>, String>,
+    /// Result of the last change, by resource
+    messages: std::collections::HashMap<u32, String>,
+    /// Index of the limit being edited, with its prompt and the text entered
+    editing: Option<(usize, String, String)>,
+    lines: Vec<String>,
+}
+
+#[cfg(target_os = "linux")]
+impl LimitsContent {
+    fn rebuild_lines(&mut self) {
+        use crate::platform::linux_inspect::format_limit;
+
+        self.lines = match &self.limits {
+            Ok(limits) => limits
+                .iter()
+                .map(|limit| {
+                    let line = format!(
+                        "{:<25} {:>20} {:>20} {:<10}",
+                        limit.name,
+                        format_limit(limit.soft),
+                        format_limit(limit.hard),
+                        limit.units
+                    );
+                    match self.messages.get(&limit.resource) {
+                        Some(message) => format!("{}  {}", line, message),
+                        None => line,
+                    }
+                })
+                .collect(),
+            Err(msg) => vec![msg.clone()],
+        };
+    }
+
+    /// Apply the limits entered for the limit at `index` to all targets.
+    /// Each target keeps its own hard limit unless a new one was entered.
+    fn apply(&mut self, index: usize, text: &str) {
+        use crate::platform::linux_inspect::{read_limits, LimitChange};
+
+        let Some(limit) = self.limits.as_ref().ok().and_then(|l| l.get(index)) else {
+            return;
+        };
+        let resource = limit.resource;
+        let message = match LimitChange::parse(text) {
+            Ok(change) => {
+                let set_limit = |pid: i32| -> Result<(), String> {
+                    let proc_dir = std::path::PathBuf::from(format!("/proc/{}", pid));
+                    let limits = read_limits(&proc_dir).map_err(|err| err.to_string())?;
+                    let current = limits
+                        .iter()
+                        .find(|l| l.resource == resource)
+                        .ok_or("unknown limit")?;
+                    let (soft, hard) = change.resolve(current)?;
+                    crate::platform::set_process_limit(pid, resource, soft, hard)
+                };
+                let failures: Vec<String> = self
+                    .targets
+                    .iter()
+                    .filter_map(|&pid| set_limit(pid).err().map(|err| format!("{}: {}", pid, err)))
+                    .collect();
+                if failures.is_empty() {
+                    format!("set for {} process(es)", self.targets.len())
+                } else {
+                    failures.join("; ")
+                }
+            }
+            Err(msg) => msg,
+        };
+        self.messages.insert(resource, message);
+        self.refresh();
+    }
+}
+
+#[cfg(target_os = "linux")]
+impl InfoScreenContent for LimitsContent {
+    fn lines(&self) -> &[String] {
+        &self.lines
+    }
+
+    fn header(&self) -> Option<String> {
+        Some(format!(
+            "{:<25} {:>20} {:>20} {:<10}",
+            "LIMIT", "SOFT", "HARD", "UNITS"
+        ))
+    }
+
+    fn refresh(&mut self) {
+        let proc_dir = std::path::PathBuf::from(format!("/proc/{}", self.pid));
+        self.limits = crate::platform::linux_inspect::read_limits(&proc_dir)
+            .map_err(|err| format!("Could not read resource limits: {}", err));
+        self.rebuild_lines();
+    }
+
+    fn function_keys(&self) -> &[(&'static str, &'static str)] {
+        &[("Enter", "Edit  ")]
+    }
+
+    fn input(&self) -> Option<(&str, &str)> {
+        self.editing
+            .as_ref()
+            .map(|(_, prompt, text)| (prompt.as_str(), text.as_str()))
+    }
+
+    fn on_key(&mut self, key: i32, selected: Option<usize>) -> bool {
+        use super::crt::{KEY_LINEFEED, KEY_RETURN};
+        use crate::platform::linux_inspect::format_limit;
+
+        if let Some((index, _, text)) = &mut self.editing {
+            match key {
+                KEY_ESC => self.editing = None,
+                KEY_RETURN | KEY_LINEFEED | KEY_ENTER => {
+                    let (index, text) = (*index, std::mem::take(text));
+                    self.editing = None;
+                    self.apply(index, &text);
+                }
+                KEY_BACKSPACE | 127 | 8 => {
+                    text.pop();
+                }
+                _ if (32..127).contains(&key) => text.push(key as u8 as char),
+                _ => {}
+            }
+            return true;
+        }
+
+        match key {
+            KEY_RETURN | KEY_LINEFEED | KEY_ENTER => {
+                let limit = selected.and_then(|i| Some((i, self.limits.as_ref().ok()?.get(i)?)));
+                if let Some((index, limit)) = limit {
+                    // Only the soft limit is filled in, so that each target
+                    // keeps its hard limit unless one is entered
+                    let prompt = format!("{} (soft [hard])", limit.name);
+                    let text = format_limit(limit.soft);
+                    self.editing = Some((index, prompt, text));
+                }
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
+/// Show the resource limits of a process and change them with prlimit(2)
+/// for `targets` (the tagged processes, or the process itself). The
+/// process shown should be one of the targets.
+pub fn show_limits_editor(crt: &mut Crt, pid: i32, command: &str, targets: Vec<i32>) {
+    let target_list: Vec<String> = targets.iter().map(|pid| pid.to_string()).collect();
+    let title = format!(
+        "Resource limits of process {} - {} (changes apply to {}: {})",
+        pid,
+        command,
+        if targets.len() == 1 {
+            "process"
+        } else {
+            "processes"
+        },
+        target_list.join(", ")
+    );
+    let config = InfoScreenConfig {
+        title,
+        header: None,
+        use_redraw_optimization: false,
+        copy_text: None,
+    };
+
+    #[cfg(target_os = "linux")]
+    {
+        let mut content = LimitsContent {
+            pid,
+            targets,
+            limits: Ok(Vec::new()),
+            messages: Default::default(),
+            editing: None,
+            lines: Vec::new(),
+        };
+        content.refresh();
+        run_info_screen_content(crt, &config, &mut content);
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    {
+        let _ = targets;
+        let mut lines =
+            vec!["Changing resource limits is not supported on this platform.".to_string()];
+        run_info_screen(crt, &config, &mut lines, None::<fn() -> Vec<String>>);
+    }
+}
+
 /// Show strace output for process (like C htop TraceScreen)
 /// On Linux: forks strace and displays output live
 /// On BSD: uses truss instead
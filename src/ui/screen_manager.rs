@@ -5,12 +5,16 @@
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::time::{Duration, Instant};
 
+use super::cgroup_panel::CgroupPanel;
+#[cfg(target_os = "linux")]
+use super::crt::KEY_LC_I;
 use super::crt::{
     ColorElement, A_NORMAL, CURSOR_INVISIBLE, CURSOR_VISIBLE, KEY_0, KEY_9, KEY_C, KEY_DOT,
-    KEY_DOWN, KEY_ESC, KEY_F, KEY_F1, KEY_F2, KEY_F3, KEY_F5, KEY_F6, KEY_F7, KEY_F8, KEY_F9,
-    KEY_GT, KEY_H, KEY_HASH, KEY_HEADER_CLICK, KEY_I, KEY_K, KEY_LBRACKET, KEY_LC_C, KEY_LC_E,
-    KEY_LC_H, KEY_LC_K, KEY_LC_L, KEY_LC_M, KEY_LC_P, KEY_LC_S, KEY_LC_T, KEY_LC_U, KEY_LC_W,
-    KEY_LC_X, KEY_M, KEY_MINUS, KEY_MOUSE, KEY_N, KEY_P, KEY_PLUS, KEY_QUESTION, KEY_RBRACKET,
+    KEY_DOWN, KEY_E, KEY_ESC, KEY_F, KEY_F1, KEY_F2, KEY_F3, KEY_F5, KEY_F6, KEY_F7, KEY_F8,
+    KEY_F9, KEY_G, KEY_GT, KEY_H, KEY_HASH, KEY_HEADER_CLICK, KEY_I, KEY_K, KEY_LBRACE,
+    KEY_LBRACKET, KEY_LC_A, KEY_LC_C, KEY_LC_D, KEY_LC_E, KEY_LC_H, KEY_LC_K, KEY_LC_L, KEY_LC_M,
+    KEY_LC_O, KEY_LC_P, KEY_LC_R, KEY_LC_S, KEY_LC_T, KEY_LC_U, KEY_LC_W, KEY_LC_X, KEY_M,
+    KEY_MINUS, KEY_MOUSE, KEY_N, KEY_P, KEY_PLUS, KEY_QUESTION, KEY_RBRACE, KEY_RBRACKET,
     KEY_RESIZE, KEY_RETURN, KEY_S, KEY_SF3, KEY_SHIFT_TAB, KEY_SPACE, KEY_STAR, KEY_T, KEY_TAB,
     KEY_TAB_CLICK, KEY_U, KEY_WHEELDOWN, KEY_WHEELUP, KEY_Z,
 };
@@ -21,7 +25,7 @@ use super::panel::HandlerResult;
 use super::Crt;
 #[cfg(target_os = "linux")]
 use crate::core::ScanFlags;
-use crate::core::{CommandStrParams, Machine, ProcessField, Settings};
+use crate::core::{CommandStrParams, Machine, ProcessField, Recorder, Replay, Settings};
 use crate::platform;
 
 /// Check if the current process can decrease nice values (increase priority).
@@ -55,6 +59,14 @@ fn can_decrease_nice() -> bool {
     false
 }
 
+/// Expand a leading `~/` to the user's home directory
+fn expand_home(path: &str) -> String {
+    match (path.strip_prefix("~/"), std::env::var("HOME")) {
+        (Some(rest), Ok(home)) => format!("{}/{}", home.trim_end_matches('/'), rest),
+        _ => path.to_string(),
+    }
+}
+
 /// Screen manager state
 pub struct ScreenManager {
     /// Header with meters
@@ -89,6 +101,26 @@ pub struct ScreenManager {
     /// When user presses keys, reset to SORT_TIMEOUT_RESET
     /// Decrements on idle, sorting only happens when this reaches 0
     sort_timeout: u8,
+
+    /// Appends every scan to a recording file (--record)
+    recorder: Option<Recorder>,
+
+    /// First error hit while recording (recording stops at that point)
+    record_error: Option<std::io::Error>,
+
+    /// Recorded session driving the UI instead of platform scans (--replay)
+    replay: Option<Replay>,
+
+    /// A replay frame was selected by key and must be loaded on the next iteration
+    replay_frame_pending: bool,
+
+    /// Filter text and user filter as applied when the current screen was shown,
+    /// so that only changes made since then are saved back to the screen
+    applied_filter: Option<String>,
+    applied_user_id: Option<u32>,
+
+    /// Cgroup view shown instead of the process list ('G')
+    cgroup_panel: Option<CgroupPanel>,
 }
 
 /// Number of idle cycles before sorting is allowed after user interaction
@@ -108,6 +140,57 @@ impl ScreenManager {
             function_bar_hidden: false,
             header_needs_redraw: true,
             sort_timeout: 0,
+            recorder: None,
+            record_error: None,
+            replay: None,
+            replay_frame_pending: false,
+            applied_filter: None,
+            applied_user_id: None,
+            cgroup_panel: None,
+        }
+    }
+
+    /// Record every scan to the given recorder
+    pub fn set_recorder(&mut self, recorder: Recorder) {
+        self.recorder = Some(recorder);
+    }
+
+    /// Take the error that stopped recording, if any
+    pub fn take_record_error(&mut self) -> Option<std::io::Error> {
+        self.record_error.take()
+    }
+
+    /// Drive the UI from a recorded session instead of scanning the system
+    pub fn set_replay(&mut self, replay: Replay) {
+        self.replay = Some(replay);
+    }
+
+    /// Refresh machine data: load the current replay frame, or scan the
+    /// system (and append the scan to the recording, if any)
+    fn scan(&mut self, machine: &mut Machine) {
+        if let Some(ref replay) = self.replay {
+            // A frame that fails to decode (truncated file) just leaves the
+            // previous data on screen
+            let _ = replay.load(machine);
+            return;
+        }
+
+        platform::scan(machine);
+        if let Some(ref mut recorder) = self.recorder {
+            if let Err(e) = recorder.record(machine) {
+                self.record_error = Some(e);
+                self.recorder = None;
+            }
+        }
+    }
+
+    /// Step the replay by `delta` frames and pause playback
+    fn step_replay(&mut self, delta: isize) {
+        if let Some(ref mut replay) = self.replay {
+            self.paused = true;
+            if replay.step(delta) {
+                self.replay_frame_pending = true;
+            }
         }
     }
 
@@ -277,6 +360,10 @@ impl ScreenManager {
 
         self.main_panel.move_to(0, panel_y);
         self.main_panel.resize(width, panel_height);
+        if let Some(ref mut panel) = self.cgroup_panel {
+            panel.move_to(0, panel_y);
+            panel.resize(width, panel_height);
+        }
     }
 
     /// Draw screen tabs (like "[Main] [I/O]" above the process panel)
@@ -493,8 +580,13 @@ impl ScreenManager {
             x += 9;
             crt.attrset(A_NORMAL);
 
-            // Draw the filter text
-            crt.attrset(bar_color);
+            // Draw the filter text (with failed search color if it is not
+            // a valid query and only matches the command)
+            if self.main_panel.is_filter_invalid() {
+                crt.attrset(crt.color(ColorElement::FailedSearch));
+            } else {
+                crt.attrset(bar_color);
+            }
             crt.addstr_raw(&search_text);
             x += search_text.len() as i32;
             crt.attrset(A_NORMAL);
@@ -587,8 +679,11 @@ impl ScreenManager {
             self.draw_screen_tabs(crt);
         }
 
-        // Draw main panel
-        self.main_panel.draw(crt, machine, &self.settings, true);
+        // Draw main panel (or the cgroup view replacing it)
+        match self.cgroup_panel {
+            Some(ref mut panel) => panel.draw(crt, true),
+            None => self.main_panel.draw(crt, machine, &self.settings, true),
+        }
 
         // Draw function bar or search/filter bar
         // Mode 0: always show
@@ -607,18 +702,39 @@ impl ScreenManager {
                 self.draw_inc_bar(crt, y)
             } else {
                 // Draw normal function bar and hide cursor
-                let x = self.main_panel.function_bar.draw_simple_return_x(crt, y);
+                let function_bar = match self.cgroup_panel {
+                    Some(ref panel) => &panel.function_bar,
+                    None => &self.main_panel.function_bar,
+                };
+                let x = function_bar.draw_simple_return_x(crt, y);
                 crt.curs_set(CURSOR_INVISIBLE);
                 x
             };
 
             // If paused, append "PAUSED" indicator (like C htop MainPanel_drawFunctionBar)
+            let mut end_x = end_x;
             if self.paused {
                 let paused_color = crt.color(ColorElement::Paused);
                 crt.attrset(paused_color);
                 crt.mv(y, end_x + 1);
                 crt.addstr_raw("PAUSED");
                 crt.attrset(A_NORMAL);
+                end_x += 7;
+            }
+
+            // When replaying, show the frame position and when it was recorded
+            if let Some(ref replay) = self.replay {
+                let recorded: chrono::DateTime<chrono::Local> = machine.realtime.into();
+                let status = format!(
+                    "REPLAY {}/{} {}",
+                    replay.position() + 1,
+                    replay.len(),
+                    recorded.format("%Y-%m-%d %H:%M:%S")
+                );
+                crt.attrset(crt.color(ColorElement::Paused));
+                crt.mv(y, end_x + 1);
+                crt.addstr_raw(&status);
+                crt.attrset(A_NORMAL);
             }
 
             // Show update interval counter on the right side (debug builds only)
@@ -660,19 +776,33 @@ impl ScreenManager {
         // Copy settings to machine for platform access
         machine.update_process_names = self.settings.update_process_names;
         machine.show_cpu_frequency = self.settings.show_cpu_frequency;
-        machine.hide_userland_threads = self.settings.hide_userland_threads;
+        // A replay carries the recorded temperatures, don't read the local sensors
+        machine.show_cpu_temperature = self.settings.show_cpu_temperature && self.replay.is_none();
+        machine.hide_userland_threads = self.settings.userland_threads_hidden();
+        machine.processes.group_by_container = self.settings.tree_group_by_container;
 
         // Compute scan flags from current screen's fields for conditional /proc reads
         #[cfg(target_os = "linux")]
         {
-            machine.scan_flags = ScanFlags::from_fields(&self.settings.current_screen().fields);
+            machine.scan_flags = ScanFlags::from_fields(&self.settings.current_screen().fields)
+                | self.main_panel.filter_scan_flags()
+                | self.header.scan_flags()
+                | self.view_scan_flags();
         }
 
         // Set check_deleted_libs from highlight_deleted_exe setting
         machine.check_deleted_libs = self.settings.highlight_deleted_exe;
 
+        // Filters given on the command line are not saved unless changed
+        self.applied_filter = self
+            .main_panel
+            .filter
+            .as_ref()
+            .map(|f| f.text().to_string());
+        self.applied_user_id = machine.filter_user_id;
+
         // Initial scan BEFORE layout so we know actual CPU count for meter heights
-        platform::scan(machine);
+        self.scan(machine);
         let cmd_params = self.build_command_str_params(crt);
         machine.update_processes(
             Some(&cmd_params),
@@ -727,21 +857,37 @@ impl ScreenManager {
             }
 
             // Determine if we should update (time-based, like C htop checkRecalculation)
-            let should_update = !self.paused
-                && self.last_update.elapsed()
-                    >= Duration::from_millis(self.settings.delay as u64 * 100);
+            let should_update = self.replay_frame_pending
+                || (!self.paused
+                    && self.last_update.elapsed()
+                        >= Duration::from_millis(self.settings.delay as u64 * 100));
 
             if should_update {
+                // When replaying, play back one recorded frame per update
+                // interval and pause on the last one
+                if let Some(ref mut replay) = self.replay {
+                    if !self.replay_frame_pending && !replay.step(1) {
+                        self.paused = true;
+                    }
+                }
+                self.replay_frame_pending = false;
+
                 // Update settings in machine before scan
                 machine.update_process_names = self.settings.update_process_names;
                 machine.show_cpu_frequency = self.settings.show_cpu_frequency;
-                machine.hide_userland_threads = self.settings.hide_userland_threads;
+                machine.show_cpu_temperature =
+                    self.settings.show_cpu_temperature && self.replay.is_none();
+                machine.hide_userland_threads = self.settings.userland_threads_hidden();
+                machine.processes.group_by_container = self.settings.tree_group_by_container;
 
                 // Compute scan flags from current screen's fields for conditional /proc reads
                 #[cfg(target_os = "linux")]
                 {
                     machine.scan_flags =
-                        ScanFlags::from_fields(&self.settings.current_screen().fields);
+                        ScanFlags::from_fields(&self.settings.current_screen().fields)
+                            | self.main_panel.filter_scan_flags()
+                            | self.header.scan_flags()
+                            | self.view_scan_flags();
                 }
 
                 // Update check_deleted_libs from highlight_deleted_exe setting
@@ -753,8 +899,8 @@ impl ScreenManager {
                     machine.needs_sort = true;
                 }
 
-                // Perform platform scan to update system state
-                platform::scan(machine);
+                // Perform platform scan (or load the replay frame) to update system state
+                self.scan(machine);
                 let cmd_params = self.build_command_str_params(crt);
                 machine.update_processes(
                     Some(&cmd_params),
@@ -772,6 +918,10 @@ impl ScreenManager {
                 // Update header meters with new data
                 self.header.update(machine);
 
+                if let Some(ref mut panel) = self.cgroup_panel {
+                    panel.update(machine, self.replay.is_none());
+                }
+
                 // Data changed - invalidate the cached display list so it gets rebuilt
                 // Header always needs redraw to show updated meters
                 self.header_needs_redraw = true;
@@ -864,7 +1014,7 @@ impl ScreenManager {
                             }
                             None
                         })
-                    } else if show_function_bar {
+                    } else if show_function_bar && self.cgroup_panel.is_none() {
                         let func_bar = &self.main_panel.function_bar;
                         Some(&|x: i32| func_bar.get_click_key(x))
                     } else {
@@ -917,6 +1067,7 @@ impl ScreenManager {
             }
         }
 
+        self.store_screen_filters(machine);
         Ok(())
     }
 
@@ -950,6 +1101,18 @@ impl ScreenManager {
             }
         }
 
+        // The cgroup view takes all keys except a few global ones
+        if let Some(ref mut panel) = self.cgroup_panel {
+            match key {
+                KEY_G | KEY_ESC => {
+                    self.cgroup_panel = None;
+                    return HandlerResult::Redraw;
+                }
+                KEY_RESIZE | KEY_F1 | KEY_QUESTION | KEY_LC_H | KEY_HASH | KEY_Z => {}
+                _ => return panel.on_key(key),
+            }
+        }
+
         // Global key handling
         match key {
             KEY_RESIZE => {
@@ -1005,6 +1168,23 @@ impl ScreenManager {
                 }
                 return HandlerResult::Redraw;
             }
+            #[cfg(target_os = "linux")]
+            KEY_LC_I => {
+                // 'i' - set IO priority (ionice)
+                // Applies to tagged processes if any, otherwise selected process
+                if !self.settings.readonly {
+                    self.set_io_priority_for_processes(crt, machine);
+                }
+                return HandlerResult::Redraw;
+            }
+            KEY_LC_A => {
+                // 'a' - set CPU affinity
+                // Applies to tagged processes if any, otherwise selected process
+                if !self.settings.readonly {
+                    self.set_affinity_for_processes(crt, machine);
+                }
+                return HandlerResult::Redraw;
+            }
             KEY_WHEELUP => {
                 // Scroll up by scroll wheel amount (matches C htop PANEL_SCROLL)
                 let amount = crt.scroll_wheel_amount();
@@ -1084,6 +1264,11 @@ impl ScreenManager {
                 self.layout(crt);
                 return HandlerResult::Redraw;
             }
+            KEY_E => {
+                // 'E' - export the visible rows to a CSV file
+                self.export_csv(crt, machine);
+                return HandlerResult::Redraw;
+            }
             KEY_F => {
                 // 'F' - cursor follows process
                 self.main_panel.toggle_following(machine);
@@ -1094,8 +1279,7 @@ impl ScreenManager {
                 // Remember currently selected PID before changing filter
                 let selected_pid = self.main_panel.get_selected_pid(machine);
 
-                self.settings.hide_userland_threads = !self.settings.hide_userland_threads;
-                self.settings.changed = true;
+                self.settings.toggle_userland_threads();
                 self.main_panel.invalidate_display_list();
 
                 // Try to keep the same process selected, fall back to first row if not visible
@@ -1141,8 +1325,7 @@ impl ScreenManager {
                 // Remember currently selected PID before changing filter
                 let selected_pid = self.main_panel.get_selected_pid(machine);
 
-                self.settings.hide_kernel_threads = !self.settings.hide_kernel_threads;
-                self.settings.changed = true;
+                self.settings.toggle_kernel_threads();
                 self.main_panel.invalidate_display_list();
 
                 // Try to keep the same process selected, fall back to first row if not visible
@@ -1213,6 +1396,29 @@ impl ScreenManager {
                 self.paused = !self.paused;
                 return HandlerResult::Handled;
             }
+            KEY_G => {
+                // 'G' - group processes by cgroup (like systemd-cgtop)
+                let mut panel = CgroupPanel::new();
+                panel.update(machine, self.replay.is_none());
+                self.cgroup_panel = Some(panel);
+                self.layout(crt);
+                // Start reading cgroups and IO with the next scan
+                #[cfg(target_os = "linux")]
+                {
+                    machine.scan_flags |= self.view_scan_flags();
+                }
+                return HandlerResult::Redraw;
+            }
+            KEY_LBRACE if self.replay.is_some() => {
+                // '{' - previous frame of a replayed recording
+                self.step_replay(-1);
+                return HandlerResult::Handled;
+            }
+            KEY_RBRACE if self.replay.is_some() => {
+                // '}' - next frame of a replayed recording
+                self.step_replay(1);
+                return HandlerResult::Handled;
+            }
             KEY_LC_C => {
                 // 'c' - tag process and its children
                 if let Some(pid) = self.main_panel.get_selected_pid(machine) {
@@ -1220,13 +1426,69 @@ impl ScreenManager {
                 }
                 return HandlerResult::Handled;
             }
-            KEY_LC_E => {
-                // 'e' - show process environment
+            KEY_LC_D => {
+                // 'd' - show process details
                 if let Some((pid, command)) = self.get_selected_pid_command(machine) {
+                    super::process_info_screens::show_process_inspector(crt, pid, &command);
+                }
+                return HandlerResult::Redraw;
+            }
+            KEY_LC_E => {
+                // 'e' - show process environment, or compare the
+                // environments if exactly two processes are tagged
+                let tagged = machine.processes.get_tagged();
+                if let [first, second] = tagged[..] {
+                    let command = |pid| {
+                        machine
+                            .processes
+                            .get(pid)
+                            .map(|p| p.get_command().to_string())
+                            .unwrap_or_default()
+                    };
+                    super::process_info_screens::show_process_env_diff(
+                        crt,
+                        (first, &command(first)),
+                        (second, &command(second)),
+                    );
+                } else if let Some((pid, command)) = self.get_selected_pid_command(machine) {
                     super::process_info_screens::show_process_env(crt, pid, &command);
                 }
                 return HandlerResult::Redraw;
             }
+            KEY_LC_O => {
+                // 'o' - show memory maps of the process
+                if let Some((pid, command)) = self.get_selected_pid_command(machine) {
+                    super::process_info_screens::show_memory_maps(crt, pid, &command);
+                }
+                return HandlerResult::Redraw;
+            }
+            KEY_LC_R => {
+                // 'r' - edit resource limits of tagged processes, or of the
+                // selected process if none are tagged
+                if !self.settings.readonly {
+                    if let Some(selected) = self.main_panel.get_selected_pid(machine) {
+                        let tagged = machine.processes.get_tagged();
+                        // Show the selected process if it is a target,
+                        // otherwise the first tagged one
+                        let (pid, targets) = if tagged.is_empty() {
+                            (selected, vec![selected])
+                        } else if tagged.contains(&selected) {
+                            (selected, tagged)
+                        } else {
+                            (tagged[0], tagged)
+                        };
+                        let command = machine
+                            .processes
+                            .get(pid)
+                            .map(|p| p.get_command().to_string())
+                            .unwrap_or_default();
+                        super::process_info_screens::show_limits_editor(
+                            crt, pid, &command, targets,
+                        );
+                    }
+                }
+                return HandlerResult::Redraw;
+            }
             KEY_LC_H => {
                 // 'h' - show help (same as F1)
                 self.show_help(crt);
@@ -1330,6 +1592,20 @@ impl ScreenManager {
         self.main_panel.on_key(key, machine)
     }
 
+    /// Scan flags for the columns summed up by the cgroup view, if shown,
+    /// and for the containers of a tree view grouped by container
+    #[cfg(target_os = "linux")]
+    fn view_scan_flags(&self) -> ScanFlags {
+        let mut flags = ScanFlags::empty();
+        if self.cgroup_panel.is_some() {
+            flags |= ScanFlags::CGROUP | ScanFlags::IO;
+        }
+        if self.settings.tree_view && self.settings.tree_group_by_container {
+            flags |= ScanFlags::CGROUP;
+        }
+        flags
+    }
+
     /// Switch to a different screen tab
     /// direction: 1 for next, -1 for previous
     fn switch_screen(&mut self, direction: i32, machine: &mut Machine) {
@@ -1355,7 +1631,9 @@ impl ScreenManager {
             return;
         }
 
+        self.store_screen_filters(machine);
         self.settings.active_screen = index;
+        self.load_screen_filters(machine);
 
         // Update main panel with new screen's fields
         let screen = &self.settings.screens[index];
@@ -1385,6 +1663,49 @@ impl ScreenManager {
         self.main_panel.invalidate_display_list();
     }
 
+    /// Save the filter and user filter into the current screen if they were
+    /// changed since the screen was shown
+    fn store_screen_filters(&mut self, machine: &mut Machine) {
+        let filter = self
+            .main_panel
+            .filter
+            .as_ref()
+            .map(|f| f.text().to_string());
+        let user_id = machine.filter_user_id;
+        if filter == self.applied_filter && user_id == self.applied_user_id {
+            return;
+        }
+
+        let user = user_id.map(|uid| machine.get_username(uid));
+        let screen = self.settings.current_screen_mut();
+        screen.filter = filter;
+        screen.user = user;
+        self.settings.changed = true;
+    }
+
+    /// Apply the current screen's saved filter, user filter and thread visibility
+    fn load_screen_filters(&mut self, machine: &mut Machine) {
+        let screen = self.settings.current_screen();
+        match screen.filter {
+            Some(ref filter) => self.main_panel.set_filter(filter),
+            None => self.main_panel.clear_filter(),
+        }
+        // Users without a passwd entry are saved by UID
+        machine.filter_user_id = screen.user.as_ref().map(|user| {
+            platform::get_uid_for_username(user)
+                .or_else(|| user.parse().ok())
+                .unwrap_or(u32::MAX)
+        });
+        machine.hide_userland_threads = self.settings.userland_threads_hidden();
+
+        self.applied_filter = screen.filter.clone();
+        self.applied_user_id = machine.filter_user_id;
+        self.main_panel.selected = 0;
+        self.main_panel.scroll_v = 0;
+        // Tasks meter shadows hidden thread counts
+        self.header_needs_redraw = true;
+    }
+
     /// Toggle tree view - matches C htop actionToggleTreeView behavior
     fn toggle_tree_view(&mut self, machine: &mut Machine) {
         // Toggle the tree view setting
@@ -1433,8 +1754,11 @@ impl ScreenManager {
             .set_function(4, "F5", tree_label);
 
         // F4: Show "FILTER" (uppercase) when filter is active, "Filter" otherwise
-        // Matches C htop MainPanel_updateLabels behavior
-        let filter_label = if self.main_panel.is_filtering() {
+        // Matches C htop MainPanel_updateLabels behavior. "FILTER?" marks a
+        // filter that is not a valid query and only matches the command.
+        let filter_label = if self.main_panel.is_filter_invalid() {
+            "FILTER?"
+        } else if self.main_panel.is_filtering() {
             "FILTER"
         } else {
             "Filter"
@@ -1548,6 +1872,128 @@ impl ScreenManager {
         super::menus::show_kill_menu(crt, machine, &mut ctx, pid);
     }
 
+    /// Show the CPU affinity menu and apply the chosen CPUs to tagged
+    /// processes, or to the selected process if none are tagged (matches
+    /// C htop actionSetAffinity). Beeps if reading or setting fails.
+    fn set_affinity_for_processes(&mut self, crt: &mut Crt, machine: &mut Machine) {
+        if machine.existing_cpus <= 1 {
+            return;
+        }
+        let pid = match self.main_panel.get_selected_pid(machine) {
+            Some(p) => p,
+            None => return,
+        };
+        // Pre-check the CPUs of the selected process
+        let current = match platform::get_process_affinity(pid) {
+            Some(affinity) => affinity,
+            None => {
+                crt.beep();
+                return;
+            }
+        };
+
+        let mut ctx = super::menus::AffinityMenuContext {
+            main_panel: &mut self.main_panel,
+            header: &self.header,
+            settings: &self.settings,
+            hide_meters: self.hide_meters,
+        };
+        let affinity = match super::menus::show_affinity_menu(crt, machine, &mut ctx, &current) {
+            Some(affinity) => affinity,
+            None => return,
+        };
+
+        let tagged = machine.processes.get_tagged();
+        let pids = if tagged.is_empty() { vec![pid] } else { tagged };
+
+        let mut all_ok = true;
+        for pid in pids {
+            if platform::set_process_affinity(pid, &affinity) {
+                // Update the process in memory immediately for instant UI feedback
+                if let Some(process) = machine.processes.get_mut(pid) {
+                    process.cpu_affinity = Some(affinity.clone());
+                }
+            } else {
+                all_ok = false;
+            }
+        }
+        if !all_ok {
+            crt.beep();
+        }
+    }
+
+    /// Show the IO priority menu and apply the chosen class/level to tagged
+    /// processes, or to the selected process if none are tagged (matches
+    /// C htop actionSetIOPriority). Beeps if setting fails.
+    #[cfg(target_os = "linux")]
+    fn set_io_priority_for_processes(&mut self, crt: &mut Crt, machine: &mut Machine) {
+        let pid = match self.main_panel.get_selected_pid(machine) {
+            Some(p) => p,
+            None => return,
+        };
+        let current = platform::linux::get_io_priority(pid);
+
+        let mut ctx = super::menus::IOPriorityMenuContext {
+            main_panel: &mut self.main_panel,
+            header: &self.header,
+            settings: &self.settings,
+            hide_meters: self.hide_meters,
+        };
+        let ioprio = match super::menus::show_io_priority_menu(crt, machine, &mut ctx, current) {
+            Some(ioprio) => ioprio,
+            None => return,
+        };
+
+        let tagged = machine.processes.get_tagged();
+        let pids = if tagged.is_empty() { vec![pid] } else { tagged };
+
+        let mut all_ok = true;
+        for pid in pids {
+            if platform::linux::set_io_priority(pid, ioprio) {
+                // Update the process in memory immediately for instant UI feedback
+                if let Some(process) = machine.processes.get_mut(pid) {
+                    process.io_priority = ioprio;
+                }
+            } else {
+                all_ok = false;
+            }
+        }
+        if !all_ok {
+            crt.beep();
+        }
+    }
+
+    /// Export the rows currently shown in the main panel to a CSV file.
+    /// Uses the active screen's columns and the current FieldWidths, so the
+    /// file matches what is on screen (filter, sort order and tree order).
+    fn export_csv(&mut self, crt: &mut Crt, machine: &Machine) {
+        let default_path = format!("htop-{}.csv", chrono::Local::now().format("%Y%m%d-%H%M%S"));
+        let path = match super::menus::prompt_line(crt, "Export CSV to", &default_path) {
+            Some(path) if !path.trim().is_empty() => expand_home(path.trim()),
+            _ => return,
+        };
+
+        let processes = self
+            .main_panel
+            .get_displayed_processes(machine, &self.settings);
+        let csv = crate::core::processes_csv(
+            processes.iter().copied(),
+            &self.main_panel.fields,
+            &machine.field_widths,
+            machine.active_cpus,
+            self.settings.tree_view,
+        );
+
+        let message = match std::fs::write(&path, csv) {
+            Ok(()) => format!("Exported {} rows to {}", processes.len(), path),
+            Err(e) => {
+                crt.beep();
+                format!("Export to {} failed: {}", path, e)
+            }
+        };
+        super::menus::show_bar_message(crt, &message);
+    }
+
     /// Show sort column selection menu (matches C htop actionSetSortColumn)
     fn show_sort_menu(&mut self, crt: &mut Crt, machine: &mut Machine) {
         let fields_copy: Vec<ProcessField> = self.main_panel.fields.clone();
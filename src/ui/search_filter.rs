@@ -211,10 +211,16 @@ impl SearchFilterState {
     /// Draw the search/filter bar or function bar
     /// Returns true if search/filter bar was drawn
     pub fn draw_bar(&self, crt: &mut Crt, y: i32, screen_width: i32) {
+        self.draw_bar_with(crt, y, screen_width, &[]);
+    }
+
+    /// Draw the bar like `draw_bar`, with extra (key, label) functions
+    /// before "Done" in the function bar
+    pub fn draw_bar_with(&self, crt: &mut Crt, y: i32, screen_width: i32, extra: &[(&str, &str)]) {
         if self.is_active() {
             self.draw_search_filter_bar(crt, y, screen_width);
         } else {
-            self.draw_function_bar(crt, y);
+            self.draw_function_bar(crt, y, extra);
         }
     }
 
@@ -267,18 +273,24 @@ impl SearchFilterState {
     }
 
     /// Draw the standard function bar
-    fn draw_function_bar(&self, crt: &mut Crt, y: i32) {
+    fn draw_function_bar(&self, crt: &mut Crt, y: i32, extra: &[(&str, &str)]) {
         let f4_label = if self.has_filter() {
             "FILTER"
         } else {
             "Filter"
         };
-        let fb = FunctionBar::with_functions(vec![
+        let mut functions = vec![
             ("F3".to_string(), "Search".to_string()),
             ("F4".to_string(), f4_label.to_string()),
             ("F5".to_string(), "Refresh".to_string()),
-            ("Esc".to_string(), "Done  ".to_string()),
-        ]);
+        ];
+        functions.extend(
+            extra
+                .iter()
+                .map(|(key, label)| (key.to_string(), label.to_string())),
+        );
+        functions.push(("Esc".to_string(), "Done  ".to_string()));
+        let fb = FunctionBar::with_functions(functions);
         fb.draw_simple(crt, y);
     }
 
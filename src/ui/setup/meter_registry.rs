@@ -15,6 +15,9 @@ pub struct MeterInfo {
     pub description: &'static str,
     /// Whether this meter type supports a parameter (e.g., CPU number)
     pub supports_param: bool,
+    /// Prompt for the device when the meter is added (e.g., a disk name),
+    /// None for meters that are not restricted to a device
+    pub device_prompt: Option<&'static str>,
 }
 
 impl MeterInfo {
@@ -28,6 +31,7 @@ impl MeterInfo {
             display_name,
             description,
             supports_param: false,
+            device_prompt: None,
         }
     }
 
@@ -41,6 +45,22 @@ impl MeterInfo {
             display_name,
             description,
             supports_param: true,
+            device_prompt: None,
+        }
+    }
+
+    const fn with_device(
+        name: &'static str,
+        display_name: &'static str,
+        description: &'static str,
+        device_prompt: &'static str,
+    ) -> Self {
+        MeterInfo {
+            name,
+            display_name,
+            description,
+            supports_param: false,
+            device_prompt: Some(device_prompt),
         }
     }
 }
@@ -145,9 +165,19 @@ const DISKIO_NETWORK_METERS: &[MeterInfo] = &[
         "Disk IO Rate",
         "Disk IO read & write bytes per second",
     ),
-    MeterInfo::new("DiskIOTime", "Disk IO Time", "Disk percent time busy"),
-    MeterInfo::new("DiskIO", "Disk IO", "Disk IO"),
-    MeterInfo::new("NetworkIO", "Network IO", "Network IO"),
+    MeterInfo::with_device(
+        "DiskIOTime",
+        "Disk IO Time",
+        "Disk percent time busy",
+        "Disk (empty for all)",
+    ),
+    MeterInfo::with_device("DiskIO", "Disk IO", "Disk IO", "Disk (empty for all)"),
+    MeterInfo::with_device(
+        "NetworkIO",
+        "Network IO",
+        "Network IO",
+        "Interfaces (glob, empty for all)",
+    ),
     MeterInfo::new(
         "FileDescriptors",
         "File Descriptors",
@@ -201,6 +231,11 @@ const LINUX_METERS: &[MeterInfo] = &[
     ),
     MeterInfo::new("Zram", "Zram", "Zram"),
     MeterInfo::new("SELinux", "SELinux", "SELinux state overview"),
+    MeterInfo::new(
+        "AppArmor",
+        "AppArmor",
+        "AppArmor state and profile overview",
+    ),
     MeterInfo::new(
         "Systemd",
         "Systemd state",
@@ -256,9 +291,10 @@ pub fn available_meters_for_platform() -> Vec<&'static MeterInfo> {
     meters
 }
 
-/// Get the display name for a meter by its internal name (used in meter columns)
+/// Get the display name for a meter by its internal name (used in meter columns),
+/// followed by the device for meters restricted to one
 /// This matches C htop's uiName field
-pub fn meter_display_name(name: &str, mode: MeterMode) -> String {
+pub fn meter_display_name(name: &str, device: Option<&str>, mode: MeterMode) -> String {
     let base_name = match name {
         "CPU" => "CPU",
         "AllCPUs" => "CPUs (1/1)",
@@ -302,6 +338,7 @@ pub fn meter_display_name(name: &str, mode: MeterMode) -> String {
         "PressureStallMemoryFull" => "PSI full memory",
         "Zram" => "Zram",
         "SELinux" => "SELinux",
+        "AppArmor" => "AppArmor",
         "Systemd" => "Systemd state",
         "SystemdUser" => "Systemd user state",
         // ZFS
@@ -321,5 +358,8 @@ pub fn meter_display_name(name: &str, mode: MeterMode) -> String {
         MeterMode::StackedGraph => "[Stacked]",
     };
 
-    format!("{} {}", base_name, mode_str)
+    match device {
+        Some(device) => format!("{} ({}) {}", base_name, device, mode_str),
+        None => format!("{} {}", base_name, mode_str),
+    }
 }
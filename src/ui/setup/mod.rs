@@ -415,6 +415,10 @@ impl SetupScreen {
                 "Hide processes running in containers",
                 SettingField::HideRunningInContainer,
             ),
+            OptionItem::check(
+                "Group tree view by container",
+                SettingField::TreeGroupByContainer,
+            ),
             OptionItem::check(
                 "Display threads in a different color",
                 SettingField::HighlightThreads,
@@ -429,6 +433,10 @@ impl SetupScreen {
                 "Highlight out-dated/removed programs (red) / libraries (yellow)",
                 SettingField::HighlightDeletedExe,
             ),
+            OptionItem::check(
+                "Highlight unconfined processes in the security attribute column",
+                SettingField::HighlightUnconfined,
+            ),
             OptionItem::check(
                 "Shadow distribution path prefixes",
                 SettingField::ShadowDistPathPrefix,
@@ -471,10 +479,22 @@ impl SetupScreen {
                 SettingField::ShowCpuUsage,
             ),
             OptionItem::check("Also show CPU frequency", SettingField::ShowCpuFrequency),
+            OptionItem::check(
+                "Also show CPU temperature",
+                SettingField::ShowCpuTemperature,
+            ),
+            OptionItem::check(
+                "Show temperature in degree Fahrenheit instead of Celsius",
+                SettingField::DegreeFahrenheit,
+            ),
             OptionItem::check(
                 "Show cached memory in graph and bar modes",
                 SettingField::ShowCachedMemory,
             ),
+            OptionItem::check(
+                "Show network interfaces in Network IO text mode",
+                SettingField::ShowNetworkInterfaces,
+            ),
             OptionItem::check("Enable the mouse", SettingField::EnableMouse),
             OptionItem::number_scaled(
                 "Update interval (in seconds)",
@@ -1047,7 +1067,7 @@ impl SetupScreen {
                 let is_selected = has_focus && i == selection;
 
                 // Get display name with mode
-                let display = meter_display_name(&meter.name, meter.mode);
+                let display = meter_display_name(&meter.name, meter.device.as_deref(), meter.mode);
 
                 // Add moving indicator (↕) if this item is being moved
                 let display_text = if is_selected && meters_moving {
@@ -1625,7 +1645,7 @@ impl SetupScreen {
     ) -> HandlerResult {
         // Handle Meters category separately since it has different panel structure
         if self.category == SetupCategory::Meters && self.focus == 1 {
-            return self.handle_meters_key(key, settings, header);
+            return self.handle_meters_key(key, settings, crt, header);
         }
 
         // Handle Screens category separately since it has different panel structure
@@ -1778,6 +1798,7 @@ impl SetupScreen {
         &mut self,
         key: i32,
         settings: &mut Settings,
+        crt: &mut Crt,
         header: &mut Header,
     ) -> HandlerResult {
         let num_columns = settings.header_layout.num_columns();
@@ -1800,7 +1821,7 @@ impl SetupScreen {
                 if is_available_panel {
                     // Add selected meter to rightmost column (like C htop)
                     let rightmost = num_columns.saturating_sub(1);
-                    self.add_meter_to_column(settings, header, rightmost);
+                    self.add_meter_to_column(settings, crt, header, rightmost);
                     return HandlerResult::Handled;
                 } else if key == KEY_ENTER_LF || key == KEY_ENTER_CR {
                     // Toggle moving mode (only for Enter, not r/R/F6)
@@ -1813,7 +1834,7 @@ impl SetupScreen {
             KEY_L | KEY_L_UPPER | KEY_F5 => {
                 if is_available_panel {
                     // Add selected meter to leftmost column (column 0)
-                    self.add_meter_to_column(settings, header, 0);
+                    self.add_meter_to_column(settings, crt, header, 0);
                     return HandlerResult::Handled;
                 }
             }
@@ -2311,6 +2332,10 @@ impl SetupScreen {
             tree_view: false,
             tree_view_always_by_pid: false,
             all_branches_collapsed: false,
+            filter: None,
+            user: None,
+            hide_kernel_threads: None,
+            hide_userland_threads: None,
         };
         // Insert after current selection
         let insert_pos = (self.screens_selection + 1).min(settings.screens.len());
@@ -2503,10 +2528,12 @@ impl SetupScreen {
 
     /// Add a meter from available meters to a specific column
     /// If target_column is None, adds to column 0 (leftmost)
+    /// Meters that can be restricted to a device prompt for it first
     /// After adding, switches focus to that column and enters moving mode
     fn add_meter_to_column(
         &mut self,
         settings: &mut Settings,
+        crt: &mut Crt,
         header: &mut Header,
         target_column: usize,
     ) {
@@ -2517,10 +2544,20 @@ impl SetupScreen {
 
         let meter_info = available_meters[self.meters_available_selection];
 
+        // Ask for the device, Esc cancels adding the meter
+        let device = match meter_info.device_prompt {
+            Some(prompt) => match super::menus::prompt_line(crt, prompt, "") {
+                Some(text) => Some(text.trim().to_string()).filter(|d| !d.is_empty()),
+                None => return,
+            },
+            None => None,
+        };
+
         // Create meter config
         let config = MeterConfig {
             name: meter_info.name.to_string(),
             param: 0,
+            device,
             mode: MeterMode::Bar,
         };
 
@@ -3823,7 +3860,8 @@ impl SetupScreen {
                 // Compute scan flags from current screen's fields for conditional /proc reads
                 #[cfg(target_os = "linux")]
                 {
-                    machine.scan_flags = ScanFlags::from_fields(&settings.current_screen().fields);
+                    machine.scan_flags = ScanFlags::from_fields(&settings.current_screen().fields)
+                        | header.scan_flags();
                 }
 
                 // Scan machine for updated system data
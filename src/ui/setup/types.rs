@@ -157,11 +157,13 @@ pub enum SettingField {
     HideKernelThreads,
     HideUserlandThreads,
     HideRunningInContainer,
+    TreeGroupByContainer,
     HighlightThreads,
     ShowThreadNames,
     ShowProgramPath,
     HighlightBaseName,
     HighlightDeletedExe,
+    HighlightUnconfined,
     ShadowDistPathPrefix,
     ShowMergedCommand,
     FindCommInCmdline,
@@ -174,7 +176,10 @@ pub enum SettingField {
     AccountGuestInCpuMeter,
     ShowCpuUsage,
     ShowCpuFrequency,
+    ShowCpuTemperature,
+    DegreeFahrenheit,
     ShowCachedMemory,
+    ShowNetworkInterfaces,
     EnableMouse,
     Delay,
     HighlightChanges,
@@ -201,11 +206,13 @@ impl SettingField {
             SettingField::HideKernelThreads => settings.hide_kernel_threads,
             SettingField::HideUserlandThreads => settings.hide_userland_threads,
             SettingField::HideRunningInContainer => settings.hide_running_in_container,
+            SettingField::TreeGroupByContainer => settings.tree_group_by_container,
             SettingField::HighlightThreads => settings.highlight_threads,
             SettingField::ShowThreadNames => settings.show_thread_names,
             SettingField::ShowProgramPath => settings.show_program_path,
             SettingField::HighlightBaseName => settings.highlight_base_name,
             SettingField::HighlightDeletedExe => settings.highlight_deleted_exe,
+            SettingField::HighlightUnconfined => settings.highlight_unconfined,
             SettingField::ShadowDistPathPrefix => settings.shadow_dist_path_prefix,
             SettingField::ShowMergedCommand => settings.show_merged_command,
             SettingField::FindCommInCmdline => settings.find_comm_in_cmdline,
@@ -218,7 +225,10 @@ impl SettingField {
             SettingField::AccountGuestInCpuMeter => settings.account_guest_in_cpu_meter,
             SettingField::ShowCpuUsage => settings.show_cpu_usage,
             SettingField::ShowCpuFrequency => settings.show_cpu_frequency,
+            SettingField::ShowCpuTemperature => settings.show_cpu_temperature,
+            SettingField::DegreeFahrenheit => settings.degree_fahrenheit,
             SettingField::ShowCachedMemory => settings.show_cached_memory,
+            SettingField::ShowNetworkInterfaces => settings.show_network_interfaces,
             SettingField::EnableMouse => settings.enable_mouse,
             SettingField::HighlightChanges => settings.highlight_changes,
             _ => false,
@@ -246,11 +256,13 @@ impl SettingField {
             SettingField::HideKernelThreads => settings.hide_kernel_threads = value,
             SettingField::HideUserlandThreads => settings.hide_userland_threads = value,
             SettingField::HideRunningInContainer => settings.hide_running_in_container = value,
+            SettingField::TreeGroupByContainer => settings.tree_group_by_container = value,
             SettingField::HighlightThreads => settings.highlight_threads = value,
             SettingField::ShowThreadNames => settings.show_thread_names = value,
             SettingField::ShowProgramPath => settings.show_program_path = value,
             SettingField::HighlightBaseName => settings.highlight_base_name = value,
             SettingField::HighlightDeletedExe => settings.highlight_deleted_exe = value,
+            SettingField::HighlightUnconfined => settings.highlight_unconfined = value,
             SettingField::ShadowDistPathPrefix => settings.shadow_dist_path_prefix = value,
             SettingField::ShowMergedCommand => settings.show_merged_command = value,
             SettingField::FindCommInCmdline => settings.find_comm_in_cmdline = value,
@@ -263,7 +275,10 @@ impl SettingField {
             SettingField::AccountGuestInCpuMeter => settings.account_guest_in_cpu_meter = value,
             SettingField::ShowCpuUsage => settings.show_cpu_usage = value,
             SettingField::ShowCpuFrequency => settings.show_cpu_frequency = value,
+            SettingField::ShowCpuTemperature => settings.show_cpu_temperature = value,
+            SettingField::DegreeFahrenheit => settings.degree_fahrenheit = value,
             SettingField::ShowCachedMemory => settings.show_cached_memory = value,
+            SettingField::ShowNetworkInterfaces => settings.show_network_interfaces = value,
             SettingField::EnableMouse => settings.enable_mouse = value,
             SettingField::HighlightChanges => settings.highlight_changes = value,
             _ => {}
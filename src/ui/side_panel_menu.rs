@@ -6,8 +6,8 @@
 
 use super::crt::{
     KEY_DOWN, KEY_END, KEY_ESC, KEY_F10, KEY_HOME, KEY_LC_Q, KEY_LINEFEED, KEY_MOUSE, KEY_NPAGE,
-    KEY_PPAGE, KEY_PRINTABLE_END, KEY_PRINTABLE_START, KEY_RETURN, KEY_UP, KEY_WHEELDOWN,
-    KEY_WHEELUP,
+    KEY_PPAGE, KEY_PRINTABLE_END, KEY_PRINTABLE_START, KEY_RETURN, KEY_SPACE, KEY_UP,
+    KEY_WHEELDOWN, KEY_WHEELUP,
 };
 use super::header::Header;
 use super::main_panel::MainPanel;
@@ -38,6 +38,7 @@ pub struct SidePanelContext<'a> {
 /// - Drawing the header, side panel, and main panel
 /// - Handling navigation keys (up, down, page up/down, home, end, mouse wheel)
 /// - Handling Enter to confirm and Escape/q/F10 to cancel
+/// - Toggling checkbox items with Space
 /// - Typing search to jump to items
 /// - Restoring the main panel to its original position
 ///
@@ -159,6 +160,9 @@ fn run_panel_event_loop(
                 // Escape, 'q', or F10 - cancel
                 return SidePanelResult::Cancelled;
             }
+            KEY_SPACE if panel.toggle_selected() => {
+                // Space - toggle checkbox items (other panels fall through to typing search)
+            }
             _ => {
                 // Try typing search (jump to item starting with this char)
                 // Note: 'q' (KEY_LC_Q) is handled above, so won't trigger typing search